@@ -0,0 +1,103 @@
+//! Timings for choosing `KARATSUBA_THRESHOLD` and `FFT_THRESHOLD`.
+//!
+//! Run with `cargo bench --bench multiplication`. The first tables time
+//! balanced products of increasing size with schoolbook multiplication and
+//! Karatsuba's method, then a fixed product with Karatsuba's method over a
+//! range of thresholds, which is where the crossover shows. The last table
+//! compares Karatsuba's method with number theoretic transforms.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use math2::ntt::FFT_THRESHOLD;
+use math2::{Polynomial, KARATSUBA_THRESHOLD};
+use num::{BigInt, BigRational};
+
+/// Coefficients of about `bits` bits from a fixed linear congruential
+/// generator.
+fn integer_polynomial(len: usize, bits: u32, seed: u64) -> Polynomial<BigInt> {
+    let mut state = seed;
+    let mut next = move || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as i64 - (1 << 30)
+    };
+    Polynomial::new_trim_zeroes(
+        (0..len)
+            .map(|_| (0..bits.div_ceil(31)).fold(BigInt::from(next()), |acc, _| (acc << 31) + next()))
+            .collect(),
+    )
+}
+
+fn rational_polynomial(len: usize, seed: u64) -> Polynomial<BigRational> {
+    let numerators = integer_polynomial(len, 64, seed);
+    // small denominators, as large ones make every addition a big gcd
+    let denominators = integer_polynomial(len, 1, seed + 1);
+    Polynomial::new_trim_zeroes(
+        numerators
+            .into_iter()
+            .zip(denominators)
+            .map(|(n, d)| BigRational::new(n, BigInt::from(d.magnitude() % 16u32) + 1))
+            .collect(),
+    )
+}
+
+/// The median of a few timings of `f`, repeated enough to take a while.
+fn time(mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    f();
+    let once = start.elapsed().max(Duration::from_micros(1));
+    let repeats = (Duration::from_millis(50).as_nanos() / once.as_nanos()).clamp(1, 1000) as u32;
+    let mut samples: Vec<Duration> = (0..5)
+        .map(|_| {
+            let start = Instant::now();
+            for _ in 0..repeats {
+                f();
+            }
+            start.elapsed() / repeats
+        })
+        .collect();
+    samples.sort();
+    samples[2]
+}
+
+fn main() {
+    println!("balanced BigInt products with 64 bit coefficients");
+    println!("{:>6} {:>12} {:>12}", "len", "schoolbook", "karatsuba");
+    for len in [8, 16, 32, 64, 128, 256, 512, 1024, 2048] {
+        let (p, q) = (integer_polynomial(len, 64, 1), integer_polynomial(len, 64, 2));
+        let schoolbook = time(|| drop(black_box(p.schoolbook_mul(&q))));
+        let karatsuba = time(|| drop(black_box(p.karatsuba_mul(&q, KARATSUBA_THRESHOLD))));
+        println!("{len:>6} {schoolbook:>12.2?} {karatsuba:>12.2?}");
+    }
+
+    println!();
+    println!("balanced BigRational products");
+    println!("{:>6} {:>12} {:>12}", "len", "schoolbook", "karatsuba");
+    for len in [8, 16, 32, 64, 128] {
+        let (p, q) = (rational_polynomial(len, 3), rational_polynomial(len, 5));
+        let schoolbook = time(|| drop(black_box(p.schoolbook_mul(&q))));
+        let karatsuba = time(|| drop(black_box(p.karatsuba_mul(&q, KARATSUBA_THRESHOLD))));
+        println!("{len:>6} {schoolbook:>12.2?} {karatsuba:>12.2?}");
+    }
+
+    for bits in [64, 1024] {
+        println!();
+        println!("BigInt products of length 512 with {bits} bit coefficients, by threshold");
+        println!("{:>9} {:>12}", "threshold", "time");
+        let (p, q) = (integer_polynomial(512, bits, 7), integer_polynomial(512, bits, 11));
+        for threshold in [2, 4, 8, 16, 24, 32, 48, 64, 128, 513] {
+            let t = time(|| drop(black_box(p.karatsuba_mul(&q, threshold))));
+            println!("{threshold:>9} {t:>12.2?}");
+        }
+    }
+
+    println!();
+    println!("balanced BigInt products with 64 bit coefficients, threshold {FFT_THRESHOLD}");
+    println!("{:>6} {:>12} {:>12}", "len", "karatsuba", "fft");
+    for len in [64, 128, 256, 512, 1024, 4096, 16384] {
+        let (p, q) = (integer_polynomial(len, 64, 13), integer_polynomial(len, 64, 17));
+        let karatsuba = time(|| drop(black_box(p.karatsuba_mul(&q, KARATSUBA_THRESHOLD))));
+        let fft = time(|| drop(black_box(p.mul_fft(&q))));
+        println!("{len:>6} {karatsuba:>12.2?} {fft:>12.2?}");
+    }
+}
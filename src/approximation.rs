@@ -0,0 +1,371 @@
+//! Numerical polynomial approximation of functions on an interval.
+
+use std::f64::consts::PI;
+use std::ops::RangeInclusive;
+
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+use crate::interval::Interval;
+use crate::real_roots::RealAlgebraic;
+use crate::traits::CommutativeRing;
+use crate::Polynomial;
+
+/// `(2x - a - b) / (b - a)`, which maps `[a, b]` onto `[-1, 1]`, to
+/// [compose](Polynomial::compose) with a polynomial on `[-1, 1]`.
+fn to_unit_interval(a: f64, b: f64) -> Polynomial<f64> {
+    Polynomial::new(vec![-(a + b) / (b - a), 2.0 / (b - a)])
+}
+
+/// The largest error `|f(x) - p(x)|` over `samples + 1` equally spaced points
+/// of `[a, b]`.
+fn sampled_error(
+    f: impl Fn(f64) -> f64,
+    p: &Polynomial<f64>,
+    a: f64,
+    b: f64,
+    samples: usize,
+) -> f64 {
+    (0..=samples)
+        .map(|i| a + (b - a) * i as f64 / samples as f64)
+        .map(|x| (f(x) - p.eval(&x)).abs())
+        .fold(0.0, f64::max)
+}
+
+/// Converts `sum(c_j T_j)`, where `T_j` is the `j`th Chebyshev polynomial of
+/// the first kind, to the monomial basis.
+///
+/// # Example
+///
+/// ```
+/// # use math2::approximation::chebyshev_to_monomial;
+/// # use math2::Polynomial;
+/// // T_0 + T_2 = 1 + (2x^2 - 1)
+/// assert_eq!(Polynomial::new(vec![0, 0, 2]), chebyshev_to_monomial(&[1i64, 0, 1]));
+/// ```
+pub fn chebyshev_to_monomial<F: CommutativeRing + PartialEq>(coeffs: &[F]) -> Polynomial<F> {
+    let two_x = Polynomial::new(vec![F::zero(), F::one() + F::one()]);
+    // t holds T_{j - 1} and T_j
+    let mut t = (Polynomial::zero(), Polynomial::one());
+    let mut res = Polynomial::zero();
+    for (j, c) in coeffs.iter().enumerate() {
+        res += t.1.clone().scalar_mul(c.clone());
+        let next = if j == 0 {
+            Polynomial::from_elem_with_degree(F::one(), 1)
+        } else {
+            two_x.clone() * t.1.clone() - t.0
+        };
+        t = (t.1, next);
+    }
+    res
+}
+
+/// The result of [`chebyshev_approximate`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChebyshevApproximation {
+    /// The coefficients `c_j` of `sum(c_j T_j(t))`, where `t` is `x` mapped
+    /// linearly from the interval onto `[-1, 1]`.
+    pub chebyshev_coeffs: Vec<f64>,
+    /// The same approximation in the monomial basis, in terms of `x`.
+    pub polynomial: Polynomial<f64>,
+    /// The largest error seen when sampling the interval densely. This is an
+    /// estimate, not a bound.
+    pub error_estimate: f64,
+}
+
+/// Approximates `f` on `interval` by interpolating it at the `degree + 1`
+/// Chebyshev nodes, which is close to the best approximation of that degree
+/// for smooth `f`.
+///
+/// # Panics
+///
+/// Panics if the interval is empty or a single point.
+///
+/// # Example
+///
+/// ```
+/// # use math2::approximation::chebyshev_approximate;
+/// let approx = chebyshev_approximate(f64::exp, 8, 0.0..=1.0);
+/// assert!(approx.error_estimate < 1e-9);
+/// ```
+pub fn chebyshev_approximate(
+    f: impl Fn(f64) -> f64,
+    degree: usize,
+    interval: RangeInclusive<f64>,
+) -> ChebyshevApproximation {
+    let (a, b) = (*interval.start(), *interval.end());
+    assert!(a < b, "interval must have positive length");
+    let n = degree + 1;
+    let nodes: Vec<_> = (0..n)
+        .map(|k| (PI * (k as f64 + 0.5) / n as f64).cos())
+        .collect();
+    let values: Vec<_> = nodes
+        .iter()
+        .map(|t| f((a + b) / 2.0 + (b - a) / 2.0 * t))
+        .collect();
+    // the discrete orthogonality of T_j on the nodes gives the coefficients
+    let chebyshev_coeffs: Vec<_> = (0..n)
+        .map(|j| {
+            let sum: f64 = nodes
+                .iter()
+                .zip(&values)
+                .map(|(t, y)| y * (j as f64 * t.acos()).cos())
+                .sum();
+            let scale = if j == 0 { 1.0 } else { 2.0 };
+            scale * sum / n as f64
+        })
+        .collect();
+    let polynomial = chebyshev_to_monomial(&chebyshev_coeffs).compose(&to_unit_interval(a, b));
+    let error_estimate = sampled_error(f, &polynomial, a, b, 64 * n);
+    ChebyshevApproximation {
+        chebyshev_coeffs,
+        polynomial,
+        error_estimate,
+    }
+}
+
+/// Solves the square system `a x = b` by Gaussian elimination with partial
+/// pivoting. `None` if `a` is singular to working precision.
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = a.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col] == 0.0 {
+            return None;
+        }
+        a.swap(pivot, col);
+        b.swap(pivot, col);
+        let (done, rest) = a.split_at_mut(col + 1);
+        let pivot_row = &done[col];
+        for (offset, row) in rest.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * p;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = (row + 1..n).map(|j| a[row][j] * x[j]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+/// The result of [`remez`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct MinimaxApproximation {
+    pub polynomial: Polynomial<f64>,
+    /// The largest error `|f(x) - p(x)|` seen on the interval.
+    pub error: f64,
+    /// The points where the error alternates in sign with (nearly) equal
+    /// magnitude, in increasing order.
+    pub reference: Vec<f64>,
+    /// Whether the error equioscillated, or vanished because `f` is a
+    /// polynomial of degree at most `degree`. Otherwise the exchange stopped
+    /// on a singular system, an error that is not finite or too few
+    /// alternations, or ran out of rounds, and the polynomial is just the
+    /// last iterate.
+    pub converged: bool,
+}
+
+/// Finds the polynomial of degree at most `degree` minimizing the largest
+/// error to `f` on `interval`, with the Remez exchange algorithm.
+///
+/// Starting from the Chebyshev extrema, each round solves for the polynomial
+/// whose error alternates in sign on the `degree + 2` reference points, then
+/// moves the reference to the extrema of the new error. The extrema are
+/// located on a fine grid, so the result is accurate to the grid spacing,
+/// which is plenty for designing approximations. Check
+/// [`MinimaxApproximation::converged`] before relying on the error bound.
+///
+/// # Panics
+///
+/// Panics if the interval is empty or a single point.
+///
+/// # Example
+///
+/// ```
+/// # use math2::approximation::remez;
+/// // the best line through x^2 on [0, 1] is x - 1/8
+/// let approx = remez(|x| x * x, 1, 0.0..=1.0);
+/// assert!(approx.converged);
+/// assert!((approx.error - 0.125).abs() < 1e-6);
+/// ```
+pub fn remez(
+    f: impl Fn(f64) -> f64,
+    degree: usize,
+    interval: RangeInclusive<f64>,
+) -> MinimaxApproximation {
+    let (a, b) = (*interval.start(), *interval.end());
+    assert!(a < b, "interval must have positive length");
+    // work on [-1, 1] to keep the linear systems well conditioned
+    let g = |t: f64| f((a + b) / 2.0 + (b - a) / 2.0 * t);
+    let n = degree + 2;
+    let grid_size = 512 * n;
+    let grid: Vec<_> = (0..=grid_size)
+        .map(|i| -1.0 + 2.0 * i as f64 / grid_size as f64)
+        .collect();
+    let targets: Vec<_> = grid.iter().map(|&t| g(t)).collect();
+
+    let mut reference: Vec<_> = (0..n)
+        .map(|i| -(PI * i as f64 / (n - 1) as f64).cos())
+        .collect();
+    let scale = targets.iter().fold(0.0, |m: f64, y| m.max(y.abs()));
+    let mut p = Polynomial::zero();
+    let mut converged = false;
+    for _ in 0..32 {
+        // sum(c_j t_i^j) + (-1)^i e = g(t_i)
+        let rows = reference
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| {
+                let mut row: Vec<_> = (0..=degree).map(|j| t.powi(j as i32)).collect();
+                row.push(if i % 2 == 0 { 1.0 } else { -1.0 });
+                row
+            })
+            .collect();
+        let Some(mut solution) = solve(rows, reference.iter().map(|&t| g(t)).collect()) else {
+            break;
+        };
+        solution.pop();
+        p = Polynomial::new_trim_zeroes(solution);
+        let errors: Vec<f64> = grid.iter().zip(&targets).map(|(&t, y)| y - p.eval(&t)).collect();
+        if errors.iter().any(|e| !e.is_finite()) {
+            break;
+        }
+        // f is a polynomial of low degree, and the error is only rounding
+        if errors.iter().all(|e| e.abs() <= 64.0 * f64::EPSILON * scale) {
+            converged = true;
+            break;
+        }
+
+        // the largest error on each run of constant sign
+        let mut runs: Vec<(usize, f64)> = Vec::new();
+        for (i, &e) in errors.iter().enumerate() {
+            match runs.last_mut() {
+                Some((j, best)) if best.signum() == e.signum() => {
+                    if e.abs() > best.abs() {
+                        (*j, *best) = (i, e);
+                    }
+                }
+                _ => runs.push((i, e)),
+            }
+        }
+        if runs.len() < n {
+            break;
+        }
+        while runs.len() > n {
+            if runs[0].1.abs() < runs[runs.len() - 1].1.abs() {
+                runs.remove(0);
+            } else {
+                runs.pop();
+            }
+        }
+        let new_reference: Vec<_> = runs.iter().map(|&(i, _)| grid[i]).collect();
+        let (min, max) = runs.iter().fold((f64::INFINITY, 0.0f64), |(min, max), (_, e)| {
+            (min.min(e.abs()), max.max(e.abs()))
+        });
+        converged = new_reference == reference || max - min <= 1e-9 * max;
+        reference = new_reference;
+        if converged {
+            break;
+        }
+    }
+
+    let error = grid
+        .iter()
+        .zip(&targets)
+        .map(|(&t, y)| (y - p.eval(&t)).abs())
+        .fold(0.0, f64::max);
+    MinimaxApproximation {
+        polynomial: p.compose(&to_unit_interval(a, b)),
+        error,
+        reference: reference
+            .into_iter()
+            .map(|t| (a + b) / 2.0 + (b - a) / 2.0 * t)
+            .collect(),
+        converged,
+    }
+}
+
+/// The first continued fraction convergent of `x` within `eps` of it.
+fn rational_within(x: f64, eps: f64) -> Option<BigRational> {
+    let target = BigRational::from_float(x)?;
+    let eps = BigRational::from_float(eps)?;
+    let (mut h, mut h_prev) = (BigInt::one(), BigInt::zero());
+    let (mut k, mut k_prev) = (BigInt::zero(), BigInt::one());
+    let mut rest = target.clone();
+    loop {
+        let a = rest.floor();
+        let a_int = a.to_integer();
+        (h, h_prev) = (&a_int * &h + &h_prev, h);
+        (k, k_prev) = (&a_int * &k + &k_prev, k);
+        let convergent = BigRational::new(h.clone(), k.clone());
+        let frac = rest - a;
+        if (&convergent - &target).abs() <= eps || frac.is_zero() {
+            return Some(convergent);
+        }
+        rest = frac.recip();
+    }
+}
+
+impl Polynomial<f64> {
+    /// Replaces every coefficient by the simplest continued fraction
+    /// convergent within `eps` of it, so that a numerical result can be
+    /// checked with exact arithmetic. Returns `None` if a coefficient is not
+    /// finite.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let p = Polynomial::new(vec![0.333333, 1.0 / 7.0, 1e-9]);
+    /// let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    /// assert_eq!(
+    ///     Polynomial::new(vec![q(1, 3), q(1, 7)]),
+    ///     p.to_rational_with_tolerance(1e-6).unwrap()
+    /// );
+    /// ```
+    pub fn to_rational_with_tolerance(&self, eps: f64) -> Option<Polynomial<BigRational>> {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .map(|&c| rational_within(c, eps.abs()))
+            .collect::<Option<_>>()?;
+        Some(Polynomial::new_trim_zeroes(coeffs))
+    }
+}
+
+/// The exact maximum of `|p(x) - q(x)|` on `interval`.
+///
+/// It is the larger of the maximum and minus the minimum of `p - q`, which
+/// are found among the ends of the interval and the isolated roots of
+/// `(p - q)'` by [`Polynomial::global_extrema_on`]. With
+/// [`Polynomial::to_rational_with_tolerance`], this gives a verified bound
+/// on the error of a numerical approximation such as [`remez`] of a
+/// polynomial.
+///
+/// # Example
+///
+/// ```
+/// # use math2::approximation::sup_norm_difference;
+/// # use math2::interval::Interval;
+/// # use math2::real_roots::RealAlgebraic;
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+/// let p: Polynomial<BigRational> = "x^2".parse().unwrap();
+/// let line = Polynomial::new(vec![q(-1, 8), q(1, 1)]);
+/// let error = sup_norm_difference(&p, &line, &Interval::new(q(0, 1), q(1, 1)));
+/// assert_eq!(RealAlgebraic::from(q(1, 8)), error);
+/// ```
+pub fn sup_norm_difference(
+    p: &Polynomial<BigRational>,
+    q: &Polynomial<BigRational>,
+    interval: &Interval,
+) -> RealAlgebraic {
+    let extrema = (p.clone() - q.clone()).global_extrema_on(interval.lo(), interval.hi());
+    extrema.max.max(-extrema.min)
+}
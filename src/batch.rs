@@ -0,0 +1,62 @@
+//! Evaluating a fixed float polynomial on many points at once.
+//!
+//! The points are processed in blocks of [`LANES`], with Horner's scheme run
+//! on a whole block at a time. The inner loop over a block has no
+//! dependencies between lanes, so the compiler turns it into SIMD
+//! instructions without any target specific code here.
+
+use crate::Polynomial;
+
+/// The number of points evaluated together.
+pub const LANES: usize = 8;
+
+fn eval_block(coeffs: &[f64], xs: &[f64; LANES]) -> [f64; LANES] {
+    let Some((last, rest)) = coeffs.split_last() else {
+        return [0.0; LANES];
+    };
+    let mut acc = [*last; LANES];
+    for c in rest.iter().rev() {
+        for (a, x) in acc.iter_mut().zip(xs) {
+            *a = *a * x + c;
+        }
+    }
+    acc
+}
+
+impl Polynomial<f64> {
+    /// Writes the value at `xs[i]` to `out[i]` for every `i`.
+    ///
+    /// This uses a separate multiply and add rather than `mul_add`, as a
+    /// fused multiply-add is a slow library call on targets built without
+    /// FMA support. The results can therefore differ in the last bit from
+    /// [`Scheme::Horner`](crate::codegen::Scheme::Horner).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `xs` and `out` have different lengths.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::new(vec![-1.0, 0.0, 1.0]);
+    /// let xs: Vec<f64> = (0..10).map(f64::from).collect();
+    /// let mut out = vec![0.0; 10];
+    /// p.eval_many_f64(&xs, &mut out);
+    /// assert_eq!(80.0, out[9]);
+    /// ```
+    pub fn eval_many_f64(&self, xs: &[f64], out: &mut [f64]) {
+        assert_eq!(xs.len(), out.len(), "need one output per point");
+        let mut xs_blocks = xs.chunks_exact(LANES);
+        let mut out_blocks = out.chunks_exact_mut(LANES);
+        for (x, y) in (&mut xs_blocks).zip(&mut out_blocks) {
+            y.copy_from_slice(&eval_block(&self.coeffs, x.try_into().unwrap()));
+        }
+        // pad the last partial block
+        let tail = xs_blocks.remainder();
+        let mut block = [0.0; LANES];
+        block[..tail.len()].copy_from_slice(tail);
+        let values = eval_block(&self.coeffs, &block);
+        out_blocks.into_remainder().copy_from_slice(&values[..tail.len()]);
+    }
+}
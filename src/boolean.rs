@@ -0,0 +1,510 @@
+//! Boolean polynomials, the ring `GF(2)[x_0, ..., x_63] / (x_i^2 - x_i)`.
+//!
+//! Every function from `n` bits to a bit is a unique boolean polynomial in
+//! algebraic normal form, a sum of distinct square free monomials. The
+//! conversion to and from truth tables is the Möbius transform over the
+//! subsets of the variables, in `O(n 2^n)` operations.
+//!
+//! [`groebner_basis`] is Buchberger's algorithm specialized to this ring,
+//! so a system of boolean equations can be simplified or solved without
+//! writing out the field equations `x_i^2 = x_i`. [`GroebnerState`] runs it
+//! one step at a time, and can be saved as text and resumed.
+
+use std::collections::{BTreeSet, VecDeque};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::fields::Zp;
+use crate::multivariate::MultiPolynomial;
+use crate::traits::{AssertUnit, CommutativeRing};
+
+/// A boolean polynomial, stored as the set of its monomials. A monomial is
+/// a bit mask with bit `i` set when `x_i` divides it, so `1` is `0` and
+/// `x_0 x_2` is `0b101`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct BooleanPolynomial {
+    monomials: BTreeSet<u64>,
+}
+
+impl BooleanPolynomial {
+    /// The sum of the given monomials, so repeated ones cancel in pairs.
+    pub fn new(monomials: impl IntoIterator<Item = u64>) -> Self {
+        let mut res = Self::zero();
+        for m in monomials {
+            res.toggle(m);
+        }
+        res
+    }
+
+    pub fn constant(c: bool) -> Self {
+        if c {
+            Self::one()
+        } else {
+            Self::zero()
+        }
+    }
+
+    /// The polynomial `x_i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= 64`.
+    pub fn var(i: usize) -> Self {
+        assert!(i < 64, "only 64 variables are supported");
+        Self::new([1 << i])
+    }
+
+    /// The largest monomial in the graded order, which compares monomials by
+    /// degree and then by bit mask, or `None` for the zero polynomial.
+    pub fn leading_monomial(&self) -> Option<u64> {
+        self.monomials.iter().copied().max_by_key(|&m| graded(m))
+    }
+
+    /// The product with the monomial `m`.
+    fn mul_monomial(&self, m: u64) -> Self {
+        Self::new(self.monomials.iter().map(|t| t | m))
+    }
+
+    /// The normal form modulo `basis`, in which no monomial is divisible by
+    /// the leading monomial of an element of `basis`. This is zero for every
+    /// element of the ideal when `basis` is a Gröbner basis.
+    pub fn reduce(&self, basis: &[Self]) -> Self {
+        let divisors: Vec<(u64, &Self)> = basis.iter().filter_map(|g| Some((g.leading_monomial()?, g))).collect();
+        let mut rest: BTreeSet<(u32, u64)> = self.monomials.iter().map(|&m| graded(m)).collect();
+        let mut res = Self::zero();
+        while let Some((_, t)) = rest.pop_last() {
+            match divisors.iter().find(|(lm, _)| lm & !t == 0) {
+                // the product cancels t and only adds smaller monomials
+                Some((lm, g)) => {
+                    for m in g.mul_monomial(t & !lm).monomials {
+                        if m != t && !rest.remove(&graded(m)) {
+                            rest.insert(graded(m));
+                        }
+                    }
+                }
+                None => res.toggle(t),
+            }
+        }
+        res
+    }
+
+    fn toggle(&mut self, m: u64) {
+        if !self.monomials.remove(&m) {
+            self.monomials.insert(m);
+        }
+    }
+
+    /// The monomials, in increasing order of their bit masks.
+    pub fn monomials(&self) -> impl Iterator<Item = u64> + '_ {
+        self.monomials.iter().copied()
+    }
+
+    /// The largest number of variables in a monomial, or `None` for the
+    /// zero polynomial.
+    pub fn degree(&self) -> Option<u32> {
+        self.monomials.iter().map(|m| m.count_ones()).max()
+    }
+
+    /// One more than the index of the last variable that appears.
+    pub fn num_vars(&self) -> usize {
+        let used = self.monomials.iter().fold(0, |acc, m| acc | m);
+        64 - used.leading_zeros() as usize
+    }
+
+    /// Evaluates at the point with `x_i` the bit `i` of `point`.
+    pub fn eval(&self, point: u64) -> bool {
+        self.monomials.iter().filter(|&&m| m & !point == 0).count() % 2 == 1
+    }
+
+    /// Replaces `x_i` with `value`. Writing `self = f_0 + x_i f_1` with
+    /// `f_0` and `f_1` free of `x_i`, this is `f_0 + value f_1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::boolean::BooleanPolynomial;
+    /// let x = BooleanPolynomial::var;
+    /// // x_0 x_1 with x_1 = x_0 + x_2 is x_0 + x_0 x_2
+    /// let p = x(0) * x(1);
+    /// assert_eq!(x(0) + x(0) * x(2), p.substitute(1, &(x(0) + x(2))));
+    /// ```
+    pub fn substitute(&self, i: usize, value: &Self) -> Self {
+        let bit = 1 << i;
+        let (with, without): (Vec<u64>, Vec<u64>) = self.monomials.iter().partition(|&&m| m & bit != 0);
+        let rest = Self::new(without);
+        let cofactor = Self::new(with.into_iter().map(|m| m & !bit));
+        rest + cofactor * value.clone()
+    }
+
+    /// Replaces `x_i` with the constant `value`.
+    pub fn specialize(&self, i: usize, value: bool) -> Self {
+        self.substitute(i, &Self::constant(value))
+    }
+
+    /// The polynomial of the function of `n` variables whose value at the
+    /// point with bits `k` is `table[k]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `table` is not a power of two or there are
+    /// more than 64 variables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::boolean::BooleanPolynomial;
+    /// let x = BooleanPolynomial::var;
+    /// // the majority of three bits is x_0 x_1 + x_0 x_2 + x_1 x_2
+    /// let table: Vec<bool> = (0..8u64).map(|k| k.count_ones() >= 2).collect();
+    /// let majority = BooleanPolynomial::from_truth_table(&table);
+    /// assert_eq!(x(0) * x(1) + x(0) * x(2) + x(1) * x(2), majority);
+    /// assert_eq!(table, majority.truth_table(3));
+    /// ```
+    pub fn from_truth_table(table: &[bool]) -> Self {
+        assert!(table.len().is_power_of_two(), "truth tables have a power of two entries");
+        let n = table.len().trailing_zeros();
+        assert!(n <= 64, "only 64 variables are supported");
+        let mut coeffs = table.to_vec();
+        mobius_transform(&mut coeffs);
+        Self {
+            monomials: (0..coeffs.len() as u64).filter(|&m| coeffs[m as usize]).collect(),
+        }
+    }
+
+    /// The values at the `2^n` points of `n` variables, with the value at
+    /// the point with bits `k` at index `k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable `x_i` with `i >= n` appears.
+    pub fn truth_table(&self, n: usize) -> Vec<bool> {
+        assert!(self.num_vars() <= n, "polynomial uses more than n variables");
+        let mut values = vec![false; 1 << n];
+        for &m in &self.monomials {
+            values[m as usize] = true;
+        }
+        // the transform is its own inverse over GF(2)
+        mobius_transform(&mut values);
+        values
+    }
+
+    /// The boolean polynomial of `p`, with every positive exponent
+    /// replaced by `1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable `x_i` with `i >= 64` appears.
+    pub fn from_multi(p: &MultiPolynomial<Zp<2>>) -> Self {
+        assert!(p.num_vars() <= 64, "only 64 variables are supported");
+        Self::new(p.terms().map(|(exponents, _)| {
+            exponents
+                .iter()
+                .enumerate()
+                .filter(|&(_, &e)| e > 0)
+                .fold(0, |m, (i, _)| m | 1 << i)
+        }))
+    }
+
+    /// This polynomial in `GF(2)[x_0, x_1, ...]`, with square free
+    /// monomials.
+    pub fn to_multi(&self) -> MultiPolynomial<Zp<2>> {
+        MultiPolynomial::new(self.monomials.iter().map(|&m| {
+            let exponents = (0..64 - m.leading_zeros() as usize).map(|i| (m >> i & 1) as usize).collect();
+            (exponents, Zp::new(1))
+        }))
+    }
+}
+
+/// The key of a monomial in the graded order. Multiplying by a monomial
+/// disjoint from the leading one keeps it leading, which is all that
+/// reduction needs.
+fn graded(m: u64) -> (u32, u64) {
+    (m.count_ones(), m)
+}
+
+/// Returns the reduced Gröbner basis of the ideal generated by `generators`
+/// in the graded order, sorted by leading monomial.
+///
+/// This is Buchberger's algorithm for the ideal together with the field
+/// equations `x_i^2 + x_i`. Those are never stored: the S-polynomial of `f`
+/// and `x_i^2 + x_i` for `x_i` in the leading monomial of `f` is the product
+/// `x_i f`, and pairs with coprime leading monomials are skipped. The
+/// equations have a common solution in `GF(2)` exactly when the basis is not
+/// `[1]`, and a unique solution gives the basis `x_i + a_i`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::boolean::{groebner_basis, BooleanPolynomial};
+/// # use num::One;
+/// let x = BooleanPolynomial::var;
+/// let one = BooleanPolynomial::one;
+/// // x_0 x_1 = 1, x_1 = x_2 and x_0 = x_1 x_2 force x_0 = x_1 = x_2 = 1
+/// let system = [x(0) * x(1) + one(), x(1) + x(2), x(0) + x(1) * x(2)];
+/// assert_eq!(vec![x(0) + one(), x(1) + one(), x(2) + one()], groebner_basis(&system));
+/// // adding x_0 + x_2 = 1 leaves no solution
+/// let system = [system[0].clone(), system[1].clone(), x(0) + x(2) + one()];
+/// assert_eq!(vec![one()], groebner_basis(&system));
+/// ```
+pub fn groebner_basis(generators: &[BooleanPolynomial]) -> Vec<BooleanPolynomial> {
+    GroebnerState::new(generators).resume()
+}
+
+/// The state of [`groebner_basis`] between steps of Buchberger's algorithm:
+/// the basis so far and the queue of polynomials still to reduce by it.
+///
+/// A long computation can be saved with [`GroebnerState::encode`] and
+/// picked up later with [`GroebnerState::decode`] and
+/// [`GroebnerState::resume`].
+///
+/// # Example
+///
+/// ```
+/// # use math2::boolean::{groebner_basis, BooleanPolynomial, GroebnerState};
+/// # use num::One;
+/// let x = BooleanPolynomial::var;
+/// let system = [x(0) * x(1) + BooleanPolynomial::one(), x(1) + x(2)];
+/// let mut state = GroebnerState::new(&system);
+/// state.step();
+/// let saved = state.encode();
+/// assert_eq!(groebner_basis(&system), GroebnerState::decode(&saved).unwrap().resume());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GroebnerState {
+    /// The polynomials added so far, each reduced by the earlier ones.
+    pub basis: Vec<BooleanPolynomial>,
+    /// The generators and S-polynomials not reduced yet.
+    pub queue: VecDeque<BooleanPolynomial>,
+}
+
+impl GroebnerState {
+    /// The state before the first step, with every generator in the queue.
+    pub fn new(generators: &[BooleanPolynomial]) -> Self {
+        GroebnerState {
+            basis: Vec::new(),
+            queue: generators.iter().cloned().collect(),
+        }
+    }
+
+    /// Whether the queue is empty, so the basis only needs
+    /// [`GroebnerState::resume`] to interreduce it.
+    pub fn is_done(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Reduces the next polynomial in the queue by the basis and, unless it
+    /// reduces to zero, adds it with its S-polynomials. Returns whether there
+    /// is more work to do.
+    pub fn step(&mut self) -> bool {
+        let Some(h) = self.queue.pop_front() else {
+            return false;
+        };
+        let h = h.reduce(&self.basis);
+        let Some(lm) = h.leading_monomial() else {
+            return !self.is_done();
+        };
+        if lm == 0 {
+            // 1 is in the ideal, which is the whole ring
+            self.basis = vec![BooleanPolynomial::one()];
+            self.queue.clear();
+            return false;
+        }
+        for g in &self.basis {
+            let lg = g.leading_monomial().unwrap();
+            if lg & lm != 0 {
+                let lcm = lg | lm;
+                self.queue.push_back(g.mul_monomial(lcm & !lg) + h.mul_monomial(lcm & !lm));
+            }
+        }
+        for i in 0..64 {
+            if lm >> i & 1 == 1 {
+                self.queue.push_back(h.mul_monomial(1 << i));
+            }
+        }
+        self.basis.push(h);
+        !self.is_done()
+    }
+
+    /// Runs the remaining steps and returns the reduced Gröbner basis, as
+    /// [`groebner_basis`] does.
+    pub fn resume(mut self) -> Vec<BooleanPolynomial> {
+        while self.step() {}
+        let basis = self.basis;
+        // keep the elements with minimal leading monomials, then reduce the
+        // remaining monomials of each by the others
+        let leading: Vec<u64> = basis.iter().map(|g| g.leading_monomial().unwrap()).collect();
+        let mut minimal: Vec<BooleanPolynomial> = Vec::new();
+        for (i, g) in basis.iter().enumerate() {
+            let redundant = leading
+                .iter()
+                .enumerate()
+                .any(|(j, &l)| j != i && l & !leading[i] == 0 && (l != leading[i] || j < i));
+            if !redundant {
+                minimal.push(g.clone());
+            }
+        }
+        let mut res: Vec<BooleanPolynomial> = (0..minimal.len())
+            .map(|i| {
+                let others: Vec<_> = minimal.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, g)| g.clone()).collect();
+                let g = &minimal[i];
+                let lm = g.leading_monomial().unwrap();
+                let mut tail = g.clone();
+                tail.toggle(lm);
+                let mut reduced = tail.reduce(&others);
+                reduced.toggle(lm);
+                reduced
+            })
+            .collect();
+        res.sort_by_key(|g| graded(g.leading_monomial().unwrap()));
+        res
+    }
+
+    /// A text form of this state for [`GroebnerState::decode`], which stays
+    /// readable by later versions.
+    ///
+    /// The first line is `groebner 1`, the format version. It is followed by
+    /// a `basis` line for each basis element and then a `queue` line for each
+    /// queued polynomial, each listing the monomials as decimal bit masks.
+    pub fn encode(&self) -> String {
+        let line = |name: &str, f: &BooleanPolynomial| {
+            std::iter::once(name.to_string()).chain(f.monomials().map(|m| m.to_string())).collect::<Vec<_>>().join(" ")
+                + "\n"
+        };
+        let mut res = "groebner 1\n".to_string();
+        for f in &self.basis {
+            res += &line("basis", f);
+        }
+        for f in &self.queue {
+            res += &line("queue", f);
+        }
+        res
+    }
+
+    /// Reads a state written by [`GroebnerState::encode`], or `None` if `s`
+    /// is not one.
+    pub fn decode(s: &str) -> Option<Self> {
+        let mut lines = s.lines().map(|line| line.split_once(' ').unwrap_or((line, "")));
+        if lines.next()? != ("groebner", "1") {
+            return None;
+        }
+        let mut state = GroebnerState { basis: Vec::new(), queue: VecDeque::new() };
+        for (name, rest) in lines {
+            let monomials: Vec<u64> = rest.split_whitespace().map(|m| m.parse().ok()).collect::<Option<_>>()?;
+            let f = BooleanPolynomial::new(monomials);
+            match name {
+                // only non-zero polynomials go into the basis
+                "basis" if state.queue.is_empty() && !f.is_zero() => state.basis.push(f),
+                "queue" => state.queue.push_back(f),
+                _ => return None,
+            }
+        }
+        Some(state)
+    }
+}
+
+/// Replaces `f` by `g` with `g(k) = sum(f(j) for j a subset of k)` over
+/// GF(2), one variable at a time.
+fn mobius_transform(values: &mut [bool]) {
+    let mut bit = 1;
+    while bit < values.len() {
+        for k in 0..values.len() {
+            if k & bit != 0 {
+                values[k] ^= values[k ^ bit];
+            }
+        }
+        bit <<= 1;
+    }
+}
+
+impl Zero for BooleanPolynomial {
+    fn zero() -> Self {
+        Self {
+            monomials: BTreeSet::new(),
+        }
+    }
+    fn is_zero(&self) -> bool {
+        self.monomials.is_empty()
+    }
+}
+
+impl One for BooleanPolynomial {
+    fn one() -> Self {
+        Self::new([0])
+    }
+}
+
+impl Add for BooleanPolynomial {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for BooleanPolynomial {
+    fn add_assign(&mut self, rhs: Self) {
+        for m in rhs.monomials {
+            self.toggle(m);
+        }
+    }
+}
+
+/// Every element is its own negative.
+impl Neg for BooleanPolynomial {
+    type Output = Self;
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl Sub for BooleanPolynomial {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs
+    }
+}
+
+/// Monomials multiply by the union of their variables, since `x_i^2 = x_i`.
+impl Mul for BooleanPolynomial {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Self) -> Self {
+        let mut res = Self::zero();
+        for a in &self.monomials {
+            for b in &rhs.monomials {
+                res.toggle(a | b);
+            }
+        }
+        res
+    }
+}
+
+impl Sum for BooleanPolynomial {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+impl Product for BooleanPolynomial {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |a, b| a * b)
+    }
+}
+
+impl CommutativeRing for BooleanPolynomial {
+    /// `1` is the only unit, since `x y = 1` gives `x = x (x y) = x y = 1`.
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(self.is_one());
+        AssertUnit(self)
+    }
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        x.0.clone().assert_is_unit()
+    }
+    /// Boolean rings are reduced, as `x^n = x`.
+    fn is_nilpotent(&self) -> bool {
+        self.is_zero()
+    }
+}
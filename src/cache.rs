@@ -0,0 +1,108 @@
+//! Shared caches for objects that many algorithms recompute.
+//!
+//! A [`Cache`] can be owned and passed around, or shared process-wide with
+//! [`Cache::global`]. It is safe to use from several threads: lookups take a
+//! read lock, and a miss computes the value without holding any lock before
+//! storing it, so two threads may occasionally compute the same value.
+//!
+//! Algorithms that use these objects have `_with_cache` variants, such as
+//! [`factor_xn_minus_1_with_cache`](crate::cyclotomic::factor_xn_minus_1_with_cache),
+//! [`Polynomial::factor_with_cache`] and
+//! [`PolyMod::x_of_degree_with_cache`](crate::fields::PolyMod::x_of_degree_with_cache).
+
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+use num::BigInt;
+
+use crate::cyclotomic::cyclotomic;
+use crate::fields::Zp;
+use crate::number_theory::primes_up_to;
+use crate::Polynomial;
+
+/// Cached primes, cyclotomic polynomials and irreducible moduli.
+#[derive(Debug, Default)]
+pub struct Cache {
+    /// All primes up to some limit, with that limit.
+    primes: RwLock<(u64, Vec<u64>)>,
+    cyclotomic: RwLock<HashMap<usize, Polynomial<BigInt>>>,
+    /// Coefficients of the moduli, keyed by the prime and the degree.
+    irreducible: RwLock<HashMap<(u64, usize), Vec<u64>>>,
+}
+
+impl Cache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A cache shared by the whole process, created on first use.
+    pub fn global() -> &'static Cache {
+        static GLOBAL: OnceLock<Cache> = OnceLock::new();
+        GLOBAL.get_or_init(Cache::new)
+    }
+
+    /// The primes up to `limit`. The sieve is extended to at least double
+    /// its previous limit on a miss, so growing requests stay cheap.
+    pub fn primes_up_to(&self, limit: u64) -> Vec<u64> {
+        {
+            let primes = self.primes.read().unwrap();
+            if primes.0 >= limit {
+                let end = primes.1.partition_point(|&p| p <= limit);
+                return primes.1[..end].to_vec();
+            }
+        }
+        let sieve_limit = limit.max(self.primes.read().unwrap().0.saturating_mul(2));
+        let sieved = primes_up_to(sieve_limit);
+        let end = sieved.partition_point(|&p| p <= limit);
+        let res = sieved[..end].to_vec();
+        let mut primes = self.primes.write().unwrap();
+        if primes.0 < sieve_limit {
+            *primes = (sieve_limit, sieved);
+        }
+        res
+    }
+
+    /// The `n`th cyclotomic polynomial, see [`cyclotomic`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    pub fn cyclotomic(&self, n: usize) -> Polynomial<BigInt> {
+        if let Some(p) = self.cyclotomic.read().unwrap().get(&n) {
+            return p.clone();
+        }
+        let p = cyclotomic(n);
+        self.cyclotomic.write().unwrap().insert(n, p.clone());
+        p
+    }
+
+    /// A monic irreducible polynomial of degree `degree` over `GF(P)`, for
+    /// building the field `GF(P^degree)`. This is the first one in the order
+    /// of [`Polynomial::irreducibles_of_degree`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degree` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::cache::Cache;
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^3 + x + 1 over GF(2)
+    /// let m = Cache::global().irreducible::<2>(3);
+    /// assert_eq!(Polynomial::new([1, 1, 0, 1].map(Zp::new).to_vec()), m);
+    /// ```
+    pub fn irreducible<const P: u64>(&self, degree: usize) -> Polynomial<Zp<P>> {
+        assert!(degree > 0, "degree must be positive");
+        let to_polynomial = |coeffs: &[u64]| Polynomial::new(coeffs.iter().map(|&c| Zp::new(c)).collect());
+        if let Some(coeffs) = self.irreducible.read().unwrap().get(&(P, degree)) {
+            return to_polynomial(coeffs);
+        }
+        let m = Polynomial::<Zp<P>>::irreducibles_of_degree(degree).next().unwrap();
+        let coeffs = m.coeffs.iter().map(|c| c.value()).collect();
+        self.irreducible.write().unwrap().insert((P, degree), coeffs);
+        m
+    }
+}
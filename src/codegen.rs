@@ -0,0 +1,166 @@
+//! Generating evaluation code for fixed float polynomials.
+//!
+//! Once an approximation has been designed (with [`remez`], say), it is
+//! usually evaluated billions of times. These helpers emit Rust source for it,
+//! or build an equivalent closure, using fused multiply-adds throughout.
+//!
+//! [`remez`]: crate::approximation::remez
+
+use std::fmt::Write;
+
+use crate::Polynomial;
+
+/// How to order the multiply-adds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Scheme {
+    /// `(((c_n x + c_{n-1}) x + ...) x + c_0`. The fewest operations, but each
+    /// one depends on the previous.
+    Horner,
+    /// Combines pairs of coefficients with `x`, then pairs of those with `x^2`,
+    /// then `x^4` and so on. The operations within a level are independent,
+    /// which suits pipelined and SIMD hardware.
+    Estrin,
+}
+
+/// Formats `c` so that it parses back to the same `f64`.
+fn literal(c: f64) -> String {
+    if c.is_nan() {
+        "f64::NAN".into()
+    } else if c.is_infinite() {
+        if c > 0.0 { "f64::INFINITY" } else { "f64::NEG_INFINITY" }.into()
+    } else {
+        // `Debug` prints the shortest representation that round trips
+        format!("{c:?}_f64")
+    }
+}
+
+/// Evaluates with Estrin's scheme, using `mul_add` at every step.
+pub(crate) fn estrin(coeffs: &[f64], x: f64) -> f64 {
+    let mut level: Vec<f64> = coeffs
+        .chunks(2)
+        .map(|c| match c {
+            [c0, c1] => c1.mul_add(x, *c0),
+            [c0] => *c0,
+            _ => unreachable!(),
+        })
+        .collect();
+    let mut power = x * x;
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|c| match c {
+                [lo, hi] => hi.mul_add(power, *lo),
+                [lo] => *lo,
+                _ => unreachable!(),
+            })
+            .collect();
+        power *= power;
+    }
+    level.first().copied().unwrap_or(0.0)
+}
+
+impl Polynomial<f64> {
+    /// Evaluates at `x` with Estrin's scheme. See [`Scheme::Estrin`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, 2.0, 3.0, 4.0]);
+    /// assert_eq!(49.0, p.eval_estrin(2.0));
+    /// ```
+    pub fn eval_estrin(&self, x: f64) -> f64 {
+        estrin(&self.coeffs, x)
+    }
+
+    /// Emits a Rust function `pub fn {name}(x: f64) -> f64` evaluating this
+    /// polynomial with the given scheme.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::codegen::Scheme;
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::new(vec![1.0, -0.5, 0.25]);
+    /// assert_eq!(
+    ///     "pub fn p(x: f64) -> f64 {
+    ///     let mut y = 0.25_f64;
+    ///     y = y.mul_add(x, -0.5_f64);
+    ///     y = y.mul_add(x, 1.0_f64);
+    ///     y
+    /// }
+    /// ",
+    ///     p.emit_rust("p", Scheme::Horner)
+    /// );
+    /// ```
+    pub fn emit_rust(&self, name: &str, scheme: Scheme) -> String {
+        let mut out = format!("pub fn {name}(x: f64) -> f64 {{\n");
+        match (scheme, self.coeffs.split_last()) {
+            (_, None) => out.push_str("    let _ = x;\n    0.0_f64\n"),
+            (Scheme::Horner, Some((last, rest))) => {
+                if rest.is_empty() {
+                    out.push_str("    let _ = x;\n");
+                }
+                writeln!(out, "    let mut y = {};", literal(*last)).unwrap();
+                for c in rest.iter().rev() {
+                    writeln!(out, "    y = y.mul_add(x, {});", literal(*c)).unwrap();
+                }
+                out.push_str("    y\n");
+            }
+            (Scheme::Estrin, Some(_)) => {
+                let mut names = Vec::new();
+                for (i, c) in self.coeffs.chunks(2).enumerate() {
+                    let value = match c {
+                        [c0, c1] => format!("f64::mul_add({}, x, {})", literal(*c1), literal(*c0)),
+                        [c0] => literal(*c0),
+                        _ => unreachable!(),
+                    };
+                    writeln!(out, "    let a{i} = {value};").unwrap();
+                    names.push(format!("a{i}"));
+                }
+                if self.coeffs.len() == 1 {
+                    out.push_str("    let _ = x;\n");
+                }
+                let mut power = String::from("x");
+                let mut level = 1;
+                while names.len() > 1 {
+                    let next_power = format!("x{}", 1 << level);
+                    writeln!(out, "    let {next_power} = {power} * {power};").unwrap();
+                    power = next_power;
+                    let mut next = Vec::new();
+                    for (i, pair) in names.chunks(2).enumerate() {
+                        match pair {
+                            [lo, hi] => {
+                                let name = format!("{}{i}", char::from(b'a' + level as u8));
+                                writeln!(out, "    let {name} = f64::mul_add({hi}, {power}, {lo});")
+                                    .unwrap();
+                                next.push(name);
+                            }
+                            [lo] => next.push(lo.clone()),
+                            _ => unreachable!(),
+                        }
+                    }
+                    names = next;
+                    level += 1;
+                }
+                writeln!(out, "    {}", names[0]).unwrap();
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Builds a closure evaluating this polynomial with the given scheme. It
+    /// performs exactly the operations of the code from
+    /// [`Polynomial::emit_rust`], so it gives bit-identical results.
+    pub fn to_closure(&self, scheme: Scheme) -> impl Fn(f64) -> f64 {
+        let coeffs = self.coeffs.clone();
+        move |x| match scheme {
+            Scheme::Horner => match coeffs.split_last() {
+                Some((last, rest)) => rest.iter().rev().fold(*last, |y, c| y.mul_add(x, *c)),
+                None => 0.0,
+            },
+            Scheme::Estrin => estrin(&coeffs, x),
+        }
+    }
+}
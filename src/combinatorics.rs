@@ -0,0 +1,428 @@
+//! Counting polynomials and counting functions from enumerative
+//! combinatorics.
+
+use num::{BigInt, BigRational, One, Zero};
+
+use crate::cyclotomic::{mobius, prime_divisors};
+use crate::factorization::integer_divisors;
+use crate::multivariate::MultiPolynomial;
+use crate::Polynomial;
+
+/// The q-integer `[n]_q = 1 + q + ... + q^(n - 1)`.
+pub fn q_integer(n: usize) -> Polynomial<BigInt> {
+    Polynomial::new(vec![BigInt::one(); n])
+}
+
+/// The q-factorial `[n]_q! = [1]_q [2]_q ... [n]_q`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::q_factorial;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// // (1 + q)(1 + q + q^2)
+/// let expected = Polynomial::new([1, 2, 2, 1].map(BigInt::from).to_vec());
+/// assert_eq!(expected, q_factorial(3));
+/// ```
+pub fn q_factorial(n: usize) -> Polynomial<BigInt> {
+    (1..=n).map(q_integer).product()
+}
+
+/// The Gaussian binomial coefficient `[n choose k]_q`, which counts the
+/// `k`-dimensional subspaces of `GF(q)^n`.
+///
+/// This uses the q-Pascal rule `[n, k] = [n - 1, k - 1] + q^k [n - 1, k]`
+/// instead of dividing q-factorials.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::q_binomial;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// // 1 + q + 2q^2 + q^3 + q^4
+/// let expected = Polynomial::new([1, 1, 2, 1, 1].map(BigInt::from).to_vec());
+/// assert_eq!(expected, q_binomial(4, 2));
+/// ```
+pub fn q_binomial(n: usize, k: usize) -> Polynomial<BigInt> {
+    if k > n {
+        return Polynomial::zero();
+    }
+    // row[j] = [m, j] for the current m.
+    let mut row = vec![Polynomial::one()];
+    for m in 1..=n {
+        let mut next = Vec::with_capacity(row.len() + 1);
+        next.push(Polynomial::one());
+        for j in 1..=m.min(k) {
+            let above = row.get(j).cloned().unwrap_or_else(Polynomial::zero);
+            next.push(row[j - 1].clone() + above.raised_by(j));
+        }
+        row = next;
+    }
+    row.swap_remove(k)
+}
+
+/// The q-Pochhammer symbol `(a; q)_n = (1 - a)(1 - aq)...(1 - aq^(n - 1))`,
+/// where `a` is itself a polynomial in `q`.
+///
+/// `q_pochhammer(q, n)` is `(q; q)_n`, which is `[n]_q!` times `(1 - q)^n`.
+pub fn q_pochhammer(a: &Polynomial<BigInt>, n: usize) -> Polynomial<BigInt> {
+    (0..n)
+        .map(|k| Polynomial::one() - a.clone().raised_by(k))
+        .product()
+}
+
+/// The number of partitions of `n`, using Euler's pentagonal number theorem:
+/// `p(n) = sum_k (-1)^(k + 1) (p(n - k(3k - 1)/2) + p(n - k(3k + 1)/2))`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::partition_count;
+/// # use num::BigInt;
+/// assert_eq!(BigInt::from(42), partition_count(10));
+/// assert_eq!(BigInt::from(190569292), partition_count(100));
+/// ```
+pub fn partition_count(n: usize) -> BigInt {
+    let mut p = vec![BigInt::one()];
+    for m in 1..=n {
+        let mut total = BigInt::zero();
+        for k in 1.. {
+            let first = k * (3 * k - 1) / 2;
+            if first > m {
+                break;
+            }
+            let mut term = p[m - first].clone();
+            let second = k * (3 * k + 1) / 2;
+            if second <= m {
+                term += &p[m - second];
+            }
+            if k % 2 == 1 {
+                total += term;
+            } else {
+                total -= term;
+            }
+        }
+        p.push(total);
+    }
+    p.swap_remove(n)
+}
+
+/// The generating function of partitions into parts from `parts`, up to and
+/// including the coefficient of `x^n`. Parts may repeat unless `distinct` is
+/// set.
+///
+/// This is the product of `1 / (1 - x^k)` (or `1 + x^k` for distinct parts)
+/// over all `k` in `parts`, truncated mod `x^(n + 1)`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::restricted_partitions;
+/// # use num::BigInt;
+/// // 10 = 5 + 5 = 5 + 2 + 2 + 1 = ...
+/// let series = restricted_partitions(&[1, 2, 5], false, 10);
+/// assert_eq!(&BigInt::from(10), series.coeff_at(10));
+/// ```
+pub fn restricted_partitions(parts: &[usize], distinct: bool, n: usize) -> Polynomial<BigInt> {
+    let mut coeffs = vec![BigInt::zero(); n + 1];
+    coeffs[0] = BigInt::one();
+    for &k in parts {
+        assert!(k > 0, "parts must be positive");
+        if distinct {
+            // multiply by 1 + x^k
+            for i in (k..=n).rev() {
+                let c = coeffs[i - k].clone();
+                coeffs[i] += c;
+            }
+        } else {
+            // divide by 1 - x^k
+            for i in k..=n {
+                let c = coeffs[i - k].clone();
+                coeffs[i] += c;
+            }
+        }
+    }
+    Polynomial::new_trim_zeroes(coeffs)
+}
+
+/// The number of partitions of `n` into parts from `parts`.
+pub fn restricted_partition_count(parts: &[usize], distinct: bool, n: usize) -> BigInt {
+    let series = restricted_partitions(parts, distinct, n);
+    if series.degree() == Some(n) {
+        series.coeff_at(n).clone()
+    } else {
+        BigInt::zero()
+    }
+}
+
+/// The number of necklaces of length `n` over an alphabet of size `k`: words
+/// up to rotation.
+///
+/// By Burnside's lemma this averages the fixed points of the rotations, and
+/// a rotation by `i` fixes `k^gcd(i, n)` words, which gives
+/// `sum(φ(d) k^(n / d) for d | n) / n`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::necklace_count;
+/// # use num::BigInt;
+/// // 000, 001, 011, 111
+/// assert_eq!(BigInt::from(4), necklace_count(3, 2));
+/// assert_eq!(BigInt::from(44_368), necklace_count(12, 3));
+/// ```
+pub fn necklace_count(n: usize, k: u64) -> BigInt {
+    assert!(n > 0, "length must be positive");
+    let k = BigInt::from(k);
+    let sum: BigInt = integer_divisors(n)
+        .into_iter()
+        .map(|d| {
+            let totient = prime_divisors(d).iter().fold(d, |acc, p| acc / p * (p - 1));
+            num::pow(k.clone(), n / d) * totient
+        })
+        .sum();
+    sum / n
+}
+
+/// The number of Lyndon words of length `n` over an alphabet of size `k`,
+/// which are the aperiodic necklaces.
+///
+/// Each necklace of length `n` is a Lyndon word of some length `d | n`
+/// repeated, and there are `k^n` words, each in a class of `d` rotations,
+/// so Möbius inversion gives `sum(μ(n / d) k^d for d | n) / n`. For a prime
+/// power `k` this is also the number of monic irreducible polynomials of
+/// degree `n` over `GF(k)`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn lyndon_count(n: usize, k: u64) -> BigInt {
+    assert!(n > 0, "length must be positive");
+    let k = BigInt::from(k);
+    let sum: BigInt = integer_divisors(n)
+        .into_iter()
+        .map(|d| num::pow(k.clone(), d) * mobius(n / d))
+        .sum();
+    sum / n
+}
+
+/// The Lyndon words of length at most `n` over the letters `0..k`, in
+/// lexicographic order. A Lyndon word is strictly smaller than all of its
+/// proper rotations.
+///
+/// This is Duval's algorithm: the next word is found by repeating the
+/// current one up to length `n`, dropping trailing maximal letters and
+/// incrementing the last letter. Each word takes constant amortized time.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::lyndon_words;
+/// let words: Vec<_> = lyndon_words(3, 2).collect();
+/// assert_eq!(vec![vec![0], vec![0, 0, 1], vec![0, 1], vec![0, 1, 1], vec![1]], words);
+/// ```
+pub fn lyndon_words(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    let mut word = if n == 0 || k == 0 { Vec::new() } else { vec![0] };
+    std::iter::from_fn(move || {
+        if word.is_empty() {
+            return None;
+        }
+        let res = word.clone();
+        let m = word.len();
+        while word.len() < n {
+            word.push(word[word.len() - m]);
+        }
+        while word.last() == Some(&(k - 1)) {
+            word.pop();
+        }
+        if let Some(last) = word.last_mut() {
+            *last += 1;
+        }
+        Some(res)
+    })
+}
+
+/// The necklaces of length `n` over the letters `0..k`, each as its
+/// lexicographically smallest rotation, in lexicographic order.
+///
+/// These are the Lyndon words whose length `d` divides `n`, repeated
+/// `n / d` times.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::necklaces;
+/// let words: Vec<_> = necklaces(4, 2).collect();
+/// assert_eq!(
+///     vec![
+///         vec![0, 0, 0, 0],
+///         vec![0, 0, 0, 1],
+///         vec![0, 0, 1, 1],
+///         vec![0, 1, 0, 1],
+///         vec![0, 1, 1, 1],
+///         vec![1, 1, 1, 1],
+///     ],
+///     words
+/// );
+/// ```
+pub fn necklaces(n: usize, k: usize) -> impl Iterator<Item = Vec<usize>> {
+    lyndon_words(n, k)
+        .filter(move |w| n.is_multiple_of(w.len()))
+        .map(move |w| w.repeat(n / w.len()))
+}
+
+/// The monomial `a_k^e`, with `a_k` the variable `x_(k - 1)`.
+fn cycle_power(k: usize, e: usize) -> MultiPolynomial<BigRational> {
+    let mut exponents = vec![0; k];
+    exponents[k - 1] = e;
+    MultiPolynomial::new([(exponents, BigRational::one())])
+}
+
+fn fraction(a: usize, b: usize) -> BigRational {
+    BigRational::new(a.into(), b.into())
+}
+
+/// The cycle index of the cyclic group `C_n` rotating `n` points,
+/// `sum(φ(d) a_d^(n / d) for d | n) / n`.
+///
+/// A cycle index is the average over the group of the monomials
+/// `a_1^c_1 a_2^c_2 ...`, where a permutation has `c_k` cycles of length `k`.
+/// The variable `a_k` is `x_(k - 1)`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::cycle_index_cyclic;
+/// # use math2::multivariate::MultiPolynomial;
+/// # use num::BigRational;
+/// // (a_1^4 + a_2^2 + 2 a_4) / 4
+/// let z = cycle_index_cyclic(4);
+/// let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+/// assert_eq!(Some(&q(1, 4)), z.coeff(&[4]));
+/// assert_eq!(Some(&q(1, 4)), z.coeff(&[0, 2]));
+/// assert_eq!(Some(&q(1, 2)), z.coeff(&[0, 0, 0, 1]));
+/// ```
+pub fn cycle_index_cyclic(n: usize) -> MultiPolynomial<BigRational> {
+    assert!(n > 0, "the group acts on at least one point");
+    integer_divisors(n)
+        .into_iter()
+        .map(|d| {
+            let totient = prime_divisors(d).iter().fold(d, |acc, p| acc / p * (p - 1));
+            cycle_power(d, n / d).scalar_mul(fraction(totient, n))
+        })
+        .sum()
+}
+
+/// The cycle index of the dihedral group `D_n` of the `2n` symmetries of a
+/// regular `n`-gon. The rotations contribute half of
+/// [`cycle_index_cyclic`], and the reflections fix one point and swap the
+/// others in pairs for odd `n`, or fix two or none for even `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+pub fn cycle_index_dihedral(n: usize) -> MultiPolynomial<BigRational> {
+    let rotations = cycle_index_cyclic(n).scalar_mul(fraction(1, 2));
+    let reflections = if n % 2 == 1 {
+        (cycle_power(1, 1) * cycle_power(2, n / 2)).scalar_mul(fraction(1, 2))
+    } else {
+        let two_fixed = cycle_power(1, 2) * cycle_power(2, n / 2 - 1);
+        (cycle_power(2, n / 2) + two_fixed).scalar_mul(fraction(1, 4))
+    };
+    rotations + reflections
+}
+
+/// The cycle index of the symmetric group `S_n` of all permutations of `n`
+/// points, by the recurrence `Z(S_n) = sum(a_k Z(S_(n - k)) for 1 <= k <= n) / n`
+/// that picks the cycle of the first point.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::cycle_index_symmetric;
+/// # use num::BigRational;
+/// // (a_1^3 + 3 a_1 a_2 + 2 a_3) / 6
+/// let z = cycle_index_symmetric(3);
+/// let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+/// assert_eq!(3, z.terms().count());
+/// assert_eq!(Some(&q(1, 2)), z.coeff(&[1, 1]));
+/// ```
+pub fn cycle_index_symmetric(n: usize) -> MultiPolynomial<BigRational> {
+    let mut indices = vec![MultiPolynomial::one()];
+    for m in 1..=n {
+        let sum: MultiPolynomial<BigRational> =
+            (1..=m).map(|k| cycle_power(k, 1) * indices[m - k].clone()).sum();
+        indices.push(sum.scalar_mul(fraction(1, m)));
+    }
+    indices.swap_remove(n)
+}
+
+/// Pólya's substitution of the figure counting series `figures` into a
+/// cycle index: each `a_k` becomes `figures` with every variable raised to
+/// the power `k`. With `figures = x_0 + ... + x_(m - 1)` for `m` colours,
+/// the coefficient of `x_0^e_0 ... x_(m - 1)^e_(m - 1)` in the result counts
+/// the orbits of colourings with `e_i` points of colour `i`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::{cycle_index_cyclic, polya_substitution};
+/// # use math2::multivariate::MultiPolynomial;
+/// # use num::BigRational;
+/// // necklaces of 4 beads, r red and 4 - r blue
+/// let colours = MultiPolynomial::var(0) + MultiPolynomial::var(1);
+/// let counts = polya_substitution(&cycle_index_cyclic(4), &colours);
+/// let n = |a: i64| BigRational::from_integer(a.into());
+/// assert_eq!(Some(&n(1)), counts.coeff(&[4, 0]));
+/// assert_eq!(Some(&n(1)), counts.coeff(&[3, 1]));
+/// // rrbb and rbrb
+/// assert_eq!(Some(&n(2)), counts.coeff(&[2, 2]));
+/// ```
+pub fn polya_substitution(
+    cycle_index: &MultiPolynomial<BigRational>,
+    figures: &MultiPolynomial<BigRational>,
+) -> MultiPolynomial<BigRational> {
+    let substituted: Vec<MultiPolynomial<BigRational>> = (1..=cycle_index.num_vars())
+        .map(|k| {
+            MultiPolynomial::new(
+                figures.terms().map(|(exponents, c)| (exponents.iter().map(|e| e * k).collect(), c.clone())),
+            )
+        })
+        .collect();
+    cycle_index
+        .terms()
+        .map(|(exponents, c)| {
+            exponents
+                .iter()
+                .zip(&substituted)
+                .flat_map(|(&e, f)| std::iter::repeat_n(f, e))
+                .fold(MultiPolynomial::constant(c.clone()), |acc, f| acc * f.clone())
+        })
+        .sum()
+}
+
+/// The number of orbits of colourings with `colours` colours, the cycle
+/// index at `a_k = colours` for every `k`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::combinatorics::{cycle_index_dihedral, polya_count};
+/// # use num::BigInt;
+/// // bracelets of 6 beads in 2 colours
+/// assert_eq!(BigInt::from(13), polya_count(&cycle_index_dihedral(6), 2));
+/// ```
+pub fn polya_count(cycle_index: &MultiPolynomial<BigRational>, colours: u64) -> BigInt {
+    let point = vec![BigRational::from_integer(colours.into()); cycle_index.num_vars()];
+    cycle_index.eval(&point).to_integer()
+}
@@ -0,0 +1,227 @@
+//! Cyclotomic polynomials and factorizations of binomials `x^n - a` over `Q`.
+
+use num::{BigInt, One, Signed, Zero};
+
+use crate::cache::Cache;
+use crate::factorization::integer_divisors;
+use crate::Polynomial;
+
+/// The Möbius function of a positive integer.
+pub(crate) fn mobius(mut n: usize) -> i8 {
+    let mut res = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            res = -res;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        res = -res;
+    }
+    res
+}
+
+pub(crate) fn prime_divisors(mut n: usize) -> Vec<usize> {
+    let mut res = Vec::new();
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            res.push(p);
+            while n.is_multiple_of(p) {
+                n /= p;
+            }
+        }
+        p += 1;
+    }
+    if n > 1 {
+        res.push(n);
+    }
+    res
+}
+
+fn sorted_divisors(n: usize) -> Vec<usize> {
+    let mut divisors = integer_divisors(n);
+    divisors.sort_unstable();
+    divisors
+}
+
+/// `x^n - a`
+fn binomial(n: usize, a: BigInt) -> Polynomial<BigInt> {
+    Polynomial::new_trim_zeroes(vec![-a]) + Polynomial::from_elem_with_degree(BigInt::one(), n)
+}
+
+/// The `n`-th cyclotomic polynomial, the minimal polynomial of a primitive
+/// `n`-th root of unity.
+///
+/// This uses `Φ_n = ∏_{d | n} (x^d - 1)^μ(n / d)`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::cyclotomic::cyclotomic;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// // x^4 - x^2 + 1
+/// let expected = Polynomial::new([1, 0, -1, 0, 1].map(BigInt::from).to_vec());
+/// assert_eq!(expected, cyclotomic(12));
+/// ```
+pub fn cyclotomic(n: usize) -> Polynomial<BigInt> {
+    assert!(n > 0, "there is no 0th cyclotomic polynomial");
+    let mut numerator = Polynomial::one();
+    let mut denominator = Polynomial::one();
+    for d in integer_divisors(n) {
+        match mobius(n / d) {
+            1 => numerator = numerator * binomial(d, BigInt::one()),
+            -1 => denominator = denominator * binomial(d, BigInt::one()),
+            _ => {}
+        }
+    }
+    // the denominator is monic, so the division is exact over Z.
+    numerator.div_rem(denominator).0
+}
+
+/// Factors `x^n - 1` into the cyclotomic polynomials `Φ_d` for `d | n`, in
+/// order of increasing `d`. These are irreducible over `Q`.
+pub fn factor_xn_minus_1(n: usize) -> Vec<Polynomial<BigInt>> {
+    sorted_divisors(n).into_iter().map(cyclotomic).collect()
+}
+
+/// [`factor_xn_minus_1`], taking the cyclotomic polynomials from `cache`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::cache::Cache;
+/// # use math2::cyclotomic::{factor_xn_minus_1, factor_xn_minus_1_with_cache};
+/// let cache = Cache::new();
+/// assert_eq!(factor_xn_minus_1(12), factor_xn_minus_1_with_cache(12, &cache));
+/// ```
+pub fn factor_xn_minus_1_with_cache(n: usize, cache: &Cache) -> Vec<Polynomial<BigInt>> {
+    sorted_divisors(n).into_iter().map(|d| cache.cyclotomic(d)).collect()
+}
+
+/// Factors `x^n + 1` into the cyclotomic polynomials `Φ_d` for the `d`
+/// dividing `2n` but not `n`, in order of increasing `d`.
+pub fn factor_xn_plus_1(n: usize) -> Vec<Polynomial<BigInt>> {
+    factor_xn_plus_1_by(n, cyclotomic)
+}
+
+/// [`factor_xn_plus_1`], taking the cyclotomic polynomials from `cache`.
+pub fn factor_xn_plus_1_with_cache(n: usize, cache: &Cache) -> Vec<Polynomial<BigInt>> {
+    factor_xn_plus_1_by(n, |d| cache.cyclotomic(d))
+}
+
+fn factor_xn_plus_1_by(n: usize, cyclotomic: impl Fn(usize) -> Polynomial<BigInt>) -> Vec<Polynomial<BigInt>> {
+    sorted_divisors(2 * n)
+        .into_iter()
+        .filter(|&d| !n.is_multiple_of(d))
+        .map(cyclotomic)
+        .collect()
+}
+
+/// Returns `b` if `a = b^k`. For even `k` and positive `a` this is the
+/// positive root.
+fn exact_root(a: &BigInt, k: u32) -> Option<BigInt> {
+    if a.is_negative() && k.is_multiple_of(2) {
+        return None;
+    }
+    let b = a.nth_root(k);
+    (b.pow(k) == *a).then_some(b)
+}
+
+/// `b^φ(d) Φ_d(x / b)`, which has integer coefficients and is irreducible.
+fn homogenized_cyclotomic(d: usize, b: &BigInt) -> Polynomial<BigInt> {
+    let phi = cyclotomic(d);
+    let degree = phi.degree().unwrap();
+    Polynomial::new(
+        phi.into_iter()
+            .enumerate()
+            .map(|(i, c)| c * b.pow((degree - i) as u32))
+            .collect(),
+    )
+}
+
+/// Capelli's theorem: `x^n - a` is irreducible over `Q` if and only if `a`
+/// is not a `p`-th power for any prime `p | n`, and `a` is not of the form
+/// `-4c^4` when `4 | n`.
+fn is_binomial_irreducible(n: usize, a: &BigInt) -> bool {
+    if prime_divisors(n)
+        .into_iter()
+        .any(|p| exact_root(a, p as u32).is_some())
+    {
+        return false;
+    }
+    if n.is_multiple_of(4) && a.is_negative() {
+        let quarter = -a / BigInt::from(4);
+        if &quarter * BigInt::from(-4) == *a && exact_root(&quarter, 4).is_some() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Factors `x^n - a` into irreducible polynomials over `Q` (with integer
+/// coefficients), when that can be done without a general factorization
+/// algorithm. Returns `None` for the remaining cases.
+///
+/// The handled cases are:
+///
+/// - `x^n - a` is irreducible by Capelli's theorem,
+/// - `a = b^n`, where the factors are `b^φ(d) Φ_d(x / b)`,
+/// - `a = b^2` for even `n`, which splits into `x^(n/2) ∓ b` and recurses,
+/// - `x^4 + 4c^4 = (x^2 + 2cx + 2c^2)(x^2 - 2cx + 2c^2)`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::cyclotomic::factor_xn_minus_a;
+/// # use num::BigInt;
+/// // x^4 - 4 = (x^2 - 2)(x^2 + 2)
+/// assert_eq!(2, factor_xn_minus_a(4, &BigInt::from(4)).unwrap().len());
+/// // x^5 - 3 is irreducible
+/// assert_eq!(1, factor_xn_minus_a(5, &BigInt::from(3)).unwrap().len());
+/// ```
+pub fn factor_xn_minus_a(n: usize, a: &BigInt) -> Option<Vec<Polynomial<BigInt>>> {
+    assert!(n > 0, "x^0 - a is constant");
+    if a.is_zero() {
+        return Some(vec![binomial(1, BigInt::zero()); n]);
+    }
+    if let Some(b) = u32::try_from(n).ok().and_then(|k| exact_root(a, k)) {
+        return Some(
+            sorted_divisors(n)
+                .into_iter()
+                .map(|d| homogenized_cyclotomic(d, &b))
+                .collect(),
+        );
+    }
+    if is_binomial_irreducible(n, a) {
+        return Some(vec![binomial(n, a.clone())]);
+    }
+    if n.is_multiple_of(2) {
+        if let Some(b) = exact_root(a, 2) {
+            let mut factors = factor_xn_minus_a(n / 2, &b)?;
+            factors.extend(factor_xn_minus_a(n / 2, &-b)?);
+            return Some(factors);
+        }
+    }
+    if n == 4 {
+        // a = -4c^4, checked by `is_binomial_irreducible`.
+        let c: BigInt = (-a / BigInt::from(4)).nth_root(4);
+        let two_c2 = &c * &c * BigInt::from(2);
+        let two_c = c * BigInt::from(2);
+        return Some(vec![
+            Polynomial::new(vec![two_c2.clone(), two_c.clone(), BigInt::one()]),
+            Polynomial::new(vec![two_c2, -two_c, BigInt::one()]),
+        ]);
+    }
+    None
+}
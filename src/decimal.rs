@@ -0,0 +1,84 @@
+//! Correctly rounded decimal output for rationals and rational polynomials.
+
+use num::{BigInt, BigRational, Signed, Zero};
+
+use crate::interval::Interval;
+use crate::Polynomial;
+
+/// `x * 10^digits` rounded to the nearest integer, halves away from zero.
+fn scaled_round(x: &BigRational, digits: u32) -> BigInt {
+    let scale = BigRational::from_integer(BigInt::from(10).pow(digits));
+    (x * scale).round().to_integer()
+}
+
+fn format_scaled(n: &BigInt, digits: u32) -> String {
+    let digits = digits as usize;
+    let mut s = n.abs().to_string();
+    if s.len() <= digits {
+        s.insert_str(0, &"0".repeat(digits + 1 - s.len()));
+    }
+    if digits > 0 {
+        s.insert(s.len() - digits, '.');
+    }
+    if n.is_negative() {
+        s.insert(0, '-');
+    }
+    s
+}
+
+/// Formats `x` with `digits` digits after the decimal point, rounding to
+/// nearest with halves away from zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::decimal::to_decimal_string;
+/// # use num::BigRational;
+/// let x = BigRational::new((-2).into(), 3.into());
+/// assert_eq!("-0.6667", to_decimal_string(&x, 4));
+/// ```
+pub fn to_decimal_string(x: &BigRational, digits: u32) -> String {
+    format_scaled(&scaled_round(x, digits), digits)
+}
+
+impl Polynomial<BigRational> {
+    /// Evaluates this polynomial at `x`, returning the value correctly rounded
+    /// to `digits` digits after the decimal point.
+    ///
+    /// The value is first enclosed with interval arithmetic, rounding
+    /// outwards to a working precision a little above `digits` so that the
+    /// numbers stay small. If both ends of the enclosure round to the same
+    /// decimal, that decimal is the answer; otherwise the working precision is
+    /// doubled. A value sitting exactly on a rounding boundary can never be
+    /// decided this way, so after a few rounds the exact value is computed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // x^2 - 2 at 1.41421
+    /// let p = Polynomial::new(vec![n(-2), n(0), n(1)]);
+    /// let x = BigRational::new(141421.into(), 100000.into());
+    /// assert_eq!("-0.0000100759", p.eval_decimal(&x, 10));
+    /// ```
+    pub fn eval_decimal(&self, x: &BigRational, digits: u32) -> String {
+        let mut precision = digits + 8;
+        for _ in 0..4 {
+            let x = Interval::point(x.clone()).round_outward(precision);
+            let y = self.eval_interval_rounded(&x, Some(precision));
+            let lo = scaled_round(y.lo(), digits);
+            if lo == scaled_round(y.hi(), digits) {
+                return format_scaled(&lo, digits);
+            }
+            precision *= 2;
+        }
+        let exact = self
+            .coeffs
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * x + c);
+        to_decimal_string(&exact, digits)
+    }
+}
@@ -1,11 +1,11 @@
 use std::num::NonZeroUsize;
-use std::ops::{RangeInclusive};
+use std::ops::{Mul, RangeInclusive};
 
 use num::integer::Roots;
-use num::traits::Inv;
-use num::{BigInt, BigRational, One, Zero, Integer};
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
 
-use crate::traits::{CommutativeRing, Field, FromUsize};
+use crate::number_theory::{divisor_lattice, factor_integer};
+use crate::traits::{CoefficientDomain, CommutativeRing, Field, FromUsize};
 use crate::Polynomial;
 
 /*
@@ -16,9 +16,11 @@ pub fn binomial_product<Ring: CommutativeRing>(nums: Vec<Ring>) -> Polynomial<Ri
 }
 */
 
-// TODO make this an iterator interface
 /// Find all positive divisors of an integer
 ///
+/// This tries every number up to the square root, see [`divisors`] for large
+/// integers.
+///
 /// # Examples
 /// 
 /// ```
@@ -36,6 +38,9 @@ pub fn integer_divisors<N: Roots + Clone>(x: N) -> Vec<N>
 where
     RangeInclusive<N>: IntoIterator<Item = N>,
 {
+    if x.is_one() {
+        return vec![x];
+    }
     let n = x.sqrt();
     let mut res = vec![ N::one(), x.clone() ];
     for div in (N::one() + N::one()) ..= n {
@@ -50,52 +55,230 @@ where
     res
 }
 
-// TODO generic
-/// Returns the polynomial that interpolates the given points. This takes O(n^2) time.
+/// The positive divisors of a positive integer, in no particular order.
+///
+/// The divisors are generated one at a time from the prime factorization of
+/// `n`, so this works for integers far too large for [`integer_divisors`].
+/// Each step multiplies the previous divisor by a prime, or divides out the
+/// prime powers of the exponents that wrap around.
+///
+/// # Panics
+///
+/// Panics if `n` is not positive.
+///
+/// # Examples
+///
+/// ```
+/// # use math2::factorization::divisors;
+/// # use num::BigInt;
+/// let mut small: Vec<_> = divisors(36).collect();
+/// small.sort_unstable();
+/// assert_eq!(vec![1, 2, 3, 4, 6, 9, 12, 18, 36], small);
+///
+/// // 2^64 + 1 = 274177 * 67280421310721
+/// let n = BigInt::from(u64::MAX) + 2;
+/// assert_eq!(4, divisors(n).count());
+/// ```
+pub fn divisors<N>(n: N) -> impl Iterator<Item = N>
+where
+    N: Into<BigInt> + TryFrom<BigInt>,
+{
+    let n: BigInt = n.into();
+    assert!(n.is_positive(), "n must be positive");
+    let factors = factor_integer(&n);
+    let mut exponents = vec![0; factors.len()];
+    let mut current = Some(BigInt::one());
+    std::iter::from_fn(move || {
+        let d = current.take()?;
+        let mut next = d.clone();
+        for ((p, e), k) in factors.iter().zip(&mut exponents) {
+            if *k < *e {
+                *k += 1;
+                next *= p;
+                current = Some(next);
+                break;
+            }
+            next /= p.pow(*k);
+            *k = 0;
+        }
+        Some(d)
+    })
+    // every divisor is at most n, which fits in N
+    .map(|d| N::try_from(d).unwrap_or_else(|_| unreachable!()))
+}
+
+/// Returns the polynomial that interpolates the given points, whose
+/// `x`-coordinates must be distinct. This takes O(n^2) time.
 ///
 /// # Example
 ///
 /// ```
 /// # use math2::factorization::lagrange_interpolation;
+/// # use math2::fields::Zp;
 /// # use math2::Polynomial;
 /// # use num::BigRational;
 /// let n = |x: i32| BigRational::from_integer(x.into());
 /// let points = vec![(n(1), n(1)), (n(2), n(-1))];
 /// let p = lagrange_interpolation(points);
 /// assert_eq!(p, Polynomial::new(vec![n(3), n(-2)]));
+///
+/// // over GF(7), x^2 + 1 takes the values 1, 2 and 5 at 0, 1 and 2
+/// let z = Zp::<7>::new;
+/// let p = lagrange_interpolation(vec![(z(0), z(1)), (z(1), z(2)), (z(2), z(5))]);
+/// assert_eq!(p, Polynomial::new(vec![z(1), z(0), z(1)]));
 /// ```
-pub fn lagrange_interpolation(points: Vec<(BigRational, BigRational)>) -> Polynomial<BigRational> {
-    let points2 = points.clone();
-    points2
-        .into_iter()
+pub fn lagrange_interpolation<F: Field + Clone + PartialEq>(points: Vec<(F, F)>) -> Polynomial<F> {
+    points
+        .iter()
         .map(|(x, y)| {
-            let p: Polynomial<BigRational> = points
+            let p: Polynomial<F> = points
                 .iter()
-                .filter(|(x2, _)| &x != x2)
-                .map(|(x, _)| Polynomial::new(vec![-x, BigRational::one()]))
+                .filter(|(x2, _)| x != x2)
+                .map(|(x, _)| Polynomial::new(vec![-x.clone(), F::one()]))
                 .product();
-            p * y
-                * points
-                    .iter()
-                    .filter(|(x2, _)| &x != x2)
-                    .map(|(x2, _)| &x - x2)
-                    .product::<BigRational>()
-                    .inv()
+            let denominator = points
+                .iter()
+                .filter(|(x2, _)| x != x2)
+                .fold(F::one(), |acc, (x2, _)| acc * (x.clone() + -x2.clone()));
+            p.scalar_mul(y.clone() * denominator.checked_inv().unwrap())
         })
         .sum()
 }
 
+/// Interpolation in the Newton form, which takes the points one at a time.
+///
+/// The interpolating polynomial of `x_0, ..., x_n` is the sum of
+/// `f[x_0, ..., x_k] (x - x_0) ... (x - x_{k - 1})`, with the divided
+/// differences `f[x_0, ..., x_k]` as coefficients. Adding a point only adds a
+/// term, so [`push`](Self::push) takes O(n) operations, updating the
+/// differences `f[x_i, ..., x_n]` that end at the last point.
+///
+/// # Example
+///
+/// ```
+/// # use math2::factorization::{lagrange_interpolation, NewtonInterpolator};
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// let mut interpolator = NewtonInterpolator::new();
+/// interpolator.push(n(0), n(1));
+/// interpolator.push(n(1), n(3));
+/// assert_eq!(&Polynomial::new(vec![n(1), n(2)]), interpolator.polynomial());
+/// // adding (2, 7) gives x^2 + x + 1
+/// interpolator.push(n(2), n(7));
+/// assert_eq!(&Polynomial::new(vec![n(1), n(1), n(1)]), interpolator.polynomial());
+/// assert_eq!(&[n(1), n(2), n(1)], interpolator.divided_differences());
+/// ```
+#[derive(Debug, Clone)]
+pub struct NewtonInterpolator<F> {
+    xs: Vec<F>,
+    /// `f[x_0, ..., x_k]` for each `k`.
+    coeffs: Vec<F>,
+    /// `f[x_i, ..., x_n]` for each `i`, with `x_n` the last point.
+    tail: Vec<F>,
+    /// `(x - x_0) ... (x - x_n)`.
+    basis: Polynomial<F>,
+    polynomial: Polynomial<F>,
+}
+
+impl<F: Field + Clone + PartialEq> NewtonInterpolator<F> {
+    pub fn new() -> Self {
+        Self {
+            xs: Vec::new(),
+            coeffs: Vec::new(),
+            tail: Vec::new(),
+            basis: Polynomial::one(),
+            polynomial: Polynomial::zero(),
+        }
+    }
+
+    /// Adds the point `(x, y)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is one of the points already added.
+    pub fn push(&mut self, x: F, y: F) {
+        let mut difference = y.clone();
+        for (xi, d) in self.xs.iter().zip(&mut self.tail).rev() {
+            let step = (x.clone() + -xi.clone())
+                .checked_inv()
+                .expect("interpolation points must be distinct");
+            difference = (difference + -d.clone()) * step;
+            *d = difference.clone();
+        }
+        self.tail.push(y);
+        self.coeffs.push(difference.clone());
+        self.polynomial += self.basis.clone().scalar_mul(difference);
+        self.basis = self.basis.clone() * Polynomial::new(vec![-x.clone(), F::one()]);
+        self.xs.push(x);
+    }
+
+    /// The polynomial of degree less than [`len`](Self::len) through the
+    /// points.
+    pub fn polynomial(&self) -> &Polynomial<F> {
+        &self.polynomial
+    }
+
+    /// The coefficients `f[x_0, ..., x_k]` of the Newton form.
+    pub fn divided_differences(&self) -> &[F] {
+        &self.coeffs
+    }
+
+    /// Evaluates the interpolating polynomial at `x` from the Newton form, in
+    /// O(n) operations.
+    pub fn eval(&self, x: &F) -> F {
+        self.xs
+            .iter()
+            .zip(&self.coeffs)
+            .rev()
+            .fold(F::zero(), |acc, (xi, c)| acc * (x.clone() + -xi.clone()) + c.clone())
+    }
+
+    /// The number of points.
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+}
+
+impl<F: Field + Clone + PartialEq> Default for NewtonInterpolator<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F: Field + Clone + PartialEq> Extend<(F, F)> for NewtonInterpolator<F> {
+    fn extend<I: IntoIterator<Item = (F, F)>>(&mut self, points: I) {
+        for (x, y) in points {
+            self.push(x, y);
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct SquareFreeFactorization<F> {
     pub leading_coeff: F,
     pub factors: Vec<(Polynomial<F>, NonZeroUsize)>,
 }
 
+/// A factorization into irreducible polynomials, with multiplicities.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Factorization<F> {
+    pub leading_coeff: F,
+    pub factors: Vec<(Polynomial<F>, NonZeroUsize)>,
+}
+
 impl<F: Field> Polynomial<F> {
     /// Returns the square free factorization of this polynomial, using Yun's algorithm.
+    ///
+    /// Each factor is divided by its content, so the factors of a rational
+    /// polynomial are primitive integer polynomials.
     pub fn square_free_factorization(self) -> SquareFreeFactorization<F>
     where
-        F: FromUsize + PartialEq,
+        F: FromUsize + PartialEq + CoefficientDomain,
     {
         if self.is_zero() {
             return SquareFreeFactorization {
@@ -106,41 +289,592 @@ impl<F: Field> Polynomial<F> {
 
         let leading_coeff = self.leading_coefficient_cloned();
         let u = self.scalar_mul(leading_coeff.clone().checked_inv().unwrap());
-        let mut factors = Vec::new();
-        let mut r = u.clone().gcd(u.clone().derivative());
-        let mut f = u.div_rem(r.clone()).0;
-        let mut j = NonZeroUsize::new(1).unwrap();
-        while !r.is_one() {
-            let g = r.clone().gcd(f.clone());
-            let s = f.div_rem(g.clone()).0;
-            if !s.is_one() {
-                factors.push((s, j));
+        let mut factors = square_free_monic(u);
+        factors.sort_by_key(|(_, j)| *j);
+        let mut leading_coeff = leading_coeff;
+        remove_contents(&mut leading_coeff, &mut factors);
+        SquareFreeFactorization {
+            leading_coeff,
+            factors,
+        }
+    }
+}
+
+/// Divides each factor by its content, moving the contents into the leading
+/// coefficient.
+fn remove_contents<F: Field + CoefficientDomain>(leading_coeff: &mut F, factors: &mut [(Polynomial<F>, NonZeroUsize)]) {
+    for (factor, exp) in factors {
+        let content = factor.clone().content();
+        *factor = factor.take().scalar_mul(content.checked_inv().unwrap());
+        for _ in 0..exp.get() {
+            *leading_coeff = leading_coeff.clone() * content.clone();
+        }
+    }
+}
+
+/// Yun's algorithm on a monic polynomial `u`.
+///
+/// In characteristic `p` the derivative of a `p`th power vanishes, so the
+/// loop runs out of work while `r` is still a non-trivial `p`th power. Its
+/// `p`th root is factored recursively and the multiplicities scaled by `p`.
+pub(crate) fn square_free_monic<F>(u: Polynomial<F>) -> Vec<(Polynomial<F>, NonZeroUsize)>
+where
+    F: Field + FromUsize + PartialEq,
+{
+    let mut factors = Vec::new();
+    let mut r = u.clone().gcd(u.clone().derivative());
+    let mut f = u.div_rem(r.clone()).0;
+    let mut j = NonZeroUsize::new(1).unwrap();
+    while !r.is_one() {
+        if f.is_one() {
+            let p = F::characteristic();
+            assert_ne!(0, p, "gcd(u, u') is not 1 but u is square free");
+            let root = Polynomial::new(r.coeffs.into_iter().step_by(p).map(F::pth_root).collect());
+            for (g, k) in square_free_monic(root) {
+                factors.push((g, k.checked_mul(NonZeroUsize::new(p).unwrap()).unwrap()));
             }
-            r = r.div_rem(g.clone()).0;
-            f = g;
-            j = j.saturating_add(1);
+            return factors;
         }
-        if !f.is_one() {
-            factors.push((f, j));
+        let g = r.clone().gcd(f.clone());
+        let s = f.div_rem(g.clone()).0;
+        if !s.is_one() {
+            factors.push((s, j));
         }
-        SquareFreeFactorization {
-            leading_coeff,
+        r = r.div_rem(g.clone()).0;
+        f = g;
+        j = j.saturating_add(1);
+    }
+    if !f.is_one() {
+        factors.push((f, j));
+    }
+    factors
+}
+
+fn expand<F: CommutativeRing>(leading_coeff: &F, factors: &[(Polynomial<F>, NonZeroUsize)]) -> Polynomial<F> {
+    factors
+        .iter()
+        .flat_map(|(f, k)| std::iter::repeat_n(f, k.get()))
+        .fold(Polynomial::new_trim_zeroes(vec![leading_coeff.clone()]), |acc, f| acc * f.clone())
+}
+
+impl<F: CommutativeRing> Factorization<F> {
+    /// Multiplies the factors back together.
+    pub fn expand(&self) -> Polynomial<F> {
+        expand(&self.leading_coeff, &self.factors)
+    }
+}
+
+/// Arithmetic on factorizations, which matches factors by equality and so
+/// expects both sides to normalize their factors the same way, as
+/// [`Polynomial::factor`] and [`Polynomial::factor_mod_p`] do.
+///
+/// # Example
+///
+/// ```
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// let poly = |c: &[i32]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+/// // 2 (x - 1)^2 (x + 1) and 4 (x - 1) (x^2 + 1)
+/// let a = poly(&[2, -2, -2, 2]).factor();
+/// let b = poly(&[-4, 4, -4, 4]).factor();
+/// // 2 (x - 1)
+/// assert_eq!(poly(&[-2, 2]), a.gcd(&b).expand());
+/// assert_eq!(poly(&[-1, 0, 1]), a.divide(&a.gcd(&b)).unwrap().expand());
+/// assert_eq!(None, a.divide(&b));
+/// assert_eq!(a.expand() * b.expand(), (a * b).expand());
+/// ```
+impl<F: CoefficientDomain + PartialEq> Factorization<F> {
+    /// The quotient `self / other`, cancelling the factors of `other`, or
+    /// `None` if `other` does not divide `self`.
+    pub fn divide(&self, other: &Self) -> Option<Self> {
+        if other.leading_coeff.is_zero() {
+            return None;
+        }
+        if self.leading_coeff.is_zero() {
+            return Some(self.clone());
+        }
+        // `other.leading_coeff` divides iff it is the gcd up to a unit
+        let normal = |x: F| x.unit_and_normal().1;
+        if normal(self.leading_coeff.gcd(&other.leading_coeff)) != normal(other.leading_coeff.clone()) {
+            return None;
+        }
+        let leading_coeff = self.leading_coeff.divide_exact(&other.leading_coeff);
+        let mut factors = self.factors.clone();
+        for (g, k) in &other.factors {
+            let i = factors.iter().position(|(f, _)| f == g)?;
+            match NonZeroUsize::new(factors[i].1.get().checked_sub(k.get())?) {
+                Some(e) => factors[i].1 = e,
+                None => {
+                    factors.remove(i);
+                }
+            }
+        }
+        Some(Self { leading_coeff, factors })
+    }
+
+    /// The gcd, with the smaller multiplicity of each common factor and the
+    /// gcd of the leading coefficients.
+    pub fn gcd(&self, other: &Self) -> Self {
+        if self.leading_coeff.is_zero() {
+            return other.clone();
+        }
+        if other.leading_coeff.is_zero() {
+            return self.clone();
+        }
+        let factors = self
+            .factors
+            .iter()
+            .filter_map(|(f, j)| {
+                let (_, k) = other.factors.iter().find(|(g, _)| g == f)?;
+                Some((f.clone(), *j.min(k)))
+            })
+            .collect();
+        Self {
+            leading_coeff: self.leading_coeff.gcd(&other.leading_coeff),
             factors,
         }
     }
 }
 
+/// Adds the multiplicities of common factors, keeping the factors of `self`
+/// in their order and appending the others.
+impl<F: CoefficientDomain + PartialEq> Mul for Factorization<F> {
+    type Output = Self;
+    fn mul(mut self, rhs: Self) -> Self {
+        if self.leading_coeff.is_zero() || rhs.leading_coeff.is_zero() {
+            return Self {
+                leading_coeff: F::zero(),
+                factors: Vec::new(),
+            };
+        }
+        for (g, k) in rhs.factors {
+            match self.factors.iter_mut().find(|(f, _)| *f == g) {
+                Some((_, j)) => *j = j.checked_add(k.get()).unwrap(),
+                None => self.factors.push((g, k)),
+            }
+        }
+        self.leading_coeff = self.leading_coeff * rhs.leading_coeff;
+        self
+    }
+}
+
+impl<F: CommutativeRing> SquareFreeFactorization<F> {
+    /// Multiplies the factors back together.
+    pub fn expand(&self) -> Polynomial<F> {
+        expand(&self.leading_coeff, &self.factors)
+    }
+}
+
+/// Splits the factors of two factorizations into pairwise coprime monic
+/// polynomials, each with its multiplicity in both. Replacing a pair that
+/// is not coprime by `f / g`, `h / g` and `g = gcd(f, h)` lowers the total
+/// degree, so this takes at most as many gcds as the degrees add up to.
+fn coprime_base<F: Field + PartialEq>(
+    a: &[(Polynomial<F>, NonZeroUsize)],
+    b: &[(Polynomial<F>, NonZeroUsize)],
+) -> Vec<(Polynomial<F>, [usize; 2])> {
+    let monic = |f: &Polynomial<F>| f.clone().scalar_mul(f.leading_coefficient_cloned().checked_inv().unwrap());
+    let mut pending: Vec<_> = a
+        .iter()
+        .map(|(f, k)| (monic(f), [k.get(), 0]))
+        .chain(b.iter().map(|(f, k)| (monic(f), [0, k.get()])))
+        .collect();
+    let mut base: Vec<(Polynomial<F>, [usize; 2])> = Vec::new();
+    while let Some((f, e)) = pending.pop() {
+        if f.degree() == Some(0) {
+            continue;
+        }
+        let common = base.iter().enumerate().find_map(|(i, (h, _))| {
+            let g = f.clone().gcd(h.clone());
+            (g.degree() != Some(0)).then_some((i, g))
+        });
+        match common {
+            Some((i, g)) => {
+                let (h, d) = base.swap_remove(i);
+                pending.push((f.div_rem(g.clone()).0, e));
+                pending.push((h.div_rem(g.clone()).0, d));
+                pending.push((g, [e[0] + d[0], e[1] + d[1]]));
+            }
+            None => base.push((f, e)),
+        }
+    }
+    base
+}
+
+/// Multiplies together the monic coprime polynomials of the same
+/// multiplicity, in increasing order of multiplicity, as
+/// [`Polynomial::square_free_factorization`] orders them.
+fn from_coprime_base<F: Field + CoefficientDomain>(
+    mut leading_coeff: F,
+    base: impl IntoIterator<Item = (Polynomial<F>, usize)>,
+) -> SquareFreeFactorization<F> {
+    let mut factors: Vec<(Polynomial<F>, NonZeroUsize)> = Vec::new();
+    for (f, k) in base {
+        let Some(k) = NonZeroUsize::new(k) else {
+            continue;
+        };
+        match factors.iter_mut().find(|(_, j)| *j == k) {
+            Some((g, _)) => *g = g.take() * f,
+            None => factors.push((f, k)),
+        }
+    }
+    factors.sort_by_key(|(_, j)| *j);
+    remove_contents(&mut leading_coeff, &mut factors);
+    SquareFreeFactorization {
+        leading_coeff,
+        factors,
+    }
+}
+
+/// Arithmetic on square free factorizations. Factors of the two sides may
+/// share roots without being equal, so they are first split with gcds into
+/// coprime parts, and the result is normalized as
+/// [`Polynomial::square_free_factorization`] would normalize it.
+///
+/// # Example
+///
+/// ```
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let poly = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+/// // (x - 1)^2 (x + 1) and 3 (x - 1) (x + 1)^3
+/// let (p, q) = (poly("x^3 - x^2 - x + 1"), poly("3x^4 + 6x^3 - 6x - 3"));
+/// let (a, b) = (p.clone().square_free_factorization(), q.clone().square_free_factorization());
+/// assert_eq!((p * q).square_free_factorization(), a.clone() * b.clone());
+/// assert_eq!(poly("x^2 - 1").square_free_factorization(), a.gcd(&b));
+/// let quotient = a.divide(&poly("x^2 - 1").square_free_factorization());
+/// assert_eq!(Some(poly("x - 1").square_free_factorization()), quotient);
+/// assert_eq!(None, a.divide(&b));
+/// ```
+impl<F: Field + CoefficientDomain + PartialEq> SquareFreeFactorization<F> {
+    /// The leading coefficient of the expanded polynomial.
+    fn expanded_leading_coeff(&self) -> F {
+        self.factors.iter().fold(self.leading_coeff.clone(), |acc, (f, k)| {
+            (0..k.get()).fold(acc, |acc, _| acc * f.leading_coefficient_cloned())
+        })
+    }
+
+    /// The quotient `self / other`, or `None` if `other` does not divide
+    /// `self`.
+    pub fn divide(&self, other: &Self) -> Option<Self> {
+        if other.leading_coeff.is_zero() {
+            return None;
+        }
+        if self.leading_coeff.is_zero() {
+            return Some(self.clone());
+        }
+        let leading_coeff = self.expanded_leading_coeff() / other.expanded_leading_coeff();
+        let base = coprime_base(&self.factors, &other.factors);
+        let base: Option<Vec<_>> = base.into_iter().map(|(f, [j, k])| Some((f, j.checked_sub(k)?))).collect();
+        Some(from_coprime_base(leading_coeff, base?))
+    }
+
+    /// The square free factorization of the monic gcd.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let (a, b) = (self.leading_coeff.is_zero(), other.leading_coeff.is_zero());
+        if a && b {
+            return self.clone();
+        }
+        // zero is divisible by everything, so the other side decides
+        let base = coprime_base(&self.factors, &other.factors);
+        let base = base.into_iter().map(|(f, [j, k])| match (a, b) {
+            (true, _) => (f, k),
+            (_, true) => (f, j),
+            _ => (f, j.min(k)),
+        });
+        from_coprime_base(F::one(), base)
+    }
+}
+
+impl<F: Field + CoefficientDomain + PartialEq> Mul for SquareFreeFactorization<F> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        if self.leading_coeff.is_zero() || rhs.leading_coeff.is_zero() {
+            return Self {
+                leading_coeff: F::zero(),
+                factors: Vec::new(),
+            };
+        }
+        let leading_coeff = self.expanded_leading_coeff() * rhs.expanded_leading_coeff();
+        let base = coprime_base(&self.factors, &rhs.factors);
+        from_coprime_base(leading_coeff, base.into_iter().map(|(f, [j, k])| (f, j + k)))
+    }
+}
+
+/// The irreducible factors of an integer polynomial, as found by
+/// [`Polynomial::kronecker`].
 pub struct Kronecker<Ring: CommutativeRing> {
     factors: Vec<Polynomial<Ring>>,
 }
 
+impl<Ring: CommutativeRing> Kronecker<Ring> {
+    /// The factors, whose product is the original polynomial. If the
+    /// polynomial is not primitive or has a negative leading coefficient, the
+    /// first factor is a constant.
+    pub fn factors(&self) -> &[Polynomial<Ring>] {
+        &self.factors
+    }
+}
+
+/// All positive and negative divisors of a non-zero `n`, positive ones first.
+fn signed_divisors(n: &BigInt) -> Vec<BigInt> {
+    let mut res = divisor_lattice(&n.abs()).divisors().to_vec();
+    let negative: Vec<_> = res.iter().map(|d| -d).collect();
+    res.extend(negative);
+    res
+}
+
+pub(crate) fn to_rational(p: &Polynomial<BigInt>) -> Polynomial<BigRational> {
+    Polynomial::new(p.coeffs.iter().cloned().map(BigRational::from_integer).collect())
+}
+
+/// `None` if some coefficient is not an integer.
+pub(crate) fn to_integer(p: &Polynomial<BigRational>) -> Option<Polynomial<BigInt>> {
+    p.coeffs
+        .iter()
+        .map(|c| c.is_integer().then(|| c.to_integer()))
+        .collect::<Option<_>>()
+        .map(Polynomial::new)
+}
+
+/// Evaluation points 0, 1, -1, 2, -2, ...
+fn kronecker_point(i: usize) -> BigInt {
+    let k = BigInt::from(i.div_ceil(2));
+    if i % 2 == 1 {
+        k
+    } else {
+        -k
+    }
+}
+
+/// Finds a factor of the primitive polynomial `f` of degree `1..=deg(f) / 2`
+/// with a positive leading coefficient, if there is one.
+fn kronecker_factor(f: &Polynomial<BigInt>) -> Option<Polynomial<BigInt>> {
+    let n = f.degree()?;
+    let rational = to_rational(f);
+    let mut points = Vec::new();
+    let mut values = Vec::new();
+    for d in 1..=n / 2 {
+        // a factor of degree d is determined by its values at d + 1 points,
+        // and each of those values divides the value of f there.
+        while points.len() <= d {
+            let x = kronecker_point(points.len());
+            let y = f
+                .coeffs
+                .iter()
+                .rev()
+                .fold(BigInt::zero(), |acc, c| acc * &x + c);
+            if y.is_zero() {
+                return Some(Polynomial::new(vec![-x, BigInt::one()]));
+            }
+            points.push(BigRational::from_integer(x));
+            values.push(signed_divisors(&y));
+        }
+        // the overall sign is fixed by taking a positive value at the first
+        // point, so only half of the divisors are tried there.
+        let first = values[0].len() / 2;
+        let mut index = vec![0; d + 1];
+        loop {
+            let interpolating = points
+                .iter()
+                .zip(&values)
+                .zip(&index)
+                .map(|((x, divisors), &i)| (x.clone(), BigRational::from_integer(divisors[i].clone())))
+                .collect();
+            let candidate = lagrange_interpolation(interpolating);
+            if candidate.degree() == Some(d) {
+                if let Some(g) = to_integer(&candidate) {
+                    let (q, r) = rational.clone().div_rem(to_rational(&g));
+                    if r.is_zero() && to_integer(&q).is_some() {
+                        return Some(g.unit_and_normal().1);
+                    }
+                }
+            }
+            // advance to the next combination of divisors
+            let mut pos = 0;
+            loop {
+                if pos > d {
+                    break;
+                }
+                index[pos] += 1;
+                let limit = if pos == 0 { first } else { values[pos].len() };
+                if index[pos] < limit {
+                    break;
+                }
+                index[pos] = 0;
+                pos += 1;
+            }
+            if pos > d {
+                break;
+            }
+        }
+    }
+    None
+}
+
 impl Polynomial<BigInt> {
+    /// Factors this polynomial into irreducibles over the integers with
+    /// Kronecker's method.
+    ///
+    /// A factor of degree `d` is determined by its values at `d + 1` points,
+    /// and those must divide the values of this polynomial there. Every
+    /// combination of divisors is interpolated and tried, which takes time
+    /// exponential in the degree, so this is only practical for small
+    /// polynomials with small coefficients.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// // x^4 + 4 = (x^2 - 2x + 2)(x^2 + 2x + 2)
+    /// let p = Polynomial::new([4, 0, 0, 0, 1].map(BigInt::from).to_vec());
+    /// let kronecker = p.clone().kronecker();
+    /// assert_eq!(2, kronecker.factors().len());
+    /// assert_eq!(p, kronecker.factors().iter().cloned().product());
+    /// ```
     pub fn kronecker(self) -> Kronecker<BigInt> {
-        if self.degree().map_or(true, |x| x <= 1) {
+        if self.is_zero() {
             return Kronecker {
                 factors: vec![self],
             };
         }
-        todo!()
+        let (unit, _) = self.leading_coefficient_cloned().unit_and_normal();
+        let constant = self.clone().content() * unit.into_inner();
+        let mut factors = Vec::new();
+        if !constant.is_one() {
+            factors.push(Polynomial::new(vec![constant.clone()]));
+        }
+        let mut stack = vec![Polynomial::new(
+            self.coeffs.iter().map(|c| c.divide_exact(&constant)).collect(),
+        )];
+        while let Some(f) = stack.pop() {
+            match kronecker_factor(&f) {
+                Some(g) => {
+                    let q = f.divide_exact(&g);
+                    stack.push(q);
+                    stack.push(g);
+                }
+                None if f.is_one() => {}
+                None => factors.push(f),
+            }
+        }
+        Kronecker { factors }
+    }
+}
+
+impl<Ring: CommutativeRing> Polynomial<Ring> {
+    /// If this is `g(x^k)` for some `k > 1`, returns `g` and the largest such
+    /// `k`, which is the gcd of the exponents of the non-constant terms.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// // x^6 + 2x^3 - 1 = g(x^3) with g = x^2 + 2x - 1
+    /// let p = Polynomial::new(vec![-1, 0, 0, 2, 0, 0, 1]);
+    /// assert_eq!(Some((Polynomial::new(vec![-1, 2, 1]), 3)), p.inner_power());
+    /// assert_eq!(None, Polynomial::new(vec![-1, 0, 1, 1]).inner_power());
+    /// ```
+    pub fn inner_power(&self) -> Option<(Self, usize)> {
+        let k = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(_, c)| !c.is_zero())
+            .fold(0usize, |acc, (i, _)| Integer::gcd(&acc, &i));
+        (k > 1).then(|| (Polynomial::new(self.coeffs.iter().step_by(k).cloned().collect()), k))
+    }
+
+    /// `p(x^k)`.
+    pub(crate) fn compose_power(&self, k: usize) -> Self {
+        let mut coeffs = vec![Ring::zero(); self.coeffs.len().saturating_sub(1) * k + 1];
+        for (i, c) in self.coeffs.iter().enumerate() {
+            coeffs[i * k] = c.clone();
+        }
+        Polynomial::new_trim_zeroes(coeffs)
+    }
+}
+
+/// The result of [`Polynomial::is_composed_power`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ComposedPower {
+    /// `(g, k)` with `f = g(x^k)`, from [`Polynomial::inner_power`].
+    pub inner: Option<(Polynomial<BigInt>, usize)>,
+    /// `(h, k)` with `f = h^k`, from [`Polynomial::perfect_power`].
+    pub power: Option<(Polynomial<BigInt>, usize)>,
+}
+
+impl Polynomial<BigInt> {
+    /// If this is `h^k` for an integer polynomial `h` and some `k > 1`,
+    /// returns `h` and the largest such `k`. The sign of `h` is chosen to
+    /// make its leading coefficient positive where that is possible.
+    ///
+    /// The multiplicities of the square free factorization must have a
+    /// common factor `k`, and the content must be a `k`th power, so this only
+    /// factors over the rationals.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// let poly = |c: &[i32]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+    /// // 4x^2 + 12x + 9 = (2x + 3)^2
+    /// assert_eq!(Some((poly(&[3, 2]), 2)), poly(&[9, 12, 4]).perfect_power());
+    /// // 2x² is not a square over the integers
+    /// assert_eq!(None, poly(&[0, 0, 2]).perfect_power());
+    /// ```
+    pub fn perfect_power(&self) -> Option<(Self, usize)> {
+        self.degree().filter(|&n| n > 0)?;
+        let sqf = to_rational(self).square_free_factorization();
+        let k = sqf.factors.iter().fold(0usize, |acc, (_, e)| Integer::gcd(&acc, &e.get()));
+        if k <= 1 {
+            return None;
+        }
+        // the factors are primitive, so the leading coefficient is an integer
+        let c = sqf.leading_coeff.to_integer();
+        let mut divisors = integer_divisors(k);
+        divisors.sort_unstable();
+        divisors.into_iter().rev().take_while(|&j| j > 1).find_map(|j| {
+            if c.is_negative() && j % 2 == 0 {
+                return None;
+            }
+            let root = c.nth_root(j as u32);
+            (num::pow(root.clone(), j) == c).then(|| {
+                let h = sqf
+                    .factors
+                    .iter()
+                    .flat_map(|(q, e)| std::iter::repeat_n(q, e.get() / j))
+                    .map(|q| to_integer(q).unwrap())
+                    .fold(Polynomial::new(vec![root]), |acc, q| acc * q);
+                (h, j)
+            })
+        })
+    }
+
+    /// Detects whether this polynomial is `g(x^k)` or `h(x)^k` for some
+    /// `k > 1`, returning both decompositions that apply, or `None` if
+    /// neither does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// let poly = |c: &[i32]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+    /// // x^4 + 2x^2 + 1 = (x^2 + 1)^2, a polynomial in x^2
+    /// let composed = poly(&[1, 0, 2, 0, 1]).is_composed_power().unwrap();
+    /// assert_eq!(Some((poly(&[1, 2, 1]), 2)), composed.inner);
+    /// assert_eq!(Some((poly(&[1, 0, 1]), 2)), composed.power);
+    /// assert_eq!(None, poly(&[1, 1, 1]).is_composed_power());
+    /// ```
+    pub fn is_composed_power(&self) -> Option<ComposedPower> {
+        let composed = ComposedPower {
+            inner: self.inner_power(),
+            power: self.perfect_power(),
+        };
+        (composed.inner.is_some() || composed.power.is_some()).then_some(composed)
     }
 }
@@ -0,0 +1,334 @@
+//! Finite fields and quotients of polynomial rings.
+
+use std::fmt::{self, Display};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::cache::Cache;
+use crate::traits::{AssertUnit, CheckedInv, CoefficientDomain, CommutativeRing, Field, FromUsize};
+use crate::Polynomial;
+
+/// The integers modulo a prime `P`, which form the field `GF(P)`.
+///
+/// `P` must be prime, otherwise inverses do not exist and [`Field`] is
+/// unsound.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Zp<const P: u64>(u64);
+
+impl<const P: u64> Zp<P> {
+    pub const fn new(x: u64) -> Self {
+        Self(x % P)
+    }
+
+    /// The representative in `0..P`.
+    pub const fn value(self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut res = Self::one();
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res * base;
+            }
+            base = base * base;
+            exp >>= 1;
+        }
+        res
+    }
+}
+
+impl<const P: u64> Display for Zp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> Add for Zp<P> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Neg for Zp<P> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self((P - self.0) % P)
+    }
+}
+
+impl<const P: u64> Sub for Zp<P> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<const P: u64> Mul for Zp<P> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self((self.0 as u128 * rhs.0 as u128 % P as u128) as u64)
+    }
+}
+
+/// # Panics
+///
+/// Panics on division by zero.
+impl<const P: u64> Div for Zp<P> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.checked_inv().expect("division by zero")
+    }
+}
+
+impl<const P: u64> Zero for Zp<P> {
+    fn zero() -> Self {
+        Self(0)
+    }
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl<const P: u64> One for Zp<P> {
+    fn one() -> Self {
+        Self::new(1)
+    }
+}
+
+impl<const P: u64> FromUsize for Zp<P> {
+    fn from_usize(n: usize) -> Self {
+        Self::new(n as u64)
+    }
+}
+
+/// By Fermat's little theorem, `x^(P - 2)` is the inverse of `x`.
+impl<const P: u64> CheckedInv for Zp<P> {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.pow(P - 2))
+        }
+    }
+}
+
+impl<const P: u64> CommutativeRing for Zp<P> {
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        x.0.checked_inv().unwrap().assert_is_unit()
+    }
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(!self.is_zero());
+        AssertUnit(self)
+    }
+    fn is_nilpotent(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+/// As in any field, every non-zero element is a unit, so gcds are `0` or `1`.
+impl<const P: u64> CoefficientDomain for Zp<P> {
+    fn unit_and_normal(self) -> (AssertUnit<Self>, Self) {
+        if self.is_zero() {
+            (Self::one().assert_is_unit(), Self::zero())
+        } else {
+            (self.assert_is_unit(), Self::one())
+        }
+    }
+    fn gcd(&self, other: &Self) -> Self {
+        if self.is_zero() && other.is_zero() {
+            Self::zero()
+        } else {
+            Self::one()
+        }
+    }
+    fn divide_exact(&self, other: &Self) -> Self {
+        *self / *other
+    }
+}
+
+impl<const P: u64> Field for Zp<P> {
+    fn characteristic() -> usize {
+        P as usize
+    }
+}
+
+/// An element of the quotient ring `F[x]/(m(x))`, stored as the remainder of
+/// its representative modulo `m`.
+///
+/// When `m` is irreducible this is a field: over [`Zp`] it is `GF(p^k)` with
+/// `k = deg m`, and over the rationals it is the number field generated by a
+/// root of `m`. Elements carry their modulus, and combining elements with
+/// different moduli panics.
+///
+/// # Example
+///
+/// ```
+/// # use math2::fields::{PolyMod, Zp};
+/// # use math2::traits::CheckedInv;
+/// # use math2::Polynomial;
+/// // GF(8) = GF(2)[x]/(x^3 + x + 1), whose multiplicative group has order 7
+/// let m = Polynomial::new([1, 1, 0, 1].map(Zp::<2>::new).to_vec());
+/// let x = PolyMod::x(m.clone());
+/// assert_eq!(PolyMod::one(m.clone()), x.pow(7));
+/// let inv = x.checked_inv().unwrap();
+/// assert_eq!(PolyMod::one(m), x * inv);
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PolyMod<F> {
+    value: Polynomial<F>,
+    modulus: Polynomial<F>,
+}
+
+impl<F: Field + PartialEq> PolyMod<F> {
+    /// The residue class of `value` modulo `modulus`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is constant.
+    pub fn new(value: Polynomial<F>, modulus: Polynomial<F>) -> Self {
+        assert!(modulus.degree().is_some_and(|d| d > 0), "modulus must not be constant");
+        let value = value.div_rem(modulus.clone()).1;
+        Self { value, modulus }
+    }
+
+    pub fn zero(modulus: Polynomial<F>) -> Self {
+        Self::new(Polynomial::zero(), modulus)
+    }
+
+    pub fn one(modulus: Polynomial<F>) -> Self {
+        Self::new(Polynomial::one(), modulus)
+    }
+
+    /// The class of `x`, a root of the modulus.
+    pub fn x(modulus: Polynomial<F>) -> Self {
+        Self::new(Polynomial::new(vec![F::zero(), F::one()]), modulus)
+    }
+
+    /// The representative of degree less than the modulus.
+    pub fn value(&self) -> &Polynomial<F> {
+        &self.value
+    }
+
+    pub fn modulus(&self) -> &Polynomial<F> {
+        &self.modulus
+    }
+
+    pub fn into_value(self) -> Polynomial<F> {
+        self.value
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.value.is_zero()
+    }
+
+    pub fn pow(&self, mut exp: u64) -> Self {
+        let mut base = self.clone();
+        let mut res = Self::one(self.modulus.clone());
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res * base.clone();
+            }
+            base = base.clone() * base;
+            exp >>= 1;
+        }
+        res
+    }
+
+    fn check_modulus(&self, other: &Self) {
+        assert!(self.modulus == other.modulus, "elements of different quotient rings");
+    }
+}
+
+impl<const P: u64> PolyMod<Zp<P>> {
+    /// The class of `x` in `GF(P^degree)`, modulo the first monic
+    /// irreducible polynomial of degree `degree` in the order of
+    /// [`Polynomial::irreducibles_of_degree`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `degree` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::{PolyMod, Zp};
+    /// // the multiplicative group of GF(9) has order 8
+    /// let x = PolyMod::<Zp<3>>::x_of_degree(2);
+    /// assert_eq!(PolyMod::one(x.modulus().clone()), x.pow(8));
+    /// ```
+    pub fn x_of_degree(degree: usize) -> Self {
+        assert!(degree > 0, "degree must be positive");
+        Self::x(Polynomial::irreducibles_of_degree(degree).next().unwrap())
+    }
+
+    /// [`PolyMod::x_of_degree`], taking the modulus from `cache`.
+    pub fn x_of_degree_with_cache(degree: usize, cache: &Cache) -> Self {
+        Self::x(cache.irreducible::<P>(degree))
+    }
+}
+
+impl<F: Field + PartialEq> Add for PolyMod<F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        Self {
+            value: self.value + rhs.value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<F: Field + PartialEq> Neg for PolyMod<F> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            value: -self.value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<F: Field + PartialEq> Sub for PolyMod<F> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        Self {
+            value: self.value - rhs.value,
+            modulus: self.modulus,
+        }
+    }
+}
+
+impl<F: Field + PartialEq> Mul for PolyMod<F> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.check_modulus(&rhs);
+        Self::new(self.value * rhs.value, self.modulus)
+    }
+}
+
+/// # Panics
+///
+/// Panics on division by a non-unit.
+impl<F: Field + PartialEq> Div for PolyMod<F> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.checked_inv().expect("division by a non-unit")
+    }
+}
+
+/// With `s v + t m = gcd(v, m)` from the extended Euclidean algorithm, `v`
+/// is a unit exactly when the gcd is `1`, and then `s` is its inverse.
+impl<F: Field + PartialEq> CheckedInv for PolyMod<F> {
+    fn checked_inv(&self) -> Option<Self> {
+        let (g, s, _) = self.value.clone().extended_gcd(self.modulus.clone());
+        g.is_one().then(|| Self::new(s, self.modulus.clone()))
+    }
+}
@@ -0,0 +1,177 @@
+//! Exact polynomial curve fitting.
+
+use num::{BigRational, One, Zero};
+
+use crate::piecewise::PiecewisePolynomial;
+use crate::Polynomial;
+
+/// Solves the square system `a x = b` by Gauss-Jordan elimination. `None` if
+/// `a` is singular.
+fn solve(mut a: Vec<Vec<BigRational>>, mut b: Vec<BigRational>) -> Option<Vec<BigRational>> {
+    let n = a.len();
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| !a[r][col].is_zero())?;
+        a.swap(pivot, col);
+        b.swap(pivot, col);
+        let inv = a[col][col].recip();
+        for x in &mut a[col][col..] {
+            *x *= &inv;
+        }
+        b[col] *= &inv;
+        let pivot_row = a[col].clone();
+        for r in 0..n {
+            if r == col || a[r][col].is_zero() {
+                continue;
+            }
+            let factor = a[r][col].clone();
+            for (x, p) in a[r][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= &factor * p;
+            }
+            let delta = &factor * &b[col];
+            b[r] -= delta;
+        }
+    }
+    Some(b)
+}
+
+/// The result of [`least_squares_fit`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LeastSquaresFit {
+    pub polynomial: Polynomial<BigRational>,
+    /// `y_i - p(x_i)` for every input point, in order.
+    pub residuals: Vec<BigRational>,
+}
+
+/// Finds the polynomial `p` of degree at most `degree` minimizing
+/// `sum((y_i - p(x_i))^2)` over the given points, exactly.
+///
+/// This solves the normal equations `AᵀA c = Aᵀy` for the Vandermonde matrix
+/// `A`. Returns `None` if there are fewer than `degree + 1` distinct `x_i`,
+/// in which case the minimizer is not unique.
+///
+/// # Example
+///
+/// ```
+/// # use math2::fitting::least_squares_fit;
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// let points = [(n(0), n(0)), (n(1), n(1)), (n(2), n(1))];
+/// let fit = least_squares_fit(&points, 1).unwrap();
+/// // y = x/2 + 1/6
+/// let r = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+/// assert_eq!(Polynomial::new(vec![r(1, 6), r(1, 2)]), fit.polynomial);
+/// assert_eq!(vec![r(-1, 6), r(1, 3), r(-1, 6)], fit.residuals);
+/// ```
+pub fn least_squares_fit(
+    points: &[(BigRational, BigRational)],
+    degree: usize,
+) -> Option<LeastSquaresFit> {
+    let size = degree + 1;
+    // sums of x^k for k up to 2 * degree, and of x^k y for k up to degree.
+    let mut power_sums = vec![BigRational::zero(); 2 * degree + 1];
+    let mut moment_sums = vec![BigRational::zero(); size];
+    for (x, y) in points {
+        let mut power = BigRational::one();
+        for k in 0..=2 * degree {
+            if k < size {
+                moment_sums[k] += &power * y;
+            }
+            power_sums[k] += &power;
+            power *= x;
+        }
+    }
+    let normal = (0..size)
+        .map(|i| power_sums[i..i + size].to_vec())
+        .collect();
+    let coeffs = solve(normal, moment_sums)?;
+    let polynomial = Polynomial::new_trim_zeroes(coeffs);
+    let residuals = points
+        .iter()
+        .map(|(x, y)| {
+            let value = polynomial
+                .coeffs
+                .iter()
+                .rev()
+                .fold(BigRational::zero(), |acc, c| acc * x + c);
+            y - value
+        })
+        .collect();
+    Some(LeastSquaresFit {
+        polynomial,
+        residuals,
+    })
+}
+
+/// Returns the natural cubic spline through the given points: the twice
+/// continuously differentiable piecewise cubic that interpolates them and has
+/// zero second derivative at both ends.
+///
+/// # Panics
+///
+/// Panics if there are fewer than two points, or if the `x`s are not strictly
+/// increasing.
+///
+/// # Example
+///
+/// ```
+/// # use math2::fitting::natural_cubic_spline;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// let spline = natural_cubic_spline(&[(n(0), n(0)), (n(1), n(1)), (n(2), n(0))]);
+/// assert_eq!(Some(n(1)), spline.eval(&n(1)));
+/// // the spline overshoots a little between the points
+/// assert_eq!(Some(BigRational::new(11.into(), 16.into())), spline.eval(&BigRational::new(1.into(), 2.into())));
+/// ```
+pub fn natural_cubic_spline(
+    points: &[(BigRational, BigRational)],
+) -> PiecewisePolynomial<BigRational> {
+    assert!(points.len() >= 2, "need at least two points");
+    let n = points.len() - 1;
+    let x = |i: usize| &points[i].0;
+    let y = |i: usize| &points[i].1;
+    let h: Vec<_> = (0..n).map(|i| x(i + 1) - x(i)).collect();
+    let slope = |i: usize| (y(i + 1) - y(i)) / &h[i];
+
+    // Solve the tridiagonal system for the second derivatives `m` at the
+    // interior points with the Thomas algorithm; `m[0] = m[n] = 0`.
+    let six = BigRational::from_integer(6.into());
+    let two = BigRational::from_integer(2.into());
+    let mut diag = Vec::with_capacity(n);
+    let mut rhs = Vec::with_capacity(n);
+    for i in 1..n {
+        let mut d = &two * (&h[i - 1] + &h[i]);
+        let mut r = &six * (slope(i) - slope(i - 1));
+        if i > 1 {
+            let w = &h[i - 1] / &diag[i - 2];
+            d -= &w * &h[i - 1];
+            r -= &w * &rhs[i - 2];
+        }
+        diag.push(d);
+        rhs.push(r);
+    }
+    let mut m = vec![BigRational::zero(); n + 1];
+    for i in (1..n).rev() {
+        let mut r = rhs[i - 1].clone();
+        if i + 1 < n {
+            r -= &h[i] * &m[i + 1];
+        }
+        m[i] = r / &diag[i - 1];
+    }
+
+    let cube = |p: Polynomial<BigRational>| p.clone() * p.clone() * p;
+    let pieces = (0..n)
+        .map(|i| {
+            let hi = &h[i];
+            // x_{i+1} - x and x - x_i
+            let u = Polynomial::new(vec![x(i + 1).clone(), -BigRational::one()]);
+            let v = Polynomial::new(vec![-x(i), BigRational::one()]);
+            let a = &m[i] / (&six * hi);
+            let b = &m[i + 1] / (&six * hi);
+            let c = y(i) / hi - &m[i] * hi / &six;
+            let d = y(i + 1) / hi - &m[i + 1] * hi / &six;
+            cube(u.clone()) * a + cube(v.clone()) * b + u * c + v * d
+        })
+        .collect();
+    PiecewisePolynomial::new(points.iter().map(|(x, _)| x.clone()).collect(), pieces)
+}
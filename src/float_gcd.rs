@@ -0,0 +1,210 @@
+//! Approximate gcds of float polynomials.
+//!
+//! Rounding errors make the exact gcd of float polynomials almost always
+//! `1`. The ε-gcd instead looks for the highest degree `k` such that both
+//! polynomials are within a tolerance of multiples of a common factor of
+//! degree `k`. That happens when the `k`th Sylvester subresultant matrix is
+//! close to singular, which is measured by its smallest singular value.
+
+use num::Zero;
+
+use crate::Polynomial;
+
+fn norm(v: &[f64]) -> f64 {
+    v.iter().map(|x| x * x).sum::<f64>().sqrt()
+}
+
+/// The matrix of multiplication by `p` on polynomials of degree `< cols`, in
+/// column major order.
+fn convolution(p: &[f64], cols: usize) -> Vec<Vec<f64>> {
+    (0..cols)
+        .map(|j| {
+            let mut col = vec![0.0; p.len() + cols - 1];
+            col[j..j + p.len()].copy_from_slice(p);
+            col
+        })
+        .collect()
+}
+
+/// The smallest singular value of a matrix with at least as many rows as
+/// columns, given in column major order, and its right singular vector.
+///
+/// This uses one-sided Jacobi rotations, which orthogonalize the columns
+/// and are accurate for small singular values.
+fn smallest_singular(mut cols: Vec<Vec<f64>>) -> (f64, Vec<f64>) {
+    let n = cols.len();
+    let mut v: Vec<Vec<f64>> = (0..n)
+        .map(|j| (0..n).map(|i| if i == j { 1.0 } else { 0.0 }).collect())
+        .collect();
+    for _sweep in 0..60 {
+        let mut rotated = false;
+        for p in 0..n {
+            for q in p + 1..n {
+                let alpha: f64 = cols[p].iter().map(|x| x * x).sum();
+                let beta: f64 = cols[q].iter().map(|x| x * x).sum();
+                let gamma: f64 = cols[p].iter().zip(&cols[q]).map(|(x, y)| x * y).sum();
+                if gamma.abs() <= f64::EPSILON * (alpha * beta).sqrt() {
+                    continue;
+                }
+                rotated = true;
+                let zeta = (beta - alpha) / (2.0 * gamma);
+                let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                let c = 1.0 / (1.0 + t * t).sqrt();
+                let s = c * t;
+                for m in [&mut cols, &mut v] {
+                    let (left, right) = m.split_at_mut(q);
+                    for (x, y) in left[p].iter_mut().zip(&mut right[0]) {
+                        (*x, *y) = (c * *x - s * *y, s * *x + c * *y);
+                    }
+                }
+            }
+        }
+        if !rotated {
+            break;
+        }
+    }
+    let (j, sigma) = cols
+        .iter()
+        .map(|c| norm(c))
+        .enumerate()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+    (sigma, v.swap_remove(j))
+}
+
+/// Solves `min |A x - b|` for a matrix with independent columns, given in
+/// column major order, with modified Gram-Schmidt.
+fn least_squares(mut cols: Vec<Vec<f64>>, mut b: Vec<f64>) -> Vec<f64> {
+    let n = cols.len();
+    let mut r = vec![vec![0.0; n]; n];
+    let mut qtb = vec![0.0; n];
+    for j in 0..n {
+        let (done, rest) = cols.split_at_mut(j);
+        let col = &mut rest[0];
+        for (i, q) in done.iter().enumerate() {
+            r[i][j] = q.iter().zip(col.iter()).map(|(x, y)| x * y).sum();
+            for (x, y) in col.iter_mut().zip(q) {
+                *x -= r[i][j] * y;
+            }
+        }
+        r[j][j] = norm(col);
+        for x in col.iter_mut() {
+            *x /= r[j][j];
+        }
+        qtb[j] = col.iter().zip(&b).map(|(x, y)| x * y).sum();
+        for (x, y) in b.iter_mut().zip(col.iter()) {
+            *x -= qtb[j] * y;
+        }
+    }
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let s: f64 = (i + 1..n).map(|k| r[i][k] * x[k]).sum();
+        x[i] = (qtb[i] - s) / r[i][i];
+    }
+    x
+}
+
+/// The result of [`Polynomial::approximate_gcd`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct ApproximateGcd {
+    /// The monic approximate gcd `h`.
+    pub gcd: Polynomial<f64>,
+    /// Cofactors `u` and `v` with `self ≈ u h` and `other ≈ v h`.
+    pub cofactors: (Polynomial<f64>, Polynomial<f64>),
+    /// The larger of `|self - u h|` and `|other - v h|`, relative to the
+    /// norms of `self` and `other`.
+    pub residual: f64,
+}
+
+impl Polynomial<f64> {
+    /// Computes an ε-gcd of `self` and `other`: a factor of the highest
+    /// degree `k` for which the `k`th Sylvester subresultant matrix of the
+    /// two polynomials, scaled to unit norm, has a singular value at most
+    /// `tolerance`.
+    ///
+    /// The cofactors come from the corresponding singular vector, and the
+    /// gcd is then fitted to both polynomials by least squares.
+    ///
+    /// # Panics
+    ///
+    /// Panics if both polynomials are zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// // (x - 1)(x + 2) and (x - 1.000001)(x - 3)
+    /// let f = Polynomial::new(vec![-2.0, 1.0, 1.0]);
+    /// let g = Polynomial::new(vec![3.000003, -4.000001, 1.0]);
+    /// assert_eq!(Some(0), f.approximate_gcd(&g, 1e-9).gcd.degree());
+    /// let result = f.approximate_gcd(&g, 1e-5);
+    /// assert_eq!(Some(1), result.gcd.degree());
+    /// assert!((result.gcd.coeff_at(0) + 1.0).abs() < 1e-5);
+    /// ```
+    pub fn approximate_gcd(&self, other: &Self, tolerance: f64) -> ApproximateGcd {
+        assert!(!self.is_zero() || !other.is_zero(), "gcd of two zero polynomials");
+        let monic = |p: &Self| {
+            let lc = p.leading_coefficient_cloned();
+            let gcd = Polynomial::new(p.coeffs.iter().map(|c| c / lc).collect());
+            (gcd, Polynomial::new(vec![lc]))
+        };
+        if other.is_zero() {
+            let (gcd, lc) = monic(self);
+            return ApproximateGcd {
+                gcd,
+                cofactors: (lc, Polynomial::new(vec![])),
+                residual: 0.0,
+            };
+        }
+        if self.is_zero() {
+            let (gcd, lc) = monic(other);
+            return ApproximateGcd {
+                gcd,
+                cofactors: (Polynomial::new(vec![]), lc),
+                residual: 0.0,
+            };
+        }
+        let (m, n) = (self.degree().unwrap(), other.degree().unwrap());
+        let (f_norm, g_norm) = (norm(&self.coeffs), norm(&other.coeffs));
+        let f: Vec<f64> = self.coeffs.iter().map(|c| c / f_norm).collect();
+        let g: Vec<f64> = other.coeffs.iter().map(|c| c / g_norm).collect();
+        for k in (1..=m.min(n)).rev() {
+            // columns for v * f + w * g with deg v < n - k + 1, deg w < m - k + 1
+            let mut sylvester = convolution(&f, n - k + 1);
+            sylvester.extend(convolution(&g, m - k + 1));
+            let (sigma, x) = smallest_singular(sylvester);
+            if sigma > tolerance {
+                continue;
+            }
+            // f v = -g w, so u = -w is the cofactor of f
+            let v = x[..n - k + 1].to_vec();
+            let u: Vec<f64> = x[n - k + 1..].iter().map(|c| -c).collect();
+            let mut cols = convolution(&u, k + 1);
+            for (col, v_col) in cols.iter_mut().zip(convolution(&v, k + 1)) {
+                col.extend(v_col);
+            }
+            let rhs: Vec<f64> = f.iter().chain(&g).copied().collect();
+            let h = least_squares(cols, rhs);
+            let h = Polynomial::new_trim_zeroes(h);
+            let u = Polynomial::new_trim_zeroes(u);
+            let v = Polynomial::new_trim_zeroes(v);
+            let residual = |p: &[f64], q: Polynomial<f64>| {
+                let diff: Vec<f64> = (0..p.len()).map(|i| p[i] - q.coeffs.get(i).unwrap_or(&0.0)).collect();
+                norm(&diff)
+            };
+            let residual = residual(&f, u.clone() * h.clone()).max(residual(&g, v.clone() * h.clone()));
+            let (gcd, lc) = monic(&h);
+            let scale = |p: Polynomial<f64>, s: f64| p.scalar_mul(s * lc.coeffs[0]);
+            return ApproximateGcd {
+                gcd,
+                cofactors: (scale(u, f_norm), scale(v, g_norm)),
+                residual,
+            };
+        }
+        ApproximateGcd {
+            gcd: Polynomial::new(vec![1.0]),
+            cofactors: (self.clone(), other.clone()),
+            residual: 0.0,
+        }
+    }
+}
@@ -0,0 +1,243 @@
+//! Polynomials of simple graphs.
+//!
+//! A [`Graph`] has at most 64 vertices, each with the bit mask of its
+//! neighbours, so that sets of vertices are bit masks too. The
+//! characteristic polynomial takes polynomial time, while the matching and
+//! chromatic polynomials count structures that make them hard in general and
+//! take exponential time in the worst case.
+
+use std::collections::HashMap;
+
+use num::{BigInt, One, Zero};
+
+use crate::linalg::Matrix;
+use crate::Polynomial;
+
+/// A finite simple graph, without loops or multiple edges, on the vertices
+/// `0..n`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Graph {
+    neighbours: Vec<u64>,
+}
+
+impl Graph {
+    /// The graph on `n` vertices with the given edges. Repeated edges are
+    /// only added once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n > 64`, or an edge is a loop or has an endpoint that is
+    /// not a vertex.
+    pub fn new(n: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        assert!(n <= 64, "only 64 vertices are supported");
+        let mut neighbours = vec![0; n];
+        for (u, v) in edges {
+            assert!(u < n && v < n, "edge endpoint is not a vertex");
+            assert_ne!(u, v, "graphs have no loops");
+            neighbours[u] |= 1 << v;
+            neighbours[v] |= 1 << u;
+        }
+        Self { neighbours }
+    }
+
+    /// The graph with the given adjacency matrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not a symmetric matrix of zeros and ones with
+    /// zeros on the diagonal, or has more than 64 rows.
+    pub fn from_adjacency_matrix(a: &Matrix<BigInt>) -> Self {
+        assert_eq!(a.num_rows(), a.num_cols(), "matrix must be square");
+        let n = a.num_rows();
+        let mut edges = Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                assert_eq!(a[(i, j)], a[(j, i)], "matrix must be symmetric");
+                if a[(i, j)].is_one() {
+                    edges.push((i, j));
+                } else {
+                    assert!(a[(i, j)].is_zero(), "entries must be zero or one");
+                }
+            }
+        }
+        Self::new(n, edges)
+    }
+
+    /// The complete graph `K_n`.
+    pub fn complete(n: usize) -> Self {
+        Self::new(n, (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))))
+    }
+
+    /// The path `P_n` on `n` vertices.
+    pub fn path(n: usize) -> Self {
+        Self::new(n, (1..n).map(|i| (i - 1, i)))
+    }
+
+    /// The cycle `C_n`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n < 3`.
+    pub fn cycle(n: usize) -> Self {
+        assert!(n >= 3, "cycles have at least three vertices");
+        Self::new(n, (0..n).map(|i| (i, (i + 1) % n)))
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.neighbours.len()
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.neighbours.iter().map(|m| m.count_ones() as usize).sum::<usize>() / 2
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.neighbours[u] >> v & 1 == 1
+    }
+
+    /// The neighbours of `v`, in increasing order.
+    pub fn neighbours(&self, v: usize) -> impl Iterator<Item = usize> {
+        bits(self.neighbours[v])
+    }
+
+    /// The edges `(u, v)` with `u < v`, in lexicographic order.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.num_vertices()).flat_map(move |u| bits(self.neighbours[u] >> u).map(move |d| (u, u + d)))
+    }
+
+    pub fn adjacency_matrix(&self) -> Matrix<BigInt> {
+        let n = self.num_vertices();
+        Matrix::from_fn(n, n, |i, j| BigInt::from(u8::from(self.has_edge(i, j))))
+    }
+
+    /// The characteristic polynomial `det(x I - A)` of the adjacency matrix
+    /// `A`. Its roots are the eigenvalues of the graph, and the coefficient
+    /// of `x^(n - 2)` is minus the number of edges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::graph::Graph;
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// // the eigenvalues of the 4-cycle are 2, 0, 0 and -2
+    /// let expected = Polynomial::new([0, 0, -4, 0, 1].map(BigInt::from).to_vec());
+    /// assert_eq!(expected, Graph::cycle(4).characteristic_polynomial());
+    /// ```
+    pub fn characteristic_polynomial(&self) -> Polynomial<BigInt> {
+        self.adjacency_matrix().char_poly()
+    }
+
+    /// The matching polynomial `sum((-1)^k m_k x^(n - 2k))`, with `m_k` the
+    /// number of matchings of `k` edges.
+    ///
+    /// Removing a vertex `v` gives `μ(G) = x μ(G - v) - sum(μ(G - v - u))`
+    /// over the neighbours `u` of `v`, as a matching either misses `v` or
+    /// matches it to a neighbour. The polynomials of the vertex sets met are
+    /// remembered, so this takes time polynomial in their number. For trees
+    /// it is the characteristic polynomial.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::graph::Graph;
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// // K_4 has 6 edges and 3 perfect matchings
+    /// let expected = Polynomial::new([3, 0, -6, 0, 1].map(BigInt::from).to_vec());
+    /// assert_eq!(expected, Graph::complete(4).matching_polynomial());
+    /// ```
+    pub fn matching_polynomial(&self) -> Polynomial<BigInt> {
+        let all = full_set(self.num_vertices());
+        self.matching_polynomial_of(all, &mut HashMap::new())
+    }
+
+    fn matching_polynomial_of(&self, set: u64, known: &mut HashMap<u64, Polynomial<BigInt>>) -> Polynomial<BigInt> {
+        if set == 0 {
+            return Polynomial::one();
+        }
+        if let Some(p) = known.get(&set) {
+            return p.clone();
+        }
+        let v = set.trailing_zeros() as usize;
+        let rest = set & !(1 << v);
+        let mut p = self.matching_polynomial_of(rest, known).raised_by(1);
+        for u in bits(self.neighbours[v] & rest) {
+            p = p - self.matching_polynomial_of(rest & !(1 << u), known);
+        }
+        known.insert(set, p.clone());
+        p
+    }
+
+    /// The chromatic polynomial, whose value at a positive integer `k` is the
+    /// number of colourings of the vertices with `k` colours in which
+    /// adjacent vertices differ.
+    ///
+    /// This uses deletion–contraction, `P(G) = P(G - e) - P(G / e)` for an
+    /// edge `e`, since the colourings of `G - e` either give the endpoints of
+    /// `e` different colours or the same one. The recursion stops at graphs
+    /// without edges, with polynomial `x^n`, and at complete graphs, with
+    /// polynomial `x (x - 1) ... (x - n + 1)`, so it takes up to `2^m` steps
+    /// for `m` edges.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::graph::Graph;
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// // (x - 1)^4 + (x - 1) for the 4-cycle, so it has 18 colourings with 3 colours
+    /// let p = Graph::cycle(4).chromatic_polynomial();
+    /// assert_eq!(Polynomial::new([0, -3, 6, -4, 1].map(BigInt::from).to_vec()), p);
+    /// assert_eq!(BigInt::from(18), p.eval(&BigInt::from(3)));
+    /// ```
+    pub fn chromatic_polynomial(&self) -> Polynomial<BigInt> {
+        chromatic(self.neighbours.clone(), full_set(self.num_vertices()))
+    }
+}
+
+/// The chromatic polynomial of the graph induced on `alive`.
+fn chromatic(mut neighbours: Vec<u64>, alive: u64) -> Polynomial<BigInt> {
+    let n = alive.count_ones() as usize;
+    let degrees: Vec<u32> = bits(alive).map(|v| (neighbours[v] & alive).count_ones()).collect();
+    if degrees.iter().all(|&d| d == 0) {
+        return Polynomial::from_elem_with_degree(BigInt::one(), n);
+    }
+    if degrees.iter().all(|&d| d as usize == n - 1) {
+        return (0..n).map(|i| Polynomial::new(vec![-BigInt::from(i), BigInt::one()])).product();
+    }
+    let u = bits(alive).find(|&v| neighbours[v] & alive != 0).unwrap();
+    let v = (neighbours[u] & alive).trailing_zeros() as usize;
+
+    // contract: v is merged into u, keeping the graph simple
+    let mut contracted = neighbours.clone();
+    let merged = (contracted[u] | contracted[v]) & !(1 << u) & !(1 << v);
+    contracted[u] = merged;
+    for w in bits(merged) {
+        contracted[w] = (contracted[w] & !(1 << v)) | 1 << u;
+    }
+    let contraction = chromatic(contracted, alive & !(1 << v));
+
+    neighbours[u] &= !(1 << v);
+    neighbours[v] &= !(1 << u);
+    chromatic(neighbours, alive) - contraction
+}
+
+/// The positions of the set bits of `mask`, in increasing order.
+fn bits(mut mask: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        (mask != 0).then(|| {
+            let i = mask.trailing_zeros() as usize;
+            mask &= mask - 1;
+            i
+        })
+    })
+}
+
+fn full_set(n: usize) -> u64 {
+    if n == 64 {
+        u64::MAX
+    } else {
+        (1 << n) - 1
+    }
+}
@@ -0,0 +1,430 @@
+//! Numerical homotopy continuation for square polynomial systems, with
+//! exact certification of the roots it finds.
+//!
+//! To solve `f = 0` for `n` polynomials in `n` variables, the start system
+//! `g_i = x_i^(d_i) - 1` with `d_i = deg f_i` has the `d_1 ... d_n` known
+//! roots of unity as roots, which is Bezout's bound on the number of
+//! isolated roots of `f`. Each is followed along
+//! `H(x, t) = (1 - t) γ g(x) + t f(x)` from `t = 0` to `t = 1`. For all but
+//! finitely many complex `γ` the paths do not meet, and together they end
+//! at every isolated root of `f`, while the other paths go to infinity.
+//!
+//! The paths are tracked in `f64` arithmetic, so the roots are only
+//! approximate. [`certify`] proves with Smale's α-theory, in exact
+//! arithmetic, that Newton's method converges from a point to a root, and
+//! bounds the distance to it.
+
+use num::complex::{Complex, Complex64};
+use num::{BigRational, Signed, ToPrimitive, Zero};
+
+use crate::linalg::Matrix;
+use crate::multivariate::MultiPolynomial;
+
+/// How a path ended.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathStatus {
+    /// Newton's method on the target system converged at the end of the
+    /// path, which is an approximate nonsingular root.
+    Converged,
+    /// The path reached the endgame, but Newton's method did not converge
+    /// quadratically there, as happens near singular roots.
+    Singular,
+    /// The path went to infinity.
+    Diverged,
+    /// The step size went below [`MIN_STEP`] before the endgame, or the
+    /// tracker ran out of steps.
+    Failed,
+}
+
+/// A path of the homotopy from a root of the start system.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Path {
+    pub start: Vec<Complex64>,
+    /// The last point on the path, after the endgame.
+    pub end: Vec<Complex64>,
+    pub status: PathStatus,
+    /// The number of accepted steps along the path.
+    pub steps: usize,
+}
+
+/// A root of the target system with a certificate from [`certify`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct CertifiedRoot {
+    pub point: Vec<Complex64>,
+    /// A bound on the distance to the exact root, in the maximum norm.
+    pub radius: BigRational,
+}
+
+/// The smallest step in `t` before a path is given up.
+pub const MIN_STEP: f64 = 1e-13;
+/// Paths are handed to the endgame if the step size underflows after this
+/// value of `t`.
+pub const ENDGAME_START: f64 = 0.9;
+const MAX_STEP: f64 = 0.1;
+const MAX_STEPS: usize = 100_000;
+/// Points beyond this norm are taken to be on a path to infinity.
+const DIVERGENCE_BOUND: f64 = 1e8;
+/// A rational below the `α_0 = (13 - 3 sqrt(17)) / 4 = 0.15767...` of
+/// Smale's α-theorem.
+const ALPHA_0: (i32, i32) = (157, 1000);
+
+/// The maximum norm.
+fn norm(v: &[Complex64]) -> f64 {
+    v.iter().map(|z| z.norm()).fold(0.0, f64::max)
+}
+
+/// Whether `x` is beyond [`DIVERGENCE_BOUND`] or not finite.
+fn diverged(x: &[Complex64]) -> bool {
+    x.iter().any(|z| !z.is_finite()) || norm(x) > DIVERGENCE_BOUND
+}
+
+/// Solves `a x = b` by Gaussian elimination with partial pivoting. `None` if
+/// a pivot is exactly zero.
+fn solve(mut a: Vec<Vec<Complex64>>, mut b: Vec<Complex64>) -> Option<Vec<Complex64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].norm().total_cmp(&a[j][col].norm()))?;
+        if a[pivot][col].is_zero() {
+            return None;
+        }
+        a.swap(pivot, col);
+        b.swap(pivot, col);
+        for r in col + 1..n {
+            let factor = a[r][col] / a[col][col];
+            let (above, below) = a.split_at_mut(r);
+            for (x, y) in below[0][col..].iter_mut().zip(&above[col][col..]) {
+                *x -= factor * y;
+            }
+            let x = b[col];
+            b[r] -= factor * x;
+        }
+    }
+    let mut x = vec![Complex64::zero(); n];
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).fold(b[i], |acc, j| acc - a[i][j] * x[j]);
+        x[i] = sum / a[i][i];
+    }
+    Some(x)
+}
+
+/// The homotopy `H(x, t) = (1 - t) γ g(x) + t f(x)` with its derivatives.
+struct Homotopy {
+    target: Vec<MultiPolynomial<Complex64>>,
+    target_jacobian: Vec<Vec<MultiPolynomial<Complex64>>>,
+    degrees: Vec<usize>,
+    gamma: Complex64,
+}
+
+impl Homotopy {
+    fn new(system: &[MultiPolynomial<BigRational>]) -> Self {
+        let n = system.len();
+        let target: Vec<_> = system
+            .iter()
+            .map(|f| MultiPolynomial::new(f.terms().map(|(e, c)| (e.to_vec(), Complex64::new(c.to_f64().unwrap(), 0.0)))))
+            .collect();
+        let target_jacobian = target
+            .iter()
+            .map(|f: &MultiPolynomial<Complex64>| (0..n).map(|j| f.derivative(j)).collect())
+            .collect();
+        Self {
+            target,
+            target_jacobian,
+            degrees: system.iter().map(|f| f.total_degree().unwrap()).collect(),
+            // any constant off a real curve works, but a fixed one keeps the
+            // paths reproducible
+            gamma: Complex64::from_polar(1.0, 1.9),
+        }
+    }
+
+    /// `g(x)` and the diagonal of its Jacobian.
+    fn start(&self, x: &[Complex64]) -> (Vec<Complex64>, Vec<Complex64>) {
+        x.iter()
+            .zip(&self.degrees)
+            .map(|(x, &d)| (x.powu(d as u32) - 1.0, x.powu(d as u32 - 1) * d as f64))
+            .unzip()
+    }
+
+    fn target(&self, x: &[Complex64]) -> Vec<Complex64> {
+        self.target.iter().map(|f| f.eval(x)).collect()
+    }
+
+    fn target_jacobian(&self, x: &[Complex64]) -> Vec<Vec<Complex64>> {
+        self.target_jacobian.iter().map(|row| row.iter().map(|d| d.eval(x)).collect()).collect()
+    }
+
+    /// `H(x, t)`, `∂H/∂x` and `∂H/∂t`.
+    fn eval(&self, x: &[Complex64], t: f64) -> (Vec<Complex64>, Vec<Vec<Complex64>>, Vec<Complex64>) {
+        let s = self.gamma * (1.0 - t);
+        let (g, dg) = self.start(x);
+        let f = self.target(x);
+        let mut jacobian = self.target_jacobian(x);
+        for (i, row) in jacobian.iter_mut().enumerate() {
+            for x in row.iter_mut() {
+                *x *= t;
+            }
+            row[i] += s * dg[i];
+        }
+        let h = f.iter().zip(&g).map(|(f, g)| s * g + f * t).collect();
+        let dt = f.iter().zip(&g).map(|(f, g)| f - self.gamma * g).collect();
+        (h, jacobian, dt)
+    }
+
+    /// Up to `iterations` Newton steps on `H(., t)`, returning the point if
+    /// the last step was small. Corrections that move far from the predicted
+    /// point are rejected, as they tend to jump to another path.
+    fn correct(&self, mut x: Vec<Complex64>, t: f64, iterations: usize) -> Option<Vec<Complex64>> {
+        let predicted = x.clone();
+        let radius = 0.1 * (1.0 + norm(&predicted));
+        for _ in 0..iterations {
+            let (h, jacobian, _) = self.eval(&x, t);
+            let dx = solve(jacobian, h)?;
+            for (x, d) in x.iter_mut().zip(&dx) {
+                *x -= d;
+            }
+            let moved: Vec<_> = x.iter().zip(&predicted).map(|(x, y)| x - y).collect();
+            if norm(&moved) > radius {
+                return None;
+            }
+            if norm(&dx) <= 1e-9 * (1.0 + norm(&x)) {
+                return Some(x);
+            }
+        }
+        None
+    }
+
+    /// Newton's method on the target system from the end of a path.
+    fn endgame(&self, mut x: Vec<Complex64>) -> (Vec<Complex64>, PathStatus) {
+        let mut last = f64::INFINITY;
+        for _ in 0..50 {
+            if diverged(&x) {
+                return (x, PathStatus::Diverged);
+            }
+            let Some(dx) = solve(self.target_jacobian(&x), self.target(&x)) else {
+                return (x, PathStatus::Singular);
+            };
+            for (x, d) in x.iter_mut().zip(&dx) {
+                *x -= d;
+            }
+            let step = norm(&dx);
+            if step <= 1e-14 * (1.0 + norm(&x)) {
+                return (x, PathStatus::Converged);
+            }
+            // quadratic convergence shrinks the steps much faster than this
+            if step > 0.25 * last && step < 1e-6 * (1.0 + norm(&x)) {
+                return (x, PathStatus::Singular);
+            }
+            last = step;
+        }
+        (x, PathStatus::Singular)
+    }
+
+    /// Follows the path from `x` at `t = 0` with an Euler predictor and a
+    /// Newton corrector, halving the step after a failed correction and
+    /// doubling it after five successes in a row.
+    fn track(&self, start: Vec<Complex64>) -> Path {
+        let (mut x, mut t, mut step) = (start.clone(), 0.0, 0.01_f64);
+        let (mut steps, mut successes) = (0, 0);
+        let status = loop {
+            if t >= 1.0 {
+                break None;
+            }
+            if steps >= MAX_STEPS {
+                break Some(PathStatus::Failed);
+            }
+            if diverged(&x) {
+                break Some(PathStatus::Diverged);
+            }
+            step = step.min(1.0 - t);
+            let (_, jacobian, dt) = self.eval(&x, t);
+            let next_t = if 1.0 - t - step < MIN_STEP { 1.0 } else { t + step };
+            let corrected = solve(jacobian, dt).and_then(|dx| {
+                let predicted = x.iter().zip(&dx).map(|(x, d)| x - d * (next_t - t)).collect();
+                self.correct(predicted, next_t, 3)
+            });
+            match corrected {
+                Some(y) => {
+                    (x, t) = (y, next_t);
+                    steps += 1;
+                    successes += 1;
+                    if successes == 5 {
+                        step = (step * 2.0).min(MAX_STEP);
+                        successes = 0;
+                    }
+                }
+                None => {
+                    step /= 2.0;
+                    successes = 0;
+                    if step < MIN_STEP {
+                        break (t < ENDGAME_START).then_some(PathStatus::Failed);
+                    }
+                }
+            }
+        };
+        let (end, status) = match status {
+            Some(status) => (x, status),
+            None => self.endgame(x),
+        };
+        Path { start, end, status, steps }
+    }
+}
+
+/// Tracks the `d_1 ... d_n` paths of the total degree homotopy for the
+/// system `f = 0` of `n` polynomials in `n` variables.
+///
+/// Every isolated root of `f` is at the end of some path with probability
+/// one. A nonsingular root ends exactly one path, which is
+/// [`PathStatus::Converged`], and a root of multiplicity `m` ends `m` paths,
+/// which are usually [`PathStatus::Singular`].
+///
+/// # Panics
+///
+/// Panics if the number of equations is not the number of variables, or if
+/// an equation is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::homotopy::{track_total_degree, PathStatus};
+/// # use math2::multivariate::MultiPolynomial;
+/// # use num::BigRational;
+/// let c = |a: i32| MultiPolynomial::constant(BigRational::from_integer(a.into()));
+/// let x = || MultiPolynomial::var(0);
+/// let y = || MultiPolynomial::var(1);
+/// // x^2 + y^2 = 5 and xy = 2 meet at (1, 2), (2, 1), (-1, -2) and (-2, -1)
+/// let paths = track_total_degree(&[x() * x() + y() * y() - c(5), x() * y() - c(2)]);
+/// assert_eq!(4, paths.len());
+/// assert!(paths.iter().all(|path| path.status == PathStatus::Converged));
+/// let mut ends: Vec<_> = paths.iter().map(|path| (path.end[0].re.round(), path.end[1].re.round())).collect();
+/// ends.sort_by(|a, b| a.partial_cmp(b).unwrap());
+/// assert_eq!(vec![(-2.0, -1.0), (-1.0, -2.0), (1.0, 2.0), (2.0, 1.0)], ends);
+/// ```
+pub fn track_total_degree(system: &[MultiPolynomial<BigRational>]) -> Vec<Path> {
+    let n = system.len();
+    assert!(system.iter().all(|f| f.num_vars() <= n), "need one equation per variable");
+    assert!(system.iter().all(|f| !f.is_zero()), "equations must be non-zero");
+    let homotopy = Homotopy::new(system);
+    // the roots of x_i^(d_i) = 1, counted in mixed radix
+    let count: usize = homotopy.degrees.iter().product();
+    (0..count)
+        .map(|mut index| {
+            let start = homotopy
+                .degrees
+                .iter()
+                .map(|&d| {
+                    let k = index % d;
+                    index /= d;
+                    Complex64::from_polar(1.0, std::f64::consts::TAU * k as f64 / d as f64)
+                })
+                .collect();
+            homotopy.track(start)
+        })
+        .collect()
+}
+
+/// `|re| + |im|`, an upper bound on the absolute value.
+fn abs_bound(z: &Complex<BigRational>) -> BigRational {
+    z.re.abs() + z.im.abs()
+}
+
+/// Proves with Smale's α-theorem that Newton's method for `f = 0` converges
+/// quadratically from `point` to a root, and returns a bound on the distance
+/// to that root in the maximum norm. `None` if the test fails, which does
+/// not mean that there is no root close by.
+///
+/// The point is converted exactly to Gaussian rationals, and everything
+/// after that is exact. With `J = Df(x)`, the test needs
+/// `α = β γ < α_0 = 0.1576...`, where `β = |J^-1 f(x)|` is the length of the
+/// Newton step and `γ = sup_k |J^-1 D^k f(x) / k!|^(1 / (k - 1))`. The
+/// terms of degree `k` in the Taylor expansion `f(x + h)` give upper bounds
+/// for the norms in `γ`, so for each `k` it is enough to compare
+/// `β^(k - 1) |J^-1 D^k f(x) / k!|` with `α_0^(k - 1)`, without any roots.
+/// Then the root is within `2β` of `x`.
+///
+/// # Panics
+///
+/// Panics if the number of equations is not the number of coordinates of
+/// `point`, or if an equation uses more variables.
+///
+/// # Example
+///
+/// ```
+/// # use math2::homotopy::certify;
+/// # use math2::multivariate::MultiPolynomial;
+/// # use num::complex::Complex64;
+/// # use num::BigRational;
+/// let c = |a: i32| MultiPolynomial::constant(BigRational::from_integer(a.into()));
+/// let x = || MultiPolynomial::var(0);
+/// // x^2 = 2 near 1.414 and not near 1
+/// let system = [x() * x() - c(2)];
+/// let radius = certify(&system, &[Complex64::new(1.414, 0.0)]).unwrap();
+/// assert!(radius < BigRational::new(1.into(), 1000.into()));
+/// assert_eq!(None, certify(&system, &[Complex64::new(1.0, 0.0)]));
+/// ```
+pub fn certify(system: &[MultiPolynomial<BigRational>], point: &[Complex64]) -> Option<BigRational> {
+    let n = point.len();
+    assert_eq!(n, system.len(), "need one equation per coordinate");
+    assert!(system.iter().all(|f| f.num_vars() <= n), "equations use more variables than there are coordinates");
+    let exact = |x: f64| BigRational::from_float(x);
+    let x: Vec<Complex<BigRational>> = point.iter().map(|z| Some(Complex::new(exact(z.re)?, exact(z.im)?))).collect::<Option<_>>()?;
+    // f(x + h) as polynomials in h
+    let shifted: Vec<MultiPolynomial<Complex<BigRational>>> = system
+        .iter()
+        .map(|f| {
+            let f = MultiPolynomial::new(f.terms().map(|(e, c)| (e.to_vec(), Complex::new(c.clone(), BigRational::zero()))));
+            x.iter().enumerate().fold(f, |f, (i, xi)| {
+                f.substitute(i, &(MultiPolynomial::var(i) + MultiPolynomial::constant(xi.clone())))
+            })
+        })
+        .collect();
+    let coeff = |f: &MultiPolynomial<Complex<BigRational>>, e: &[usize]| f.coeff(e).cloned().unwrap_or_else(Complex::zero);
+    let unit = |j: usize| (0..n).map(|i| usize::from(i == j)).collect::<Vec<_>>();
+    let jacobian = Matrix::from_fn(n, n, |i, j| coeff(&shifted[i], &unit(j)));
+    if jacobian.rank_gaussian() < n {
+        return None;
+    }
+    let value: Vec<_> = shifted.iter().map(|f| coeff(f, &[])).collect();
+    let newton_step = jacobian.solve(&value).unwrap();
+    let beta = newton_step.iter().map(abs_bound).max().unwrap_or_else(BigRational::zero);
+
+    // the terms of each degree k > 1, as vectors over the equations
+    let mut exponents: Vec<&[usize]> = shifted.iter().flat_map(|f| f.terms().map(|(e, _)| e)).collect();
+    exponents.sort_unstable();
+    exponents.dedup();
+    let max_degree = shifted.iter().filter_map(MultiPolynomial::total_degree).max().unwrap_or(0);
+    let alpha_0 = BigRational::new(ALPHA_0.0.into(), ALPHA_0.1.into());
+    for k in 2..=max_degree {
+        let mut bounds = vec![BigRational::zero(); n];
+        for e in exponents.iter().filter(|e| e.iter().sum::<usize>() == k) {
+            let column: Vec<_> = shifted.iter().map(|f| coeff(f, e)).collect();
+            for (bound, w) in bounds.iter_mut().zip(jacobian.solve(&column).unwrap()) {
+                *bound += abs_bound(&w);
+            }
+        }
+        let bound = bounds.into_iter().max().unwrap_or_else(BigRational::zero);
+        let e = (k - 1) as i32;
+        if beta.pow(e) * bound >= alpha_0.pow(e) {
+            return None;
+        }
+    }
+    Some(beta * BigRational::from_integer(2.into()))
+}
+
+/// Tracks the paths of [`track_total_degree`] and returns the ends of the
+/// converged paths that [`certify`] proves to be close to a root.
+///
+/// # Panics
+///
+/// Panics as [`track_total_degree`] does.
+pub fn solve_certified(system: &[MultiPolynomial<BigRational>]) -> Vec<CertifiedRoot> {
+    track_total_degree(system)
+        .into_iter()
+        .filter(|path| path.status == PathStatus::Converged)
+        .filter_map(|path| {
+            let radius = certify(system, &path.end)?;
+            Some(CertifiedRoot {
+                point: path.end,
+                radius,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,135 @@
+//! Closed intervals with rational endpoints.
+
+use std::fmt::{self, Display};
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::{BigInt, BigRational, Zero};
+
+use crate::Polynomial;
+
+/// The closed interval `[lo, hi]`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Interval {
+    lo: BigRational,
+    hi: BigRational,
+}
+
+impl Interval {
+    /// # Panics
+    ///
+    /// Panics if `lo > hi`.
+    pub fn new(lo: BigRational, hi: BigRational) -> Self {
+        assert!(lo <= hi, "empty interval");
+        Self { lo, hi }
+    }
+
+    /// The interval containing only `x`.
+    pub fn point(x: BigRational) -> Self {
+        Self {
+            lo: x.clone(),
+            hi: x,
+        }
+    }
+
+    pub fn lo(&self) -> &BigRational {
+        &self.lo
+    }
+
+    pub fn hi(&self) -> &BigRational {
+        &self.hi
+    }
+
+    pub fn width(&self) -> BigRational {
+        &self.hi - &self.lo
+    }
+
+    pub fn contains(&self, x: &BigRational) -> bool {
+        &self.lo <= x && x <= &self.hi
+    }
+
+    /// Widens the interval so that both endpoints are multiples of `10^-digits`.
+    ///
+    /// Repeated interval arithmetic makes the denominators of the endpoints
+    /// grow quickly; rounding outwards after each step keeps them bounded
+    /// while still containing the same values.
+    pub fn round_outward(&self, digits: u32) -> Self {
+        let scale = BigRational::from_integer(BigInt::from(10).pow(digits));
+        Self {
+            lo: (&self.lo * &scale).floor() / &scale,
+            hi: (&self.hi * &scale).ceil() / &scale,
+        }
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+impl Add for Interval {
+    type Output = Interval;
+    fn add(self, rhs: Self) -> Self::Output {
+        Interval {
+            lo: self.lo + rhs.lo,
+            hi: self.hi + rhs.hi,
+        }
+    }
+}
+
+impl Neg for Interval {
+    type Output = Interval;
+    fn neg(self) -> Self::Output {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl Sub for Interval {
+    type Output = Interval;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl Mul for Interval {
+    type Output = Interval;
+    fn mul(self, rhs: Self) -> Self::Output {
+        let products = [
+            &self.lo * &rhs.lo,
+            &self.lo * &rhs.hi,
+            &self.hi * &rhs.lo,
+            &self.hi * &rhs.hi,
+        ];
+        let lo = products.iter().min().unwrap().clone();
+        let hi = products.iter().max().unwrap().clone();
+        Interval { lo, hi }
+    }
+}
+
+impl Polynomial<BigRational> {
+    /// Encloses the values of this polynomial on `x`, using Horner's scheme
+    /// in interval arithmetic.
+    ///
+    /// The result always contains `f(x)` for every `x` in the interval, but
+    /// may be wider than the exact range.
+    pub fn eval_interval(&self, x: &Interval) -> Interval {
+        self.eval_interval_rounded(x, None)
+    }
+
+    /// Like [`Polynomial::eval_interval`], rounding outwards to `digits`
+    /// decimal places after every step when `digits` is given.
+    pub(crate) fn eval_interval_rounded(&self, x: &Interval, digits: Option<u32>) -> Interval {
+        let mut acc = Interval::point(BigRational::zero());
+        for c in self.coeffs.iter().rev() {
+            acc = acc * x.clone() + Interval::point(c.clone());
+            if let Some(digits) = digits {
+                acc = acc.round_outward(digits);
+            }
+        }
+        acc
+    }
+}
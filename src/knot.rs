@@ -0,0 +1,277 @@
+//! The Kauffman bracket and the Jones polynomial of knots and links.
+//!
+//! Diagrams are read from a planar diagram code, as in KnotInfo and the
+//! Knot Atlas, or from a braid word whose closure is the link. The bracket
+//! is a sum over the `2^n` ways to smooth the `n` crossings, so this is only
+//! practical up to twenty crossings or so.
+
+use std::collections::HashMap;
+
+use num::{BigInt, One};
+
+use crate::laurent::LaurentPolynomial;
+
+/// An oriented link diagram. The arcs between crossings are numbered
+/// `0..num_arcs`, and each crossing lists its four arcs counterclockwise
+/// starting from the incoming lower arc.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LinkDiagram {
+    crossings: Vec<[usize; 4]>,
+    /// `1` for a positive crossing and `-1` for a negative one.
+    signs: Vec<i64>,
+    num_arcs: usize,
+    /// Arcs that are the same, at the closure of a braid.
+    joins: Vec<(usize, usize)>,
+    /// Components without crossings.
+    free_loops: usize,
+}
+
+impl LinkDiagram {
+    /// The diagram with planar diagram code `pd`. A crossing `[a, b, c, d]`
+    /// lists the labels of its arcs counterclockwise from the incoming lower
+    /// arc `a`, so the lower strand runs from `a` to `c`.
+    ///
+    /// The labels must increase along the orientation of each component,
+    /// except where it closes up, as in KnotInfo. The crossing is then
+    /// positive when the upper strand runs from `d` to `b`, that is when
+    /// `b = d + 1` or `d > b + 1`. An empty code is the unknot.
+    ///
+    /// # Panics
+    ///
+    /// Panics if some label does not appear exactly twice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::knot::LinkDiagram;
+    /// // the figure eight knot is amphichiral, with writhe zero in its minimal diagram
+    /// let figure_eight = LinkDiagram::from_pd_code(&[[4, 2, 5, 1], [8, 6, 1, 5], [6, 3, 7, 4], [2, 7, 3, 8]]);
+    /// assert_eq!(0, figure_eight.writhe());
+    /// assert_eq!(1, figure_eight.num_components());
+    /// ```
+    pub fn from_pd_code(pd: &[[usize; 4]]) -> Self {
+        let mut index = HashMap::new();
+        let mut uses = Vec::new();
+        let crossings: Vec<[usize; 4]> = pd
+            .iter()
+            .map(|crossing| {
+                crossing.map(|label| {
+                    let next = index.len();
+                    let i = *index.entry(label).or_insert(next);
+                    if i == uses.len() {
+                        uses.push(0);
+                    }
+                    uses[i] += 1;
+                    i
+                })
+            })
+            .collect();
+        assert!(uses.iter().all(|&n| n == 2), "every label must appear exactly twice");
+        let signs = pd
+            .iter()
+            .map(|&[a, b, c, d]| {
+                let positive = a == b || c == d || b == d + 1 || d > b + 1;
+                if positive {
+                    1
+                } else {
+                    -1
+                }
+            })
+            .collect();
+        Self {
+            crossings,
+            signs,
+            num_arcs: index.len(),
+            joins: Vec::new(),
+            free_loops: usize::from(pd.is_empty()),
+        }
+    }
+
+    /// The closure of the braid on `strands` strands with the given word.
+    /// The generator `i` crosses strand `i - 1` over strand `i` in a
+    /// positive crossing, and `-i` is its inverse, with strands numbered
+    /// from zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `strands` is zero or a generator is zero or more than
+    /// `strands - 1` in absolute value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::knot::LinkDiagram;
+    /// // the closure of s_1 s_2^-1 s_1 s_2^-1 is the figure eight knot
+    /// let figure_eight = LinkDiagram::from_braid(3, &[1, -2, 1, -2]);
+    /// assert_eq!(1, figure_eight.num_components());
+    /// // and the closure of the empty braid on 3 strands is a 3-component unlink
+    /// assert_eq!(3, LinkDiagram::from_braid(3, &[]).num_components());
+    /// ```
+    pub fn from_braid(strands: usize, word: &[i32]) -> Self {
+        assert!(strands > 0, "braids have at least one strand");
+        let mut ends: Vec<usize> = (0..strands).collect();
+        let mut num_arcs = strands;
+        let mut crossings = Vec::with_capacity(word.len());
+        let mut signs = Vec::with_capacity(word.len());
+        for &generator in word {
+            let k = generator.unsigned_abs() as usize;
+            assert!(k >= 1 && k < strands, "generator out of range");
+            let (left, right) = (ends[k - 1], ends[k]);
+            let (new_left, new_right) = (num_arcs, num_arcs + 1);
+            num_arcs += 2;
+            // strands go up, the left one to the right and the right one to the left
+            if generator > 0 {
+                crossings.push([right, new_right, new_left, left]);
+                signs.push(1);
+            } else {
+                crossings.push([left, right, new_right, new_left]);
+                signs.push(-1);
+            }
+            ends[k - 1] = new_left;
+            ends[k] = new_right;
+        }
+        Self {
+            crossings,
+            signs,
+            num_arcs,
+            joins: ends.into_iter().enumerate().map(|(start, end)| (end, start)).collect(),
+            free_loops: 0,
+        }
+    }
+
+    pub fn num_crossings(&self) -> usize {
+        self.crossings.len()
+    }
+
+    /// The number of positive crossings minus the number of negative ones.
+    pub fn writhe(&self) -> i64 {
+        self.signs.iter().sum()
+    }
+
+    /// The number of components of the link.
+    pub fn num_components(&self) -> usize {
+        let mut arcs = Arcs::new(self.num_arcs);
+        for &[a, b, c, d] in &self.crossings {
+            arcs.join(a, c);
+            arcs.join(b, d);
+        }
+        self.free_loops + arcs.count(&self.joins)
+    }
+
+    /// The Kauffman bracket `<D>` in the variable `A`, normalized so that
+    /// the unknot without crossings has bracket `1`.
+    ///
+    /// It is determined by `<X> = A <=> + A^-1 <)(>` at each crossing, where
+    /// the `A` smoothing joins the regions swept counterclockwise by the
+    /// upper strand, and by a factor `-A^2 - A^-2` for each extra loop. So
+    /// `<D>` is the sum over the states of `A^(a - b) (-A^2 - A^-2)^(l - 1)`,
+    /// with `a` and `b` the numbers of smoothings of each kind and `l` the
+    /// number of loops. The bracket is invariant under the second and third
+    /// Reidemeister moves, but not the first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::knot::LinkDiagram;
+    /// # use math2::laurent::LaurentPolynomial;
+    /// # use num::BigInt;
+    /// let hopf = LinkDiagram::from_braid(2, &[1, 1]);
+    /// let expected = LaurentPolynomial::new([(4, -1), (-4, -1)].map(|(e, c)| (e, BigInt::from(c))));
+    /// assert_eq!(expected, hopf.kauffman_bracket());
+    /// ```
+    pub fn kauffman_bracket(&self) -> LaurentPolynomial<BigInt> {
+        let n = self.crossings.len();
+        assert!(n < 64, "too many crossings");
+        // the number of states with each number of A smoothings and loops
+        let mut states: HashMap<(usize, usize), u64> = HashMap::new();
+        for state in 0..1u64 << n {
+            let mut arcs = Arcs::new(self.num_arcs);
+            for (i, &[a, b, c, d]) in self.crossings.iter().enumerate() {
+                if state >> i & 1 == 0 {
+                    arcs.join(a, b);
+                    arcs.join(c, d);
+                } else {
+                    arcs.join(a, d);
+                    arcs.join(b, c);
+                }
+            }
+            let loops = self.free_loops + arcs.count(&self.joins);
+            let a_smoothings = n - state.count_ones() as usize;
+            *states.entry((a_smoothings, loops)).or_default() += 1;
+        }
+
+        let loop_factor = LaurentPolynomial::new([(2, BigInt::from(-1)), (-2, BigInt::from(-1))]);
+        let max_loops = states.keys().map(|&(_, l)| l).max().unwrap_or(1);
+        let mut powers = vec![LaurentPolynomial::one()];
+        for _ in 1..max_loops {
+            let next = powers.last().unwrap().clone() * loop_factor.clone();
+            powers.push(next);
+        }
+        states
+            .into_iter()
+            .map(|((a, loops), count)| {
+                let exponent = 2 * a as i64 - n as i64;
+                LaurentPolynomial::monomial(BigInt::from(count), exponent) * powers[loops - 1].clone()
+            })
+            .sum()
+    }
+
+    /// The Jones polynomial `V(t)`, as a Laurent polynomial in `t^(1/2)`.
+    /// It is `(-A^3)^-w <D>` with `A = t^(-1/4)`, for the writhe `w`, which
+    /// is invariant under all Reidemeister moves. The exponents of `t` are
+    /// integers for knots, and for links with an odd number of components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::knot::LinkDiagram;
+    /// # use math2::laurent::LaurentPolynomial;
+    /// # use num::BigInt;
+    /// // t + t^3 - t^4 for the right-handed trefoil
+    /// let trefoil = LinkDiagram::from_pd_code(&[[1, 5, 2, 4], [3, 1, 4, 6], [5, 3, 6, 2]]);
+    /// let expected = LaurentPolynomial::new([(2, 1), (6, 1), (8, -1)].map(|(e, c)| (e, BigInt::from(c))));
+    /// assert_eq!(expected, trefoil.jones_polynomial());
+    /// assert_eq!(expected, LinkDiagram::from_braid(2, &[1, 1, 1]).jones_polynomial());
+    /// ```
+    pub fn jones_polynomial(&self) -> LaurentPolynomial<BigInt> {
+        let w = self.writhe();
+        let sign = if w % 2 == 0 { BigInt::one() } else { -BigInt::one() };
+        let normalized = self.kauffman_bracket() * LaurentPolynomial::monomial(sign, -3 * w);
+        // A^e = t^(-e/4) = (t^(1/2))^(-e/2), and e is always even
+        LaurentPolynomial::new(normalized.terms().map(|(e, c)| (-e / 2, c.clone())))
+    }
+}
+
+/// A union-find structure on the arcs.
+struct Arcs {
+    parent: Vec<usize>,
+}
+
+impl Arcs {
+    fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+        }
+    }
+
+    fn find(&mut self, mut a: usize) -> usize {
+        while self.parent[a] != a {
+            self.parent[a] = self.parent[self.parent[a]];
+            a = self.parent[a];
+        }
+        a
+    }
+
+    fn join(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        self.parent[a] = b;
+    }
+
+    /// The number of classes after also joining the given pairs.
+    fn count(&mut self, joins: &[(usize, usize)]) -> usize {
+        for &(a, b) in joins {
+            self.join(a, b);
+        }
+        (0..self.parent.len()).filter(|&a| self.find(a) == a).count()
+    }
+}
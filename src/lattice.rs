@@ -0,0 +1,481 @@
+//! Integer lattices: basis reduction, closest and shortest vectors.
+//!
+//! A lattice is given by a basis of linearly independent integer vectors, one
+//! per row. All arithmetic is exact.
+
+use num::{BigInt, BigRational, One, Signed, ToPrimitive, Zero};
+
+use crate::Polynomial;
+
+fn to_rational(v: &[BigInt]) -> Vec<BigRational> {
+    v.iter().cloned().map(BigRational::from_integer).collect()
+}
+
+fn dot(a: &[BigRational], b: &[BigRational]) -> BigRational {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+/// The Gram-Schmidt orthogonalization of a basis.
+struct GramSchmidt {
+    /// The orthogonal vectors `b*_i`.
+    vectors: Vec<Vec<BigRational>>,
+    /// `mu[i][j] = <b_i, b*_j> / <b*_j, b*_j>` for `j < i`.
+    mu: Vec<Vec<BigRational>>,
+    /// The squared lengths `<b*_i, b*_i>`.
+    norms: Vec<BigRational>,
+}
+
+impl GramSchmidt {
+    /// # Panics
+    ///
+    /// Panics if the basis is linearly dependent.
+    fn new(basis: &[Vec<BigInt>]) -> Self {
+        let n = basis.len();
+        let mut vectors: Vec<Vec<BigRational>> = Vec::with_capacity(n);
+        let mut mu = vec![vec![BigRational::zero(); n]; n];
+        let mut norms: Vec<BigRational> = Vec::with_capacity(n);
+        for (i, b) in basis.iter().enumerate() {
+            let b = to_rational(b);
+            let mut v = b.clone();
+            for j in 0..i {
+                mu[i][j] = dot(&b, &vectors[j]) / &norms[j];
+                for (x, y) in v.iter_mut().zip(&vectors[j]) {
+                    *x -= &mu[i][j] * y;
+                }
+            }
+            let norm = dot(&v, &v);
+            assert!(!norm.is_zero(), "basis vectors must be linearly independent");
+            norms.push(norm);
+            vectors.push(v);
+        }
+        Self { vectors, mu, norms }
+    }
+
+    /// Updates the orthogonalization for `b_k <- b_k - q b_j` with `j < k`,
+    /// which leaves the orthogonal vectors as they are.
+    fn size_reduce(&mut self, k: usize, j: usize, q: &BigInt) {
+        let q = BigRational::from_integer(q.clone());
+        self.mu[k][j] -= &q;
+        for i in 0..j {
+            let t = &q * &self.mu[j][i];
+            self.mu[k][i] -= t;
+        }
+    }
+
+    /// Updates the orthogonalization for swapping `b_(k - 1)` and `b_k`,
+    /// which only changes `b*_(k - 1)` and `b*_k`.
+    ///
+    /// With `μ = mu[k][k - 1]`, the new `b*_(k - 1)` is `b*_k + μ b*_(k - 1)`,
+    /// with squared length `B = B_k + μ^2 B_(k - 1)`, and the new `b*_k` is
+    /// what is left of the old `b*_(k - 1)` orthogonal to it.
+    fn swap(&mut self, k: usize) {
+        let m = self.mu[k][k - 1].clone();
+        let b = &self.norms[k] + &m * &m * &self.norms[k - 1];
+        let new_mu = &m * &self.norms[k - 1] / &b;
+        self.norms[k] = &self.norms[k - 1] * &self.norms[k] / &b;
+        self.norms[k - 1] = b;
+
+        let first: Vec<BigRational> =
+            self.vectors[k].iter().zip(&self.vectors[k - 1]).map(|(x, y)| x + &m * y).collect();
+        let second = self.vectors[k - 1].iter().zip(&first).map(|(x, y)| x - &new_mu * y).collect();
+        self.vectors[k - 1] = first;
+        self.vectors[k] = second;
+
+        let (before, after) = self.mu.split_at_mut(k);
+        before[k - 1][..k - 1].swap_with_slice(&mut after[0][..k - 1]);
+        self.mu[k][k - 1] = new_mu;
+        for i in k + 1..self.mu.len() {
+            let t = self.mu[i][k].clone();
+            self.mu[i][k] = &self.mu[i][k - 1] - &m * &t;
+            self.mu[i][k - 1] = t + &self.mu[k][k - 1] * &self.mu[i][k];
+        }
+    }
+}
+
+/// Reduces a lattice basis with the Lenstra–Lenstra–Lovász algorithm, using
+/// `δ = 3/4`.
+///
+/// The first vector of the result is at most `2^((n - 1) / 2)` times as long
+/// as a shortest non-zero lattice vector, and in practice is usually much
+/// closer. The Gram-Schmidt coefficients are orthogonalized once and then
+/// updated after each size reduction and swap, at the cost of `O(n)` and
+/// `O(n + dim)` rational operations.
+///
+/// # Panics
+///
+/// Panics if the basis is linearly dependent.
+///
+/// # Example
+///
+/// ```
+/// # use math2::lattice::lll;
+/// # use num::BigInt;
+/// let basis = [[1, 1, 1], [-1, 0, 2], [3, 5, 6]].map(|v| v.map(BigInt::from).to_vec());
+/// let reduced = lll(&basis);
+/// assert_eq!([0, 1, 0].map(BigInt::from).to_vec(), reduced[0]);
+/// ```
+pub fn lll(basis: &[Vec<BigInt>]) -> Vec<Vec<BigInt>> {
+    let mut basis = basis.to_vec();
+    let delta = BigRational::new(3.into(), 4.into());
+    let mut gs = GramSchmidt::new(&basis);
+    let mut k = 1;
+    while k < basis.len() {
+        // size reduction
+        for j in (0..k).rev() {
+            let q = gs.mu[k][j].round().to_integer();
+            if !q.is_zero() {
+                let (done, rest) = basis.split_at_mut(k);
+                for (x, y) in rest[0].iter_mut().zip(&done[j]) {
+                    *x -= &q * y;
+                }
+                gs.size_reduce(k, j, &q);
+            }
+        }
+        // the Lovász condition
+        let mu = &gs.mu[k][k - 1];
+        if gs.norms[k] >= (&delta - mu * mu) * &gs.norms[k - 1] {
+            k += 1;
+        } else {
+            basis.swap(k, k - 1);
+            gs.swap(k);
+            k = (k - 1).max(1);
+        }
+    }
+    basis
+}
+
+fn combination(basis: &[Vec<BigInt>], coeffs: &[BigInt]) -> Vec<BigInt> {
+    let dim = basis.first().map_or(0, Vec::len);
+    let mut v = vec![BigInt::zero(); dim];
+    for (b, c) in basis.iter().zip(coeffs) {
+        for (x, y) in v.iter_mut().zip(b) {
+            *x += c * y;
+        }
+    }
+    v
+}
+
+/// Finds a lattice vector close to `target` with Babai's nearest plane
+/// algorithm on an LLL reduced basis.
+///
+/// The distance to the result is at most `2^(n / 2)` times the distance to a
+/// closest lattice vector.
+///
+/// # Panics
+///
+/// Panics if the basis is linearly dependent.
+///
+/// # Example
+///
+/// ```
+/// # use math2::lattice::babai_nearest_plane;
+/// # use num::{BigInt, BigRational};
+/// let basis = [[2, 0], [1, 3]].map(|v| v.map(BigInt::from).to_vec());
+/// let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+/// let target = [q(21, 5), q(29, 5)];
+/// assert_eq!(vec![BigInt::from(4), BigInt::from(6)], babai_nearest_plane(&basis, &target));
+/// ```
+pub fn babai_nearest_plane(basis: &[Vec<BigInt>], target: &[BigRational]) -> Vec<BigInt> {
+    let basis = lll(basis);
+    let gs = GramSchmidt::new(&basis);
+    let mut rest = target.to_vec();
+    let mut coeffs = vec![BigInt::zero(); basis.len()];
+    for i in (0..basis.len()).rev() {
+        let c = (dot(&rest, &gs.vectors[i]) / &gs.norms[i]).round();
+        for (x, y) in rest.iter_mut().zip(&basis[i]) {
+            *x -= &c * BigRational::from_integer(y.clone());
+        }
+        coeffs[i] = c.to_integer();
+    }
+    combination(&basis, &coeffs)
+}
+
+/// Depth first search over the coefficients `x_i` for `i < level`, given the
+/// coefficients above. `partial` is the squared length contributed by the
+/// levels above, and `best` the smallest squared length found so far.
+fn enumerate(
+    gs: &GramSchmidt,
+    level: usize,
+    coeffs: &mut Vec<BigInt>,
+    partial: BigRational,
+    best: &mut (BigRational, Vec<BigInt>),
+) {
+    if level == 0 {
+        if !partial.is_zero() && partial < best.0 {
+            *best = (partial, coeffs.clone());
+        }
+        return;
+    }
+    let i = level - 1;
+    let n = gs.norms.len();
+    // the projection onto b*_i is (x_i - center) b*_i
+    let center: BigRational = -(i + 1..n)
+        .map(|j| &gs.mu[j][i] * BigRational::from_integer(coeffs[j].clone()))
+        .sum::<BigRational>();
+    let length = |x: &BigInt| {
+        let d = BigRational::from_integer(x.clone()) - &center;
+        &partial + &d * &d * &gs.norms[i]
+    };
+    // the admissible x_i form an interval around the center
+    let start = center.floor().to_integer();
+    for step in [-1, 1] {
+        let mut x = if step < 0 { start.clone() } else { &start + 1 };
+        loop {
+            let len = length(&x);
+            if len >= best.0 {
+                break;
+            }
+            coeffs[i] = x.clone();
+            enumerate(gs, i, coeffs, len, best);
+            x += step;
+        }
+    }
+    coeffs[i] = BigInt::zero();
+}
+
+/// Finds a shortest non-zero vector of the lattice, by enumerating all
+/// lattice vectors shorter than the first vector of an LLL reduced basis.
+///
+/// This takes time exponential in the dimension, so it is meant for small
+/// lattices.
+///
+/// # Panics
+///
+/// Panics if the basis is empty or linearly dependent.
+///
+/// # Example
+///
+/// ```
+/// # use math2::lattice::shortest_vector;
+/// # use num::BigInt;
+/// let basis = [[1, 0, 0, 1345], [0, 1, 0, 35], [0, 0, 1, 154]];
+/// let basis = basis.map(|v| v.map(BigInt::from).to_vec());
+/// // -1345 - 35 + 9 * 154 = 6
+/// let v = shortest_vector(&basis);
+/// assert_eq!([1, 1, -9, -6].map(BigInt::from).to_vec(), v);
+/// ```
+pub fn shortest_vector(basis: &[Vec<BigInt>]) -> Vec<BigInt> {
+    assert!(!basis.is_empty(), "empty lattice");
+    let basis = lll(basis);
+    let gs = GramSchmidt::new(&basis);
+    let first = to_rational(&basis[0]);
+    let n = basis.len();
+    let mut first_coeffs = vec![BigInt::zero(); n];
+    first_coeffs[0] = BigInt::from(1);
+    let mut best = (dot(&first, &first), first_coeffs);
+    let mut coeffs = vec![BigInt::zero(); n];
+    enumerate(&gs, n, &mut coeffs, BigRational::zero(), &mut best);
+    let v = combination(&basis, &best.1);
+    // pick a canonical sign
+    match v.iter().find(|x| !x.is_zero()) {
+        Some(x) if x.is_negative() => v.into_iter().map(|x| -x).collect(),
+        _ => v,
+    }
+}
+
+/// `round(x * scale)`.
+fn scaled(x: &BigRational, scale: &BigInt) -> BigInt {
+    (x * BigRational::from_integer(scale.clone())).round().to_integer()
+}
+
+/// Finds integers `a_i`, not all zero and at most `bound` in absolute value,
+/// with `a_0 x_0 + ... + a_(n-1) x_(n-1)` close to zero, if LLL can find any.
+///
+/// The inputs are scaled by `bound^(n + 1)` and appended to an identity
+/// basis, so they should be accurate to about `bound^-(n + 1)`. A candidate
+/// is accepted if the scaled sum is no larger than `bound`, and exact
+/// relations among exact inputs are always accepted. The first non-zero
+/// entry of the result is positive.
+///
+/// # Panics
+///
+/// Panics if `bound` is not positive.
+///
+/// # Example
+///
+/// ```
+/// # use math2::lattice::find_integer_relation;
+/// # use num::{BigInt, BigRational};
+/// // an approximation of the golden ratio, which satisfies x^2 - x - 1 = 0
+/// let phi = BigRational::new(1618033988749895u64.into(), 1000000000000000u64.into());
+/// let powers = [BigRational::from_integer(1.into()), phi.clone(), &phi * &phi];
+/// let relation = find_integer_relation(&powers, &BigInt::from(10)).unwrap();
+/// assert_eq!([1, 1, -1].map(BigInt::from).to_vec(), relation);
+/// ```
+pub fn find_integer_relation(xs: &[BigRational], bound: &BigInt) -> Option<Vec<BigInt>> {
+    assert!(bound.is_positive(), "bound must be positive");
+    let n = xs.len();
+    let scale = num::pow(bound.clone(), n + 1);
+    let basis: Vec<Vec<BigInt>> = xs
+        .iter()
+        .enumerate()
+        .map(|(i, x)| {
+            let mut row = vec![BigInt::zero(); n + 1];
+            row[i] = BigInt::from(1);
+            row[n] = scaled(x, &scale);
+            row
+        })
+        .collect();
+    lll(&basis).into_iter().find_map(|mut v| {
+        v.pop();
+        if v.iter().all(Zero::is_zero) || v.iter().any(|a| &a.abs() > bound) {
+            return None;
+        }
+        let sum: BigRational = v
+            .iter()
+            .zip(xs)
+            .map(|(a, x)| BigRational::from_integer(a.clone()) * x)
+            .sum();
+        if scaled(&sum.abs(), &scale) > *bound {
+            return None;
+        }
+        let negative = v.iter().find(|a| !a.is_zero()).unwrap().is_negative();
+        Some(if negative { v.into_iter().map(|a| -a).collect() } else { v })
+    })
+}
+
+/// Finds a common denominator `0 < q <= denominator_bound` and numerators
+/// `p_i` such that every `p_i / q` is close to `x_i`, returning `(q, p)`.
+///
+/// By Dirichlet's theorem some such `q` has `|q x_i - p_i| <= Q^(-1 / n)`
+/// for all `i`, where `Q` is the bound. The `q` found by LLL reduction is
+/// within a factor exponential in `n` of that, and the best of the reduced
+/// basis vectors is returned.
+///
+/// # Panics
+///
+/// Panics if `xs` is empty or `denominator_bound` is not positive.
+///
+/// # Example
+///
+/// ```
+/// # use math2::lattice::simultaneous_approximation;
+/// # use num::{BigInt, BigRational};
+/// let q = |a: u64, b: u64| BigRational::new(a.into(), b.into());
+/// // close to 1/7 and 3/7
+/// let xs = [q(142858, 1000000), q(428571, 1000000)];
+/// let (denominator, numerators) = simultaneous_approximation(&xs, &BigInt::from(20));
+/// assert_eq!(BigInt::from(7), denominator);
+/// assert_eq!(vec![BigInt::from(1), BigInt::from(3)], numerators);
+/// ```
+pub fn simultaneous_approximation(
+    xs: &[BigRational],
+    denominator_bound: &BigInt,
+) -> (BigInt, Vec<BigInt>) {
+    assert!(!xs.is_empty(), "nothing to approximate");
+    assert!(denominator_bound.is_positive(), "bound must be positive");
+    let n = xs.len();
+    // weight the errors so that an error of Q^(-1 / n) counts as much as a
+    // denominator of Q
+    let scale = num::integer::Roots::nth_root(&num::pow(denominator_bound.clone(), n + 1), n as u32);
+    let mut basis = vec![vec![BigInt::zero(); n + 1]; n + 1];
+    basis[0][0] = BigInt::from(1);
+    for (i, x) in xs.iter().enumerate() {
+        basis[0][i + 1] = scaled(x, &scale);
+        basis[i + 1][i + 1] = scale.clone();
+    }
+    let numerators = |q: &BigInt| -> Vec<BigInt> {
+        let q = BigRational::from_integer(q.clone());
+        xs.iter().map(|x| (x * &q).round().to_integer()).collect()
+    };
+    let error = |q: &BigInt| -> BigRational {
+        let qr = BigRational::from_integer(q.clone());
+        xs.iter()
+            .zip(numerators(q))
+            .map(|(x, p)| (x * &qr - BigRational::from_integer(p)).abs())
+            .max()
+            .unwrap()
+    };
+    let q = lll(&basis)
+        .into_iter()
+        .map(|v| v[0].abs())
+        .filter(|q| q.is_positive() && q <= denominator_bound)
+        .min_by_key(|q| error(q))
+        .unwrap_or_else(|| BigInt::from(1));
+    let p = numerators(&q);
+    (q, p)
+}
+
+/// A candidate minimal polynomial, found by [`guess_minimal_polynomial`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct MinimalPolynomialGuess {
+    /// An irreducible primitive polynomial with a positive leading
+    /// coefficient.
+    pub polynomial: Polynomial<BigInt>,
+    /// `|p(x)|` at the given approximation.
+    pub residual: BigRational,
+    /// How many decimal digits smaller the residual is than `H^-d`, the size
+    /// expected of a chance relation of degree `d` and height `H`. A guess
+    /// with only a few excess digits may well be spurious. This is infinite
+    /// if `x` is an exact root.
+    pub excess_digits: f64,
+}
+
+/// An approximation of `log10(x)` for a positive `x`, which works for values
+/// beyond the range of `f64`.
+fn log10(x: &BigRational) -> f64 {
+    let log2 = |n: &BigInt| {
+        let shift = n.bits().saturating_sub(64);
+        (n >> shift).to_f64().unwrap().log2() + shift as f64
+    };
+    (log2(x.numer()) - log2(x.denom())) * std::f64::consts::LOG10_2
+}
+
+/// Guesses the minimal polynomial of the algebraic number approximated by
+/// `x`, trying each degree up to `max_degree` in turn.
+///
+/// `x` is taken to be accurate to about one over its denominator, which is
+/// right for decimal expansions and for floats converted exactly with
+/// `BigRational::from_float`. At degree `d` the coefficients are bounded so
+/// that `H^(d + 2)` is about that accuracy, an integer relation among the
+/// powers of `x` is looked for with [`find_integer_relation`], and the
+/// irreducible factor of it closest to vanishing at `x` is returned.
+///
+/// # Example
+///
+/// ```
+/// # use math2::lattice::guess_minimal_polynomial;
+/// # use math2::Polynomial;
+/// # use num::{BigInt, BigRational};
+/// let x = BigRational::from_float(2f64.sqrt() + 3f64.sqrt()).unwrap();
+/// let guess = guess_minimal_polynomial(&x, 6).unwrap();
+/// assert_eq!(Polynomial::new([1, 0, -10, 0, 1].map(BigInt::from).to_vec()), guess.polynomial);
+/// assert!(guess.excess_digits > 2.0);
+/// ```
+pub fn guess_minimal_polynomial(x: &BigRational, max_degree: usize) -> Option<MinimalPolynomialGuess> {
+    let eval = |p: &Polynomial<BigInt>| {
+        p.coeffs
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * x + BigRational::from_integer(c.clone()))
+    };
+    let precision = x.denom();
+    let mut powers = vec![BigRational::one()];
+    for d in 1..=max_degree {
+        powers.push(&powers[d - 1] * x);
+        let bound = num::integer::Roots::nth_root(precision, d as u32 + 2).max(BigInt::from(2));
+        let Some(relation) = find_integer_relation(&powers, &bound) else {
+            continue;
+        };
+        let polynomial = Polynomial::new_trim_zeroes(relation)
+            .factor()
+            .factors
+            .into_iter()
+            .map(|(f, _)| f)
+            .min_by_key(|f| eval(f).abs())?;
+        let residual = eval(&polynomial).abs();
+        let degree = polynomial.degree().unwrap();
+        let height = polynomial.coeffs.iter().map(Signed::abs).max().unwrap();
+        let excess_digits = if residual.is_zero() {
+            f64::INFINITY
+        } else {
+            -log10(&residual) - degree as f64 * log10(&BigRational::from_integer(height))
+        };
+        return Some(MinimalPolynomialGuess {
+            polynomial,
+            residual,
+            excess_digits,
+        });
+    }
+    None
+}
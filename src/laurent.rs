@@ -0,0 +1,210 @@
+//! Laurent polynomials, which allow negative exponents.
+
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::sparse::Terms;
+use crate::traits::{AssertUnit, CommutativeRing, Field};
+use crate::Polynomial;
+
+/// A Laurent polynomial, a finite sum of terms `c x^e` with `e` any integer,
+/// stored as a map from exponents to non-zero coefficients in the same way
+/// as a [`SparsePolynomial`](crate::sparse::SparsePolynomial).
+///
+/// # Example
+///
+/// ```
+/// # use math2::laurent::LaurentPolynomial;
+/// let d = LaurentPolynomial::<i64>::new([(2, -1), (-2, -1)]);
+/// // (-x^2 - x^-2)^2 = x^4 + 2 + x^-4
+/// assert_eq!(LaurentPolynomial::new([(4, 1), (0, 2), (-4, 1)]), d.clone() * d);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LaurentPolynomial<Ring> {
+    terms: Terms<i64, Ring>,
+}
+
+impl<Ring: CommutativeRing> LaurentPolynomial<Ring> {
+    /// Creates a Laurent polynomial from `(exponent, coefficient)` pairs.
+    /// Repeated exponents are added together.
+    pub fn new(terms: impl IntoIterator<Item = (i64, Ring)>) -> Self {
+        Self {
+            terms: Terms::new(terms),
+        }
+    }
+
+    pub fn constant(c: Ring) -> Self {
+        Self::new([(0, c)])
+    }
+
+    /// The Laurent polynomial `c x^exponent`.
+    pub fn monomial(c: Ring, exponent: i64) -> Self {
+        Self::new([(exponent, c)])
+    }
+
+    /// The coefficient of `x^exponent`, or `None` if it is zero.
+    pub fn coeff(&self, exponent: i64) -> Option<&Ring> {
+        self.terms.get(exponent)
+    }
+
+    /// The non-zero terms, in increasing order of their exponents.
+    pub fn terms(&self) -> impl Iterator<Item = (i64, &Ring)> {
+        self.terms.iter()
+    }
+
+    /// The number of non-zero terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// The largest exponent, or `None` for zero.
+    pub fn degree(&self) -> Option<i64> {
+        self.terms.highest()
+    }
+
+    /// The smallest exponent, or `None` for zero.
+    pub fn low_degree(&self) -> Option<i64> {
+        self.terms.lowest()
+    }
+
+    pub fn scalar_mul(self, x: Ring) -> Self {
+        Self {
+            terms: self.terms.scalar_mul(x),
+        }
+    }
+
+    /// The Laurent polynomial `self(x^k)`. With `k = -1` this exchanges `x`
+    /// and `x^-1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is zero.
+    pub fn compose_power(&self, k: i64) -> Self {
+        assert_ne!(k, 0, "x^0 is not a variable");
+        Self {
+            terms: self.terms.map_exponents(|e| e * k),
+        }
+    }
+
+    /// The polynomial `x^-low_degree self`, which has a non-zero constant
+    /// term, and the exponent `low_degree` it was shifted by. Zero gives zero
+    /// and `0`.
+    pub fn to_polynomial(&self) -> (Polynomial<Ring>, i64) {
+        let low = self.low_degree().unwrap_or(0);
+        let mut coeffs = vec![Ring::zero(); self.degree().map_or(0, |d| (d - low + 1) as usize)];
+        for (e, c) in self.terms.iter() {
+            coeffs[(e - low) as usize] = c.clone();
+        }
+        (Polynomial::new(coeffs), low)
+    }
+
+    /// Evaluates at `x`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is zero and a negative exponent appears.
+    pub fn eval(&self, x: &Ring) -> Ring
+    where
+        Ring: Field,
+    {
+        let (p, low) = self.to_polynomial();
+        let value = p.eval(x);
+        let base = if low < 0 {
+            x.clone().checked_inv().expect("negative power of zero")
+        } else {
+            x.clone()
+        };
+        (0..low.unsigned_abs()).fold(value, |acc, _| acc * base.clone())
+    }
+}
+
+impl<Ring: CommutativeRing> From<Polynomial<Ring>> for LaurentPolynomial<Ring> {
+    fn from(p: Polynomial<Ring>) -> Self {
+        Self::new(p.coeffs.into_iter().enumerate().map(|(e, c)| (e as i64, c)))
+    }
+}
+
+impl<Ring: CommutativeRing> Zero for LaurentPolynomial<Ring> {
+    fn zero() -> Self {
+        Self::new([])
+    }
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+impl<Ring: CommutativeRing> One for LaurentPolynomial<Ring> {
+    fn one() -> Self {
+        Self::constant(Ring::one())
+    }
+}
+
+impl<Ring: CommutativeRing> Add for LaurentPolynomial<Ring> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<Ring: CommutativeRing> AddAssign for LaurentPolynomial<Ring> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.terms += rhs.terms;
+    }
+}
+
+impl<Ring: CommutativeRing> Neg for LaurentPolynomial<Ring> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { terms: -self.terms }
+    }
+}
+
+impl<Ring: CommutativeRing> Sub for LaurentPolynomial<Ring> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.neg()
+    }
+}
+
+impl<Ring: CommutativeRing> Mul for LaurentPolynomial<Ring> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            terms: &self.terms * &rhs.terms,
+        }
+    }
+}
+
+impl<Ring: CommutativeRing> Sum for LaurentPolynomial<Ring> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+impl<Ring: CommutativeRing> Product for LaurentPolynomial<Ring> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |a, b| a * b)
+    }
+}
+
+impl<Ring: CommutativeRing> CommutativeRing for LaurentPolynomial<Ring> {
+    /// Only monomials with a unit coefficient are supported, which are all
+    /// the units over a domain.
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert_eq!(1, self.terms.len(), "only monomials are supported as units");
+        self.terms.values().next().unwrap().clone().assert_is_unit();
+        AssertUnit(self)
+    }
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        let (e, c) = x.0.terms.iter().next().unwrap();
+        let c = Ring::invert(&c.clone().assert_is_unit());
+        Self::monomial(c.into_inner(), -e).assert_is_unit()
+    }
+    fn is_nilpotent(&self) -> bool {
+        self.terms.values().all(Ring::is_nilpotent)
+    }
+}
@@ -0,0 +1,227 @@
+//! Checking implementations of the algebraic traits against their axioms.
+//!
+//! The algorithms in this crate rely on the laws behind [`CommutativeRing`],
+//! [`Field`] and the other traits, but the compiler cannot check them. The
+//! functions here test the laws on every pair and triple of a list of
+//! samples, so a list of `n` samples costs `O(n^3)` operations. They are
+//! meant for the tests of new implementations, including ones outside this
+//! crate.
+//!
+//! # Example
+//!
+//! ```
+//! # use math2::fields::Zp;
+//! # use math2::laws::check_field_laws;
+//! let elements: Vec<_> = (0..7).map(Zp::<7>::new).collect();
+//! check_field_laws(&elements).unwrap();
+//! ```
+
+use std::fmt::{self, Debug, Display};
+
+use crate::traits::{CoefficientDomain, CommutativeRing, EuclideanDomain, Field};
+
+/// A law that does not hold, with the samples it fails for.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct LawViolation {
+    /// The law, such as `a + (b + c) = (a + b) + c`.
+    pub law: &'static str,
+    /// The samples, in the order of the variables in the law, formatted with
+    /// their `Debug` implementation.
+    pub elements: Vec<String>,
+}
+
+impl Display for LawViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` does not hold for {}", self.law, self.elements.join(", "))
+    }
+}
+
+impl std::error::Error for LawViolation {}
+
+fn check<R: Debug>(law: &'static str, holds: bool, elements: &[&R]) -> Result<(), LawViolation> {
+    if holds {
+        Ok(())
+    } else {
+        Err(LawViolation {
+            law,
+            elements: elements.iter().map(|x| format!("{x:?}")).collect(),
+        })
+    }
+}
+
+/// `x^n` by repeated squaring.
+fn pow<R: CommutativeRing>(x: &R, mut n: usize) -> R {
+    let (mut base, mut res) = (x.clone(), R::one());
+    while n > 0 {
+        if n & 1 == 1 {
+            res = res * base.clone();
+        }
+        base = base.clone() * base;
+        n >>= 1;
+    }
+    res
+}
+
+/// `n * x` by repeated doubling.
+fn multiple<R: CommutativeRing>(x: &R, mut n: usize) -> R {
+    let (mut base, mut res) = (x.clone(), R::zero());
+    while n > 0 {
+        if n & 1 == 1 {
+            res = res + base.clone();
+        }
+        base = base.clone() + base;
+        n >>= 1;
+    }
+    res
+}
+
+/// Checks the axioms of a commutative ring with identity: addition and
+/// multiplication are associative and commutative with identities `0` and
+/// `1`, negation gives additive inverses, multiplication distributes over
+/// addition, and [`CommutativeRing::sub`] subtracts. Also checks that
+/// [`CommutativeRing::is_nilpotent`] holds for zero, fails for one unless
+/// the ring is trivial, and is closed under multiplication.
+pub fn check_commutative_ring_laws<R: CommutativeRing + PartialEq + Debug>(samples: &[R]) -> Result<(), LawViolation> {
+    let (zero, one) = (R::zero(), R::one());
+    check("0 is zero", zero.is_zero(), &[&zero])?;
+    check("0 is nilpotent", zero.is_nilpotent(), &[&zero])?;
+    check("1 is nilpotent only if 1 = 0", one.is_nilpotent() == one.is_zero(), &[&one])?;
+    for a in samples {
+        check("a is zero iff a = 0", a.is_zero() == (*a == zero), &[a])?;
+        check("a + 0 = a", a.clone() + zero.clone() == *a, &[a])?;
+        check("a * 1 = a", a.clone() * one.clone() == *a, &[a])?;
+        check("a * 0 = 0", (a.clone() * zero.clone()).is_zero(), &[a])?;
+        check("a + -a = 0", (a.clone() + -a.clone()).is_zero(), &[a])?;
+        for b in samples {
+            check("a + b = b + a", a.clone() + b.clone() == b.clone() + a.clone(), &[a, b])?;
+            check("a * b = b * a", a.clone() * b.clone() == b.clone() * a.clone(), &[a, b])?;
+            check(
+                "a - b = a + -b",
+                CommutativeRing::sub(a.clone(), b.clone()) == a.clone() + -b.clone(),
+                &[a, b],
+            )?;
+            if a.is_nilpotent() {
+                check("a nilpotent implies a * b nilpotent", (a.clone() * b.clone()).is_nilpotent(), &[a, b])?;
+            }
+            for c in samples {
+                check(
+                    "a + (b + c) = (a + b) + c",
+                    a.clone() + (b.clone() + c.clone()) == (a.clone() + b.clone()) + c.clone(),
+                    &[a, b, c],
+                )?;
+                check(
+                    "a * (b * c) = (a * b) * c",
+                    a.clone() * (b.clone() * c.clone()) == (a.clone() * b.clone()) * c.clone(),
+                    &[a, b, c],
+                )?;
+                check(
+                    "a * (b + c) = a * b + a * c",
+                    a.clone() * (b.clone() + c.clone()) == a.clone() * b.clone() + a.clone() * c.clone(),
+                    &[a, b, c],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the commutative ring laws, and that every non-zero sample has an
+/// inverse and zero has none, that division multiplies by the inverse, and
+/// that `1 + 1 + ... + 1` vanishes for [`Field::characteristic`] terms. In
+/// characteristic `p > 0`, also checks that `x -> x^p` is additive and that
+/// [`Field::pth_root`] undoes it.
+pub fn check_field_laws<F: Field + PartialEq + Debug>(samples: &[F]) -> Result<(), LawViolation> {
+    check_commutative_ring_laws(samples)?;
+    let one = F::one();
+    check("1 != 0", !one.is_zero(), &[&one])?;
+    check("0 has no inverse", F::zero().checked_inv().is_none(), &[&F::zero()])?;
+    let p = F::characteristic();
+    if p > 0 {
+        check("p * 1 = 0", multiple(&one, p).is_zero(), &[&one])?;
+    }
+    for a in samples {
+        if !a.is_zero() {
+            let inverse = a.checked_inv();
+            check(
+                "a * a^-1 = 1",
+                inverse.is_some_and(|i| (a.clone() * i).is_one()),
+                &[a],
+            )?;
+        }
+        if p > 0 {
+            check("(a^p)^(1/p) = a", pow(a, p).pth_root() == *a, &[a])?;
+        }
+        for b in samples {
+            if !b.is_zero() {
+                let product = a.clone() * b.checked_inv().unwrap();
+                check("a / b = a * b^-1", a.clone() / b.clone() == product, &[a, b])?;
+                check("Field::div(a, b) = a * b^-1", Field::div(a.clone(), b.clone()) == Some(product), &[a, b])?;
+            } else {
+                check("Field::div(a, 0) = None", Field::div(a.clone(), b.clone()).is_none(), &[a, b])?;
+            }
+            if p > 0 {
+                check(
+                    "(a + b)^p = a^p + b^p",
+                    pow(&(a.clone() + b.clone()), p) == pow(a, p) + pow(b, p),
+                    &[a, b],
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks the commutative ring laws, and that [`EuclideanDomain::div_rem`]
+/// gives a remainder of smaller norm and that
+/// [`EuclideanDomain::extended_euclidean`] gives Bezout coefficients for a
+/// common divisor.
+pub fn check_euclidean_domain_laws<E: EuclideanDomain + PartialEq + Debug>(samples: &[E]) -> Result<(), LawViolation> {
+    check_commutative_ring_laws(samples)?;
+    let divides = |g: &E, a: &E| if g.is_zero() { a.is_zero() } else { EuclideanDomain::div_rem(a, g).1.is_zero() };
+    for a in samples {
+        for b in samples {
+            if !b.is_zero() {
+                let (q, r) = EuclideanDomain::div_rem(a, b);
+                check("a = q * b + r", q * b.clone() + r.clone() == *a, &[a, b])?;
+                check("r = 0 or norm(r) < norm(b)", r.is_zero() || r.norm() < b.norm(), &[a, b])?;
+            }
+            let (g, s, t) = a.extended_euclidean(b);
+            check("s * a + t * b = g", s * a.clone() + t * b.clone() == g, &[a, b])?;
+            check("g divides a and b", divides(&g, a) && divides(&g, b), &[a, b])?;
+        }
+    }
+    Ok(())
+}
+
+/// Checks the commutative ring laws, and that
+/// [`CoefficientDomain::unit_and_normal`] splits every sample into an
+/// invertible unit and a normal part that is its own normal part, and that
+/// [`CoefficientDomain::gcd`] divides both arguments with
+/// [`CoefficientDomain::divide_exact`] recovering them. Pairs of zeros are
+/// skipped in the gcd checks.
+pub fn check_coefficient_domain_laws<D: CoefficientDomain + PartialEq + Debug>(
+    samples: &[D],
+) -> Result<(), LawViolation> {
+    check_commutative_ring_laws(samples)?;
+    for a in samples {
+        let (unit, normal) = a.clone().unit_and_normal();
+        let inverse = D::invert(&unit).into_inner();
+        let unit = unit.into_inner();
+        check("unit * normal = a", unit.clone() * normal.clone() == *a, &[a])?;
+        check("unit * unit^-1 = 1", (unit * inverse).is_one(), &[a])?;
+        check("normal(normal(a)) = normal(a)", normal.clone().unit_and_normal().1 == normal, &[a])?;
+        for b in samples {
+            if a.is_zero() && b.is_zero() {
+                continue;
+            }
+            let g = a.gcd(b);
+            check("gcd(a, b) != 0", !g.is_zero(), &[a, b])?;
+            check(
+                "gcd(a, b) divides a and b",
+                a.divide_exact(&g) * g.clone() == *a && b.divide_exact(&g) * g.clone() == *b,
+                &[a, b],
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -3,22 +3,162 @@ use std::iter::{repeat_with, Product, Sum};
 use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
 
 use num::{One, Zero};
-use traits::{CommutativeRing, Field, FromUsize, CoefficientDomain};
+use traits::{CommutativeRing, Field, FromUsize, CoefficientDomain, EuclideanDomain, Semiring};
+
+/// Adds to one of the [`stats::OpCounts`] when the `stats` feature is enabled.
+macro_rules! count {
+    ($field:ident, $n:expr) => {
+        #[cfg(feature = "stats")]
+        $crate::stats::record(|counts| counts.$field += $n as u64);
+    };
+}
 
+pub mod approximation;
+pub mod boolean;
+#[cfg(feature = "batch")]
+pub mod batch;
+pub mod cache;
+pub mod codegen;
+pub mod combinatorics;
+pub mod cyclotomic;
+pub mod decimal;
 pub mod factorization;
+pub mod fields;
+#[cfg(feature = "float")]
+pub mod float_gcd;
+pub mod fitting;
+pub mod graph;
+#[cfg(feature = "float")]
+pub mod homotopy;
+pub mod interval;
+pub mod knot;
+pub mod laws;
+pub mod lattice;
+pub mod laurent;
+pub mod linalg;
+pub mod parse;
 pub mod print;
+pub mod radix;
+pub mod random;
+pub mod rational_function;
+pub mod real_roots;
+pub mod reference;
+pub mod resultant;
+pub mod semiring;
+pub mod series;
+pub mod sparse;
+pub mod sum_of_squares;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod taylor_model;
 pub mod traits;
+pub mod tropical;
+pub mod mobius;
 pub mod modular_arith;
+pub mod modular_factorization;
+pub mod multimodular;
+pub mod multivariate;
+pub mod newton;
+pub mod ntt;
+pub mod number_theory;
+pub mod piecewise;
+pub mod polytope;
+pub mod umbral;
+pub mod zassenhaus;
 
 #[cfg(test)]
 mod tests;
 
+/// The number of coefficients from which [`Polynomial::compose`] splits
+/// the outer polynomial instead of using Horner's scheme.
+pub const COMPOSE_THRESHOLD: usize = 16;
+
+/// Composes the polynomial with coefficients `coeffs` with `powers[0]`,
+/// where `powers[k] = powers[0]^(2^k)`.
+fn compose_split<Ring: CommutativeRing>(coeffs: &[Ring], powers: &[Polynomial<Ring>]) -> Polynomial<Ring> {
+    if coeffs.len() < COMPOSE_THRESHOLD {
+        return Polynomial::new_trim_zeroes(coeffs.to_vec()).eval(&powers[0]);
+    }
+    // the largest power of two below the length
+    let k = (coeffs.len() - 1).ilog2() as usize;
+    let (lo, hi) = coeffs.split_at(1 << k);
+    compose_split(lo, &powers[..k]) + powers[k].clone() * compose_split(hi, powers)
+}
+
+/// The number of coefficients of the shorter factor from which
+/// multiplication splits the factors with Karatsuba's method. Below it, the
+/// extra additions and allocations cost more than the saved multiplications.
+///
+/// With `benches/multiplication.rs`, the best threshold for 512 coefficient
+/// `BigInt` products was between 8 and 24 for 64 bit coefficients, and
+/// between 4 and 8 for 1024 bit ones, where the multiplications dominate.
+pub const KARATSUBA_THRESHOLD: usize = 16;
+
+/// Adds `x` to `acc` term by term. `acc` must be at least as long as `x`.
+fn add_into<Ring: Semiring>(acc: &mut [Ring], x: Vec<Ring>) {
+    for (a, b) in acc.iter_mut().zip(x) {
+        let c = std::mem::replace(a, Ring::zero());
+        *a = c + b;
+    }
+}
+
+/// The sum of two coefficient slices, as long as the longer one.
+fn add_slices<Ring: Semiring>(a: &[Ring], b: &[Ring]) -> Vec<Ring> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = long.to_vec();
+    add_into(&mut sum, short.to_vec());
+    sum
+}
+
+fn mul_schoolbook<Ring: Semiring>(a: &[Ring], b: &[Ring]) -> Vec<Ring> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    count!(multiplications, a.len() * b.len());
+    let mut product: Vec<Ring> = repeat_with(Ring::zero).take(a.len() + b.len() - 1).collect();
+    for (i, x) in a.iter().enumerate() {
+        add_into(&mut product[i..], b.iter().map(|y| x.clone() * y.clone()).collect());
+    }
+    product
+}
+
+/// The product of two coefficient slices, with `a.len() + b.len() - 1`
+/// coefficients unless one of them is empty.
+fn mul_karatsuba<Ring: CommutativeRing>(a: &[Ring], b: &[Ring], threshold: usize) -> Vec<Ring> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    if b.len() < threshold.max(2) {
+        return mul_schoolbook(a, b);
+    }
+    let mut product: Vec<Ring> = repeat_with(Ring::zero).take(a.len() + b.len() - 1).collect();
+    if a.len() >= 2 * b.len() {
+        // multiply balanced pieces of the longer factor
+        for (i, chunk) in a.chunks(b.len()).enumerate() {
+            add_into(&mut product[i * b.len()..], mul_karatsuba(chunk, b, threshold));
+        }
+        return product;
+    }
+    // a = a0 + x^m a1 and b = b0 + x^m b1, where b1 is not empty as m < b.len()
+    let m = a.len() / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let low = mul_karatsuba(a0, b0, threshold);
+    let high = mul_karatsuba(a1, b1, threshold);
+    // a0 b1 + a1 b0 = (a0 + a1)(b0 + b1) - a0 b0 - a1 b1
+    let mut middle = mul_karatsuba(&add_slices(a0, a1), &add_slices(b0, b1), threshold);
+    add_into(&mut middle, low.iter().cloned().map(Neg::neg).collect());
+    add_into(&mut middle, high.iter().cloned().map(Neg::neg).collect());
+    add_into(&mut product[m..], middle);
+    add_into(&mut product[2 * m..], high);
+    add_into(&mut product, low);
+    product
+}
+
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Polynomial<Ring> {
     pub(crate) coeffs: Vec<Ring>,
 }
 
-impl<Ring: CommutativeRing> Polynomial<Ring> {
+impl<Ring: Semiring> Polynomial<Ring> {
     /// The coefficients of the polynomial in order of increasing degree.
     ///
     /// There must be no trailing zeros.
@@ -53,10 +193,6 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
         }
     }
 
-    pub fn into_iter(self) -> impl ExactSizeIterator<Item = Ring> {
-        self.coeffs.into_iter()
-    }
-
     /// The degree of the polynomial. `None` if the polynomial is zero.
     pub fn degree(&self) -> Option<usize> {
         self.coeffs.len().checked_sub(1)
@@ -79,6 +215,9 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
 
     /// `"x^2 + 1".raise_by(2) = "x^4 + x^2"`
     pub fn raise_by(&mut self, n: usize) {
+        if self.is_zero() {
+            return;
+        }
         self.coeffs.resize_with(self.coeffs.len() + n, Ring::zero);
         self.coeffs.rotate_right(n);
     }
@@ -90,6 +229,7 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
 
     /// Multiples all the coefficients by a given scalar.
     pub fn scalar_mul_mut(&mut self, x: Ring) {
+        count!(multiplications, self.coeffs.len());
         for coeff in self.coeffs.iter_mut() {
             let c = std::mem::replace(coeff, Ring::zero());
             *coeff = c * x.clone();
@@ -102,7 +242,43 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
         self
     }
 
-    /// Performs differentiation with respect to the polynomial's variable
+    /// Evaluates at `x` with Horner's scheme. `x` can come from any ring
+    /// that the coefficients add into, such as a rational point for an
+    /// integer polynomial, or a polynomial for composition.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::{BigInt, BigRational};
+    /// // x^2 - 2x + 3
+    /// let p = Polynomial::new([3, -2, 1].map(BigInt::from).to_vec());
+    /// assert_eq!(BigInt::from(11), p.eval(&BigInt::from(4)));
+    /// let half = BigRational::new(1.into(), 2.into());
+    /// assert_eq!(BigRational::new(9.into(), 4.into()), p.eval(&half));
+    /// // p(x + 1) = x^2 + 2
+    /// let shift = Polynomial::new([1, 1].map(BigInt::from).to_vec());
+    /// assert_eq!(Polynomial::new([2, 0, 1].map(BigInt::from).to_vec()), p.eval(&shift));
+    /// ```
+    pub fn eval<S>(&self, x: &S) -> S
+    where
+        S: Zero + Clone + Mul<Output = S> + Add<Ring, Output = S>,
+    {
+        self.coeffs
+            .iter()
+            .rev()
+            .fold(S::zero(), |acc, c| acc * x.clone() + c.clone())
+    }
+
+    /// Multiplies term by term, with `deg self * deg other` coefficient
+    /// multiplications.
+    pub fn schoolbook_mul(&self, other: &Self) -> Self {
+        Polynomial::new_trim_zeroes(mul_schoolbook(&self.coeffs, &other.coeffs))
+    }
+}
+
+impl<Ring: CommutativeRing> Polynomial<Ring> {
+    /// Replaces the polynomial with its [derivative](Self::derivative).
     pub fn derive_in_place(&mut self)
     where
         Ring: FromUsize,
@@ -116,8 +292,19 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
             *coeff = c * Ring::from_usize(i);
         }
         self.coeffs.remove(0);
+        // in positive characteristic the leading term can vanish
+        self.trim_zeros();
     }
 
+    /// The formal derivative, with `i a_i x^(i - 1)` for each term `a_i x^i`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::<i64>::new(vec![5, 3, 0, 2]);
+    /// assert_eq!(Polynomial::new(vec![3, 0, 6]), p.derivative());
+    /// ```
     pub fn derivative(mut self) -> Self
     where
         Ring: FromUsize,
@@ -126,6 +313,238 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
         self
     }
 
+    /// The `k`-th derivative, with `i (i - 1) ... (i - k + 1) a_i x^(i - k)`
+    /// for each term `a_i x^i` of degree at least `k`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::<i64>::new(vec![1, 1, 1, 1, 1]);
+    /// assert_eq!(Polynomial::new(vec![6, 24]), p.nth_derivative(3));
+    /// assert_eq!(p, p.nth_derivative(0));
+    /// ```
+    pub fn nth_derivative(&self, k: usize) -> Self
+    where
+        Ring: FromUsize,
+    {
+        let coeffs = self
+            .coeffs
+            .iter()
+            .enumerate()
+            .skip(k)
+            .map(|(i, c)| (i - k + 1..=i).fold(c.clone(), |acc, j| acc * Ring::from_usize(j)))
+            .collect();
+        // in positive characteristic the leading terms can vanish
+        Polynomial::new_trim_zeroes(coeffs)
+    }
+
+    /// The antiderivative with zero constant term, with
+    /// `a_i / (i + 1) x^(i + 1)` for each term `a_i x^i`, so that its
+    /// [derivative](Self::derivative) is `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i + 1` is zero in the field for some term, which happens in
+    /// characteristic `p` when the degree is at least `p - 1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let p: Polynomial<BigRational> = "3x^2 + x - 2".parse().unwrap();
+    /// let expected: Polynomial<BigRational> = "x^3 + 1/2 x^2 - 2x".parse().unwrap();
+    /// assert_eq!(expected, p.antiderivative());
+    /// assert_eq!(p, expected.derivative());
+    /// ```
+    pub fn antiderivative(&self) -> Self
+    where
+        Ring: Field + FromUsize,
+    {
+        if self.is_zero() {
+            return Self::zero();
+        }
+        let coeffs = self.coeffs.iter().enumerate().map(|(i, c)| {
+            let inv = Ring::from_usize(i + 1)
+                .checked_inv()
+                .expect("antiderivative divides by zero in this characteristic");
+            c.clone() * inv
+        });
+        Polynomial::new(std::iter::once(Ring::zero()).chain(coeffs).collect())
+    }
+
+    /// The definite integral from `a` to `b`, the difference of the values
+    /// of the [antiderivative](Self::antiderivative) at `b` and `a`.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the antiderivative does.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let p: Polynomial<BigRational> = "x^2".parse().unwrap();
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// assert_eq!(BigRational::new(26.into(), 3.into()), p.integral(&n(1), &n(3)));
+    /// ```
+    pub fn integral(&self, a: &Ring, b: &Ring) -> Ring
+    where
+        Ring: Field + FromUsize,
+    {
+        let antiderivative = self.antiderivative();
+        antiderivative.eval(b) + -antiderivative.eval(a)
+    }
+
+    /// Multiplies with Karatsuba's method, which computes a product of two
+    /// polynomials of `n` coefficients from three products of half the
+    /// size, for `O(n^1.59)` coefficient multiplications. Products where the
+    /// shorter factor has fewer than `threshold` coefficients use
+    /// [`Polynomial::schoolbook_mul`], and a much longer factor is cut into
+    /// pieces the size of the shorter one.
+    ///
+    /// The `*` operator calls this with [`KARATSUBA_THRESHOLD`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::<i64>::new((1..=100).collect());
+    /// let q = Polynomial::<i64>::new((1..=60).rev().collect());
+    /// assert_eq!(p.schoolbook_mul(&q), p.karatsuba_mul(&q, 4));
+    /// ```
+    pub fn karatsuba_mul(&self, other: &Self, threshold: usize) -> Self {
+        Polynomial::new_trim_zeroes(mul_karatsuba(&self.coeffs, &other.coeffs, threshold))
+    }
+
+    /// Returns `self(other(x))`.
+    ///
+    /// Small polynomials use Horner's scheme. From [`COMPOSE_THRESHOLD`]
+    /// coefficients on, `self = lo + x^m hi` is split in half and
+    /// `lo(other) + other^m hi(other)` is computed recursively with the powers
+    /// `other^(2^k)`, so most of the work is in a few products of balanced
+    /// size instead of many products with a small factor.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let p: Polynomial<BigRational> = "x^2 - 2x".parse().unwrap();
+    /// let q: Polynomial<BigRational> = "x + 1".parse().unwrap();
+    /// assert_eq!("x^2 - 1".parse::<Polynomial<BigRational>>().unwrap(), p.compose(&q));
+    /// ```
+    pub fn compose(&self, other: &Self) -> Self {
+        if self.coeffs.len() < COMPOSE_THRESHOLD {
+            return self.eval(other);
+        }
+        let mut powers = vec![other.clone()];
+        while 1 << powers.len() < self.coeffs.len() {
+            let last = powers.last().unwrap().clone();
+            powers.push(last.clone() * last);
+        }
+        compose_split(&self.coeffs, &powers)
+    }
+
+    /// Returns `self(x + a)`.
+    ///
+    /// Below [`COMPOSE_THRESHOLD`] coefficients this is `n` passes of
+    /// synthetic division by `x - a` in place, like
+    /// [`Polynomial::eval_with_derivatives`] run to the full degree. Larger
+    /// polynomials go through the divide and conquer path of
+    /// [`Polynomial::compose`], which multiplies by the powers `(x + a)^(2^k)`
+    /// and so needs fewer passes over the long coefficient vectors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// // x^3 - 2x at x + 2 is x^3 + 6x^2 + 10x + 4
+    /// let p = Polynomial::<i64>::new(vec![0, -2, 0, 1]);
+    /// assert_eq!(Polynomial::new(vec![4, 10, 6, 1]), p.taylor_shift(&2));
+    /// ```
+    pub fn taylor_shift(&self, a: &Ring) -> Self {
+        let n = self.coeffs.len();
+        if n >= COMPOSE_THRESHOLD {
+            return self.compose(&Polynomial::new(vec![a.clone(), Ring::one()]));
+        }
+        let mut coeffs = self.coeffs.clone();
+        for i in 1..n {
+            for j in (i - 1..n - 1).rev() {
+                let t = a.clone() * coeffs[j + 1].clone();
+                let c = std::mem::replace(&mut coeffs[j], Ring::zero());
+                coeffs[j] = c + t;
+            }
+        }
+        Polynomial::new(coeffs)
+    }
+
+    /// Returns `[f(x), f'(x), ..., f^(k)(x)]`.
+    ///
+    /// This takes `k + 1` passes of synthetic division by `t - x`, each of
+    /// which turns one more coefficient into a Taylor coefficient at `x`.
+    /// That is `O(k * degree)` ring operations instead of differentiating and
+    /// evaluating `k` times.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// // x^3 - 2x at 2
+    /// let p = Polynomial::<i64>::new(vec![0, -2, 0, 1]);
+    /// assert_eq!(vec![4, 10, 12, 6, 0], p.eval_with_derivatives(&2, 4));
+    /// ```
+    pub fn eval_with_derivatives(&self, x: &Ring, k: usize) -> Vec<Ring>
+    where
+        Ring: FromUsize,
+    {
+        let mut taylor = self.coeffs.clone();
+        taylor.resize_with(taylor.len().max(k + 1), Ring::zero);
+        let degree = self.coeffs.len().saturating_sub(1);
+        for i in 0..=k.min(degree) {
+            for j in (i..degree).rev() {
+                let next = taylor[j + 1].clone();
+                let c = std::mem::replace(&mut taylor[j], Ring::zero());
+                taylor[j] = c + next * x.clone();
+            }
+        }
+        taylor.truncate(k + 1);
+        let mut factorial = Ring::one();
+        for (i, t) in taylor.iter_mut().enumerate().skip(2) {
+            factorial = factorial * Ring::from_usize(i);
+            let c = std::mem::replace(t, Ring::zero());
+            *t = c * factorial.clone();
+        }
+        taylor
+    }
+
+    /// Returns `self^n`, by repeated squaring. That takes about `log2(n)`
+    /// squarings, and most of the work is in the last one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::<i64>::new(vec![1, 1]);
+    /// assert_eq!(Polynomial::new(vec![1, 4, 6, 4, 1]), p.pow(4));
+    /// assert_eq!(Polynomial::new(vec![1]), p.pow(0));
+    /// ```
+    pub fn pow(&self, mut n: u32) -> Self {
+        let (mut base, mut res) = (self.clone(), Polynomial::one());
+        while n > 0 {
+            if n & 1 == 1 {
+                res = res * base.clone();
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.clone() * base;
+            }
+        }
+        res
+    }
+
     /// Performs polynomial division, returns a (quotient, remainder) tuple.
     pub fn div_rem(self, other: Polynomial<Ring>) -> (Polynomial<Ring>, Polynomial<Ring>)
     where
@@ -146,36 +565,100 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
         while m >= n {
             let lcr = remainder.leading_coefficient_cloned();
             let s = lcr.clone() / lcv.clone();
+            count!(divisions, 1);
             quotient += Polynomial::from_elem_with_degree(s.clone(), m - n);
             let poly = (other.clone() - Polynomial::from_elem_with_degree(lcv.clone(), n))
                 .scalar_mul(s)
                 .raised_by(m - n);
             remainder = (remainder - Polynomial::from_elem_with_degree(lcr, m)) - poly;
-            m = remainder.degree().unwrap_or(0);
+            let Some(degree) = remainder.degree() else {
+                break;
+            };
+            m = degree;
         }
 
         (quotient, remainder)
     }
 
     /// Returns a *monic* polynomial that is a factor in both `self` and `other`.
-    pub fn gcd(mut self, mut other: Self) -> Self
+    pub fn gcd(self, other: Self) -> Self
     where
         Ring: Field,
     {
         if self.is_zero() && other.is_zero() {
             return Polynomial::zero();
         }
-        while !other.is_zero() {
-            let r = self.clone().div_rem(other.clone()).1;
-            self = other;
-            other = r;
+        count!(gcds, 1);
+        let gcd = EuclideanDomain::euclidean_gcd(&self, &other);
+        let lc = gcd.leading_coefficient_cloned().checked_inv().unwrap();
+        gcd.scalar_mul(lc)
+    }
+
+    /// Returns `(g, s, t)` where `g` is the monic gcd of `self` and `other`,
+    /// and `s * self + t * other = g`.
+    ///
+    /// If neither polynomial divides the other, `deg s < deg other - deg g`
+    /// and `deg t < deg self - deg g`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let a: Polynomial<BigRational> = "x^2 - 1".parse().unwrap();
+    /// let b: Polynomial<BigRational> = "x^2 + 3x + 2".parse().unwrap();
+    /// let (g, s, t) = a.clone().extended_gcd(b.clone());
+    /// assert_eq!(g, "x + 1".parse().unwrap());
+    /// assert_eq!(g, s * a + t * b);
+    /// ```
+    pub fn extended_gcd(self, other: Self) -> (Self, Self, Self)
+    where
+        Ring: Field,
+    {
+        if self.is_zero() && other.is_zero() {
+            return (Polynomial::zero(), Polynomial::zero(), Polynomial::zero());
+        }
+        count!(gcds, 1);
+        let (g, s, t) = EuclideanDomain::extended_euclidean(&self, &other);
+        let lc = g.leading_coefficient_cloned().checked_inv().unwrap();
+        (g.scalar_mul(lc.clone()), s.scalar_mul(lc.clone()), t.scalar_mul(lc))
+    }
+
+    /// Returns `self^exp mod modulus`, a power in `F[x] / (modulus)`, by
+    /// repeated squaring with a reduction after every product, so that no
+    /// intermediate result has degree `2 deg(modulus)` or more.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `exp` is negative or `modulus` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// // x^(5^3) = x mod f for every f of degree 3 that is irreducible over GF(5)
+    /// let f = Polynomial::new([3, 3, 0, 1].map(Zp::<5>::new).to_vec());
+    /// let x = Polynomial::new(vec![Zp::new(0), Zp::new(1)]);
+    /// assert_eq!(x, x.pow_mod(&BigInt::from(125), &f));
+    /// assert_ne!(x, x.pow_mod(&BigInt::from(5), &f));
+    /// ```
+    pub fn pow_mod(&self, exp: &num::BigInt, modulus: &Self) -> Self
+    where
+        Ring: Field,
+    {
+        assert!(exp.sign() != num::bigint::Sign::Minus, "negative exponent");
+        assert!(!modulus.is_zero(), "zero modulus");
+        let base = self.clone().div_rem(modulus.clone()).1;
+        let mut res = Polynomial::one().div_rem(modulus.clone()).1;
+        for i in (0..exp.bits()).rev() {
+            res = (res.clone() * res).div_rem(modulus.clone()).1;
+            if exp.bit(i) {
+                res = (res * base.clone()).div_rem(modulus.clone()).1;
+            }
         }
-        let lc = self
-            .leading_coefficient_cloned()
-            .clone()
-            .checked_inv()
-            .unwrap();
-        self.scalar_mul(lc)
+        res
     }
 
     /// Returns the "content" of this polynomial, which is the non-negative
@@ -189,6 +672,7 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
             Ok([x]) => x.unit_and_normal().1,
             Err(mut v) => {
                 let mut x = v.pop().unwrap();
+                count!(gcds, v.len());
                 for y in v {
                     x = x.gcd(&y);
                 }
@@ -198,13 +682,22 @@ impl<Ring: CommutativeRing> Polynomial<Ring> {
     }
 }
 
-impl<Ring: CommutativeRing> FromIterator<Ring> for Polynomial<Ring> {
+impl<Ring: Semiring> FromIterator<Ring> for Polynomial<Ring> {
     fn from_iter<T: IntoIterator<Item = Ring>>(iter: T) -> Self {
         Self::new(iter.into_iter().collect())
     }
 }
 
-impl<Ring: CommutativeRing> Zero for Polynomial<Ring> {
+impl<Ring: Semiring> IntoIterator for Polynomial<Ring> {
+    type Item = Ring;
+    type IntoIter = std::vec::IntoIter<Ring>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.coeffs.into_iter()
+    }
+}
+
+impl<Ring: Semiring> Zero for Polynomial<Ring> {
     fn is_zero(&self) -> bool {
         self.coeffs.is_empty()
     }
@@ -224,7 +717,7 @@ impl<Ring: CommutativeRing> One for Polynomial<Ring> {
     }
 }
 
-impl<Ring: CommutativeRing> Add for Polynomial<Ring> {
+impl<Ring: Semiring> Add for Polynomial<Ring> {
     type Output = Polynomial<Ring>;
 
     fn add(self, other: Self) -> Self {
@@ -245,14 +738,23 @@ impl<Ring: CommutativeRing> Add for Polynomial<Ring> {
     }
 }
 
+/// Adds a constant.
+impl<Ring: Semiring> Add<Ring> for Polynomial<Ring> {
+    type Output = Polynomial<Ring>;
+    fn add(self, rhs: Ring) -> Self::Output {
+        self + Polynomial::new(vec![rhs])
+    }
+}
+
 impl<Ring: CommutativeRing> Sub for Polynomial<Ring> {
     type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn sub(self, rhs: Self) -> Self::Output {
         self + rhs.neg()
     }
 }
 
-impl<Ring: CommutativeRing> AddAssign for Polynomial<Ring> {
+impl<Ring: Semiring> AddAssign for Polynomial<Ring> {
     fn add_assign(&mut self, rhs: Self) {
         let lhs = self.take();
         *self = lhs + rhs
@@ -261,18 +763,14 @@ impl<Ring: CommutativeRing> AddAssign for Polynomial<Ring> {
 
 impl<Ring: CommutativeRing> Mul for Polynomial<Ring> {
     type Output = Polynomial<Ring>;
+    /// Uses [`CommutativeRing::mul_polynomials`] if the ring has a faster
+    /// method for these sizes, and otherwise [`Polynomial::karatsuba_mul`]
+    /// with [`KARATSUBA_THRESHOLD`].
     fn mul(self, rhs: Self) -> Self::Output {
-        let mut coeffs = Vec::new();
-        for (i, a) in self.coeffs.into_iter().enumerate() {
-            let mut new_coeffs = repeat_with(Ring::zero).take(i).collect::<Vec<_>>();
-            for b in rhs.coeffs.iter().cloned() {
-                new_coeffs.push(a.clone() * b);
-            }
-            coeffs.push(Polynomial::new(new_coeffs));
+        match Ring::mul_polynomials(&self.coeffs, &rhs.coeffs) {
+            Some(coeffs) => Polynomial::new_trim_zeroes(coeffs),
+            None => self.karatsuba_mul(&rhs, KARATSUBA_THRESHOLD),
         }
-        coeffs
-            .into_iter()
-            .fold(Polynomial::new(vec![]), |a, b| a + b)
     }
 }
 
@@ -285,6 +783,7 @@ impl<Ring: CommutativeRing + PartialEq> Mul<Ring> for Polynomial<Ring> {
         } else if rhs.is_one() {
             self
         } else {
+            count!(multiplications, self.coeffs.len());
             self.coeffs = self.coeffs.into_iter().map(|a| a * rhs.clone()).collect();
             self
         }
@@ -297,7 +796,7 @@ impl<Ring: CommutativeRing> Product for Polynomial<Ring> {
     }
 }
 
-impl<Ring: CommutativeRing> Sum for Polynomial<Ring> {
+impl<Ring: Semiring> Sum for Polynomial<Ring> {
     fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
         iter.fold(Self::zero(), |a, b| a + b)
     }
@@ -0,0 +1,1151 @@
+//! Exact linear algebra.
+//!
+//! The integer and rational algorithms take matrices as a slice of rows.
+//! [`Matrix`] is a matrix over any commutative ring, with the arithmetic and
+//! the elimination the generic algorithms need.
+
+use std::num::NonZeroUsize;
+use std::ops::{Add, Index, IndexMut, Mul, Neg, Sub};
+
+use num::{BigInt, BigRational, Integer, One, Signed, ToPrimitive, Zero};
+
+use crate::factorization::to_rational;
+use crate::traits::{CoefficientDomain, CommutativeRing, Field, Semiring};
+use crate::Polynomial;
+
+/// Primes below `2^31`, largest first, so that products of residues fit in a
+/// `u64`.
+pub(crate) fn word_primes() -> impl Iterator<Item = u64> {
+    let is_prime = |n: u64| (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    (2..1u64 << 31).rev().filter(move |&n| is_prime(n))
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, p: u64) -> u64 {
+    let mut res = 1;
+    base %= p;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = res * base % p;
+        }
+        base = base * base % p;
+        exp >>= 1;
+    }
+    res
+}
+
+fn inv_mod(x: u64, p: u64) -> u64 {
+    pow_mod(x, p - 2, p)
+}
+
+fn reduce(x: &BigInt, p: u64) -> u64 {
+    x.mod_floor(&BigInt::from(p)).to_u64().unwrap()
+}
+
+/// The determinant modulo `p` by Gaussian elimination.
+fn determinant_mod(mut rows: Vec<Vec<u64>>, p: u64) -> u64 {
+    let n = rows.len();
+    let mut det = 1;
+    for col in 0..n {
+        let Some(pivot) = (col..n).find(|&r| rows[r][col] != 0) else {
+            return 0;
+        };
+        if pivot != col {
+            rows.swap(pivot, col);
+            det = (p - det) % p;
+        }
+        let (top, bottom) = rows.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        let inv = inv_mod(pivot_row[col], p);
+        for row in bottom {
+            let factor = row[col] * inv % p;
+            for (x, y) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x = (*x + p - factor * y % p) % p;
+            }
+        }
+        det = det * pivot_row[col] % p;
+    }
+    det
+}
+
+/// Solves `a x = b` modulo `p` by Gauss-Jordan elimination. `None` if `a` is
+/// singular modulo `p`.
+fn solve_mod(mut a: Vec<Vec<u64>>, mut b: Vec<u64>, p: u64) -> Option<Vec<u64>> {
+    let n = a.len();
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| a[r][col] != 0)?;
+        a.swap(pivot, col);
+        b.swap(pivot, col);
+        let inv = inv_mod(a[col][col], p);
+        for x in &mut a[col][col..] {
+            *x = *x * inv % p;
+        }
+        b[col] = b[col] * inv % p;
+        let pivot_row = a[col].clone();
+        for r in 0..n {
+            if r == col || a[r][col] == 0 {
+                continue;
+            }
+            let factor = a[r][col];
+            for (x, y) in a[r][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x = (*x + p - factor * y % p) % p;
+            }
+            b[r] = (b[r] + p - factor * b[col] % p) % p;
+        }
+    }
+    Some(b)
+}
+
+/// The square of Hadamard's bound `prod(|row|)` on the absolute value of the
+/// determinant.
+fn hadamard_bound_squared<'a>(rows: impl Iterator<Item = &'a [BigInt]>) -> BigInt {
+    rows.map(|row| row.iter().map(|x| x * x).sum::<BigInt>())
+        .product()
+}
+
+/// Combines residues modulo the word primes until their product exceeds
+/// twice the bound whose square is `bound_squared`, then returns the values
+/// in the symmetric range. `residues(p)` returns `None` to skip a prime.
+fn chinese_remainder(
+    len: usize,
+    bound_squared: &BigInt,
+    mut residues: impl FnMut(u64) -> Option<Vec<u64>>,
+) -> Vec<BigInt> {
+    let mut modulus = BigInt::one();
+    let mut values = vec![BigInt::zero(); len];
+    for p in word_primes() {
+        if &modulus * &modulus > bound_squared * 4 {
+            break;
+        }
+        let Some(rs) = residues(p) else {
+            continue;
+        };
+        // x = v + m * ((r - v) * m^-1 mod p)
+        let m_inv = inv_mod(reduce(&modulus, p), p);
+        for (v, r) in values.iter_mut().zip(rs) {
+            let t = (r + p - reduce(v, p)) % p * m_inv % p;
+            *v += &modulus * t;
+        }
+        modulus *= p;
+    }
+    let half = &modulus / 2;
+    for v in &mut values {
+        if *v > half {
+            *v -= &modulus;
+        }
+    }
+    values
+}
+
+fn reduce_matrix(rows: &[Vec<BigInt>], p: u64) -> Vec<Vec<u64>> {
+    rows.iter()
+        .map(|row| row.iter().map(|x| reduce(x, p)).collect())
+        .collect()
+}
+
+/// Computes the determinant of a square integer matrix modulo many word-size
+/// primes and reconstructs it with the Chinese remainder theorem.
+///
+/// Enough primes are used for their product to exceed twice Hadamard's
+/// bound, so the result is exact. Each prime costs one elimination with
+/// machine integers, which is much faster than fraction-free elimination on
+/// big integers for large dense matrices.
+///
+/// # Panics
+///
+/// Panics if the matrix is not square.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::determinant_modular;
+/// # use num::BigInt;
+/// let rows = [[2, -3, 1], [2, 0, -1], [1, 4, 5]].map(|row| row.map(BigInt::from).to_vec());
+/// assert_eq!(BigInt::from(49), determinant_modular(&rows));
+/// ```
+pub fn determinant_modular(rows: &[Vec<BigInt>]) -> BigInt {
+    assert!(rows.iter().all(|row| row.len() == rows.len()), "matrix must be square");
+    let bound = hadamard_bound_squared(rows.iter().map(Vec::as_slice));
+    chinese_remainder(1, &bound, |p| Some(vec![determinant_mod(reduce_matrix(rows, p), p)]))
+        .pop()
+        .unwrap()
+}
+
+/// Solves `a x = b` for a square integer matrix `a`, returning `(y, d)` with
+/// `x = y / d` and `d = |det(a)|`. `None` if `a` is singular.
+///
+/// By Cramer's rule `d x_i` is the determinant of `a` with column `i`
+/// replaced by `b`, an integer bounded by Hadamard's bound for `(a | b)`. It
+/// is reconstructed from `d x` modulo many word-size primes, skipping the
+/// finitely many primes dividing `d`.
+///
+/// # Panics
+///
+/// Panics if `a` is not square or `b` has the wrong length.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::solve_modular;
+/// # use num::BigInt;
+/// // 2x + y = 1, x + 3y = 2
+/// let a = [[2, 1], [1, 3]].map(|row| row.map(BigInt::from).to_vec());
+/// let b = [1, 2].map(BigInt::from);
+/// let (y, d) = solve_modular(&a, &b).unwrap();
+/// assert_eq!(BigInt::from(5), d);
+/// assert_eq!(vec![BigInt::from(1), BigInt::from(3)], y);
+/// ```
+pub fn solve_modular(a: &[Vec<BigInt>], b: &[BigInt]) -> Option<(Vec<BigInt>, BigInt)> {
+    assert_eq!(a.len(), b.len(), "need one right hand side per row");
+    let det = determinant_modular(a);
+    if det.is_zero() {
+        return None;
+    }
+    let augmented: Vec<Vec<BigInt>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, x)| row.iter().chain([x]).cloned().collect())
+        .collect();
+    let bound = hadamard_bound_squared(augmented.iter().map(Vec::as_slice));
+    let y = chinese_remainder(a.len(), &bound, |p| {
+        let d = reduce(&det, p);
+        if d == 0 {
+            return None;
+        }
+        let b = b.iter().map(|x| reduce(x, p)).collect();
+        let x = solve_mod(reduce_matrix(a, p), b, p)?;
+        Some(x.into_iter().map(|x| x * d % p).collect())
+    });
+    let (y, det) = if det.is_negative() {
+        (y.into_iter().map(|y| -y).collect(), -det)
+    } else {
+        (y, det)
+    };
+    Some((y, det))
+}
+
+/// The inverse of `a` modulo `p` by Gauss-Jordan elimination. `None` if `a`
+/// is singular modulo `p`.
+fn inverse_mod(mut a: Vec<Vec<u64>>, p: u64) -> Option<Vec<Vec<u64>>> {
+    let n = a.len();
+    for (i, row) in a.iter_mut().enumerate() {
+        row.extend((0..n).map(|j| u64::from(i == j)));
+    }
+    for col in 0..n {
+        let pivot = (col..n).find(|&r| a[r][col] != 0)?;
+        a.swap(pivot, col);
+        let inv = inv_mod(a[col][col], p);
+        for x in &mut a[col][col..] {
+            *x = *x * inv % p;
+        }
+        let pivot_row = a[col].clone();
+        for (r, row) in a.iter_mut().enumerate() {
+            if r == col || row[col] == 0 {
+                continue;
+            }
+            let factor = row[col];
+            for (x, y) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x = (*x + p - factor * y % p) % p;
+            }
+        }
+    }
+    Some(a.into_iter().map(|row| row[n..].to_vec()).collect())
+}
+
+/// Finds the fraction `r / s` with `|r| <= numer_bound` and
+/// `0 < s <= denom_bound` that is congruent to `a` modulo `m`, if there is
+/// one. It is unique when `m > 2 * numer_bound * denom_bound`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::rational_reconstruction;
+/// # use num::{BigInt, BigRational};
+/// // 3 * 5 = 15 = 1 (mod 7)
+/// let n = BigInt::from;
+/// let x = rational_reconstruction(&n(5), &n(7), &n(1), &n(3));
+/// assert_eq!(Some(BigRational::new(1.into(), 3.into())), x);
+/// ```
+pub fn rational_reconstruction(
+    a: &BigInt,
+    m: &BigInt,
+    numer_bound: &BigInt,
+    denom_bound: &BigInt,
+) -> Option<BigRational> {
+    // the extended Euclidean algorithm keeps r_i = s_i a (mod m)
+    let (mut r0, mut r1) = (m.clone(), a.mod_floor(m));
+    let (mut s0, mut s1) = (BigInt::zero(), BigInt::one());
+    while &r1 > numer_bound {
+        let q = &r0 / &r1;
+        let r2 = &r0 - &q * &r1;
+        let s2 = &s0 - &q * &s1;
+        (r0, r1) = (r1, r2);
+        (s0, s1) = (s1, s2);
+    }
+    if s1.is_zero() || &s1.abs() > denom_bound || !Integer::gcd(&r1, &s1).is_one() {
+        return None;
+    }
+    Some(BigRational::new(r1, s1))
+}
+
+/// Solves `a x = b` for a square integer matrix `a` with Dixon's `p`-adic
+/// lifting. `None` if `a` is singular.
+///
+/// The inverse of `a` is computed once modulo a word-size prime `p`. Each
+/// step then finds the next `p`-adic digit of the solution with a
+/// matrix-vector product modulo `p` and divides the residual by `p` exactly.
+/// After enough digits, the rational solution is recovered with
+/// [`rational_reconstruction`], with the bounds from Cramer's rule.
+///
+/// # Panics
+///
+/// Panics if `a` is not square or `b` has the wrong length.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::solve_dixon;
+/// # use num::{BigInt, BigRational};
+/// // 2x + y = 1, x + 3y = 2
+/// let a = [[2, 1], [1, 3]].map(|row| row.map(BigInt::from).to_vec());
+/// let b = [1, 2].map(BigInt::from);
+/// let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+/// assert_eq!(Some(vec![q(1, 5), q(3, 5)]), solve_dixon(&a, &b));
+/// ```
+pub fn solve_dixon(a: &[Vec<BigInt>], b: &[BigInt]) -> Option<Vec<BigRational>> {
+    assert_eq!(a.len(), b.len(), "need one right hand side per row");
+    let n = a.len();
+    let det = determinant_modular(a).abs();
+    if det.is_zero() {
+        return None;
+    }
+    let (p, inverse) =
+        word_primes().find_map(|p| Some((p, inverse_mod(reduce_matrix(a, p), p)?)))?;
+
+    // by Cramer's rule, the solution has denominator det and numerators
+    // bounded by Hadamard's bound for (a | b).
+    let augmented: Vec<Vec<BigInt>> = a
+        .iter()
+        .zip(b)
+        .map(|(row, x)| row.iter().chain([x]).cloned().collect())
+        .collect();
+    let numer_bound = hadamard_bound_squared(augmented.iter().map(Vec::as_slice)).sqrt() + 1;
+    let target = &numer_bound * &det * 2;
+
+    let mut residual = b.to_vec();
+    let mut x = vec![BigInt::zero(); n];
+    let mut modulus = BigInt::one();
+    while modulus <= target {
+        let r: Vec<u64> = residual.iter().map(|r| reduce(r, p)).collect();
+        let digit: Vec<u64> = inverse
+            .iter()
+            .map(|row| row.iter().zip(&r).fold(0, |acc, (c, r)| (acc + c * r) % p))
+            .collect();
+        for (residual, row) in residual.iter_mut().zip(a) {
+            let ax: BigInt = row.iter().zip(&digit).map(|(a, d)| a * d).sum();
+            *residual = (&*residual - ax) / p;
+        }
+        for (x, d) in x.iter_mut().zip(&digit) {
+            *x += &modulus * d;
+        }
+        modulus *= p;
+    }
+    x.iter()
+        .map(|x| rational_reconstruction(x, &modulus, &numer_bound, &det))
+        .collect()
+}
+
+/// Solves `a x = b` over the rationals, by clearing denominators row by row
+/// and using [`solve_dixon`]. `None` if `a` is singular.
+///
+/// # Panics
+///
+/// Panics if `a` is not square or `b` has the wrong length.
+pub fn solve_rational(a: &[Vec<BigRational>], b: &[BigRational]) -> Option<Vec<BigRational>> {
+    assert_eq!(a.len(), b.len(), "need one right hand side per row");
+    let (a, b): (Vec<Vec<BigInt>>, Vec<BigInt>) = a
+        .iter()
+        .zip(b)
+        .map(|(row, rhs)| {
+            let lcm = row
+                .iter()
+                .chain([rhs])
+                .fold(BigInt::one(), |acc, x| acc.lcm(x.denom()));
+            let scale = |x: &BigRational| (x * &lcm).to_integer();
+            (row.iter().map(scale).collect(), scale(rhs))
+        })
+        .unzip();
+    solve_dixon(&a, &b)
+}
+
+/// `det(A_0 + x A_1 + ... + x^k A_k)` for square rational matrices `A_i` of
+/// the same size.
+///
+/// Each row is scaled to integers and the determinant is computed without
+/// fractions over `Z[x]`.
+///
+/// # Panics
+///
+/// Panics if there are no matrices, or they are not square or not all the
+/// same size.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::determinant_polynomial;
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let matrix = |rows: [[i32; 2]; 2]| rows.map(|row| row.map(|x| BigRational::from_integer(x.into())).to_vec()).to_vec();
+/// // det [[1 + x, 2], [3, 4 - x]] = -x^2 + 3x - 2
+/// let p = determinant_polynomial(&[matrix([[1, 2], [3, 4]]), matrix([[1, 0], [0, -1]])]);
+/// assert_eq!(p, "-x^2 + 3x - 2".parse().unwrap());
+/// ```
+pub fn determinant_polynomial(matrices: &[Vec<Vec<BigRational>>]) -> Polynomial<BigRational> {
+    let n = matrices.first().expect("need at least one matrix").len();
+    assert!(
+        matrices.iter().all(|a| a.len() == n && a.iter().all(|row| row.len() == n)),
+        "matrices must be square and of the same size"
+    );
+    let mut scale = BigInt::one();
+    let rows: Vec<Vec<Polynomial<BigInt>>> = (0..n)
+        .map(|i| {
+            let lcm = matrices
+                .iter()
+                .flat_map(|a| &a[i])
+                .fold(BigInt::one(), |acc, x| acc.lcm(x.denom()));
+            scale *= &lcm;
+            (0..n)
+                .map(|j| {
+                    let coeffs = matrices.iter().map(|a| (&a[i][j] * &lcm).to_integer()).collect();
+                    Polynomial::new_trim_zeroes(coeffs)
+                })
+                .collect()
+        })
+        .collect();
+    to_rational(&Matrix::from_rows(rows).determinant()).scalar_mul(BigRational::new(BigInt::one(), scale))
+}
+
+/// The finite eigenvalues of a polynomial eigenvalue problem, as found by
+/// [`polyeig`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PolynomialEigenvalues {
+    /// `det(A_0 + λ A_1 + ... + λ^k A_k)`, whose roots are the eigenvalues.
+    pub determinant: Polynomial<BigRational>,
+    /// The rational eigenvalues, with their algebraic multiplicities.
+    pub rational: Vec<(BigRational, NonZeroUsize)>,
+    /// The irrational eigenvalues, as the irreducible integer polynomials of
+    /// degree at least two they are the roots of, with multiplicities.
+    pub irrational: Vec<(Polynomial<BigInt>, NonZeroUsize)>,
+}
+
+/// Solves `det(A_0 + λ A_1 + ... + λ^k A_k) = 0` exactly, by factoring the
+/// determinant polynomial over the integers. `None` if the determinant
+/// vanishes identically, in which case every `λ` is an eigenvalue.
+///
+/// Eigenvalues at infinity, which exist when `A_k` is singular, show up only
+/// as the determinant having degree less than `kn`, and are not reported.
+///
+/// # Panics
+///
+/// Panics if there are no matrices, or they are not square or not all the
+/// same size.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::polyeig;
+/// # use num::{BigInt, BigRational};
+/// let matrix = |rows: [[i32; 2]; 2]| rows.map(|row| row.map(|x| BigRational::from_integer(x.into())).to_vec()).to_vec();
+/// // the eigenvalues of [[0, 2], [1, 0]] are the roots of λ^2 - 2
+/// let eig = polyeig(&[matrix([[0, 2], [1, 0]]), matrix([[-1, 0], [0, -1]])]).unwrap();
+/// assert!(eig.rational.is_empty());
+/// assert_eq!("x^2 - 2".parse::<math2::Polynomial<BigRational>>().unwrap(), eig.determinant);
+/// assert_eq!(1, eig.irrational.len());
+/// ```
+pub fn polyeig(matrices: &[Vec<Vec<BigRational>>]) -> Option<PolynomialEigenvalues> {
+    let determinant = determinant_polynomial(matrices);
+    if determinant.is_zero() {
+        return None;
+    }
+    let lcm = determinant
+        .coeffs
+        .iter()
+        .fold(BigInt::one(), |acc, x| acc.lcm(x.denom()));
+    let integer = Polynomial::new(
+        determinant
+            .coeffs
+            .iter()
+            .map(|x| (x * &lcm).to_integer())
+            .collect(),
+    );
+    let mut rational = Vec::new();
+    let mut irrational = Vec::new();
+    for (f, multiplicity) in integer.factor().factors {
+        match f.degree() {
+            Some(1) => rational.push((
+                BigRational::new(-f.coeffs[0].clone(), f.coeffs[1].clone()),
+                multiplicity,
+            )),
+            _ => irrational.push((f, multiplicity)),
+        }
+    }
+    rational.sort();
+    Some(PolynomialEigenvalues {
+        determinant,
+        rational,
+        irrational,
+    })
+}
+
+/// A matrix over a commutative semiring, stored by rows.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::Matrix;
+/// let a = Matrix::<i64>::from_rows(vec![vec![1, 2], vec![3, 4]]);
+/// let b = Matrix::from_rows(vec![vec![0, 1], vec![1, 0]]);
+/// assert_eq!(Matrix::from_rows(vec![vec![2, 1], vec![4, 3]]), a.clone() * b);
+/// assert_eq!(-2, a.determinant());
+/// assert_eq!(Matrix::from_rows(vec![vec![1, 3], vec![2, 4]]), a.transpose());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Matrix<R> {
+    rows: usize,
+    cols: usize,
+    entries: Vec<R>,
+}
+
+impl<R: Semiring> Matrix<R> {
+    /// # Panics
+    ///
+    /// Panics if the rows have different lengths.
+    pub fn from_rows(rows: Vec<Vec<R>>) -> Self {
+        let cols = rows.first().map_or(0, Vec::len);
+        assert!(rows.iter().all(|row| row.len() == cols), "rows must have the same length");
+        Self {
+            rows: rows.len(),
+            cols,
+            entries: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// The matrix with entries `f(i, j)`.
+    pub fn from_fn(rows: usize, cols: usize, mut f: impl FnMut(usize, usize) -> R) -> Self {
+        Self {
+            rows,
+            cols,
+            entries: (0..rows * cols).map(|k| f(k / cols, k % cols)).collect(),
+        }
+    }
+
+    pub fn zero(rows: usize, cols: usize) -> Self {
+        Self::from_fn(rows, cols, |_, _| R::zero())
+    }
+
+    pub fn identity(n: usize) -> Self {
+        Self::from_fn(n, n, |i, j| if i == j { R::one() } else { R::zero() })
+    }
+
+    pub fn num_rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn num_cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn row(&self, i: usize) -> &[R] {
+        &self.entries[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[R]> {
+        // `chunks` rejects a chunk size of zero
+        (0..self.rows).map(|i| self.row(i))
+    }
+
+    pub fn into_rows(self) -> Vec<Vec<R>> {
+        let mut entries = self.entries.into_iter();
+        (0..self.rows).map(|_| entries.by_ref().take(self.cols).collect()).collect()
+    }
+
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.cols, self.rows, |i, j| self[(j, i)].clone())
+    }
+
+    pub fn scalar_mul(mut self, c: R) -> Self {
+        for x in &mut self.entries {
+            *x = x.clone() * c.clone();
+        }
+        self
+    }
+
+    /// The product with the column vector `v`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` does not have one entry per column.
+    pub fn mul_vec(&self, v: &[R]) -> Vec<R> {
+        assert_eq!(self.cols, v.len(), "need one entry per column");
+        self.rows()
+            .map(|row| row.iter().zip(v).fold(R::zero(), |acc, (a, b)| acc + a.clone() * b.clone()))
+            .collect()
+    }
+
+    /// The `n`th power of a square matrix by repeated squaring, which is the
+    /// identity for `n = 0`.
+    ///
+    /// Over the tropical semiring the entries of `A^n` are the lengths of the
+    /// shortest walks with `n` edges in the graph with edge lengths `A`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::linalg::Matrix;
+    /// # use math2::tropical::Tropical;
+    /// # use num::{BigRational, One};
+    /// let t = |x: i32| Tropical::new(BigRational::from_integer(x.into()));
+    /// let inf = Tropical::infinity;
+    /// // the path 0 -> 1 -> 2 with a shortcut 0 -> 2 and loops of length 0
+    /// let lengths = Matrix::from_rows(vec![
+    ///     vec![Tropical::one(), t(1), t(5)],
+    ///     vec![inf(), Tropical::one(), t(2)],
+    ///     vec![inf(), inf(), Tropical::one()],
+    /// ]);
+    /// assert_eq!(t(3), lengths.pow(2)[(0, 2)]);
+    /// let fibonacci = Matrix::<i64>::from_rows(vec![vec![1, 1], vec![1, 0]]);
+    /// assert_eq!(55, fibonacci.pow(10)[(0, 1)]);
+    /// ```
+    pub fn pow(&self, mut n: u64) -> Self {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        let (mut base, mut res) = (self.clone(), Self::identity(self.rows));
+        while n > 0 {
+            if n & 1 == 1 {
+                res = res * base.clone();
+            }
+            n >>= 1;
+            if n > 0 {
+                base = base.clone() * base;
+            }
+        }
+        res
+    }
+
+    fn assert_same_shape(&self, other: &Self) {
+        assert_eq!((self.rows, self.cols), (other.rows, other.cols), "matrices must have the same shape");
+    }
+}
+
+impl<R> Index<(usize, usize)> for Matrix<R> {
+    type Output = R;
+    fn index(&self, (i, j): (usize, usize)) -> &R {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &self.entries[i * self.cols + j]
+    }
+}
+
+impl<R> IndexMut<(usize, usize)> for Matrix<R> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut R {
+        assert!(i < self.rows && j < self.cols, "index out of bounds");
+        &mut self.entries[i * self.cols + j]
+    }
+}
+
+impl<R: Semiring> Add for Matrix<R> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        self.assert_same_shape(&rhs);
+        for (x, y) in self.entries.iter_mut().zip(rhs.entries) {
+            *x = x.clone() + y;
+        }
+        self
+    }
+}
+
+impl<R: CommutativeRing> Neg for Matrix<R> {
+    type Output = Self;
+    fn neg(mut self) -> Self {
+        for x in &mut self.entries {
+            *x = -x.clone();
+        }
+        self
+    }
+}
+
+impl<R: CommutativeRing> Sub for Matrix<R> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.neg()
+    }
+}
+
+/// # Panics
+///
+/// Panics if the number of columns of the left side is not the number of
+/// rows of the right side.
+impl<R: Semiring> Mul for Matrix<R> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        assert_eq!(self.cols, rhs.rows, "inner dimensions must agree");
+        count!(multiplications, self.rows * self.cols * rhs.cols);
+        Self::from_fn(self.rows, rhs.cols, |i, j| {
+            (0..self.cols).fold(R::zero(), |acc, k| acc + self[(i, k)].clone() * rhs[(k, j)].clone())
+        })
+    }
+}
+
+impl<R: CommutativeRing> Matrix<R> {
+    /// The characteristic polynomial `det(x I - self)`, by Berkowitz'
+    /// algorithm, which uses no divisions and so works over any commutative
+    /// ring, in `O(n^4)` operations.
+    ///
+    /// Writing the leading `(t + 1) x (t + 1)` submatrix as
+    /// `[[A_t, c], [r, a]]`, its characteristic polynomial is a Toeplitz
+    /// matrix with first column `1, -a, -r c, -r A_t c, -r A_t^2 c, ...`
+    /// times that of `A_t`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::linalg::Matrix;
+    /// # use math2::Polynomial;
+    /// let a = Matrix::<i64>::from_rows(vec![vec![2, 1], vec![1, 2]]);
+    /// // x^2 - 4x + 3, with the trace and the determinant as coefficients
+    /// assert_eq!(Polynomial::new(vec![3, -4, 1]), a.char_poly());
+    /// ```
+    pub fn char_poly(&self) -> Polynomial<R> {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        // coefficients from the highest degree down
+        let mut p = vec![R::one()];
+        for t in 0..self.rows {
+            let mut column = vec![R::one(), -self[(t, t)].clone()];
+            let mut v: Vec<R> = (0..t).map(|i| self[(i, t)].clone()).collect();
+            for _ in 0..t {
+                let rv = (0..t).fold(R::zero(), |acc, j| acc + self[(t, j)].clone() * v[j].clone());
+                column.push(-rv);
+                v = (0..t)
+                    .map(|i| (0..t).fold(R::zero(), |acc, j| acc + self[(i, j)].clone() * v[j].clone()))
+                    .collect();
+            }
+            p = (0..t + 2)
+                .map(|i| (0..=i.min(t)).fold(R::zero(), |acc, j| acc + column[i - j].clone() * p[j].clone()))
+                .collect();
+        }
+        p.reverse();
+        Polynomial::new_trim_zeroes(p)
+    }
+
+    /// The determinant as `(-1)^n` times the constant coefficient of the
+    /// [characteristic polynomial](Self::char_poly), for rings without
+    /// exact division such as multivariate polynomials.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn determinant_division_free(&self) -> R {
+        let c = self.char_poly().coeffs.swap_remove(0);
+        if self.rows.is_multiple_of(2) {
+            c
+        } else {
+            -c
+        }
+    }
+
+    /// The permanent, the sum over permutations `σ` of the products
+    /// `a_{0 σ(0)} ... a_{n-1 σ(n-1)}` without signs, by Ryser's formula
+    /// `(-1)^n sum((-1)^|S| prod_i sum(a_ij for j in S))` over the sets of
+    /// columns `S`. The sets are visited in Gray code order, so the row sums
+    /// change by one column at a time and this takes `O(2^n n)` operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square or has 64 rows or more.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::linalg::Matrix;
+    /// # use num::BigInt;
+    /// // the permanent of J - I counts the derangements, 44 for n = 5
+    /// let a = Matrix::from_fn(5, 5, |i, j| BigInt::from(i32::from(i != j)));
+    /// assert_eq!(BigInt::from(44), a.permanent());
+    /// ```
+    pub fn permanent(&self) -> R {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        assert!(self.rows < 64, "too many rows for Ryser's formula");
+        let n = self.rows;
+        let mut sums = vec![R::zero(); n];
+        let mut total = R::zero();
+        for k in 1..1u64 << n {
+            let j = k.trailing_zeros() as usize;
+            let gray = k ^ (k >> 1);
+            let added = gray >> j & 1 == 1;
+            for (i, sum) in sums.iter_mut().enumerate() {
+                let a = self[(i, j)].clone();
+                *sum = if added { sum.clone() + a } else { CommutativeRing::sub(sum.clone(), a) };
+            }
+            let product = sums.iter().fold(R::one(), |acc, s| acc * s.clone());
+            total = if (n as u32 - gray.count_ones()).is_multiple_of(2) {
+                total + product
+            } else {
+                CommutativeRing::sub(total, product)
+            };
+        }
+        if n == 0 {
+            R::one()
+        } else {
+            total
+        }
+    }
+}
+
+impl<R: CoefficientDomain> Matrix<R> {
+    /// Fraction-free elimination to row echelon form, by Bareiss' method
+    /// with rows swapped for zero pivots and columns without pivots skipped.
+    /// Every entry stays a minor of the original matrix, so the divisions
+    /// are exact. Returns the pivots and whether the rows were swapped an
+    /// odd number of times.
+    fn echelon_fraction_free(&self) -> (Vec<R>, bool) {
+        let mut a = self.clone();
+        let mut prev = R::one();
+        let mut pivots = Vec::new();
+        let mut negate = false;
+        for col in 0..self.cols {
+            let r = pivots.len();
+            let Some(pivot) = (r..self.rows).find(|&i| !a[(i, col)].is_zero()) else {
+                continue;
+            };
+            if pivot != r {
+                for j in col..self.cols {
+                    a.entries.swap(pivot * self.cols + j, r * self.cols + j);
+                }
+                negate = !negate;
+            }
+            for i in r + 1..self.rows {
+                for j in col + 1..self.cols {
+                    let x = CommutativeRing::sub(
+                        a[(i, j)].clone() * a[(r, col)].clone(),
+                        a[(i, col)].clone() * a[(r, j)].clone(),
+                    );
+                    a[(i, j)] = x.divide_exact(&prev);
+                }
+                a[(i, col)] = R::zero();
+            }
+            prev = a[(r, col)].clone();
+            pivots.push(prev.clone());
+        }
+        (pivots, negate)
+    }
+
+    /// The determinant by Bareiss' fraction-free elimination, which takes
+    /// `O(n^3)` operations and only exact divisions, so the entries stay
+    /// small over the integers and in `Z[x]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn determinant(&self) -> R {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        let (pivots, negate) = self.echelon_fraction_free();
+        if pivots.len() < self.rows {
+            return R::zero();
+        }
+        let det = pivots.last().cloned().unwrap_or_else(R::one);
+        if negate {
+            -det
+        } else {
+            det
+        }
+    }
+
+    /// The rank over the field of fractions, by fraction-free elimination.
+    pub fn rank(&self) -> usize {
+        self.echelon_fraction_free().0.len()
+    }
+
+    /// The Pfaffian of a skew-symmetric matrix, whose square is the
+    /// determinant. Only the entries above the diagonal are used.
+    ///
+    /// This eliminates two rows and columns at a time. After `t` steps the
+    /// entry at `(k, l)` is the Pfaffian of the principal submatrix on the
+    /// first `2t` indices and `k, l`, and the Pfaffian analogue of Sylvester's
+    /// identity makes the division by the previous pivot exact, as in
+    /// Bareiss' method. It takes `O(n^3)` operations.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::linalg::Matrix;
+    /// // a_01 a_23 - a_02 a_13 + a_03 a_12
+    /// let a = Matrix::<i64>::from_rows(vec![
+    ///     vec![0, 1, 2, 3],
+    ///     vec![-1, 0, 4, 5],
+    ///     vec![-2, -4, 0, 6],
+    ///     vec![-3, -5, -6, 0],
+    /// ]);
+    /// assert_eq!(6 - 2 * 5 + 3 * 4, a.pfaffian());
+    /// assert_eq!(a.determinant(), a.pfaffian() * a.pfaffian());
+    /// ```
+    pub fn pfaffian(&self) -> R {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        if !self.rows.is_multiple_of(2) {
+            return R::zero();
+        }
+        let upper = |i: usize, j: usize| match i.cmp(&j) {
+            std::cmp::Ordering::Less => self[(i, j)].clone(),
+            std::cmp::Ordering::Equal => R::zero(),
+            std::cmp::Ordering::Greater => -self[(j, i)].clone(),
+        };
+        let mut s: Vec<Vec<R>> = (0..self.rows).map(|i| (0..self.rows).map(|j| upper(i, j)).collect()).collect();
+        let mut prev = R::one();
+        let mut negate = false;
+        let pfaffian = loop {
+            let m = s.len();
+            if m == 0 {
+                break R::one();
+            }
+            let Some(l) = (1..m).find(|&l| !s[0][l].is_zero()) else {
+                return R::zero();
+            };
+            if l != 1 {
+                // swapping two indices negates the Pfaffian
+                s.swap(1, l);
+                for row in &mut s {
+                    row.swap(1, l);
+                }
+                negate = !negate;
+            }
+            let pivot = s[0][1].clone();
+            if m == 2 {
+                break pivot;
+            }
+            let mut next = vec![vec![R::zero(); m - 2]; m - 2];
+            for k in 2..m {
+                for l in k + 1..m {
+                    let x = CommutativeRing::sub(
+                        pivot.clone() * s[k][l].clone() + s[1][k].clone() * s[0][l].clone(),
+                        s[0][k].clone() * s[1][l].clone(),
+                    );
+                    let x = x.divide_exact(&prev);
+                    next[l - 2][k - 2] = -x.clone();
+                    next[k - 2][l - 2] = x;
+                }
+            }
+            prev = pivot;
+            s = next;
+        };
+        if negate {
+            -pfaffian
+        } else {
+            pfaffian
+        }
+    }
+}
+
+impl<F: Field> Matrix<F> {
+    /// Reduces to reduced row echelon form by Gauss–Jordan elimination,
+    /// returning the columns of the pivots and the product of the pivots
+    /// before normalization, negated for an odd number of row swaps.
+    fn gauss_jordan(&mut self) -> (Vec<usize>, F) {
+        let mut pivots = Vec::new();
+        let mut scale = F::one();
+        for col in 0..self.cols {
+            let r = pivots.len();
+            let Some(pivot) = (r..self.rows).find(|&i| !self[(i, col)].is_zero()) else {
+                continue;
+            };
+            if pivot != r {
+                for j in 0..self.cols {
+                    self.entries.swap(pivot * self.cols + j, r * self.cols + j);
+                }
+                scale = -scale;
+            }
+            let p = self[(r, col)].clone();
+            let inv = p.checked_inv().unwrap();
+            scale = scale * p;
+            for j in col..self.cols {
+                self[(r, j)] = self[(r, j)].clone() * inv.clone();
+            }
+            for i in (0..self.rows).filter(|&i| i != r) {
+                let factor = self[(i, col)].clone();
+                if factor.is_zero() {
+                    continue;
+                }
+                for j in col..self.cols {
+                    let x = CommutativeRing::sub(self[(i, j)].clone(), factor.clone() * self[(r, j)].clone());
+                    self[(i, j)] = x;
+                }
+            }
+            pivots.push(col);
+        }
+        (pivots, scale)
+    }
+
+    /// The reduced row echelon form, and the columns of its pivots.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::linalg::Matrix;
+    /// # use num::BigRational;
+    /// let m = |rows: Vec<Vec<i32>>| Matrix::from_rows(rows.into_iter().map(|row| row.into_iter().map(|x| BigRational::from_integer(x.into())).collect()).collect());
+    /// let (rref, pivots) = m(vec![vec![1, 2, 3], vec![2, 4, 7]]).row_echelon_form();
+    /// assert_eq!(m(vec![vec![1, 2, 0], vec![0, 0, 1]]), rref);
+    /// assert_eq!(vec![0, 2], pivots);
+    /// ```
+    pub fn row_echelon_form(&self) -> (Self, Vec<usize>) {
+        let mut a = self.clone();
+        let (pivots, _) = a.gauss_jordan();
+        (a, pivots)
+    }
+
+    /// The determinant by Gaussian elimination, for fields such as `f64`
+    /// without exact division of the entries.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn determinant_gaussian(&self) -> F {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        let mut a = self.clone();
+        let (pivots, scale) = a.gauss_jordan();
+        if pivots.len() < self.rows {
+            F::zero()
+        } else {
+            scale
+        }
+    }
+
+    /// The rank by Gaussian elimination.
+    pub fn rank_gaussian(&self) -> usize {
+        self.row_echelon_form().1.len()
+    }
+
+    /// The characteristic polynomial `det(x I - self)` in `O(n^3)`
+    /// operations, by reducing to upper Hessenberg form with similarity
+    /// transformations and expanding the determinant along the last column
+    /// of each leading submatrix.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the matrix is not square.
+    pub fn char_poly_hessenberg(&self) -> Polynomial<F> {
+        assert_eq!(self.rows, self.cols, "matrix must be square");
+        let n = self.rows;
+        let mut h = self.clone();
+        for m in 1..n.saturating_sub(1) {
+            let Some(pivot) = (m..n).find(|&i| !h[(i, m - 1)].is_zero()) else {
+                continue;
+            };
+            if pivot != m {
+                for j in 0..n {
+                    h.entries.swap(pivot * n + j, m * n + j);
+                }
+                for i in 0..n {
+                    h.entries.swap(i * n + pivot, i * n + m);
+                }
+            }
+            let inv = h[(m, m - 1)].checked_inv().unwrap();
+            for i in m + 1..n {
+                let u = h[(i, m - 1)].clone() * inv.clone();
+                if u.is_zero() {
+                    continue;
+                }
+                // subtract u times row m from row i, then add u times column
+                // i to column m, which keeps the matrix similar
+                for j in 0..n {
+                    h[(i, j)] = CommutativeRing::sub(h[(i, j)].clone(), u.clone() * h[(m, j)].clone());
+                }
+                for j in 0..n {
+                    h[(j, m)] = h[(j, m)].clone() + u.clone() * h[(j, i)].clone();
+                }
+            }
+        }
+        // p[k] is the characteristic polynomial of the leading k x k block
+        let x = Polynomial::from_elem_with_degree(F::one(), 1);
+        let mut p = vec![Polynomial::one()];
+        for k in 1..=n {
+            let mut next = (x.clone() - Polynomial::new_trim_zeroes(vec![h[(k - 1, k - 1)].clone()])) * p[k - 1].clone();
+            let mut subdiagonal = F::one();
+            for i in (1..k).rev() {
+                subdiagonal = subdiagonal * h[(i, i - 1)].clone();
+                let c = h[(i - 1, k - 1)].clone() * subdiagonal.clone();
+                next = next - p[i - 1].clone().scalar_mul(c);
+            }
+            p.push(next);
+        }
+        p.pop().unwrap()
+    }
+
+    /// A solution `x` of `self x = b`, or `None` if there is none, by
+    /// Gauss–Jordan elimination on `(self | b)`. The variables of columns
+    /// without a pivot are zero, and every solution is this one plus a
+    /// vector in the [`nullspace`](Self::nullspace).
+    ///
+    /// The arithmetic is exact over fields such as `BigRational` and
+    /// `Zp<P>`. For square integer systems, [`solve_dixon`] is faster.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `b` does not have one entry per row.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::linalg::Matrix;
+    /// # use num::BigRational;
+    /// let q = |a: i32| BigRational::from_integer(a.into());
+    /// // x + 2y + 3z = 6, 2x + 4y + 7z = 13
+    /// let a = Matrix::from_rows(vec![vec![q(1), q(2), q(3)], vec![q(2), q(4), q(7)]]);
+    /// assert_eq!(Some(vec![q(3), q(0), q(1)]), a.solve(&[q(6), q(13)]));
+    /// assert_eq!(vec![vec![q(-2), q(1), q(0)]], a.nullspace());
+    /// // x + 2y + 3z = 6, 2x + 4y + 6z = 13 has no solution
+    /// let b = Matrix::from_rows(vec![vec![q(1), q(2), q(3)], vec![q(2), q(4), q(6)]]);
+    /// assert_eq!(None, b.solve(&[q(6), q(13)]));
+    /// ```
+    pub fn solve(&self, b: &[F]) -> Option<Vec<F>> {
+        assert_eq!(self.rows, b.len(), "need one right hand side per row");
+        let n = self.cols;
+        let mut augmented = Self::from_fn(self.rows, n + 1, |i, j| if j < n { self[(i, j)].clone() } else { b[i].clone() });
+        let (pivots, _) = augmented.gauss_jordan();
+        // a pivot in the last column is the equation 0 = 1
+        if pivots.last() == Some(&n) {
+            return None;
+        }
+        let mut x = vec![F::zero(); n];
+        for (r, &col) in pivots.iter().enumerate() {
+            x[col] = augmented[(r, n)].clone();
+        }
+        Some(x)
+    }
+
+    /// A basis of the vectors `v` with `self v = 0`, with one vector for each
+    /// column without a pivot in the reduced row echelon form, which has a
+    /// one in that column and zeros in the other such columns.
+    pub fn nullspace(&self) -> Vec<Vec<F>> {
+        let (rref, pivots) = self.row_echelon_form();
+        (0..self.cols)
+            .filter(|c| !pivots.contains(c))
+            .map(|free| {
+                let mut v = vec![F::zero(); self.cols];
+                v[free] = F::one();
+                for (r, &pivot) in pivots.iter().enumerate() {
+                    v[pivot] = -rref[(r, free)].clone();
+                }
+                v
+            })
+            .collect()
+    }
+}
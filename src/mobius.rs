@@ -0,0 +1,129 @@
+//! Möbius transformations `x -> (ax + b) / (cx + d)` acting on polynomials.
+//!
+//! Substituting a Möbius transformation into a polynomial `p` of degree `n`
+//! gives a rational function with denominator `(cx + d)^n`, and its
+//! numerator is a polynomial again. Root isolation in the style of
+//! Vincent, Collins and Akritas works entirely with such numerators, and
+//! keeps the composed transformation around to map the roots it finds back
+//! to roots of `p`.
+
+use num::{One, Zero};
+
+use crate::traits::{CommutativeRing, Field};
+use crate::Polynomial;
+
+/// The transformation `x -> (ax + b) / (cx + d)`, which should have
+/// `ad - bc` non-zero.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Mobius<R> {
+    pub a: R,
+    pub b: R,
+    pub c: R,
+    pub d: R,
+}
+
+impl<R: CommutativeRing> Mobius<R> {
+    pub fn new(a: R, b: R, c: R, d: R) -> Self {
+        Self { a, b, c, d }
+    }
+
+    /// `x -> x`.
+    pub fn identity() -> Self {
+        Self::new(R::one(), R::zero(), R::zero(), R::one())
+    }
+
+    /// `x -> x + t`.
+    pub fn translation(t: R) -> Self {
+        Self::new(R::one(), t, R::zero(), R::one())
+    }
+
+    /// `x -> sx`.
+    pub fn scaling(s: R) -> Self {
+        Self::new(s, R::zero(), R::zero(), R::one())
+    }
+
+    /// `x -> 1 / x`.
+    pub fn reciprocal() -> Self {
+        Self::new(R::zero(), R::one(), R::one(), R::zero())
+    }
+
+    /// The transformation `x -> self(other(x))`, from the product of the
+    /// matrices `[[a, b], [c, d]]`.
+    pub fn compose(&self, other: &Self) -> Self {
+        let dot = |x: &R, y: &R, z: &R, w: &R| x.clone() * z.clone() + y.clone() * w.clone();
+        Self::new(
+            dot(&self.a, &self.b, &other.a, &other.c),
+            dot(&self.a, &self.b, &other.b, &other.d),
+            dot(&self.c, &self.d, &other.a, &other.c),
+            dot(&self.c, &self.d, &other.b, &other.d),
+        )
+    }
+
+    /// The determinant `ad - bc`.
+    pub fn determinant(&self) -> R {
+        CommutativeRing::sub(self.a.clone() * self.d.clone(), self.b.clone() * self.c.clone())
+    }
+
+    /// The numerator `(cx + d)^n p((ax + b) / (cx + d))` of `p` after the
+    /// substitution, where `n` is the degree of `p`, so the transformed
+    /// function is this divided by `(cx + d)^n`.
+    ///
+    /// The numerator has degree at most `n`, and less exactly when `p` has a
+    /// root at `a / c`, which the transformation sends to infinity. It is
+    /// computed with Horner's scheme as
+    /// `q_k = q_(k + 1) (ax + b) + p_k (cx + d)^(n - k)`, in `O(n^2)`
+    /// operations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::mobius::Mobius;
+    /// # use math2::Polynomial;
+    /// // x^2 - 3x + 2 at 1 / (x + 1) is (1 - 3(x + 1) + 2(x + 1)^2) / (x + 1)^2
+    /// let p = Polynomial::<i64>::new(vec![2, -3, 1]);
+    /// let m = Mobius::reciprocal().compose(&Mobius::translation(1));
+    /// assert_eq!(Polynomial::new(vec![0, 1, 2]), m.apply(&p));
+    /// ```
+    pub fn apply(&self, p: &Polynomial<R>) -> Polynomial<R> {
+        let Some(n) = p.degree() else {
+            return Polynomial::zero();
+        };
+        let numerator = Polynomial::new_trim_zeroes(vec![self.b.clone(), self.a.clone()]);
+        let denominator = Polynomial::new_trim_zeroes(vec![self.d.clone(), self.c.clone()]);
+        let mut power = Polynomial::one();
+        let mut q = Polynomial::new(vec![p.coeffs[n].clone()]);
+        for k in (0..n).rev() {
+            power = power * denominator.clone();
+            q = q * numerator.clone() + power.clone().scalar_mul(p.coeffs[k].clone());
+        }
+        q
+    }
+}
+
+impl<F: Field> Mobius<F> {
+    /// The image of `x`, or `None` if `x` is the pole `-d / c`.
+    pub fn eval(&self, x: &F) -> Option<F> {
+        let den = self.c.clone() * x.clone() + self.d.clone();
+        den.checked_inv().map(|inv| (self.a.clone() * x.clone() + self.b.clone()) * inv)
+    }
+
+    /// The image of infinity, `a / c`, or `None` if that is infinity again.
+    pub fn eval_at_infinity(&self) -> Option<F> {
+        self.c.checked_inv().map(|inv| self.a.clone() * inv)
+    }
+
+    /// The inverse transformation `x -> (dx - b) / (-cx + a)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the determinant is zero.
+    pub fn inverse(&self) -> Self {
+        let det = self.determinant().checked_inv().expect("singular transformation");
+        Self::new(
+            self.d.clone() * det.clone(),
+            -self.b.clone() * det.clone(),
+            -self.c.clone() * det.clone(),
+            self.a.clone() * det,
+        )
+    }
+}
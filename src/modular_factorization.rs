@@ -0,0 +1,457 @@
+//! Factorization of polynomials over the prime fields `GF(p)`.
+
+use std::num::NonZeroUsize;
+
+use num::{BigInt, One, Zero};
+
+use crate::combinatorics::lyndon_count;
+use crate::cyclotomic::prime_divisors;
+use crate::factorization::Factorization;
+use crate::fields::Zp;
+use crate::linalg::Matrix;
+use crate::traits::Field;
+use crate::Polynomial;
+
+/// `base^exp mod modulus`, as [`Polynomial::pow_mod`] with a small exponent.
+pub(crate) fn pow_mod<F: Field>(base: &Polynomial<F>, exp: u64, modulus: &Polynomial<F>) -> Polynomial<F> {
+    base.pow_mod(&BigInt::from(exp), modulus)
+}
+
+fn make_monic<F: Field>(f: Polynomial<F>) -> Polynomial<F> {
+    let inv = f.leading_coefficient_cloned().checked_inv().unwrap();
+    f.scalar_mul(inv)
+}
+
+/// The intermediate objects of Berlekamp's algorithm, from
+/// [`Polynomial::berlekamp_steps`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BerlekampSteps<const P: u64> {
+    /// The matrix `Q` whose row `i` holds the coefficients of
+    /// `x^(iP) mod f`.
+    pub q_matrix: Vec<Vec<Zp<P>>>,
+    /// A basis of the row vectors `v` with `v (Q - I) = 0`. These are the
+    /// coefficients of the splitting polynomials `g` with `g^P = g (mod f)`,
+    /// and the first is always the constant `1`.
+    pub nullspace: Vec<Vec<Zp<P>>>,
+    /// Every gcd that split off a proper factor, in order.
+    pub splittings: Vec<Splitting<P>>,
+    /// The monic irreducible factors of `f`, in the order they were found.
+    pub factors: Vec<Polynomial<Zp<P>>>,
+}
+
+/// One step of Berlekamp's algorithm, where `gcd(factor, g - shift)` is a
+/// proper factor of `factor` for a splitting polynomial `g`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Splitting<const P: u64> {
+    pub factor: Polynomial<Zp<P>>,
+    pub splitting_polynomial: Polynomial<Zp<P>>,
+    pub shift: Zp<P>,
+    pub gcd: Polynomial<Zp<P>>,
+}
+
+/// A xorshift generator, so that Cantor–Zassenhaus is reproducible.
+pub(crate) struct Xorshift(pub(crate) u64);
+
+impl Xorshift {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Orders factors by degree, then by coefficients, so that results do not
+/// depend on the order they were found in.
+fn sort_factors<const P: u64>(factors: &mut [(Polynomial<Zp<P>>, NonZeroUsize)]) {
+    factors.sort_by_key(|(f, _)| {
+        let coeffs: Vec<u64> = f.coeffs.iter().rev().map(|c| c.value()).collect();
+        (f.coeffs.len(), coeffs)
+    });
+}
+
+impl<const P: u64> Polynomial<Zp<P>> {
+    /// Factors a square free monic polynomial into irreducibles, calling
+    /// `split` on each square free factor.
+    pub(crate) fn factor_square_free_parts(
+        self,
+        split: impl Fn(Self) -> Vec<Self>,
+    ) -> Factorization<Zp<P>> {
+        let sqf = self.square_free_factorization();
+        let mut factors = Vec::new();
+        for (f, multiplicity) in sqf.factors {
+            for g in split(make_monic(f)) {
+                factors.push((g, multiplicity));
+            }
+        }
+        sort_factors(&mut factors);
+        Factorization {
+            leading_coeff: sqf.leading_coeff,
+            factors,
+        }
+    }
+
+    /// The Berlekamp matrix `Q` of a monic `self` of degree `n`, whose row `i`
+    /// holds the coefficients of `x^(ip) mod self`.
+    pub(crate) fn berlekamp_matrix(&self) -> Vec<Vec<Zp<P>>> {
+        let n = self.degree().unwrap();
+        let x = Polynomial::from_elem_with_degree(Zp::one(), 1);
+        let xp = pow_mod(&x, P, self);
+        let mut row = Polynomial::one();
+        let mut q = Vec::with_capacity(n);
+        for _ in 0..n {
+            let mut coeffs = row.coeffs.clone();
+            coeffs.resize(n, Zp::zero());
+            q.push(coeffs);
+            row = (row * xp.clone()).div_rem(self.clone()).1;
+        }
+        q
+    }
+
+    /// Splits a square free monic polynomial with Berlekamp's algorithm.
+    pub(crate) fn berlekamp_split(self) -> Vec<Self> {
+        if self.degree().is_some_and(|n| n <= 1) {
+            return vec![self];
+        }
+        self.berlekamp_steps().factors
+    }
+
+    /// Runs Berlekamp's algorithm on a square free monic polynomial `f` and
+    /// returns its intermediate objects, for following along with the
+    /// algorithm.
+    ///
+    /// The polynomials `g` with `g^p = g (mod f)` form a vector space, the
+    /// null space of `Q - I`, whose dimension is the number of irreducible
+    /// factors of `f`. Every such `g` satisfies `f = prod_s gcd(f, g - s)`,
+    /// so gcds with basis elements shifted by constants separate all factors.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is constant or not monic. The result is
+    /// meaningless if it is not square free.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^4 + 1 = (x^2 + x + 2)(x^2 + 2x + 2) over GF(3)
+    /// let p = Polynomial::new([1, 0, 0, 0, 1].map(Zp::<3>::new).to_vec());
+    /// let steps = p.berlekamp_steps();
+    /// assert_eq!(4, steps.q_matrix.len());
+    /// // two factors, so the null space of Q - I is two dimensional
+    /// assert_eq!(2, steps.nullspace.len());
+    /// assert_eq!(1, steps.splittings.len());
+    /// assert_eq!(2, steps.factors.len());
+    /// ```
+    pub fn berlekamp_steps(&self) -> BerlekampSteps<P> {
+        let n = self.degree().filter(|&n| n > 0).expect("constant polynomial");
+        assert!(self.leading_coefficient_cloned().is_one(), "polynomial must be monic");
+        let q = self.berlekamp_matrix();
+        // v (Q - I) = 0, transposed
+        let rows = (0..n)
+            .map(|j| {
+                (0..n)
+                    .map(|i| q[i][j] - if i == j { Zp::one() } else { Zp::zero() })
+                    .collect()
+            })
+            .collect();
+        let basis = Matrix::from_rows(rows).nullspace();
+        let count = basis.len();
+        let mut splittings = Vec::new();
+        let mut factors = vec![self.clone()];
+        for v in &basis {
+            if factors.len() == count {
+                break;
+            }
+            let g = Polynomial::new_trim_zeroes(v.clone());
+            if g.degree().is_none_or(|d| d == 0) {
+                continue;
+            }
+            let mut next = Vec::new();
+            for h in factors {
+                let mut h = h;
+                for s in 0..P {
+                    if h.degree() == Some(1) || next.len() + 1 >= count {
+                        break;
+                    }
+                    let shifted = g.clone() - Polynomial::new_trim_zeroes(vec![Zp::new(s)]);
+                    let d = h.clone().gcd(shifted);
+                    if d.degree().is_some_and(|d| d > 0) && d.degree() != h.degree() {
+                        splittings.push(Splitting {
+                            factor: h.clone(),
+                            splitting_polynomial: g.clone(),
+                            shift: Zp::new(s),
+                            gcd: d.clone(),
+                        });
+                        h = h.div_rem(d.clone()).0;
+                        next.push(d);
+                    }
+                }
+                next.push(h);
+            }
+            factors = next;
+        }
+        BerlekampSteps {
+            q_matrix: q,
+            nullspace: basis,
+            splittings,
+            factors,
+        }
+    }
+
+    /// Factors this polynomial into irreducibles over `GF(P)` with
+    /// Berlekamp's algorithm, after a square free factorization.
+    ///
+    /// This tries every constant of the field when splitting, so it is meant
+    /// for small `P`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^4 + 1 = (x^2 + x + 2)(x^2 + 2x + 2) over GF(3)
+    /// let p = Polynomial::new([1, 0, 0, 0, 1].map(Zp::<3>::new).to_vec());
+    /// let factors = p.berlekamp().factors;
+    /// assert_eq!(Polynomial::new([2, 1, 1].map(Zp::new).to_vec()), factors[0].0);
+    /// assert_eq!(Polynomial::new([2, 2, 1].map(Zp::new).to_vec()), factors[1].0);
+    /// ```
+    pub fn berlekamp(self) -> Factorization<Zp<P>> {
+        self.factor_square_free_parts(Self::berlekamp_split)
+    }
+
+    /// Splits a square free monic polynomial into products of irreducible
+    /// factors of equal degree, returning each product with the degree of its
+    /// factors.
+    ///
+    /// The product of all monic irreducibles of degree dividing `d` is
+    /// `x^(P^d) - x`, so `gcd(f, x^(P^d) - x)` collects the factors of degree
+    /// `d` once those of smaller degree are divided out.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^5 - x = x (x + 1) (x + 2) (x^2 + 1) over GF(3)
+    /// let p = Polynomial::new([0, 2, 0, 0, 0, 1].map(Zp::<3>::new).to_vec());
+    /// let ddf = p.distinct_degree_factorization();
+    /// assert_eq!(Polynomial::new([0, 2, 0, 1].map(Zp::new).to_vec()), ddf[0].0);
+    /// assert_eq!(Polynomial::new([1, 0, 1].map(Zp::new).to_vec()), ddf[1].0);
+    /// assert_eq!(vec![1, 2], ddf.iter().map(|(_, d)| *d).collect::<Vec<_>>());
+    /// ```
+    pub fn distinct_degree_factorization(self) -> Vec<(Self, usize)> {
+        let mut f = self;
+        let mut res = Vec::new();
+        let x = Polynomial::from_elem_with_degree(Zp::one(), 1);
+        let mut h = x.clone();
+        let mut d = 0;
+        while f.degree().is_some_and(|n| n >= 2 * (d + 1)) {
+            d += 1;
+            h = pow_mod(&h, P, &f);
+            let g = f.clone().gcd(h.clone() - x.clone());
+            if !g.is_one() {
+                f = f.div_rem(g.clone()).0;
+                h = h.div_rem(f.clone()).1;
+                res.push((g, d));
+            }
+        }
+        if let Some(n) = f.degree().filter(|&n| n > 0) {
+            res.push((f, n));
+        }
+        res
+    }
+
+    /// Splits a monic product of distinct irreducibles, all of degree
+    /// `degree`, with the Cantor–Zassenhaus algorithm.
+    ///
+    /// For a random `a`, `a^((P^d - 1) / 2) - 1` is divisible by about half
+    /// of the factors, so its gcd with the polynomial is a proper factor with
+    /// good probability. In characteristic 2 the trace
+    /// `a + a^2 + ... + a^(2^(d - 1))` plays the same role. Random elements
+    /// come from a fixed seed, so the result is deterministic.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^2 + 1 = (x + 2) (x + 3) over GF(5)
+    /// let p = Polynomial::new([1, 0, 1].map(Zp::<5>::new).to_vec());
+    /// let mut factors = p.equal_degree_factorization(1);
+    /// factors.sort_by_key(|f| f.coeff_at(0).value());
+    /// assert_eq!(Polynomial::new([2, 1].map(Zp::new).to_vec()), factors[0]);
+    /// assert_eq!(Polynomial::new([3, 1].map(Zp::new).to_vec()), factors[1]);
+    /// ```
+    pub fn equal_degree_factorization(self, degree: usize) -> Vec<Self> {
+        let mut rng = Xorshift(0x9e37_79b9_7f4a_7c15);
+        let mut done = Vec::new();
+        let mut stack = vec![self];
+        while let Some(f) = stack.pop() {
+            let n = f.degree().unwrap();
+            if n <= degree {
+                done.push(f);
+                continue;
+            }
+            let a = Polynomial::new_trim_zeroes((0..n).map(|_| Zp::new(rng.next())).collect());
+            if a.degree().is_none_or(|d| d == 0) {
+                stack.push(f);
+                continue;
+            }
+            let b = if P == 2 {
+                let mut t = a.clone();
+                let mut sum = a;
+                for _ in 1..degree {
+                    t = pow_mod(&t, 2, &f);
+                    sum += t.clone();
+                }
+                sum
+            } else {
+                // (P^d - 1) / 2 = (1 + P + ... + P^(d - 1)) (P - 1) / 2
+                let mut t = a.clone();
+                let mut norm = a;
+                for _ in 1..degree {
+                    t = pow_mod(&t, P, &f);
+                    norm = (norm * t.clone()).div_rem(f.clone()).1;
+                }
+                pow_mod(&norm, (P - 1) / 2, &f) - Polynomial::one()
+            };
+            let g = f.clone().gcd(b);
+            if g.degree().is_some_and(|d| d > 0 && d < n) {
+                stack.push(f.clone().div_rem(g.clone()).0);
+                stack.push(g);
+            } else {
+                stack.push(f);
+            }
+        }
+        done
+    }
+
+    /// Factors this polynomial into irreducibles over `GF(P)` with the
+    /// Cantor–Zassenhaus algorithm: a square free factorization, then
+    /// [`distinct_degree_factorization`] and
+    /// [`equal_degree_factorization`] on each part.
+    ///
+    /// Unlike [`berlekamp`], this does not try every constant of the field,
+    /// so it is suitable for large `P`.
+    ///
+    /// [`distinct_degree_factorization`]: Polynomial::distinct_degree_factorization
+    /// [`equal_degree_factorization`]: Polynomial::equal_degree_factorization
+    /// [`berlekamp`]: Polynomial::berlekamp
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^4 + 1 = (x^2 + x + 2)(x^2 + 2x + 2) over GF(3)
+    /// let p = Polynomial::new([1, 0, 0, 0, 1].map(Zp::<3>::new).to_vec());
+    /// assert_eq!(p.clone().berlekamp(), p.factor_mod_p());
+    /// ```
+    pub fn factor_mod_p(self) -> Factorization<Zp<P>> {
+        self.factor_square_free_parts(|f| {
+            f.distinct_degree_factorization()
+                .into_iter()
+                .flat_map(|(g, d)| g.equal_degree_factorization(d))
+                .collect()
+        })
+    }
+
+    /// Whether this polynomial is irreducible over `GF(P)`, by Rabin's test.
+    ///
+    /// A polynomial `f` of degree `n` is irreducible exactly when it divides
+    /// `x^(P^n) - x` but is coprime to `x^(P^(n / q)) - x` for every prime
+    /// `q` dividing `n`, which rules out factors of degree `n / q` or less.
+    /// Constants are not irreducible.
+    pub fn is_irreducible(&self) -> bool {
+        let Some(n) = self.degree().filter(|&n| n > 0) else {
+            return false;
+        };
+        let f = make_monic(self.clone());
+        let x = Polynomial::from_elem_with_degree(Zp::one(), 1);
+        // frobenius[k] = x^(P^k) mod f
+        let mut frobenius = vec![x.clone().div_rem(f.clone()).1];
+        for k in 0..n {
+            let next = pow_mod(&frobenius[k], P, &f);
+            frobenius.push(next);
+        }
+        frobenius[n] == frobenius[0]
+            && prime_divisors(n)
+                .into_iter()
+                .all(|q| f.clone().gcd(frobenius[n / q].clone() - x.clone()).is_one())
+    }
+
+    /// The monic irreducible polynomials of degree `degree`, ordered by
+    /// their coefficients from the highest degree down.
+    ///
+    /// There are [`count_irreducible`]`(P, degree)` of them, found by testing
+    /// each of the `P^degree` monic polynomials.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::fields::Zp;
+    /// # use math2::Polynomial;
+    /// // x^2 + 1, x^2 + x + 2 and x^2 + 2x + 2 over GF(3)
+    /// let irreducibles: Vec<_> = Polynomial::<Zp<3>>::irreducibles_of_degree(2).collect();
+    /// assert_eq!(
+    ///     vec![
+    ///         Polynomial::new([1, 0, 1].map(Zp::new).to_vec()),
+    ///         Polynomial::new([2, 1, 1].map(Zp::new).to_vec()),
+    ///         Polynomial::new([2, 2, 1].map(Zp::new).to_vec()),
+    ///     ],
+    ///     irreducibles
+    /// );
+    /// ```
+    pub fn irreducibles_of_degree(degree: usize) -> impl Iterator<Item = Self> {
+        let mut lower = Some(vec![0; degree]);
+        std::iter::from_fn(move || {
+            let digits = lower.as_mut()?;
+            let mut coeffs: Vec<Zp<P>> = digits.iter().map(|&c| Zp::new(c)).collect();
+            coeffs.push(Zp::one());
+            // count in base P with the constant term as the lowest digit
+            match digits.iter().position(|&c| c + 1 < P) {
+                Some(i) => {
+                    digits[i] += 1;
+                    digits[..i].fill(0);
+                }
+                None => lower = None,
+            }
+            Some(Polynomial::new(coeffs))
+        })
+        .filter(Self::is_irreducible)
+    }
+
+    /// All monic irreducible polynomials, the primes of `GF(P)[x]`, by
+    /// increasing degree and then as in [`irreducibles_of_degree`].
+    ///
+    /// [`irreducibles_of_degree`]: Polynomial::irreducibles_of_degree
+    pub fn irreducibles() -> impl Iterator<Item = Self> {
+        (1..).flat_map(Self::irreducibles_of_degree)
+    }
+}
+
+/// The number of monic irreducible polynomials of degree `n` over `GF(q)`,
+/// for a prime power `q`.
+///
+/// Every element of `GF(q^n)` has a minimal polynomial of degree dividing
+/// `n`, so `q^n` is the sum of `d` times the count for each `d | n`. This is
+/// the same relation as for Lyndon words, so the count is
+/// [`lyndon_count`]`(n, q)`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::modular_factorization::count_irreducible;
+/// # use num::BigInt;
+/// assert_eq!(BigInt::from(3), count_irreducible(3, 2));
+/// assert_eq!(BigInt::from(335), count_irreducible(2, 12));
+/// ```
+pub fn count_irreducible(q: u64, n: usize) -> BigInt {
+    lyndon_count(n, q)
+}
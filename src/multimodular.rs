@@ -0,0 +1,399 @@
+//! Reconstructing integer polynomials from their images modulo many primes.
+//!
+//! Multi-modular algorithms compute a result modulo several primes and
+//! combine the images with the Chinese remainder theorem. An image can be
+//! wrong, for example from a hardware fault, a bug in a fast path, or an
+//! unlucky prime that was not detected, and a single wrong image spoils the
+//! combination. With more primes than the bound strictly needs, the images
+//! can instead vote on the result.
+//!
+//! [`crt`] and [`crt_poly`] are the Chinese remainder theorem itself, for
+//! integers and for polynomials over a field, with moduli that need not be
+//! coprime.
+//!
+//! [`modular_gcd`] is the gcd of integer polynomials from their gcds modulo
+//! many primes.
+
+use std::cmp::Ordering;
+
+use num::{BigInt, Integer, One, Signed, ToPrimitive, Zero};
+
+use crate::fields::Zp;
+use crate::linalg::word_primes;
+use crate::traits::{CoefficientDomain, Field};
+use crate::zassenhaus::{divide, primitive};
+use crate::Polynomial;
+
+pub(crate) fn mul_mod(a: u64, b: u64, p: u64) -> u64 {
+    (a as u128 * b as u128 % p as u128) as u64
+}
+
+pub(crate) fn inv_mod(x: u64, p: u64) -> u64 {
+    let (mut base, mut exp, mut res) = (x, p - 2, 1);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = mul_mod(res, base, p);
+        }
+        base = mul_mod(base, base, p);
+        exp >>= 1;
+    }
+    res
+}
+
+pub(crate) fn reduce(x: &BigInt, p: u64) -> u64 {
+    x.mod_floor(&BigInt::from(p)).to_u64().unwrap()
+}
+
+/// The `x` with `0 <= x < m` and `x = a (mod n)` for every `(a, n)` in
+/// `residues`, where `m` is the lcm of the moduli `n`. `None` if the
+/// congruences contradict each other, which is only possible if the moduli
+/// are not coprime.
+///
+/// Each congruence is merged into the solution so far with the extended
+/// Euclidean algorithm: with `s m + t n = g`, `x + m s (a - x) / g` solves
+/// both `x` modulo `m` and `a` modulo `n` when `g` divides `a - x`.
+///
+/// # Panics
+///
+/// Panics if a modulus is not positive.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multimodular::crt;
+/// # use num::BigInt;
+/// let n = BigInt::from;
+/// assert_eq!(Some(n(23)), crt(&[(n(2), n(3)), (n(3), n(5)), (n(2), n(7))]));
+/// // 4 and 6 are not coprime, so the solution is modulo 12
+/// assert_eq!(Some(n(9)), crt(&[(n(1), n(4)), (n(3), n(6))]));
+/// assert_eq!(None, crt(&[(n(0), n(4)), (n(1), n(6))]));
+/// ```
+pub fn crt(residues: &[(BigInt, BigInt)]) -> Option<BigInt> {
+    let (mut x, mut m) = (BigInt::zero(), BigInt::one());
+    for (a, n) in residues {
+        assert!(n.is_positive(), "moduli must be positive");
+        let gcd = m.extended_gcd(n);
+        let (q, r) = (a - &x).div_mod_floor(&gcd.gcd);
+        if !r.is_zero() {
+            return None;
+        }
+        let n = n / &gcd.gcd;
+        x += &m * (q * gcd.x).mod_floor(&n);
+        m *= n;
+    }
+    Some(x.mod_floor(&m))
+}
+
+/// The polynomial `f` with `deg f < deg m` and `f = a (mod n)` for every
+/// `(a, n)` in `residues`, where `m` is the lcm of the moduli `n`. `None`
+/// if the congruences contradict each other.
+///
+/// With moduli `x - x_i`, this interpolates the values `a(x_i)`.
+///
+/// # Panics
+///
+/// Panics if a modulus is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multimodular::crt_poly;
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let p = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+/// // f(1) = 2, and f = x modulo x^2 + 1
+/// let f = crt_poly(&[(p("2"), p("x - 1")), (p("x"), p("x^2 + 1"))]).unwrap();
+/// assert_eq!(p("1/2 x^2 + x + 1/2"), f);
+/// assert_eq!(None, crt_poly(&[(p("1"), p("x^2 - 1")), (p("0"), p("x - 1"))]));
+/// ```
+pub fn crt_poly<F: Field + PartialEq>(residues: &[(Polynomial<F>, Polynomial<F>)]) -> Option<Polynomial<F>> {
+    let (mut x, mut m) = (Polynomial::zero(), Polynomial::one());
+    for (a, n) in residues {
+        assert!(!n.is_zero(), "moduli must be non-zero");
+        let (g, s, _) = m.clone().extended_gcd(n.clone());
+        let (q, r) = (a.clone() - x.clone()).div_rem(g.clone());
+        if !r.is_zero() {
+            return None;
+        }
+        let n = n.clone().div_rem(g).0;
+        // x has degree less than m, so this has degree less than m n
+        x += m.clone() * (q * s).div_rem(n.clone()).1;
+        m = m * n;
+    }
+    Some(x.div_rem(m).1)
+}
+
+/// Removes trailing zeros.
+pub(crate) fn trim(mut f: Vec<u64>) -> Vec<u64> {
+    while f.last() == Some(&0) {
+        f.pop();
+    }
+    f
+}
+
+/// The remainder of `a` by the non-zero `b` modulo `p`.
+pub(crate) fn rem_mod(mut a: Vec<u64>, b: &[u64], p: u64) -> Vec<u64> {
+    let n = b.len() - 1;
+    let inv = inv_mod(b[n], p);
+    while a.len() > n {
+        let m = a.len() - 1;
+        let factor = mul_mod(a[m], inv, p);
+        for (x, y) in a[m - n..].iter_mut().zip(b) {
+            *x = (*x + p - mul_mod(factor, *y, p)) % p;
+        }
+        a = trim(a);
+    }
+    a
+}
+
+/// The monic gcd of `a` and `b` modulo `p`, which must not both be zero.
+pub(crate) fn gcd_mod(mut a: Vec<u64>, mut b: Vec<u64>, p: u64) -> Vec<u64> {
+    while !b.is_empty() {
+        let r = rem_mod(a, &b, p);
+        a = b;
+        b = r;
+    }
+    let inv = inv_mod(*a.last().unwrap(), p);
+    a.iter().map(|&c| mul_mod(c, inv, p)).collect()
+}
+
+/// The gcd of integer polynomials with the small primes modular algorithm,
+/// normalized with a positive leading coefficient like
+/// [`CoefficientDomain::gcd`].
+///
+/// The primitive parts are reduced modulo word sized primes that do not
+/// divide the gcd `l` of their leading coefficients, and their monic gcds
+/// are scaled by `l` so that the images of the same integer polynomial can
+/// be combined with the Chinese remainder theorem. An image of too large a
+/// degree comes from an unlucky prime and is dropped, and one of smaller
+/// degree means that all the images so far were unlucky. Once the
+/// combination stops changing, its primitive part is the gcd if it divides
+/// both polynomials.
+///
+/// The remainder sequences that [`CoefficientDomain::gcd`] uses have
+/// coefficients that grow with the degrees even when the gcd is small, and
+/// here only the primes needed for the gcd itself are used.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multimodular::modular_gcd;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// let p = |c: &[i64]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+/// // (2x + 3)(x - 1) and (2x + 3)(x^2 + 1), times 6 and 4
+/// let a = p(&[-18, 6, 12]);
+/// let b = p(&[12, 8, 12, 8]);
+/// assert_eq!(p(&[6, 4]), modular_gcd(&a, &b));
+/// assert_eq!(p(&[1]), modular_gcd(&p(&[-1, 1]), &p(&[1, 1])));
+/// ```
+pub fn modular_gcd(a: &Polynomial<BigInt>, b: &Polynomial<BigInt>) -> Polynomial<BigInt> {
+    if a.is_zero() || b.is_zero() {
+        return (a.clone() + b.clone()).unit_and_normal().1;
+    }
+    count!(gcds, 1);
+    let content = Integer::gcd(&a.clone().content(), &b.clone().content());
+    let (a, b) = (primitive(a.clone()), primitive(b.clone()));
+    let l = Integer::gcd(&a.leading_coefficient_cloned(), &b.leading_coefficient_cloned());
+    let image = |f: &Polynomial<BigInt>, p| trim(f.coeffs.iter().map(|c| reduce(c, p)).collect());
+
+    // the combined images, of degree `degree`, modulo `modulus`
+    let mut values: Vec<BigInt> = Vec::new();
+    let mut modulus = BigInt::one();
+    let mut degree = usize::MAX;
+    let mut candidate = Polynomial::zero();
+    for p in word_primes() {
+        let lp = reduce(&l, p);
+        if lp == 0 {
+            continue;
+        }
+        let g = gcd_mod(image(&a, p), image(&b, p), p);
+        if g.len() == 1 {
+            return Polynomial::new(vec![content]);
+        }
+        match (g.len() - 1).cmp(&degree) {
+            Ordering::Greater => continue,
+            Ordering::Less => {
+                degree = g.len() - 1;
+                values = vec![BigInt::zero(); g.len()];
+                modulus = BigInt::one();
+            }
+            Ordering::Equal => {}
+        }
+        // x = v + m * ((r - v) * m^-1 mod p)
+        let m_inv = inv_mod(reduce(&modulus, p), p);
+        for (v, r) in values.iter_mut().zip(&g) {
+            let r = mul_mod(*r, lp, p);
+            let t = mul_mod((r + p - reduce(v, p)) % p, m_inv, p);
+            *v += &modulus * t;
+        }
+        modulus *= p;
+        let half = &modulus / 2;
+        let symmetric = values.iter().map(|v| if *v > half { v - &modulus } else { v.clone() }).collect();
+        let next = primitive(Polynomial::new(symmetric));
+        if next == candidate && divide(&a, &candidate).is_some() && divide(&b, &candidate).is_some() {
+            return candidate.scalar_mul(content);
+        }
+        candidate = next;
+    }
+    unreachable!("ran out of primes")
+}
+
+/// The result of [`MultiModularReconstructor::reconstruct`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Reconstruction {
+    pub polynomial: Polynomial<BigInt>,
+    /// The primes whose images disagree with the polynomial, in the order
+    /// they were added.
+    pub faulty: Vec<u64>,
+}
+
+/// Collects images of an integer polynomial modulo distinct primes and
+/// reconstructs it by majority vote.
+///
+/// Each candidate is combined from a run of consecutive images, cyclically
+/// in the order they were added, just long enough for the product of the
+/// primes to exceed twice the coefficient bound. The candidate agreeing with
+/// the most images wins if those are a strict majority. So if the images can
+/// be split into `g` such runs, up to `g - 1` faulty images are detected and
+/// corrected, as long as they are a minority.
+///
+/// # Example
+///
+/// ```
+/// # use math2::fields::Zp;
+/// # use math2::multimodular::MultiModularReconstructor;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// // 1000x - 999 from images modulo 7, 11, 13, 17 and 19, with a wrong image modulo 13
+/// let mut reconstructor = MultiModularReconstructor::new(BigInt::from(1000));
+/// reconstructor.add_image(&Polynomial::new([2, 6].map(Zp::<7>::new).to_vec()));
+/// reconstructor.add_image(&Polynomial::new([2, 10].map(Zp::<11>::new).to_vec()));
+/// reconstructor.add_image(&Polynomial::new([5, 5].map(Zp::<13>::new).to_vec()));
+/// reconstructor.add_image(&Polynomial::new([4, 14].map(Zp::<17>::new).to_vec()));
+/// reconstructor.add_image(&Polynomial::new([8, 12].map(Zp::<19>::new).to_vec()));
+/// let reconstruction = reconstructor.reconstruct().unwrap();
+/// assert_eq!(Polynomial::new([-999, 1000].map(BigInt::from).to_vec()), reconstruction.polynomial);
+/// assert_eq!(vec![13], reconstruction.faulty);
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiModularReconstructor {
+    bound: BigInt,
+    images: Vec<(u64, Vec<u64>)>,
+}
+
+impl MultiModularReconstructor {
+    /// A reconstructor for polynomials whose coefficients are at most `bound`
+    /// in absolute value.
+    pub fn new(bound: BigInt) -> Self {
+        Self {
+            bound: bound.abs(),
+            images: Vec::new(),
+        }
+    }
+
+    /// Adds the image modulo the prime `p`, as coefficients in order of
+    /// increasing degree. Trailing zeros are allowed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is already an image modulo `p`.
+    pub fn add_residues(&mut self, p: u64, mut residues: Vec<u64>) {
+        assert!(self.images.iter().all(|(q, _)| *q != p), "repeated prime {p}");
+        for r in &mut residues {
+            *r %= p;
+        }
+        while residues.last() == Some(&0) {
+            residues.pop();
+        }
+        self.images.push((p, residues));
+    }
+
+    /// Adds the image modulo `P`.
+    pub fn add_image<const P: u64>(&mut self, image: &Polynomial<Zp<P>>) {
+        self.add_residues(P, image.coeffs.iter().map(|c| c.value()).collect());
+    }
+
+    /// The primes added so far.
+    pub fn primes(&self) -> impl Iterator<Item = u64> + '_ {
+        self.images.iter().map(|(p, _)| *p)
+    }
+
+    /// Combines the images in `window` into the polynomial with coefficients in
+    /// the symmetric range modulo the product of their primes.
+    fn combine(&self, window: &[&(u64, Vec<u64>)]) -> Polynomial<BigInt> {
+        let len = window.iter().map(|(_, r)| r.len()).max().unwrap_or(0);
+        let mut modulus = BigInt::one();
+        let mut values = vec![BigInt::zero(); len];
+        for (p, residues) in window {
+            let p = *p;
+            // x = v + m * ((r - v) * m^-1 mod p)
+            let m_inv = inv_mod(reduce(&modulus, p), p);
+            for (i, v) in values.iter_mut().enumerate() {
+                let r = residues.get(i).copied().unwrap_or(0);
+                let t = mul_mod((r + p - reduce(v, p)) % p, m_inv, p);
+                *v += &modulus * t;
+            }
+            modulus *= p;
+        }
+        let half = &modulus / 2;
+        for v in &mut values {
+            if *v > half {
+                *v -= &modulus;
+            }
+        }
+        Polynomial::new_trim_zeroes(values)
+    }
+
+    fn is_consistent(polynomial: &Polynomial<BigInt>, (p, residues): &(u64, Vec<u64>)) -> bool {
+        let len = polynomial.coeffs.len().max(residues.len());
+        (0..len).all(|i| {
+            let c = polynomial.coeffs.get(i).map_or(0, |c| reduce(c, *p));
+            c == residues.get(i).copied().unwrap_or(0)
+        })
+    }
+
+    /// Reconstructs the polynomial by majority vote. `None` if the product of
+    /// all the primes is not above twice the bound, or if no candidate agrees
+    /// with a strict majority of the images.
+    pub fn reconstruct(&self) -> Option<Reconstruction> {
+        let n = self.images.len();
+        let target = &self.bound * 2;
+        let mut best: Option<(Polynomial<BigInt>, usize)> = None;
+        let mut tried = Vec::new();
+        for start in 0..n {
+            let mut window = Vec::new();
+            let mut modulus = BigInt::one();
+            for image in self.images.iter().cycle().skip(start).take(n) {
+                if modulus > target {
+                    break;
+                }
+                modulus *= image.0;
+                window.push(image);
+            }
+            if modulus <= target {
+                return None;
+            }
+            let candidate = self.combine(&window);
+            if candidate.coeffs.iter().any(|c| c.abs() > self.bound) || tried.contains(&candidate) {
+                continue;
+            }
+            let support = self.images.iter().filter(|image| Self::is_consistent(&candidate, image)).count();
+            if best.as_ref().is_none_or(|(_, s)| support > *s) {
+                best = Some((candidate.clone(), support));
+            }
+            tried.push(candidate);
+        }
+        let (polynomial, support) = best?;
+        if 2 * support <= n {
+            return None;
+        }
+        let faulty = self
+            .images
+            .iter()
+            .filter(|image| !Self::is_consistent(&polynomial, image))
+            .map(|(p, _)| *p)
+            .collect();
+        Some(Reconstruction { polynomial, faulty })
+    }
+}
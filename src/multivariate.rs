@@ -0,0 +1,439 @@
+//! Multivariate polynomials.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::linalg::Matrix;
+use crate::traits::{AssertUnit, CommutativeRing, FromUsize};
+use crate::Polynomial;
+
+/// A polynomial in the variables `x_0, x_1, ...`, stored sparsely as a map
+/// from exponent vectors to non-zero coefficients.
+///
+/// Exponent vectors are stored without trailing zeros, so `x_0` is `[1]`
+/// no matter how many variables appear elsewhere, and the constant term is
+/// `[]`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MultiPolynomial<Ring> {
+    terms: BTreeMap<Vec<usize>, Ring>,
+}
+
+fn trimmed(exponents: &[usize]) -> &[usize] {
+    let len = exponents.iter().rposition(|&e| e != 0).map_or(0, |i| i + 1);
+    &exponents[..len]
+}
+
+impl<Ring: CommutativeRing> MultiPolynomial<Ring> {
+    /// Creates a polynomial from `(exponents, coefficient)` pairs. Repeated
+    /// exponent vectors are added together.
+    pub fn new(terms: impl IntoIterator<Item = (Vec<usize>, Ring)>) -> Self {
+        let mut res = Self::zero();
+        for (exponents, c) in terms {
+            res.add_term(trimmed(&exponents).to_vec(), c);
+        }
+        res
+    }
+
+    pub fn constant(c: Ring) -> Self {
+        Self::new([(Vec::new(), c)])
+    }
+
+    /// The polynomial `x_i`.
+    pub fn var(i: usize) -> Self {
+        let mut exponents = vec![0; i + 1];
+        exponents[i] = 1;
+        Self::new([(exponents, Ring::one())])
+    }
+
+    /// Adds `c` to the coefficient of a trimmed exponent vector.
+    fn add_term(&mut self, exponents: Vec<usize>, c: Ring) {
+        match self.terms.entry(exponents) {
+            Entry::Vacant(entry) => {
+                if !c.is_zero() {
+                    entry.insert(c);
+                }
+            }
+            Entry::Occupied(mut entry) => {
+                let sum = entry.get().clone() + c;
+                if sum.is_zero() {
+                    entry.remove();
+                } else {
+                    entry.insert(sum);
+                }
+            }
+        }
+    }
+
+    /// The coefficient of the monomial with the given exponents, treating
+    /// missing exponents as zero.
+    pub fn coeff(&self, exponents: &[usize]) -> Option<&Ring> {
+        self.terms.get(trimmed(exponents))
+    }
+
+    /// The non-zero terms, in lexicographic order of their exponents.
+    pub fn terms(&self) -> impl Iterator<Item = (&[usize], &Ring)> {
+        self.terms.iter().map(|(e, c)| (e.as_slice(), c))
+    }
+
+    /// One more than the index of the last variable that appears.
+    pub fn num_vars(&self) -> usize {
+        self.terms.keys().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// The largest total degree of a term, or `None` for the zero
+    /// polynomial.
+    pub fn total_degree(&self) -> Option<usize> {
+        self.terms.keys().map(|e| e.iter().sum()).max()
+    }
+
+    /// The degree in the variable `x_i`, or `None` for the zero polynomial.
+    pub fn degree_in(&self, i: usize) -> Option<usize> {
+        self.terms.keys().map(|e| e.get(i).copied().unwrap_or(0)).max()
+    }
+
+    pub fn scalar_mul(self, x: Ring) -> Self {
+        Self::new(self.terms.into_iter().map(|(e, c)| (e, c * x.clone())))
+    }
+
+    /// Evaluates at `point`, which assigns `point[i]` to `x_i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable that appears has no value.
+    pub fn eval(&self, point: &[Ring]) -> Ring {
+        assert!(point.len() >= self.num_vars(), "missing values for some variables");
+        self.terms
+            .iter()
+            .map(|(exponents, c)| {
+                exponents
+                    .iter()
+                    .zip(point)
+                    .fold(c.clone(), |acc, (&e, x)| acc * pow(x, e))
+            })
+            .fold(Ring::zero(), |a, b| a + b)
+    }
+
+    /// Replaces `x_i` with `value`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::multivariate::MultiPolynomial;
+    /// let x = || MultiPolynomial::<i64>::var(0);
+    /// let y = || MultiPolynomial::<i64>::var(1);
+    /// // x^2 y with y = x + 1 is x^3 + x^2
+    /// let p = x() * x() * y();
+    /// assert_eq!(x() * x() * x() + x() * x(), p.substitute(1, &(x() + MultiPolynomial::constant(1))));
+    /// ```
+    pub fn substitute(&self, i: usize, value: &Self) -> Self {
+        let mut powers = vec![Self::one()];
+        let mut res = Self::zero();
+        for (exponents, c) in &self.terms {
+            let e = exponents.get(i).copied().unwrap_or(0);
+            while powers.len() <= e {
+                let next = powers.last().unwrap().clone() * value.clone();
+                powers.push(next);
+            }
+            let mut rest = exponents.clone();
+            if e > 0 {
+                rest[i] = 0;
+            }
+            let rest = Self::new([(rest, c.clone())]);
+            res += rest * powers[e].clone();
+        }
+        res
+    }
+
+    /// Replaces `x_i` with the constant `value`, leaving a polynomial in the
+    /// other variables.
+    ///
+    /// The terms are grouped by their monomial in the other variables, and
+    /// each group is evaluated as a polynomial in `x_i` with Horner's scheme,
+    /// skipping the powers of `x_i` that do not appear.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::multivariate::MultiPolynomial;
+    /// let x = || MultiPolynomial::<i64>::var(0);
+    /// let y = || MultiPolynomial::<i64>::var(1);
+    /// let c = MultiPolynomial::constant;
+    /// // x^2 y + x y + 3 with x = 2 is 6y + 3
+    /// let p = x() * x() * y() + x() * y() + c(3);
+    /// assert_eq!(c(6) * y() + c(3), p.specialize(0, &2));
+    /// ```
+    pub fn specialize(&self, i: usize, value: &Ring) -> Self {
+        let mut groups: BTreeMap<Vec<usize>, Vec<(usize, Ring)>> = BTreeMap::new();
+        for (exponents, c) in &self.terms {
+            let e = exponents.get(i).copied().unwrap_or(0);
+            let mut rest = exponents.clone();
+            if e > 0 {
+                rest[i] = 0;
+                rest.truncate(trimmed(&rest).len());
+            }
+            groups.entry(rest).or_default().push((e, c.clone()));
+        }
+        Self::new(groups.into_iter().map(|(rest, mut group)| {
+            group.sort_unstable_by_key(|&(e, _)| std::cmp::Reverse(e));
+            let mut last = group[0].0;
+            let mut acc = Ring::zero();
+            for (e, c) in group {
+                acc = acc * pow(value, last - e) + c;
+                last = e;
+            }
+            (rest, acc * pow(value, last))
+        }))
+    }
+
+    /// Replaces each `x_i` with `values[&i]`, leaving a polynomial in the
+    /// variables without a value, by [`specialize`](Self::specialize) in
+    /// one variable at a time.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::BTreeMap;
+    /// # use math2::multivariate::MultiPolynomial;
+    /// let x = |i| MultiPolynomial::<i64>::var(i);
+    /// let c = MultiPolynomial::constant;
+    /// // x_0 x_1 + x_1 x_2 + x_2 with x_0 = 2 and x_2 = 3 is 5 x_1 + 3
+    /// let p = x(0) * x(1) + x(1) * x(2) + x(2);
+    /// let values = BTreeMap::from([(0, 2), (2, 3)]);
+    /// assert_eq!(c(5) * x(1) + c(3), p.partial_eval(&values));
+    /// ```
+    pub fn partial_eval(&self, values: &BTreeMap<usize, Ring>) -> Self {
+        values
+            .iter()
+            .fold(self.clone(), |p, (&i, value)| p.specialize(i, value))
+    }
+
+    /// The polynomial `p(x_i)`.
+    pub fn from_univariate(p: Polynomial<Ring>, i: usize) -> Self {
+        Self::new(p.coeffs.into_iter().enumerate().map(|(degree, c)| {
+            let mut exponents = vec![0; i + 1];
+            exponents[i] = degree;
+            (exponents, c)
+        }))
+    }
+
+    /// Views this as a polynomial in `x_i` whose coefficients are polynomials
+    /// in the other variables.
+    pub fn to_univariate(&self, i: usize) -> Polynomial<Self> {
+        let mut coeffs = vec![Self::zero(); self.degree_in(i).map_or(0, |d| d + 1)];
+        for (exponents, c) in &self.terms {
+            let mut rest = exponents.clone();
+            let e = rest.get(i).copied().unwrap_or(0);
+            if e > 0 {
+                rest[i] = 0;
+            }
+            coeffs[e].add_term(trimmed(&rest).to_vec(), c.clone());
+        }
+        Polynomial::new(coeffs)
+    }
+
+    /// Whether every term has the same total degree. The zero polynomial is
+    /// homogeneous.
+    pub fn is_homogeneous(&self) -> bool {
+        let mut degrees = self.terms.keys().map(|e| e.iter().sum::<usize>());
+        degrees.next().is_none_or(|d| degrees.all(|e| e == d))
+    }
+
+    /// Multiplies each term by the power of the new variable `x_var` that
+    /// brings it up to the total degree, giving the projective closure of
+    /// the affine hypersurface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x_var` already appears.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::multivariate::MultiPolynomial;
+    /// let x = |i| MultiPolynomial::<i64>::var(i);
+    /// let c = MultiPolynomial::constant;
+    /// // y = x^2 + 1 becomes yz = x^2 + z^2
+    /// let p = x(1) - x(0) * x(0) - c(1);
+    /// let h = p.homogenize(2);
+    /// assert_eq!(x(1) * x(2) - x(0) * x(0) - x(2) * x(2), h);
+    /// assert_eq!(p, h.dehomogenize(2));
+    /// ```
+    pub fn homogenize(&self, var: usize) -> Self {
+        assert_eq!(0, self.degree_in(var).unwrap_or(0), "the homogenizing variable must not appear");
+        let degree = self.total_degree().unwrap_or(0);
+        Self::new(self.terms.iter().map(|(exponents, c)| {
+            let mut exponents = exponents.clone();
+            if exponents.len() <= var {
+                exponents.resize(var + 1, 0);
+            }
+            exponents[var] = degree - exponents.iter().sum::<usize>();
+            (exponents, c.clone())
+        }))
+    }
+
+    /// Sets `x_var` to one, the affine part of a projective hypersurface
+    /// where `x_var` is not zero. The other variables keep their indices.
+    pub fn dehomogenize(&self, var: usize) -> Self {
+        self.specialize(var, &Ring::one())
+    }
+}
+
+impl<Ring: CommutativeRing + FromUsize> MultiPolynomial<Ring> {
+    /// The partial derivative with respect to `x_i`.
+    pub fn derivative(&self, i: usize) -> Self {
+        Self::new(self.terms.iter().filter_map(|(exponents, c)| {
+            let e = exponents.get(i).copied().filter(|&e| e > 0)?;
+            let mut exponents = exponents.clone();
+            exponents[i] -= 1;
+            Some((exponents, c.clone() * Ring::from_usize(e)))
+        }))
+    }
+
+    /// The partial derivatives with respect to `x_0, ..., x_(n - 1)`, for
+    /// the `n` variables of [`num_vars`](Self::num_vars).
+    pub fn gradient(&self) -> Vec<Self> {
+        (0..self.num_vars()).map(|i| self.derivative(i)).collect()
+    }
+
+    /// The Jacobian matrix of the map with components `polys`, with the
+    /// partial derivatives of `polys[i]` in row `i` and a column for each
+    /// variable that appears in any of them.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::multivariate::MultiPolynomial;
+    /// let x = || MultiPolynomial::<i64>::var(0);
+    /// let y = || MultiPolynomial::<i64>::var(1);
+    /// let c = MultiPolynomial::constant;
+    /// // z -> z^2 as a map of the plane, (x^2 - y^2, 2xy)
+    /// let j = MultiPolynomial::jacobian(&[x() * x() - y() * y(), c(2) * x() * y()]);
+    /// assert_eq!(c(-2) * y(), j[(0, 1)]);
+    /// assert_eq!(c(4) * (x() * x() + y() * y()), j.determinant_division_free());
+    /// ```
+    pub fn jacobian(polys: &[Self]) -> Matrix<Self> {
+        let n = polys.iter().map(Self::num_vars).max().unwrap_or(0);
+        Matrix::from_fn(polys.len(), n, |i, j| polys[i].derivative(j))
+    }
+
+    /// The Hessian matrix of second partial derivatives, which is the
+    /// Jacobian of the gradient.
+    pub fn hessian(&self) -> Matrix<Self> {
+        let gradient = self.gradient();
+        let n = gradient.len();
+        Matrix::from_fn(n, n, |i, j| gradient[i].derivative(j))
+    }
+}
+
+fn pow<Ring: CommutativeRing>(x: &Ring, e: usize) -> Ring {
+    (0..e).fold(Ring::one(), |acc, _| acc * x.clone())
+}
+
+impl<Ring: CommutativeRing> Zero for MultiPolynomial<Ring> {
+    fn zero() -> Self {
+        Self {
+            terms: BTreeMap::new(),
+        }
+    }
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+impl<Ring: CommutativeRing> One for MultiPolynomial<Ring> {
+    fn one() -> Self {
+        Self::constant(Ring::one())
+    }
+}
+
+impl<Ring: CommutativeRing> Add for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        for (exponents, c) in rhs.terms {
+            self.add_term(exponents, c);
+        }
+        self
+    }
+}
+
+impl<Ring: CommutativeRing> AddAssign for MultiPolynomial<Ring> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (exponents, c) in rhs.terms {
+            self.add_term(exponents, c);
+        }
+    }
+}
+
+impl<Ring: CommutativeRing> Neg for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self {
+            terms: self.terms.into_iter().map(|(e, c)| (e, -c)).collect(),
+        }
+    }
+}
+
+impl<Ring: CommutativeRing> Sub for MultiPolynomial<Ring> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.neg()
+    }
+}
+
+impl<Ring: CommutativeRing> Mul for MultiPolynomial<Ring> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let mut res = Self::zero();
+        for (a, x) in &self.terms {
+            for (b, y) in &rhs.terms {
+                let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+                let mut exponents = long.clone();
+                for (e, f) in exponents.iter_mut().zip(short) {
+                    *e += f;
+                }
+                res.add_term(exponents, x.clone() * y.clone());
+            }
+        }
+        res
+    }
+}
+
+impl<Ring: CommutativeRing> Sum for MultiPolynomial<Ring> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+impl<Ring: CommutativeRing> Product for MultiPolynomial<Ring> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |a, b| a * b)
+    }
+}
+
+impl<Ring: CommutativeRing> CommutativeRing for MultiPolynomial<Ring> {
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(!self.is_zero());
+        for (exponents, c) in &self.terms {
+            if exponents.is_empty() {
+                c.clone().assert_is_unit();
+            } else {
+                assert!(c.is_nilpotent());
+            }
+        }
+        AssertUnit(self)
+    }
+    /// Only constant units are supported, as for [`Polynomial`].
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        assert_eq!(Some(0), x.0.total_degree(), "inverting non-constant units is unsupported");
+        let c = Ring::invert(&x.0.terms[&Vec::new()].clone().assert_is_unit());
+        Self::constant(c.into_inner()).assert_is_unit()
+    }
+    fn is_nilpotent(&self) -> bool {
+        self.terms.values().all(Ring::is_nilpotent)
+    }
+}
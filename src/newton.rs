@@ -0,0 +1,79 @@
+//! Newton's method for square systems of polynomial equations.
+
+use num::{BigInt, BigRational, Zero};
+
+use crate::linalg::{solve_rational, Matrix};
+use crate::multivariate::MultiPolynomial;
+
+/// The iterates of Newton's method `x_(k + 1) = x_k - J(x_k)^-1 F(x_k)` for
+/// the system `F = 0` of `n` polynomials in `n` variables, in exact rational
+/// arithmetic, starting with `start` itself.
+///
+/// The iteration stops early at an exact root, or where the Jacobian `J` is
+/// singular. The denominators of exact iterates roughly double with every
+/// step, so `digits` rounds each iterate to the nearest multiple of
+/// `10^-digits`. Near a simple root the iterates then still converge
+/// quadratically until they are within about `10^-digits` of it.
+///
+/// # Panics
+///
+/// Panics if the number of equations is not the number of coordinates of
+/// `start`, or if an equation uses more variables.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multivariate::MultiPolynomial;
+/// # use math2::newton::newton_solve;
+/// # use num::BigRational;
+/// let q = |a: i32| BigRational::from_integer(a.into());
+/// let x = || MultiPolynomial::var(0);
+/// let y = || MultiPolynomial::var(1);
+/// let c = MultiPolynomial::constant;
+/// // x^2 + y^2 = 2 and x = y meet at (1, 1)
+/// let system = [x() * x() + y() * y() - c(q(2)), x() - y()];
+/// let iterates = newton_solve(&system, &[q(2), q(2)], 3, None);
+/// assert_eq!(4, iterates.len());
+/// let half = BigRational::new(1.into(), 2.into());
+/// assert_eq!(vec![q(5) / q(4), q(5) / q(4)], iterates[1]);
+/// // after 20 steps the iterate is within 10^-30 of the root
+/// let last = newton_solve(&system, &[q(2), q(2)], 20, Some(30)).pop().unwrap();
+/// assert!((&last[0] - q(1)) * q(10).pow(30) < half);
+/// ```
+pub fn newton_solve(
+    system: &[MultiPolynomial<BigRational>],
+    start: &[BigRational],
+    iterations: usize,
+    digits: Option<u32>,
+) -> Vec<Vec<BigRational>> {
+    let n = start.len();
+    assert_eq!(n, system.len(), "need one equation per variable");
+    assert!(system.iter().all(|f| f.num_vars() <= n), "equations use more variables than there are coordinates");
+    let jacobian = Matrix::from_fn(n, n, |i, j| system[i].derivative(j));
+    let scale = digits.map(|digits| BigRational::from_integer(BigInt::from(10).pow(digits)));
+    let mut iterates = vec![start.to_vec()];
+    for _ in 0..iterations {
+        let x = iterates.last().unwrap();
+        let residual: Vec<BigRational> = system.iter().map(|f| f.eval(x)).collect();
+        if residual.iter().all(Zero::is_zero) {
+            break;
+        }
+        let j: Vec<Vec<BigRational>> = jacobian.rows().map(|row| row.iter().map(|d| d.eval(x)).collect()).collect();
+        let Some(step) = solve_rational(&j, &residual) else {
+            break;
+        };
+        let next = x
+            .iter()
+            .zip(step)
+            .map(|(x, d)| {
+                let y = x - d;
+                match &scale {
+                    Some(scale) => (y * scale).round() / scale,
+                    None => y,
+                }
+            })
+            .collect();
+        iterates.push(next);
+    }
+    iterates
+}
@@ -0,0 +1,972 @@
+//! Elementary number theory on `BigInt`, and summatory and prime counting
+//! functions on `u64`.
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use num::{BigInt, BigRational, Integer, One, Signed, ToPrimitive, Zero};
+
+/// Euler's totient function, from a factorization.
+fn totient(factors: &[(BigInt, u32)]) -> BigInt {
+    factors
+        .iter()
+        .map(|(p, e)| p.pow(e - 1) * (p - 1))
+        .product()
+}
+
+/// Returns the multiplicative order of `a` modulo `n`, the least `k > 0` with
+/// `a^k ≡ 1 (mod n)`. `None` if `a` is not invertible modulo `n`.
+///
+/// The order divides `φ(n)`, so this starts from `φ(n)` and removes prime
+/// factors as long as the power stays `1`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::multiplicative_order;
+/// # use num::BigInt;
+/// assert_eq!(Some(BigInt::from(6)), multiplicative_order(&BigInt::from(10), &BigInt::from(7)));
+/// assert_eq!(None, multiplicative_order(&BigInt::from(2), &BigInt::from(6)));
+/// ```
+pub fn multiplicative_order(a: &BigInt, n: &BigInt) -> Option<BigInt> {
+    assert!(n.is_positive(), "modulus must be positive");
+    if n.is_one() {
+        return Some(BigInt::one());
+    }
+    if !a.gcd(n).is_one() {
+        return None;
+    }
+    let phi = totient(&factor_integer(n));
+    let mut order = phi.clone();
+    for (p, _) in factor_integer(&phi) {
+        while order.is_multiple_of(&p) && a.modpow(&(&order / &p), n).is_one() {
+            order /= &p;
+        }
+    }
+    Some(order)
+}
+
+/// The shape of the expansion of a rational number in some base: `pre_period`
+/// digits after the point that do not repeat, followed by a repeating block of
+/// `period` digits. Terminating expansions have a period of zero.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Expansion {
+    pub pre_period: usize,
+    pub period: BigInt,
+}
+
+/// Returns the pre-period and period of the base-`base` expansion of `q`.
+///
+/// After taking out the largest factor of the denominator that divides a
+/// power of the base, the pre-period is the exponent of that power and the
+/// period is the order of the base modulo what is left.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::{period_in_base, Expansion};
+/// # use num::{BigInt, BigRational};
+/// // 1/12 = 0.0020202... in base 3
+/// let q = BigRational::new(1.into(), 12.into());
+/// let expansion = period_in_base(&q, 3);
+/// assert_eq!(Expansion { pre_period: 1, period: BigInt::from(2) }, expansion);
+/// ```
+pub fn period_in_base(q: &BigRational, base: u32) -> Expansion {
+    assert!(base >= 2, "base must be at least 2");
+    let base = BigInt::from(base);
+    let mut denom = q.denom().abs();
+    let mut pre_period = 0;
+    loop {
+        let g = denom.gcd(&base);
+        if g.is_one() {
+            break;
+        }
+        denom /= g;
+        pre_period += 1;
+    }
+    let period = if denom.is_one() {
+        BigInt::zero()
+    } else {
+        multiplicative_order(&base, &denom).unwrap()
+    };
+    Expansion { pre_period, period }
+}
+
+/// Returns the pre-period and period of the decimal expansion of `q`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::decimal_period;
+/// # use num::{BigInt, BigRational};
+/// // 1/12 = 0.08333...
+/// let expansion = decimal_period(&BigRational::new(1.into(), 12.into()));
+/// assert_eq!(2, expansion.pre_period);
+/// assert_eq!(BigInt::from(1), expansion.period);
+/// ```
+pub fn decimal_period(q: &BigRational) -> Expansion {
+    period_in_base(q, 10)
+}
+
+/// The divisors of a positive integer, partially ordered by divisibility.
+///
+/// The divisors are sorted, and the poset operations take and return
+/// indices into [`DivisorLattice::divisors`]. The lattice is isomorphic to a
+/// product of chains, one for each prime power in the factorization, which
+/// makes meets, joins and the Möbius function easy to compute from exponents.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DivisorLattice {
+    primes: Vec<BigInt>,
+    divisors: Vec<BigInt>,
+    exponents: Vec<Vec<u32>>,
+}
+
+impl DivisorLattice {
+    /// All positive divisors, in increasing order.
+    pub fn divisors(&self) -> &[BigInt] {
+        &self.divisors
+    }
+
+    /// The index of `d` in [`DivisorLattice::divisors`], if it is a divisor.
+    pub fn index_of(&self, d: &BigInt) -> Option<usize> {
+        self.divisors.binary_search(d).ok()
+    }
+
+    fn index_with_exponents(&self, exponents: &[u32]) -> usize {
+        let d: BigInt = self
+            .primes
+            .iter()
+            .zip(exponents)
+            .map(|(p, &e)| p.pow(e))
+            .product();
+        self.index_of(&d).unwrap()
+    }
+
+    /// Whether divisor `a` divides divisor `b`.
+    pub fn divides(&self, a: usize, b: usize) -> bool {
+        self.exponents[a].iter().zip(&self.exponents[b]).all(|(x, y)| x <= y)
+    }
+
+    /// The gcd of two divisors.
+    pub fn meet(&self, a: usize, b: usize) -> usize {
+        let e: Vec<u32> = self.exponents[a].iter().zip(&self.exponents[b]).map(|(x, y)| *x.min(y)).collect();
+        self.index_with_exponents(&e)
+    }
+
+    /// The lcm of two divisors.
+    pub fn join(&self, a: usize, b: usize) -> usize {
+        let e: Vec<u32> = self.exponents[a].iter().zip(&self.exponents[b]).map(|(x, y)| *x.max(y)).collect();
+        self.index_with_exponents(&e)
+    }
+
+    /// The covering pairs `(a, b)` of the Hasse diagram, where `b / a` is
+    /// prime.
+    pub fn covers(&self) -> Vec<(usize, usize)> {
+        let mut res = Vec::new();
+        for (a, exponents) in self.exponents.iter().enumerate() {
+            for i in 0..self.primes.len() {
+                let mut e = exponents.clone();
+                e[i] += 1;
+                if self.exponents.last().unwrap()[i] >= e[i] {
+                    res.push((a, self.index_with_exponents(&e)));
+                }
+            }
+        }
+        res.sort_unstable();
+        res
+    }
+
+    /// The Möbius function `μ(a, b)` of the lattice, which is the number
+    /// theoretic `μ(b / a)` when `a` divides `b` and zero otherwise.
+    pub fn mobius(&self, a: usize, b: usize) -> i32 {
+        if !self.divides(a, b) {
+            return 0;
+        }
+        let mut res = 1;
+        for (x, y) in self.exponents[a].iter().zip(&self.exponents[b]) {
+            match y - x {
+                0 => {}
+                1 => res = -res,
+                _ => return 0,
+            }
+        }
+        res
+    }
+}
+
+/// Returns the lattice of divisors of `n`.
+///
+/// # Panics
+///
+/// Panics if `n` is not positive.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::divisor_lattice;
+/// # use num::BigInt;
+/// let lattice = divisor_lattice(&BigInt::from(12));
+/// let index = |d: i32| lattice.index_of(&BigInt::from(d)).unwrap();
+/// assert_eq!(6, lattice.divisors().len());
+/// assert_eq!(index(2), lattice.meet(index(4), index(6)));
+/// assert_eq!(index(12), lattice.join(index(4), index(6)));
+/// // μ(12 / 2) = μ(6) = 1
+/// assert_eq!(1, lattice.mobius(index(2), index(12)));
+/// assert_eq!(0, lattice.mobius(index(1), index(4)));
+/// ```
+pub fn divisor_lattice(n: &BigInt) -> DivisorLattice {
+    assert!(n.is_positive(), "n must be positive");
+    let factors = factor_integer(n);
+    let mut pairs = vec![(BigInt::one(), Vec::new())];
+    for (p, e) in &factors {
+        pairs = pairs
+            .into_iter()
+            .flat_map(|(d, exponents)| {
+                (0..=*e).map(move |k| {
+                    let mut exponents = exponents.clone();
+                    exponents.push(k);
+                    (&d * p.pow(k), exponents)
+                })
+            })
+            .collect();
+    }
+    pairs.sort_unstable();
+    let (divisors, exponents) = pairs.into_iter().unzip();
+    DivisorLattice {
+        primes: factors.into_iter().map(|(p, _)| p).collect(),
+        divisors,
+        exponents,
+    }
+}
+
+/// The unitary divisors of `n`, those `d` with `gcd(d, n / d) = 1`. They are
+/// the products of subsets of the prime powers exactly dividing `n`, and
+/// are produced in no particular order.
+///
+/// # Panics
+///
+/// Panics if `n` is not positive.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::unitary_divisors;
+/// # use num::BigInt;
+/// let mut divisors: Vec<_> = unitary_divisors(&BigInt::from(72)).collect();
+/// divisors.sort();
+/// assert_eq!([1, 8, 9, 72].map(BigInt::from).to_vec(), divisors);
+/// ```
+pub fn unitary_divisors(n: &BigInt) -> impl Iterator<Item = BigInt> {
+    assert!(n.is_positive(), "n must be positive");
+    let powers: Vec<BigInt> = factor_integer(n).into_iter().map(|(p, e)| p.pow(e)).collect();
+    (0..1u64 << powers.len()).map(move |mask| {
+        powers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| mask >> i & 1 == 1)
+            .map(|(_, q)| q)
+            .product()
+    })
+}
+
+/// The ordered pairs `(a, b)` of divisors of `n` with `gcd(a, b) = 1`, in no
+/// particular order.
+///
+/// For each prime `p^e` exactly dividing `n`, either `a` or `b` or neither
+/// takes a power of `p`, so there are `prod(2e + 1)` pairs.
+///
+/// # Panics
+///
+/// Panics if `n` is not positive.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::coprime_divisor_pairs;
+/// # use num::BigInt;
+/// // 12 = 2^2 * 3, so there are 5 * 3 pairs
+/// assert_eq!(15, coprime_divisor_pairs(&BigInt::from(12)).count());
+/// assert!(coprime_divisor_pairs(&BigInt::from(12)).any(|(a, b)| a == BigInt::from(4) && b == BigInt::from(3)));
+/// ```
+pub fn coprime_divisor_pairs(n: &BigInt) -> impl Iterator<Item = (BigInt, BigInt)> {
+    assert!(n.is_positive(), "n must be positive");
+    let factors = factor_integer(n);
+    let total: u64 = factors.iter().map(|(_, e)| 2 * u64::from(*e) + 1).product();
+    (0..total).map(move |mut index| {
+        let (mut a, mut b) = (BigInt::one(), BigInt::one());
+        for (p, e) in &factors {
+            let choices = 2 * e + 1;
+            // 0 for neither, 1..=e for a power in a, e + 1..=2e for a power in b
+            let choice = (index % u64::from(choices)) as u32;
+            index /= u64::from(choices);
+            if choice == 0 {
+                continue;
+            } else if choice <= *e {
+                a *= p.pow(choice);
+            } else {
+                b *= p.pow(choice - e);
+            }
+        }
+        (a, b)
+    })
+}
+
+/// Sieves `μ(k)` and `φ(k)` for `k <= limit` with a linear sieve.
+fn sieve_mobius_totient(limit: usize) -> (Vec<i8>, Vec<u64>) {
+    let mut mu = vec![1i8; limit + 1];
+    let mut phi: Vec<u64> = (0..=limit as u64).collect();
+    let mut composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+    mu[0] = 0;
+    for i in 2..=limit {
+        if !composite[i] {
+            primes.push(i);
+            mu[i] = -1;
+            phi[i] = i as u64 - 1;
+        }
+        for &p in &primes {
+            if i * p > limit {
+                break;
+            }
+            composite[i * p] = true;
+            if i.is_multiple_of(p) {
+                mu[i * p] = 0;
+                phi[i * p] = phi[i] * p as u64;
+                break;
+            }
+            mu[i * p] = -mu[i];
+            phi[i * p] = phi[i] * (p as u64 - 1);
+        }
+    }
+    (mu, phi)
+}
+
+/// Evaluates `S(n)` for a summatory function satisfying
+/// `sum(S(n / k) for 1 <= k <= n) = g(n)`, given `prefix[v] = S(v)` for small
+/// `v`.
+///
+/// Only the values `S(n / i)` are needed, and the sum over `k` is grouped
+/// into runs with the same quotient. With a prefix table up to `n^(2/3)`
+/// this takes `O(n^(2/3))` time.
+fn summatory_recursion(n: u64, prefix: &[i128], g: impl Fn(u64) -> i128) -> i128 {
+    let limit = prefix.len() as u64 - 1;
+    if n <= limit {
+        return prefix[n as usize];
+    }
+    // large[i] = S(n / i) for the i with n / i > limit
+    let count = (n / (limit + 1)) as usize;
+    let mut large = vec![0i128; count + 1];
+    for i in (1..=count).rev() {
+        let v = n / i as u64;
+        let mut s = g(v);
+        let mut k = 2;
+        while k <= v {
+            let q = v / k;
+            let last = v / q;
+            let sq = if q <= limit {
+                prefix[q as usize]
+            } else {
+                large[i * k as usize]
+            };
+            s -= (last - k + 1) as i128 * sq;
+            k = last + 1;
+        }
+        large[i] = s;
+    }
+    large[1]
+}
+
+/// The prefix table size for [`summatory_recursion`], about `n^(2/3)`.
+fn summatory_limit(n: u64) -> usize {
+    let limit = (n as f64).powf(2.0 / 3.0) as u64;
+    limit.clamp(1, n.max(1)) as usize
+}
+
+/// The Mertens function `M(n) = sum(μ(k) for 1 <= k <= n)`.
+///
+/// This uses `sum(M(n / k) for 1 <= k <= n) = 1`, with the Möbius function
+/// sieved up to `n^(2/3)`, which takes `O(n^(2/3))` time and memory.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::mertens;
+/// assert_eq!(-1, mertens(10));
+/// assert_eq!(2, mertens(1000));
+/// assert_eq!(212, mertens(1_000_000));
+/// assert_eq!(-222, mertens(1_000_000_000));
+/// ```
+pub fn mertens(n: u64) -> i64 {
+    if n == 0 {
+        return 0;
+    }
+    let (mu, _) = sieve_mobius_totient(summatory_limit(n));
+    let prefix: Vec<i128> = mu
+        .iter()
+        .scan(0, |acc, &m| {
+            *acc += i128::from(m);
+            Some(*acc)
+        })
+        .collect();
+    summatory_recursion(n, &prefix, |_| 1) as i64
+}
+
+/// The summatory totient `Φ(n) = sum(φ(k) for 1 <= k <= n)`.
+///
+/// This uses `sum(Φ(n / k) for 1 <= k <= n) = n (n + 1) / 2`, with the
+/// totient sieved up to `n^(2/3)`, which takes `O(n^(2/3))` time and memory.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::totient_summatory;
+/// assert_eq!(32, totient_summatory(10));
+/// assert_eq!(303_963_552_392, totient_summatory(1_000_000));
+/// ```
+pub fn totient_summatory(n: u64) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let (_, phi) = sieve_mobius_totient(summatory_limit(n));
+    let prefix: Vec<i128> = phi
+        .iter()
+        .scan(0, |acc, &p| {
+            *acc += i128::from(p);
+            Some(*acc)
+        })
+        .collect();
+    summatory_recursion(n, &prefix, |v| i128::from(v) * (i128::from(v) + 1) / 2) as u128
+}
+
+/// The divisor summatory function `D(n) = sum(d(k) for 1 <= k <= n)`, where
+/// `d(k)` is the number of divisors of `k`.
+///
+/// By Dirichlet's hyperbola method, counting the lattice points under
+/// `xy = n` gives `D(n) = 2 sum(n / k for k <= √n) - ⌊√n⌋^2`, in `O(√n)`
+/// time.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::divisor_summatory;
+/// assert_eq!(27, divisor_summatory(10));
+/// assert_eq!(27_785_452_449_086, divisor_summatory(1_000_000_000_000));
+/// ```
+pub fn divisor_summatory(n: u64) -> u128 {
+    let s = num::integer::Roots::sqrt(&n);
+    let sum: u128 = (1..=s).map(|k| u128::from(n / k)).sum();
+    2 * sum - u128::from(s) * u128::from(s)
+}
+
+/// The primes up to `limit`, by the sieve of Eratosthenes.
+pub(crate) fn primes_up_to(limit: u64) -> Vec<u64> {
+    let mut composite = vec![false; limit as usize + 1];
+    let mut primes = Vec::new();
+    for i in 2..=limit as usize {
+        if composite[i] {
+            continue;
+        }
+        primes.push(i as u64);
+        for k in (i * i..=limit as usize).step_by(i) {
+            composite[k] = true;
+        }
+    }
+    primes
+}
+
+/// The number of primes `π(n)` up to `n`.
+///
+/// This is the Lucy_Hedgehog variant of the Legendre–Meissel method: for
+/// each of the `O(√n)` values `v = n / i`, `S(v)` starts as the count of
+/// `2..=v` and sieving out each prime `p <= √n` removes the numbers whose
+/// smallest prime factor is `p`, which are counted by
+/// `S(v / p) - S(p - 1)`. It takes `O(n^(3/4))` time and `O(√n)` memory.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::prime_pi;
+/// assert_eq!(4, prime_pi(10));
+/// assert_eq!(455_052_511, prime_pi(10_000_000_000));
+/// ```
+pub fn prime_pi(n: u64) -> u64 {
+    if n < 2 {
+        return 0;
+    }
+    let r = num::integer::Roots::sqrt(&n) as usize;
+    // small[v] = S(v), large[i] = S(n / i)
+    let mut small: Vec<u64> = (0..=r as u64).map(|v| v.saturating_sub(1)).collect();
+    let mut large: Vec<u64> = (0..=r as u64).map(|i| n.checked_div(i).map_or(0, |v| v - 1)).collect();
+    for p in 2..=r {
+        if small[p] == small[p - 1] {
+            continue;
+        }
+        let below = small[p - 1];
+        let square = (p * p) as u64;
+        for i in 1..=r.min((n / square) as usize) {
+            let d = i * p;
+            let s = if d <= r { large[d] } else { small[(n / d as u64) as usize] };
+            large[i] -= s - below;
+        }
+        for v in (p * p..=r).rev() {
+            small[v] -= small[v / p] - below;
+        }
+    }
+    large[1]
+}
+
+/// The `n`th prime, counting from `nth_prime(1) = 2`.
+///
+/// The primes up to the lower bound `n (ln n + ln ln n - 1)` are counted
+/// with [`prime_pi`], and the rest are found with a segmented sieve below
+/// the upper bound `n (ln n + ln ln n)`, both valid for `n >= 6`.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::nth_prime;
+/// assert_eq!(29, nth_prime(10));
+/// assert_eq!(15_485_863, nth_prime(1_000_000));
+/// ```
+pub fn nth_prime(n: u64) -> u64 {
+    assert!(n > 0, "there is no zeroth prime");
+    if n < 6 {
+        return [2, 3, 5, 7, 11][n as usize - 1];
+    }
+    let ln = (n as f64).ln();
+    let lower = (n as f64 * (ln + ln.ln() - 1.0)) as u64;
+    let upper = (n as f64 * (ln + ln.ln())) as u64 + 1;
+    let base = primes_up_to(num::integer::Roots::sqrt(&upper));
+    let mut count = prime_pi(lower);
+    const WINDOW: u64 = 1 << 16;
+    let mut start = lower + 1;
+    loop {
+        // sieve [start, start + WINDOW)
+        let mut composite = [false; WINDOW as usize];
+        for &p in base.iter().take_while(|&&p| p * p < start + WINDOW) {
+            let first = (p * p).max(start.div_ceil(p) * p);
+            for k in (first..start + WINDOW).step_by(p as usize) {
+                composite[(k - start) as usize] = true;
+            }
+        }
+        for (i, &c) in composite.iter().enumerate() {
+            if !c {
+                count += 1;
+                if count == n {
+                    return start + i as u64;
+                }
+            }
+        }
+        start += WINDOW;
+    }
+}
+
+/// The primes below 40, the bases of the deterministic Miller–Rabin test.
+const SMALL_PRIMES: [u32; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/// The least composite that passes the Miller–Rabin test for every base in
+/// [`SMALL_PRIMES`].
+const MILLER_RABIN_BOUND: u128 = 318_665_857_834_031_151_167_461;
+
+/// Whether the odd `n > 2` is a strong probable prime to base `a`: with
+/// `n - 1 = d 2^s` for odd `d`, either `a^d = 1` or `a^(d 2^r) = -1` for
+/// some `r < s`.
+fn miller_rabin(n: &BigInt, a: &BigInt) -> bool {
+    let m: BigInt = n - 1;
+    let s = m.trailing_zeros().unwrap();
+    let mut x = a.modpow(&(&m >> s), n);
+    if x.is_one() || x == m {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == m {
+            return true;
+        }
+    }
+    false
+}
+
+/// The Jacobi symbol `(a / n)` for odd positive `n`.
+fn jacobi(a: &BigInt, n: &BigInt) -> i32 {
+    let (mut a, mut n) = (a.mod_floor(n), n.clone());
+    let mut res = 1;
+    while !a.is_zero() {
+        let twos = a.trailing_zeros().unwrap();
+        a >>= twos;
+        let residue = |x: &BigInt, m: u32| (x % m).to_u32().unwrap();
+        // (2 / n) = -1 exactly for n = 3, 5 (mod 8)
+        if twos % 2 == 1 && matches!(residue(&n, 8), 3 | 5) {
+            res = -res;
+        }
+        // quadratic reciprocity, with a sign change when both are 3 (mod 4)
+        if residue(&a, 4) == 3 && residue(&n, 4) == 3 {
+            res = -res;
+        }
+        std::mem::swap(&mut a, &mut n);
+        a = a.mod_floor(&n);
+    }
+    if n.is_one() {
+        res
+    } else {
+        0
+    }
+}
+
+/// Halves `x` modulo the odd `n`.
+fn half_mod(x: BigInt, n: &BigInt) -> BigInt {
+    if x.is_odd() {
+        (x + n) / 2
+    } else {
+        x / 2
+    }
+}
+
+/// The strong Lucas probable prime test of the odd `n > 2`, which must not
+/// be a square, with Selfridge's parameters: the first `D` of
+/// `5, -7, 9, -11, ...` with `(D / n) = -1`, `P = 1` and `Q = (1 - D) / 4`.
+///
+/// With `n + 1 = d 2^s` for odd `d`, a prime passes if `U_d = 0` or
+/// `V_(d 2^r) = 0` modulo `n` for some `r < s`.
+fn strong_lucas(n: &BigInt) -> bool {
+    let mut d = BigInt::from(5);
+    loop {
+        match jacobi(&d, n) {
+            -1 => break,
+            0 if d.abs() != *n => return false,
+            _ => {}
+        }
+        d = if d.is_positive() { -(d + 2u32) } else { 2u32 - d };
+    }
+    let q: BigInt = (BigInt::one() - &d) / 4;
+    let m: BigInt = n + 1;
+    let s = m.trailing_zeros().unwrap();
+    let odd = &m >> s;
+    // U_k, V_k and Q^k modulo n, by doubling and stepping along the bits of
+    // `odd` from the top
+    let (mut u, mut v, mut qk) = (BigInt::one(), BigInt::one(), q.mod_floor(n));
+    for i in (0..odd.bits() - 1).rev() {
+        u = &u * &v % n;
+        v = (&v * &v - &qk * 2u32).mod_floor(n);
+        qk = &qk * &qk % n;
+        if odd.bit(i) {
+            let next_u = half_mod(&u + &v, n);
+            v = half_mod((&d * &u + &v).mod_floor(n), n);
+            u = next_u;
+            qk = (&qk * &q).mod_floor(n);
+        }
+    }
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = (&v * &v - &qk * 2u32).mod_floor(n);
+        qk = &qk * &qk % n;
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+/// Whether `n` is a (positive) prime.
+///
+/// After trial division by the primes below 40, inputs below
+/// `3.1 * 10^23` take the Miller–Rabin test for those primes as bases, which
+/// is known to be exact in that range. Larger inputs take the Baillie–PSW
+/// test, the Miller–Rabin test to base 2 followed by a strong Lucas test,
+/// which has no known counterexample but is not proven to be exact.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::is_prime;
+/// # use num::BigInt;
+/// assert!(is_prime(&BigInt::from(1_000_000_007)));
+/// // the Carmichael number 561 = 3 * 11 * 17
+/// assert!(!is_prime(&BigInt::from(561)));
+/// assert!(is_prime(&((BigInt::from(1) << 127) - 1)));
+/// assert!(!is_prime(&((BigInt::from(1) << 128) + 1)));
+/// ```
+pub fn is_prime(n: &BigInt) -> bool {
+    if *n < BigInt::from(2) {
+        return false;
+    }
+    for p in SMALL_PRIMES {
+        if *n == BigInt::from(p) {
+            return true;
+        }
+        if n.is_multiple_of(&BigInt::from(p)) {
+            return false;
+        }
+    }
+    if *n < BigInt::from(SMALL_PRIMES[11] * SMALL_PRIMES[11]) {
+        return true;
+    }
+    if *n < BigInt::from(MILLER_RABIN_BOUND) {
+        return SMALL_PRIMES.iter().all(|&a| miller_rabin(n, &BigInt::from(a)));
+    }
+    let root = num::integer::Roots::sqrt(n);
+    miller_rabin(n, &BigInt::from(2)) && &root * &root != *n && strong_lucas(n)
+}
+
+/// The least prime greater than `n`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::next_prime;
+/// # use num::BigInt;
+/// assert_eq!(BigInt::from(2), next_prime(&BigInt::from(-5)));
+/// assert_eq!(BigInt::from(1_000_000_007), next_prime(&BigInt::from(1_000_000_000)));
+/// ```
+pub fn next_prime(n: &BigInt) -> BigInt {
+    if *n < BigInt::from(2) {
+        return BigInt::from(2);
+    }
+    let mut candidate = n + 1;
+    while !is_prime(&candidate) {
+        candidate += 1;
+    }
+    candidate
+}
+
+/// The bound on the primes and prime powers in Pollard's `p - 1` method.
+const P_MINUS_ONE_BOUND: u64 = 10_000;
+
+/// The number of steps of Pollard's rho method between gcds.
+const RHO_BATCH: u64 = 64;
+
+/// `r` and the largest `k > 1` with `n = r^k`, if `n > 1` is a perfect power.
+fn perfect_power(n: &BigInt) -> Option<(BigInt, u32)> {
+    (2..n.bits() as u32).rev().find_map(|k| {
+        let r = num::integer::Roots::nth_root(n, k);
+        (num::pow(r.clone(), k as usize) == *n).then_some((r, k))
+    })
+}
+
+/// A non-trivial factor of `n` by Pollard's `p - 1` method, if some prime
+/// factor `p` of `n` has `p - 1` a product of prime powers below
+/// [`P_MINUS_ONE_BOUND`].
+///
+/// The gcd is taken after each prime, so that the factors are still told
+/// apart when the exponent becomes a multiple of each of their orders at
+/// different primes, as for `2` modulo a Mersenne prime.
+fn pollard_p_minus_one(n: &BigInt) -> Option<BigInt> {
+    let mut a = BigInt::from(2);
+    for q in primes_up_to(P_MINUS_ONE_BOUND) {
+        let mut power = q;
+        while power * q <= P_MINUS_ONE_BOUND {
+            power *= q;
+        }
+        a = a.modpow(&BigInt::from(power), n);
+        let g = (&a - 1u32).gcd(n);
+        if !g.is_one() {
+            return (g != *n).then_some(g);
+        }
+    }
+    None
+}
+
+/// A non-trivial factor of the odd composite `n`, which must not be a
+/// perfect power, by Pollard's rho method with Brent's cycle detection.
+///
+/// The differences are multiplied together for [`RHO_BATCH`] steps before
+/// taking a gcd. When a batch collects every prime factor at once, it is
+/// stepped through again one gcd at a time, and if even that finds only
+/// `n` the next polynomial `x^2 + c` is tried.
+fn pollard_rho(n: &BigInt) -> BigInt {
+    let mut c = BigInt::one();
+    loop {
+        let f = |x: &BigInt| (x * x + &c) % n;
+        let mut y = BigInt::from(2);
+        let (mut x, mut saved) = (y.clone(), y.clone());
+        let mut product = BigInt::one();
+        let mut g = BigInt::one();
+        let mut r = 1;
+        while g.is_one() {
+            x = y.clone();
+            for _ in 0..r {
+                y = f(&y);
+            }
+            let mut k = 0;
+            while k < r && g.is_one() {
+                saved = y.clone();
+                for _ in 0..RHO_BATCH.min(r - k) {
+                    y = f(&y);
+                    product = product * (&x - &y).abs() % n;
+                }
+                g = product.gcd(n);
+                k += RHO_BATCH;
+            }
+            r *= 2;
+        }
+        if g == *n {
+            loop {
+                saved = f(&saved);
+                g = (&x - &saved).abs().gcd(n);
+                if !g.is_one() {
+                    break;
+                }
+            }
+        }
+        if g != *n {
+            return g;
+        }
+        c += 1;
+    }
+}
+
+/// A stage of [`factor_integer_traced`].
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Stage {
+    TrialDivision,
+    /// Recognizing primes with [`is_prime`], which finds no factors.
+    Primality,
+    PerfectPower,
+    PMinusOne,
+    Rho,
+}
+
+/// One split in the factor tree: `stage` found the `factor` of `n`. For a
+/// perfect power `n = r^k`, `factor` is `r`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TraceStep {
+    pub stage: Stage,
+    pub n: BigInt,
+    pub factor: BigInt,
+}
+
+/// The result of [`factor_integer_traced`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct FactorizationTrace {
+    /// The factorization, as returned by [`factor_integer`].
+    pub factors: Vec<(BigInt, u32)>,
+    /// The splits in the order they were made, depth first.
+    pub steps: Vec<TraceStep>,
+    /// The total time spent in each stage, including failed attempts such as
+    /// a `p - 1` run that is followed by rho.
+    pub time: BTreeMap<Stage, Duration>,
+}
+
+impl FactorizationTrace {
+    fn timed<T>(&mut self, stage: Stage, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let res = f();
+        *self.time.entry(stage).or_default() += start.elapsed();
+        res
+    }
+
+    fn record(&mut self, stage: Stage, n: &BigInt, factor: &BigInt) {
+        self.steps.push(TraceStep {
+            stage,
+            n: n.clone(),
+            factor: factor.clone(),
+        });
+    }
+}
+
+/// Pushes the prime factors of `n > 1` without small prime factors onto
+/// `primes`, with repetition.
+fn split(n: BigInt, primes: &mut Vec<BigInt>, trace: &mut FactorizationTrace) {
+    if trace.timed(Stage::Primality, || is_prime(&n)) {
+        primes.push(n);
+    } else if let Some((r, k)) = trace.timed(Stage::PerfectPower, || perfect_power(&n)) {
+        trace.record(Stage::PerfectPower, &n, &r);
+        for _ in 0..k {
+            split(r.clone(), primes, trace);
+        }
+    } else {
+        let (stage, d) = match trace.timed(Stage::PMinusOne, || pollard_p_minus_one(&n)) {
+            Some(d) => (Stage::PMinusOne, d),
+            None => (Stage::Rho, trace.timed(Stage::Rho, || pollard_rho(&n))),
+        };
+        trace.record(stage, &n, &d);
+        let other = &n / &d;
+        split(d, primes, trace);
+        split(other, primes, trace);
+    }
+}
+
+/// The prime factorization of `|n|` as `(prime, exponent)` pairs in
+/// increasing order of the prime.
+///
+/// Primes below 1000 are taken out by trial division. What is left is split
+/// by Pollard's `p - 1` method, which quickly finds prime factors `p` with
+/// `p - 1` a product of small primes, or else by Pollard's rho method, which
+/// takes about `√p` steps to find the prime factor `p`, and the factors are
+/// recognized with [`is_prime`]. A product of two primes of 20 digits each
+/// is already out of reach unless one of them is next to a smooth number.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::factor_integer;
+/// # use num::BigInt;
+/// let n = BigInt::from(1_000_000_007u64) * BigInt::from(998_244_353u64) * 12;
+/// let factors = [(2, 2), (3, 1), (998_244_353, 1), (1_000_000_007, 1)].map(|(p, e)| (BigInt::from(p), e));
+/// assert_eq!(factors.to_vec(), factor_integer(&n));
+/// assert_eq!(Vec::<(BigInt, u32)>::new(), factor_integer(&BigInt::from(-1)));
+/// ```
+pub fn factor_integer(n: &BigInt) -> Vec<(BigInt, u32)> {
+    factor_integer_traced(n).factors
+}
+
+/// [`factor_integer`], also recording which stage found each factor and how
+/// long each stage took, for benchmarking and debugging.
+///
+/// # Panics
+///
+/// Panics if `n` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use math2::number_theory::{factor_integer_traced, Stage};
+/// # use num::BigInt;
+/// // 2^3 by trial division, then 1000003^2 as a perfect power
+/// let n = BigInt::from(1_000_003u64).pow(2) * 8;
+/// let trace = factor_integer_traced(&n);
+/// let stages: Vec<_> = trace.steps.iter().map(|s| s.stage).collect();
+/// assert_eq!(vec![Stage::TrialDivision; 3].into_iter().chain([Stage::PerfectPower]).collect::<Vec<_>>(), stages);
+/// assert_eq!(BigInt::from(1_000_003), trace.steps[3].factor);
+/// assert!(trace.time.contains_key(&Stage::Primality));
+/// ```
+pub fn factor_integer_traced(n: &BigInt) -> FactorizationTrace {
+    assert!(!n.is_zero(), "zero has no factorization");
+    let mut trace = FactorizationTrace::default();
+    let mut n = n.abs();
+    let mut primes = Vec::new();
+    let start = Instant::now();
+    for p in primes_up_to(1000) {
+        let p = BigInt::from(p);
+        if &p * &p > n {
+            break;
+        }
+        while n.is_multiple_of(&p) {
+            trace.record(Stage::TrialDivision, &n, &p);
+            n /= &p;
+            primes.push(p.clone());
+        }
+    }
+    *trace.time.entry(Stage::TrialDivision).or_default() += start.elapsed();
+    if n > BigInt::one() {
+        split(n, &mut primes, &mut trace);
+    }
+    primes.sort_unstable();
+    for p in primes {
+        match trace.factors.last_mut() {
+            Some((q, e)) if *q == p => *e += 1,
+            _ => trace.factors.push((p, 1)),
+        }
+    }
+    trace
+}
@@ -0,0 +1,211 @@
+//! Parsing polynomials from text, as printed by [`crate::print`].
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use num::{BigInt, BigRational, One, Zero};
+
+use crate::Polynomial;
+
+/// The largest exponent accepted by [`Polynomial::parse_with_var`]. The
+/// coefficients are stored densely, so a short input like `x^1000000000000`
+/// would otherwise ask for an enormous allocation.
+pub const MAX_DEGREE: usize = 1 << 20;
+
+/// An error from parsing a polynomial, with the byte offset it occurred at.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParsePolynomialError {
+    position: usize,
+    message: &'static str,
+}
+
+impl ParsePolynomialError {
+    /// The byte offset into the input where parsing failed.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl std::error::Error for ParsePolynomialError {}
+
+struct Parser<'a> {
+    input: &'a str,
+    position: usize,
+    var: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, message: &'static str) -> ParsePolynomialError {
+        ParsePolynomialError {
+            position: self.position,
+            message,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.position..]
+    }
+
+    /// Skips whitespace and returns the next character, without consuming it.
+    fn peek(&mut self) -> Option<char> {
+        let trimmed = self.rest().trim_start();
+        self.position = self.input.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.position += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn digits(&mut self) -> &'a str {
+        let len = self.rest().find(|c: char| !c.is_ascii_digit()).unwrap_or(self.rest().len());
+        let start = self.position;
+        self.position += len;
+        &self.input[start..self.position]
+    }
+
+    fn integer(&mut self) -> Result<BigInt, ParsePolynomialError> {
+        self.peek();
+        let digits = self.digits();
+        if digits.is_empty() {
+            return Err(self.error("expected a number"));
+        }
+        Ok(digits.parse().unwrap())
+    }
+
+    /// A number such as `3`, `2/5` or `1.25`.
+    fn number(&mut self) -> Result<BigRational, ParsePolynomialError> {
+        let mut n = BigRational::from_integer(self.integer()?);
+        if self.rest().starts_with('.') {
+            self.position += 1;
+            let digits = self.digits();
+            if digits.is_empty() {
+                return Err(self.error("expected digits after the decimal point"));
+            }
+            let scale = num::pow(BigInt::from(10), digits.len());
+            n += BigRational::new(digits.parse().unwrap(), scale);
+        }
+        if self.eat('/') {
+            let denom = self.integer()?;
+            if denom.is_zero() {
+                return Err(self.error("division by zero"));
+            }
+            n /= BigRational::from_integer(denom);
+        }
+        Ok(n)
+    }
+
+    /// A term without its sign: a coefficient, a power of the variable, or
+    /// both.
+    fn term(&mut self) -> Result<(BigRational, usize), ParsePolynomialError> {
+        let coeff = match self.peek() {
+            Some(c) if c.is_ascii_digit() => Some(self.number()?),
+            _ => None,
+        };
+        let has_var = if coeff.is_some() && self.eat('*') {
+            true
+        } else {
+            self.peek();
+            self.rest().starts_with(self.var)
+        };
+        if !has_var {
+            return coeff.map(|c| (c, 0)).ok_or_else(|| self.error("expected a term"));
+        }
+        if !self.rest().starts_with(self.var) {
+            return Err(self.error("expected the variable"));
+        }
+        self.position += self.var.len();
+        let degree = if self.eat('^') {
+            self.peek();
+            let start = self.position;
+            usize::try_from(self.integer()?)
+                .ok()
+                .filter(|&degree| degree <= MAX_DEGREE)
+                .ok_or(ParsePolynomialError {
+                    position: start,
+                    message: "exponent too large",
+                })?
+        } else {
+            1
+        };
+        Ok((coeff.unwrap_or_else(BigRational::one), degree))
+    }
+
+    fn polynomial(&mut self) -> Result<Polynomial<BigRational>, ParsePolynomialError> {
+        let mut coeffs = Vec::new();
+        let mut negative = self.eat('-');
+        loop {
+            let (coeff, degree) = self.term()?;
+            if coeffs.len() <= degree {
+                coeffs.resize(degree + 1, BigRational::zero());
+            }
+            if negative {
+                coeffs[degree] -= coeff;
+            } else {
+                coeffs[degree] += coeff;
+            }
+            if self.eat('+') {
+                negative = false;
+            } else if self.eat('-') {
+                negative = true;
+            } else if self.peek().is_none() {
+                return Ok(Polynomial::new_trim_zeroes(coeffs));
+            } else {
+                return Err(self.error("expected `+` or `-`"));
+            }
+        }
+    }
+}
+
+impl Polynomial<BigRational> {
+    /// Parses a polynomial in the variable `var`, such as
+    /// `3x^4 - 2/5 x^2 + 7`.
+    ///
+    /// Coefficients are integers, fractions or decimals, optionally followed
+    /// by `*`. Whitespace is ignored, terms may appear in any order and
+    /// repeated powers are added together. `"0"` is the zero polynomial.
+    /// Exponents above [`MAX_DEGREE`] are rejected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    /// let p = Polynomial::parse_with_var("3t^4 - 2/5 t^2 + 7", "t").unwrap();
+    /// assert_eq!(Polynomial::new(vec![q(7, 1), q(0, 1), q(-2, 5), q(0, 1), q(3, 1)]), p);
+    /// let printed = p.print_with_var("t").to_string();
+    /// assert_eq!(p, Polynomial::parse_with_var(&printed, "t").unwrap());
+    /// ```
+    pub fn parse_with_var(s: &str, var: &str) -> Result<Self, ParsePolynomialError> {
+        assert!(
+            !var.is_empty() && !var.starts_with(|c: char| c.is_ascii_digit() || c.is_whitespace()),
+            "invalid variable name"
+        );
+        Parser {
+            input: s,
+            position: 0,
+            var,
+        }
+        .polynomial()
+    }
+}
+
+/// Parses a polynomial in `x`, see [`Polynomial::parse_with_var`].
+impl FromStr for Polynomial<BigRational> {
+    type Err = ParsePolynomialError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_var(s, "x")
+    }
+}
@@ -0,0 +1,210 @@
+//! Piecewise polynomial functions.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use crate::traits::{CommutativeRing, Field, FromUsize};
+use crate::Polynomial;
+
+/// A function that is given by `pieces[i]` on `[breakpoints[i], breakpoints[i + 1])`
+/// (the last piece also includes the last breakpoint).
+///
+/// Pieces are polynomials in the global variable, not shifted to start at
+/// their breakpoint.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PiecewisePolynomial<F> {
+    breakpoints: Vec<F>,
+    pieces: Vec<Polynomial<F>>,
+}
+
+impl<F: CommutativeRing + PartialOrd> PiecewisePolynomial<F> {
+    /// # Panics
+    ///
+    /// Panics if there is not exactly one more breakpoint than pieces, or if
+    /// the breakpoints are not strictly increasing.
+    pub fn new(breakpoints: Vec<F>, pieces: Vec<Polynomial<F>>) -> Self {
+        assert_eq!(
+            breakpoints.len(),
+            pieces.len() + 1,
+            "need one more breakpoint than pieces"
+        );
+        assert!(
+            breakpoints.windows(2).all(|w| w[0] < w[1]),
+            "breakpoints must be strictly increasing"
+        );
+        Self {
+            breakpoints,
+            pieces,
+        }
+    }
+
+    pub fn breakpoints(&self) -> &[F] {
+        &self.breakpoints
+    }
+
+    pub fn pieces(&self) -> &[Polynomial<F>] {
+        &self.pieces
+    }
+
+    /// The index of the piece defining the function at `x`. `None` if `x` is
+    /// outside of the domain.
+    pub fn piece_index(&self, x: &F) -> Option<usize> {
+        let last = self.pieces.len().checked_sub(1)?;
+        if x < &self.breakpoints[0] || x > &self.breakpoints[last + 1] {
+            return None;
+        }
+        // the number of breakpoints after the first one that are <= x
+        let i = self.breakpoints[1..].partition_point(|b| b <= x);
+        Some(i.min(last))
+    }
+
+    /// Evaluates the function at `x`. `None` if `x` is outside of the domain.
+    pub fn eval(&self, x: &F) -> Option<F> {
+        let piece = &self.pieces[self.piece_index(x)?];
+        Some(
+            piece
+                .coeffs
+                .iter()
+                .rev()
+                .fold(F::zero(), |acc, c| acc * x.clone() + c.clone()),
+        )
+    }
+
+    /// Differentiates every piece. At the breakpoints the result is the
+    /// one-sided derivative from the right.
+    pub fn derivative(self) -> Self
+    where
+        F: FromUsize,
+    {
+        Self {
+            breakpoints: self.breakpoints,
+            pieces: self
+                .pieces
+                .into_iter()
+                .map(Polynomial::derivative)
+                .collect(),
+        }
+    }
+
+    /// Returns the pieces of this function on a finer set of breakpoints
+    /// covering the same domain.
+    fn refine(&self, breakpoints: &[F]) -> Vec<Polynomial<F>> {
+        breakpoints[..breakpoints.len() - 1]
+            .iter()
+            .map(|b| self.pieces[self.piece_index(b).unwrap()].clone())
+            .collect()
+    }
+
+    /// Applies `op` piece by piece after refining both functions to the union
+    /// of their breakpoints.
+    fn zip_with(
+        &self,
+        other: &Self,
+        op: impl Fn(Polynomial<F>, Polynomial<F>) -> Polynomial<F>,
+    ) -> Self {
+        let (a, b) = (&self.breakpoints, &other.breakpoints);
+        assert!(
+            a[0] == b[0] && a[a.len() - 1] == b[b.len() - 1],
+            "piecewise polynomials must have the same domain"
+        );
+        let mut breakpoints = Vec::with_capacity(a.len() + b.len());
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() || j < b.len() {
+            let next = if j == b.len() || (i < a.len() && a[i] < b[j]) {
+                i += 1;
+                &a[i - 1]
+            } else if i == a.len() || b[j] < a[i] {
+                j += 1;
+                &b[j - 1]
+            } else {
+                i += 1;
+                j += 1;
+                &a[i - 1]
+            };
+            breakpoints.push(next.clone());
+        }
+        let pieces = self
+            .refine(&breakpoints)
+            .into_iter()
+            .zip(other.refine(&breakpoints))
+            .map(|(p, q)| op(p, q))
+            .collect();
+        Self {
+            breakpoints,
+            pieces,
+        }
+    }
+}
+
+impl<F: Field + FromUsize + PartialOrd> PiecewisePolynomial<F> {
+    /// The antiderivative of this function that is zero at the start of the
+    /// domain and continuous at every breakpoint.
+    pub fn integral(&self) -> Self {
+        let mut offset = F::zero();
+        let mut pieces = Vec::with_capacity(self.pieces.len());
+        for (piece, window) in self.pieces.iter().zip(self.breakpoints.windows(2)) {
+            let antiderivative = piece.antiderivative();
+            let eval = |x: &F| {
+                antiderivative
+                    .coeffs
+                    .iter()
+                    .rev()
+                    .fold(F::zero(), |acc, c| acc * x.clone() + c.clone())
+            };
+            let start = eval(&window[0]);
+            let end = eval(&window[1]);
+            pieces.push(
+                antiderivative + Polynomial::new_trim_zeroes(vec![offset.clone() + -start.clone()]),
+            );
+            offset = offset + end + -start;
+        }
+        Self {
+            breakpoints: self.breakpoints.clone(),
+            pieces,
+        }
+    }
+
+    /// The integral of this function over its whole domain.
+    pub fn definite_integral(&self) -> F {
+        let integral = self.integral();
+        integral.eval(self.breakpoints.last().unwrap()).unwrap()
+    }
+}
+
+/// Pointwise addition.
+///
+/// # Panics
+///
+/// Panics if the two functions are not defined on the same interval. Their
+/// breakpoints do not need to agree.
+impl<F: CommutativeRing + PartialOrd> Add for PiecewisePolynomial<F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.zip_with(&rhs, |p, q| p + q)
+    }
+}
+
+/// Pointwise subtraction. Panics under the same conditions as addition.
+impl<F: CommutativeRing + PartialOrd> Sub for PiecewisePolynomial<F> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.zip_with(&rhs, |p, q| p - q)
+    }
+}
+
+/// Pointwise multiplication. Panics under the same conditions as addition.
+impl<F: CommutativeRing + PartialOrd> Mul for PiecewisePolynomial<F> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.zip_with(&rhs, |p, q| p * q)
+    }
+}
+
+impl<F: CommutativeRing + PartialOrd> Neg for PiecewisePolynomial<F> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            breakpoints: self.breakpoints,
+            pieces: self.pieces.into_iter().map(Neg::neg).collect(),
+        }
+    }
+}
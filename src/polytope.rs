@@ -0,0 +1,340 @@
+//! Lattice polytopes in small dimensions, their mixed volumes, and
+//! Bernstein's bound on the number of solutions of a sparse polynomial
+//! system.
+//!
+//! A polytope is given by lattice points, integer vectors with a common
+//! number of coordinates, and is their convex hull. The facets are found by
+//! trying the hyperplane through every `d` of the points in dimension `d`,
+//! which is only practical for the handful of points and the two or three
+//! dimensions of small systems.
+
+use std::collections::BTreeSet;
+
+use num::{BigInt, BigRational, Integer, One, Zero};
+
+use crate::linalg::Matrix;
+use crate::multivariate::MultiPolynomial;
+use crate::traits::CommutativeRing;
+
+type Point = Vec<i64>;
+
+fn dot(a: &[i64], b: &[i64]) -> i64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn sub(a: &[i64], b: &[i64]) -> Point {
+    a.iter().zip(b).map(|(x, y)| x - y).collect()
+}
+
+/// The `k` element subsets of `0..n`, in lexicographic order.
+fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    fn extend(start: usize, n: usize, k: usize, current: &mut Vec<usize>, res: &mut Vec<Vec<usize>>) {
+        if current.len() == k {
+            res.push(current.clone());
+            return;
+        }
+        for i in start..n {
+            current.push(i);
+            extend(i + 1, n, k, current, res);
+            current.pop();
+        }
+    }
+    let mut res = Vec::new();
+    extend(0, n, k, &mut Vec::with_capacity(k), &mut res);
+    res
+}
+
+/// The distinct points, sorted.
+fn distinct(points: &[Vec<i64>]) -> Vec<Point> {
+    let d = points.first().map_or(0, Vec::len);
+    assert!(points.iter().all(|p| p.len() == d), "points must have the same number of coordinates");
+    points.iter().cloned().collect::<BTreeSet<_>>().into_iter().collect()
+}
+
+/// Coordinates on which the projection of the non-empty `points` is
+/// injective, as many as the dimension of their affine hull.
+fn hull_coordinates(points: &[Point]) -> Vec<usize> {
+    let diffs: Vec<Point> = points[1..].iter().map(|p| sub(p, &points[0])).collect();
+    let rank = |coords: &[usize]| Matrix::from_fn(diffs.len(), coords.len(), |i, j| diffs[i][coords[j]]).rank();
+    let mut coords = Vec::new();
+    for j in 0..points[0].len() {
+        coords.push(j);
+        if rank(&coords) < coords.len() {
+            coords.pop();
+        }
+    }
+    coords
+}
+
+/// The facets of the convex hull of `points`, which must span `R^d` for
+/// some `d >= 1`, as primitive outer normals `a` and offsets `b` such that
+/// `a . x <= b` on the polytope with equality on the facet.
+fn facets(points: &[Point]) -> Vec<(Point, i64)> {
+    let d = points[0].len();
+    let mut res = BTreeSet::new();
+    for subset in subsets(points.len(), d) {
+        let base = &points[subset[0]];
+        let diffs: Vec<Point> = subset[1..].iter().map(|&i| sub(&points[i], base)).collect();
+        // the generalized cross product of the differences
+        let mut normal: Point = (0..d)
+            .map(|j| {
+                let minor = Matrix::from_fn(d - 1, d - 1, |r, c| diffs[r][if c < j { c } else { c + 1 }]).determinant();
+                if j % 2 == 0 {
+                    minor
+                } else {
+                    -minor
+                }
+            })
+            .collect();
+        let g = normal.iter().fold(0, |g, a| g.gcd(a));
+        if g == 0 {
+            continue;
+        }
+        normal.iter_mut().for_each(|a| *a /= g);
+        let offset = dot(&normal, base);
+        if points.iter().all(|p| dot(&normal, p) <= offset) {
+            res.insert((normal, offset));
+        } else if points.iter().all(|p| dot(&normal, p) >= offset) {
+            res.insert((normal.iter().map(|a| -a).collect(), -offset));
+        }
+    }
+    res.into_iter().collect()
+}
+
+/// The `d` dimensional volume of the convex hull of the non-empty `points`
+/// in `R^d`, from the pyramids over the facets with a common apex.
+fn hull_volume(points: &[Point]) -> BigRational {
+    let d = points[0].len();
+    if d == 0 {
+        return BigRational::one();
+    }
+    if hull_coordinates(points).len() < d {
+        return BigRational::zero();
+    }
+    let apex = &points[0];
+    let mut total = BigRational::zero();
+    for (normal, offset) in facets(points) {
+        let height = offset - dot(&normal, apex);
+        if height == 0 {
+            continue;
+        }
+        // dropping a coordinate in which the normal is non-zero scales the
+        // facet by `|normal[j]| / |normal|`, and the height is
+        // `height / |normal|`
+        let j = normal.iter().position(|&a| a != 0).unwrap();
+        let face: Vec<Point> = points
+            .iter()
+            .filter(|p| dot(&normal, p) == offset)
+            .map(|p| p.iter().enumerate().filter(|&(i, _)| i != j).map(|(_, &x)| x).collect())
+            .collect();
+        total += hull_volume(&face) * BigRational::new(height.into(), normal[j].abs().into());
+    }
+    total / BigRational::from_integer(d.into())
+}
+
+/// The vertices of the convex hull of `points`, sorted.
+///
+/// A point of the polytope is a vertex when the normals of the facets
+/// containing it span the directions of the affine hull.
+///
+/// # Panics
+///
+/// Panics if the points have different numbers of coordinates.
+///
+/// # Example
+///
+/// ```
+/// # use math2::polytope::vertices;
+/// let square = [[0, 0], [1, 0], [2, 0], [1, 1], [0, 2], [2, 2], [2, 1]].map(|p| p.to_vec());
+/// assert_eq!(vec![vec![0, 0], vec![0, 2], vec![2, 0], vec![2, 2]], vertices(&square));
+/// let segment = [[0, 0, 0], [1, 1, 1], [3, 3, 3]].map(|p| p.to_vec());
+/// assert_eq!(vec![vec![0, 0, 0], vec![3, 3, 3]], vertices(&segment));
+/// ```
+pub fn vertices(points: &[Vec<i64>]) -> Vec<Vec<i64>> {
+    let points = distinct(points);
+    if points.len() <= 1 {
+        return points;
+    }
+    let coords = hull_coordinates(&points);
+    let projected: Vec<Point> = points.iter().map(|p| coords.iter().map(|&j| p[j]).collect()).collect();
+    let facets = facets(&projected);
+    points
+        .into_iter()
+        .zip(&projected)
+        .filter(|(_, p)| {
+            let normals = facets.iter().filter(|(a, b)| dot(a, p) == *b).map(|(a, _)| a.clone()).collect();
+            Matrix::from_rows(normals).rank() == coords.len()
+        })
+        .map(|(p, _)| p)
+        .collect()
+}
+
+/// The volume of the convex hull of `points` in `R^d`, where `d` is their
+/// number of coordinates. It is zero for polytopes of smaller dimension.
+///
+/// # Panics
+///
+/// Panics if the points have different numbers of coordinates.
+///
+/// # Example
+///
+/// ```
+/// # use math2::polytope::volume;
+/// # use num::BigRational;
+/// let simplex = [[0, 0, 0], [1, 0, 0], [0, 1, 0], [0, 0, 1]].map(|p| p.to_vec());
+/// assert_eq!(BigRational::new(1.into(), 6.into()), volume(&simplex));
+/// ```
+pub fn volume(points: &[Vec<i64>]) -> BigRational {
+    let points = distinct(points);
+    if points.is_empty() {
+        return BigRational::zero();
+    }
+    hull_volume(&points)
+}
+
+/// The vertices of the Minkowski sum `{a + b}` of the convex hulls of `a`
+/// and `b`, sorted.
+///
+/// # Panics
+///
+/// Panics if the points have different numbers of coordinates.
+pub fn minkowski_sum(a: &[Vec<i64>], b: &[Vec<i64>]) -> Vec<Vec<i64>> {
+    let (a, b) = (vertices(a), vertices(b));
+    let sums: Vec<Point> = a
+        .iter()
+        .flat_map(|p| b.iter().map(move |q| p.iter().zip(q).map(|(x, y)| x + y).collect()))
+        .collect();
+    vertices(&sums)
+}
+
+/// The mixed volume of `n` polytopes in `R^n`, normalized so that the mixed
+/// volume of `n` copies of `P` is `n! vol(P)`.
+///
+/// It is the alternating sum of the volumes of the Minkowski sums of all
+/// subsets of the polytopes, `sum (-1)^(n - |S|) vol(sum_(i in S) P_i)`, so
+/// this takes `2^n` volume computations.
+///
+/// # Panics
+///
+/// Panics if a polytope is empty or a point does not have `n` coordinates.
+///
+/// # Example
+///
+/// ```
+/// # use math2::polytope::mixed_volume;
+/// # use num::BigInt;
+/// // a square and a triangle in the plane
+/// let square = [[0, 0], [1, 0], [0, 1], [1, 1]].map(|p| p.to_vec()).to_vec();
+/// let triangle = [[0, 0], [1, 0], [0, 1]].map(|p| p.to_vec()).to_vec();
+/// assert_eq!(BigInt::from(2), mixed_volume(&[square.clone(), triangle]));
+/// assert_eq!(BigInt::from(2), mixed_volume(&[square.clone(), square]));
+/// ```
+pub fn mixed_volume(polytopes: &[Vec<Vec<i64>>]) -> BigInt {
+    let n = polytopes.len();
+    assert!(polytopes.iter().all(|p| !p.is_empty()), "polytopes must not be empty");
+    assert!(
+        polytopes.iter().flatten().all(|p| p.len() == n),
+        "the points of n polytopes must have n coordinates"
+    );
+    let polytopes: Vec<_> = polytopes.iter().map(|p| vertices(p)).collect();
+    // the sums of the subsets, indexed by bit masks
+    let mut sums: Vec<Vec<Point>> = vec![vec![vec![0; n]]];
+    let mut total = BigRational::zero();
+    for s in 0..1usize << n {
+        if s > 0 {
+            let sum = minkowski_sum(&sums[s & (s - 1)], &polytopes[s.trailing_zeros() as usize]);
+            sums.push(sum);
+        }
+        let v = hull_volume(&sums[s]);
+        if (n - s.count_ones() as usize).is_multiple_of(2) {
+            total += v;
+        } else {
+            total -= v;
+        }
+    }
+    assert!(total.is_integer(), "mixed volumes of lattice polytopes are integers");
+    total.to_integer()
+}
+
+/// The vertices of the Newton polytope of `p` in `R^n`, the convex hull of
+/// the exponent vectors of its terms, sorted.
+///
+/// # Panics
+///
+/// Panics if `p` uses a variable `x_i` with `i >= n`.
+pub fn newton_polytope<Ring: CommutativeRing>(p: &MultiPolynomial<Ring>, n: usize) -> Vec<Vec<i64>> {
+    assert!(p.num_vars() <= n, "polynomial uses more than n variables");
+    let support: Vec<Point> = p
+        .terms()
+        .map(|(exponents, _)| (0..n).map(|i| exponents.get(i).map_or(0, |&e| e as i64)).collect())
+        .collect();
+    vertices(&support)
+}
+
+fn newton_polytopes<Ring: CommutativeRing>(system: &[MultiPolynomial<Ring>]) -> Vec<Vec<Point>> {
+    let n = system.len();
+    assert!(system.iter().all(|p| !p.is_zero()), "polynomials must not be zero");
+    system.iter().map(|p| newton_polytope(p, n)).collect()
+}
+
+/// Bernstein's bound on the number of isolated solutions with non-zero
+/// coordinates of a system of `n` polynomials in `n` variables: the mixed
+/// volume of their Newton polytopes.
+///
+/// It is attained for generic coefficients, and is often much smaller than
+/// the Bézout bound, the product of the total degrees, for sparse systems.
+///
+/// # Panics
+///
+/// Panics if a polynomial is zero or uses a variable `x_i` with `i >= n`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multivariate::MultiPolynomial;
+/// # use math2::polytope::bernstein_bound;
+/// # use num::BigInt;
+/// let x = || MultiPolynomial::<i64>::var(0);
+/// let y = || MultiPolynomial::<i64>::var(1);
+/// let c = MultiPolynomial::constant;
+/// // two quadrics meet in up to four points, but bilinear ones in only two
+/// let f = x() * y() + x() + y() - c(1);
+/// let g = x() * y().scalar_mul(2) - x() + y().scalar_mul(3) + c(1);
+/// assert_eq!(BigInt::from(2), bernstein_bound(&[f, g]));
+/// ```
+pub fn bernstein_bound<Ring: CommutativeRing>(system: &[MultiPolynomial<Ring>]) -> BigInt {
+    mixed_volume(&newton_polytopes(system))
+}
+
+/// The bound of Li and Wang on the number of isolated solutions in `C^n`
+/// of a system of `n` polynomials in `n` variables, including those with
+/// zero coordinates: the mixed volume of the Newton polytopes with the
+/// origin added.
+///
+/// # Panics
+///
+/// Panics if a polynomial is zero or uses a variable `x_i` with `i >= n`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multivariate::MultiPolynomial;
+/// # use math2::polytope::{affine_bernstein_bound, bernstein_bound};
+/// # use num::BigInt;
+/// let x = || MultiPolynomial::<i64>::var(0);
+/// // x^2 - x = 0 at 0 and 1, and only 1 is in the torus
+/// let system = [x() * x() - x()];
+/// assert_eq!(BigInt::from(1), bernstein_bound(&system));
+/// assert_eq!(BigInt::from(2), affine_bernstein_bound(&system));
+/// ```
+pub fn affine_bernstein_bound<Ring: CommutativeRing>(system: &[MultiPolynomial<Ring>]) -> BigInt {
+    let n = system.len();
+    let polytopes: Vec<Vec<Point>> = newton_polytopes(system)
+        .into_iter()
+        .map(|mut p| {
+            p.push(vec![0; n]);
+            p
+        })
+        .collect();
+    mixed_volume(&polytopes)
+}
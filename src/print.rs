@@ -1,9 +1,11 @@
 use std::fmt::{self, Display};
 
-use num::Signed;
+use num::{BigInt, BigRational, Signed, Zero};
 
 use crate::factorization::SquareFreeFactorization;
-use crate::traits::CommutativeRing;
+use crate::piecewise::PiecewisePolynomial;
+use crate::rational_function::RationalFunction;
+use crate::traits::{CommutativeRing, Field};
 use crate::Polynomial;
 
 pub trait PrintableCoeff: Display + CommutativeRing + PartialEq + Signed {}
@@ -27,7 +29,7 @@ fn print_as_factor(x: &impl PrintableCoeff, f: &mut fmt::Formatter<'_>) -> fmt::
 impl<T: PrintableCoeff> Polynomial<T> {
     pub fn print_with_var<'a>(&'a self, var: &'a str) -> PrintWithVar<'a, Polynomial<T>> {
         PrintWithVar {
-            var: var.into(),
+            var,
             thing: self,
         }
     }
@@ -39,7 +41,28 @@ impl<T: PrintableCoeff> SquareFreeFactorization<T> {
         var: &'a str,
     ) -> PrintWithVar<'a, SquareFreeFactorization<T>> {
         PrintWithVar {
-            var: var.into(),
+            var,
+            thing: self,
+        }
+    }
+}
+
+impl<T: PrintableCoeff + PartialOrd> PiecewisePolynomial<T> {
+    pub fn print_with_var<'a>(
+        &'a self,
+        var: &'a str,
+    ) -> PrintWithVar<'a, PiecewisePolynomial<T>> {
+        PrintWithVar {
+            var,
+            thing: self,
+        }
+    }
+}
+
+impl<T: PrintableCoeff + Field> RationalFunction<T> {
+    pub fn print_with_var<'a>(&'a self, var: &'a str) -> PrintWithVar<'a, RationalFunction<T>> {
+        PrintWithVar {
+            var,
             thing: self,
         }
     }
@@ -101,3 +124,222 @@ impl<T: PrintableCoeff> Display for PrintWithVar<'_, SquareFreeFactorization<T>>
         Ok(())
     }
 }
+
+/// Prints `p / q`, with parentheses around sums, or just `p` if `q` is `1`.
+impl<T: PrintableCoeff + Field> Display for PrintWithVar<'_, RationalFunction<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (numerator, denominator) = (self.thing.numerator(), self.thing.denominator());
+        if numerator.is_zero() {
+            return f.write_str("0");
+        }
+        let write = |p: &Polynomial<T>, f: &mut fmt::Formatter<'_>| {
+            if p.coeffs.iter().filter(|c| !c.is_zero()).count() > 1 {
+                write!(f, "({})", p.print_with_var(self.var))
+            } else {
+                write!(f, "{}", p.print_with_var(self.var))
+            }
+        };
+        if denominator.degree() == Some(0) {
+            return write!(f, "{}", numerator.print_with_var(self.var));
+        }
+        write(numerator, f)?;
+        f.write_str(" / ")?;
+        write(denominator, f)
+    }
+}
+
+/// Prints one piece per line, as `p(x) for a <= x < b`.
+impl<T: PrintableCoeff + PartialOrd> Display for PrintWithVar<'_, PiecewisePolynomial<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let breakpoints = self.thing.breakpoints();
+        let pieces = self.thing.pieces();
+        for (i, piece) in pieces.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            if piece.is_zero() {
+                f.write_str("0")?;
+            } else {
+                write!(f, "{}", piece.print_with_var(self.var))?;
+            }
+            let upper = if i + 1 == pieces.len() { "<=" } else { "<" };
+            write!(
+                f,
+                " for {} <= {} {upper} {}",
+                breakpoints[i],
+                self.var,
+                breakpoints[i + 1]
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Coefficients that can be written in LaTeX.
+pub trait LatexCoeff: PrintableCoeff {
+    /// Writes the absolute value of this coefficient.
+    fn fmt_latex_abs(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.abs())
+    }
+}
+
+impl LatexCoeff for i64 {}
+impl LatexCoeff for f64 {}
+impl LatexCoeff for BigInt {}
+
+/// Fractions are written as `\frac{p}{q}`.
+impl LatexCoeff for BigRational {
+    fn fmt_latex_abs(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer().abs())
+        } else {
+            write!(f, "\\frac{{{}}}{{{}}}", self.numer().abs(), self.denom())
+        }
+    }
+}
+
+impl<T: LatexCoeff> Polynomial<T> {
+    /// Prints this polynomial as LaTeX math, as in `3x^{12} - \frac{2}{5}x + 1`.
+    pub fn print_latex<'a>(&'a self, var: &'a str) -> PrintLatex<'a, Polynomial<T>> {
+        PrintLatex {
+            var,
+            thing: self,
+        }
+    }
+}
+
+impl<T: LatexCoeff> SquareFreeFactorization<T> {
+    pub fn print_latex<'a>(&'a self, var: &'a str) -> PrintLatex<'a, SquareFreeFactorization<T>> {
+        PrintLatex {
+            var,
+            thing: self,
+        }
+    }
+}
+
+pub struct PrintLatex<'a, F> {
+    var: &'a str,
+    thing: &'a F,
+}
+
+impl<T: LatexCoeff> Display for PrintLatex<'_, Polynomial<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        let Self { var, .. } = self;
+        for (degree, coeff) in self.thing.coeffs.iter().enumerate().rev() {
+            if coeff.is_zero() {
+                continue;
+            }
+            if first {
+                if coeff.is_negative() {
+                    f.write_str("-")?;
+                }
+            } else {
+                f.write_str(if coeff.is_negative() { " - " } else { " + " })?;
+            }
+            first = false;
+
+            if degree == 0 || !coeff.abs().is_one() {
+                coeff.fmt_latex_abs(f)?;
+            }
+            match degree {
+                0 => {}
+                1 => write!(f, "{var}")?,
+                _ => write!(f, "{var}^{{{degree}}}")?,
+            }
+        }
+        if first {
+            f.write_str("0")?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: LatexCoeff> Display for PrintLatex<'_, SquareFreeFactorization<T>> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lc = &self.thing.leading_coeff;
+        if lc.is_negative() {
+            f.write_str("-")?;
+        }
+        if !lc.abs().is_one() || self.thing.factors.is_empty() {
+            lc.fmt_latex_abs(f)?;
+        }
+        for (poly, exp) in &self.thing.factors {
+            write!(f, "\\left({}\\right)", poly.print_latex(self.var))?;
+            if exp.get() > 1 {
+                write!(f, "^{{{exp}}}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display + CommutativeRing + PartialEq> Polynomial<T> {
+    /// Compares this polynomial with `other` term by term, like a diff from
+    /// `self` to `other`.
+    ///
+    /// Terms where the coefficients differ are shown as a `-` line with the
+    /// coefficient in `self` and a `+` line with the one in `other`, from the
+    /// highest degree down. Runs of equal terms are collapsed into a count,
+    /// and identical polynomials print `no differences`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// let p = Polynomial::new(vec![1, 0, 5, 2, 0, 0, 3]);
+    /// let q = Polynomial::new(vec![1, 0, 4, 2, 0, 0, 3, 1]);
+    /// let expected = "\
+    /// - x^7: 0
+    /// + x^7: 1
+    ///   (2 equal terms)
+    /// - x^2: 5
+    /// + x^2: 4
+    ///   (1 equal term)
+    /// ";
+    /// assert_eq!(expected, p.diff_display(&q).to_string());
+    /// ```
+    pub fn diff_display<'a>(&'a self, other: &'a Self) -> DiffDisplay<'a, T> {
+        DiffDisplay { old: self, new: other }
+    }
+}
+
+/// The result of [`Polynomial::diff_display`].
+pub struct DiffDisplay<'a, T> {
+    old: &'a Polynomial<T>,
+    new: &'a Polynomial<T>,
+}
+
+impl<T: Display + CommutativeRing + PartialEq> Display for DiffDisplay<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn flush_equal(equal: &mut usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match std::mem::take(equal) {
+                0 => Ok(()),
+                1 => writeln!(f, "  (1 equal term)"),
+                n => writeln!(f, "  ({n} equal terms)"),
+            }
+        }
+        let zero = T::zero();
+        let len = self.old.coeffs.len().max(self.new.coeffs.len());
+        let mut equal = 0;
+        let mut differences = false;
+        for degree in (0..len).rev() {
+            let old = self.old.coeffs.get(degree).unwrap_or(&zero);
+            let new = self.new.coeffs.get(degree).unwrap_or(&zero);
+            if old == new {
+                // only count the terms that are actually there
+                equal += usize::from(!old.is_zero());
+                continue;
+            }
+            flush_equal(&mut equal, f)?;
+            differences = true;
+            writeln!(f, "- x^{degree}: {old}")?;
+            writeln!(f, "+ x^{degree}: {new}")?;
+        }
+        if differences {
+            flush_equal(&mut equal, f)
+        } else {
+            f.write_str("no differences")
+        }
+    }
+}
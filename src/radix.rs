@@ -0,0 +1,83 @@
+//! Integers written in a base, as digit polynomials.
+//!
+//! The digit polynomial of `n` in base `b` has the digits of `n` as its
+//! coefficients, so evaluating it at `b` gives back `n`. Going the other way,
+//! evaluating any integer polynomial at a large enough power of two packs its
+//! coefficients into one integer, which is Kronecker substitution.
+
+use num::{BigInt, Integer, One, Signed, Zero};
+
+use crate::Polynomial;
+
+fn assert_base(base: &BigInt) {
+    assert!(base > &BigInt::one(), "base must be at least 2");
+}
+
+/// The digits of `|n|` in base `base`, least significant first. Zero has no
+/// digits.
+fn digits(n: &BigInt, base: &BigInt) -> Vec<BigInt> {
+    assert_base(base);
+    let mut n = n.abs();
+    let mut res = Vec::new();
+    while !n.is_zero() {
+        let (q, r) = n.div_rem(base);
+        res.push(r);
+        n = q;
+    }
+    res
+}
+
+/// Returns the polynomial whose coefficients are the base-`base` digits of
+/// `n`. For negative `n` every digit is negated, so that evaluating the result
+/// at `base` always gives `n`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::radix::{digit_polynomial, from_digit_polynomial};
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// let ten = BigInt::from(10);
+/// let p = digit_polynomial(&BigInt::from(-305), &ten);
+/// assert_eq!(Polynomial::new([-5, 0, -3].map(BigInt::from).to_vec()), p);
+/// assert_eq!(BigInt::from(-305), from_digit_polynomial(&p, &ten));
+/// ```
+pub fn digit_polynomial(n: &BigInt, base: &BigInt) -> Polynomial<BigInt> {
+    let mut coeffs = digits(n, base);
+    if n.is_negative() {
+        for c in &mut coeffs {
+            *c = -&*c;
+        }
+    }
+    Polynomial::new(coeffs)
+}
+
+/// Evaluates `p` at `base`. The coefficients do not need to be digits, which
+/// is what makes this usable for Kronecker substitution.
+pub fn from_digit_polynomial(p: &Polynomial<BigInt>, base: &BigInt) -> BigInt {
+    p.coeffs
+        .iter()
+        .rev()
+        .fold(BigInt::zero(), |acc, c| acc * base + c)
+}
+
+/// The sum of the base-`base` digits of `|n|`.
+pub fn digit_sum(n: &BigInt, base: &BigInt) -> BigInt {
+    digits(n, base).into_iter().sum()
+}
+
+/// Returns whether `|n|` reads the same forwards and backwards in base `base`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::radix::is_palindrome;
+/// # use num::BigInt;
+/// // 0b1001
+/// assert!(is_palindrome(&BigInt::from(9), &BigInt::from(2)));
+/// assert!(!is_palindrome(&BigInt::from(10), &BigInt::from(10)));
+/// ```
+pub fn is_palindrome(n: &BigInt, base: &BigInt) -> bool {
+    let digits = digits(n, base);
+    digits.iter().eq(digits.iter().rev())
+}
@@ -0,0 +1,178 @@
+//! Pseudo-random matrices with prescribed properties, for examples and for
+//! testing the linear algebra.
+//!
+//! Every generator is deterministic in its `seed`, and builds its matrix as
+//! a product of factors whose determinant, rank or singular values are
+//! known, so the entries stay small.
+
+use num::{BigInt, BigRational, One, Zero};
+
+use crate::linalg::Matrix;
+use crate::traits::{Field, FromUsize, Semiring};
+
+/// The SplitMix64 generator.
+struct SplitMix(u64);
+
+impl SplitMix {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// An integer in `-2..=2`.
+    fn small(&mut self) -> i64 {
+        (self.next() % 5) as i64 - 2
+    }
+
+    /// A permutation of `0..n`, by Fisher–Yates shuffling.
+    fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut perm: Vec<usize> = (0..n).collect();
+        for i in (1..n).rev() {
+            perm.swap(i, (self.next() % (i as u64 + 1)) as usize);
+        }
+        perm
+    }
+}
+
+fn from_small<F: Field + FromUsize>(x: i64) -> F {
+    let magnitude = F::from_usize(x.unsigned_abs() as usize);
+    if x < 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// `L U` for unit triangular `L` and `U` with small entries, which is
+/// invertible over any ring as its determinant is `1`.
+fn unit_lu<R: Semiring>(n: usize, rng: &mut SplitMix, small: impl Fn(i64) -> R) -> (Matrix<R>, Matrix<R>) {
+    let mut triangular = |lower: bool| {
+        Matrix::from_fn(n, n, |i, j| match i.cmp(&j) {
+            std::cmp::Ordering::Equal => R::one(),
+            std::cmp::Ordering::Greater if lower => small(rng.small()),
+            std::cmp::Ordering::Less if !lower => small(rng.small()),
+            _ => R::zero(),
+        })
+    };
+    let l = triangular(true);
+    let u = triangular(false);
+    (l, u)
+}
+
+/// An `n x n` integer matrix with determinant `1` or `-1`, so that its
+/// inverse is an integer matrix too.
+///
+/// This is the product of unit lower and upper triangular matrices with
+/// entries between `-2` and `2`, with its rows permuted and some of them
+/// negated.
+///
+/// # Example
+///
+/// ```
+/// # use math2::random::unimodular;
+/// # use num::{BigInt, Signed};
+/// let a = unimodular(5, 42);
+/// assert_eq!(BigInt::from(1), a.determinant().abs());
+/// ```
+pub fn unimodular(n: usize, seed: u64) -> Matrix<BigInt> {
+    let mut rng = SplitMix(seed);
+    let (l, u) = unit_lu(n, &mut rng, BigInt::from);
+    let a = l * u;
+    let perm = rng.permutation(n);
+    let signs: Vec<bool> = (0..n).map(|_| rng.next() & 1 == 1).collect();
+    Matrix::from_fn(n, n, |i, j| {
+        let x = a[(perm[i], j)].clone();
+        if signs[i] {
+            -x
+        } else {
+            x
+        }
+    })
+}
+
+/// A `rows x cols` matrix of rank `rank` over a field.
+///
+/// It is `B C`, with `B` the first `rank` columns of an invertible
+/// `rows x rows` matrix and `C` the first `rank` rows of an invertible
+/// `cols x cols` matrix, so both have full rank `rank`.
+///
+/// # Panics
+///
+/// Panics if `rank` is more than `rows` or `cols`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::random::with_rank;
+/// # use num::BigRational;
+/// let a = with_rank::<BigRational>(4, 6, 3, 7);
+/// assert_eq!((4, 6), (a.num_rows(), a.num_cols()));
+/// assert_eq!(3, a.rank());
+/// ```
+pub fn with_rank<F: Field + FromUsize>(rows: usize, cols: usize, rank: usize, seed: u64) -> Matrix<F> {
+    assert!(rank <= rows.min(cols), "rank is larger than the dimensions");
+    let mut rng = SplitMix(seed);
+    let (l, u) = unit_lu(rows, &mut rng, from_small::<F>);
+    let left = l * u;
+    let (l, u) = unit_lu(cols, &mut rng, from_small::<F>);
+    let right = l * u;
+    let b = Matrix::from_fn(rows, rank, |i, j| left[(i, j)].clone());
+    let c = Matrix::from_fn(rank, cols, |i, j| right[(i, j)].clone());
+    b * c
+}
+
+/// An `n x n` rational matrix whose singular values are all `1` or `2`, so
+/// that its condition number is at most `2`.
+///
+/// It is `Q_1 D Q_2` for rational orthogonal matrices `Q_1` and `Q_2` and a
+/// diagonal `D` of ones and twos. The orthogonal matrices are Cayley
+/// transforms `(I + S)^-1 (I - S)` of skew-symmetric integer matrices `S`,
+/// as `I + S` is always invertible.
+///
+/// # Example
+///
+/// ```
+/// # use math2::random::well_conditioned;
+/// # use num::{BigRational, Signed};
+/// let a = well_conditioned(4, 1);
+/// // the determinant of Q_1 D Q_2 is a product of ones and twos, up to sign
+/// let det = a.determinant_gaussian().abs();
+/// assert!([1, 2, 4, 8, 16].map(|d| BigRational::from_integer(d.into())).contains(&det));
+/// ```
+pub fn well_conditioned(n: usize, seed: u64) -> Matrix<BigRational> {
+    let mut rng = SplitMix(seed);
+    let mut orthogonal = || {
+        let mut s = Matrix::<BigRational>::zero(n, n);
+        for i in 0..n {
+            for j in i + 1..n {
+                let x = BigRational::from_integer(rng.small().into());
+                s[(j, i)] = -x.clone();
+                s[(i, j)] = x;
+            }
+        }
+        let plus = Matrix::identity(n) + s.clone();
+        let minus = Matrix::identity(n) - s;
+        let columns: Vec<Vec<BigRational>> = (0..n)
+            .map(|j| {
+                let column: Vec<BigRational> = (0..n).map(|i| minus[(i, j)].clone()).collect();
+                plus.solve(&column).expect("I + S is invertible for skew-symmetric S")
+            })
+            .collect();
+        Matrix::from_fn(n, n, |i, j| columns[j][i].clone())
+    };
+    let q1 = orthogonal();
+    let q2 = orthogonal();
+    let d = Matrix::from_fn(n, n, |i, j| {
+        if i != j {
+            BigRational::zero()
+        } else if rng.next() & 1 == 1 {
+            BigRational::from_integer(2.into())
+        } else {
+            BigRational::one()
+        }
+    });
+    q1 * d * q2
+}
@@ -0,0 +1,279 @@
+//! Rational functions over a field.
+
+use std::num::NonZeroUsize;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use num::{One, Zero};
+
+use crate::traits::{AssertUnit, CheckedInv, CoefficientDomain, CommutativeRing, Field, FromUsize};
+use crate::Polynomial;
+
+/// A quotient `p / q` of polynomials, an element of the field `F(x)`.
+///
+/// The fraction is kept reduced: `p` and `q` are coprime and `q` is monic,
+/// so equal functions have equal representations. Zero is `0 / 1`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RationalFunction<F> {
+    numerator: Polynomial<F>,
+    denominator: Polynomial<F>,
+}
+
+impl<F: Field> RationalFunction<F> {
+    /// Reduces `numerator / denominator`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `denominator` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::rational_function::RationalFunction;
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let p = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+    /// // (2x^2 - 2) / (2x + 2) = (x - 1) / 1
+    /// let f = RationalFunction::new(p("2x^2 - 2"), p("2x + 2"));
+    /// assert_eq!(&p("x - 1"), f.numerator());
+    /// assert_eq!(&p("1"), f.denominator());
+    /// ```
+    pub fn new(numerator: Polynomial<F>, denominator: Polynomial<F>) -> Self {
+        assert!(!denominator.is_zero(), "zero denominator");
+        if numerator.is_zero() {
+            return Self::zero();
+        }
+        let g = numerator.clone().gcd(denominator.clone());
+        let numerator = numerator.div_rem(g.clone()).0;
+        let denominator = denominator.div_rem(g).0;
+        let lc = denominator.leading_coefficient_cloned().checked_inv().unwrap();
+        Self {
+            numerator: numerator.scalar_mul(lc.clone()),
+            denominator: denominator.scalar_mul(lc),
+        }
+    }
+
+    pub fn from_polynomial(p: Polynomial<F>) -> Self {
+        Self {
+            numerator: p,
+            denominator: Polynomial::one(),
+        }
+    }
+
+    pub fn numerator(&self) -> &Polynomial<F> {
+        &self.numerator
+    }
+
+    /// The monic denominator.
+    pub fn denominator(&self) -> &Polynomial<F> {
+        &self.denominator
+    }
+
+    pub fn into_parts(self) -> (Polynomial<F>, Polynomial<F>) {
+        (self.numerator, self.denominator)
+    }
+
+    /// Evaluates at `x`. `None` if `x` is a pole.
+    pub fn eval(&self, x: &F) -> Option<F> {
+        let den = self.denominator.eval(x);
+        (!den.is_zero()).then(|| self.numerator.eval(x) / den)
+    }
+}
+
+/// A term `numerator / factor^power` of a partial fraction decomposition,
+/// with `deg numerator < deg factor`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PartialFraction<F> {
+    pub numerator: Polynomial<F>,
+    pub factor: Polynomial<F>,
+    pub power: NonZeroUsize,
+}
+
+/// The result of [`RationalFunction::partial_fractions`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PartialFractions<F> {
+    /// The quotient of the numerator by the denominator.
+    pub polynomial: Polynomial<F>,
+    /// The proper part, grouped by factor in the order of the square free
+    /// factorization and then by increasing power.
+    pub terms: Vec<PartialFraction<F>>,
+}
+
+impl<F: Field + FromUsize + PartialEq + CoefficientDomain> RationalFunction<F> {
+    /// Decomposes this function into a polynomial plus a sum of terms
+    /// `a(x) / q(x)^k`, where the `q` are the factors of the square free
+    /// factorization of the denominator and `deg a < deg q`.
+    ///
+    /// The denominator `q_1^k_1 ... q_m^k_m` is split into coprime prime
+    /// powers, the numerator of each `q_i^k_i` is found with the inverse of
+    /// the other powers modulo it, and then expanded in powers of `q_i`.
+    /// Factors are only separated as far as the square free factorization
+    /// goes, so `1 / (x^2 - 1)` is a single term.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::rational_function::RationalFunction;
+    /// # use math2::Polynomial;
+    /// # use num::{BigRational, Zero};
+    /// let p = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+    /// // (x^2 - x + 1) / (x^2 (x + 1)) = 3 / (x + 1) - 2 / x + 1 / x^2
+    /// let f = RationalFunction::new(p("x^2 - x + 1"), p("x^3 + x^2"));
+    /// let decomposition = f.partial_fractions();
+    /// assert!(decomposition.polynomial.is_zero());
+    /// let terms: Vec<_> = decomposition
+    ///     .terms
+    ///     .iter()
+    ///     .map(|t| (t.numerator.clone(), t.factor.clone(), t.power.get()))
+    ///     .collect();
+    /// assert_eq!(vec![(p("3"), p("x + 1"), 1), (p("-2"), p("x"), 1), (p("1"), p("x"), 2)], terms);
+    /// ```
+    pub fn partial_fractions(&self) -> PartialFractions<F> {
+        let (polynomial, remainder) = self.numerator.clone().div_rem(self.denominator.clone());
+        let sqf = self.denominator.clone().square_free_factorization();
+        // the denominator is lc * prod(q_i^k_i)
+        let remainder = remainder.scalar_mul(sqf.leading_coeff.checked_inv().unwrap());
+        let powers: Vec<Polynomial<F>> = sqf
+            .factors
+            .iter()
+            .map(|(q, k)| (0..k.get()).map(|_| q.clone()).product())
+            .collect();
+        let mut terms = Vec::new();
+        for (i, (q, k)) in sqf.factors.iter().enumerate() {
+            let others: Polynomial<F> = powers
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| j != i)
+                .map(|(_, p)| p.clone())
+                .product();
+            // s others = 1 (mod q^k), so r / prod = r s / q^k + ... modulo the others
+            let (_, s, _) = others.extended_gcd(powers[i].clone());
+            let mut c = (remainder.clone() * s).div_rem(powers[i].clone()).1;
+            // c = a_0 + a_1 q + ... + a_(k-1) q^(k-1), and a_j q^j / q^k = a_j / q^(k-j)
+            let mut digits = Vec::with_capacity(k.get());
+            for _ in 0..k.get() {
+                let (quotient, digit) = c.div_rem(q.clone());
+                digits.push(digit);
+                c = quotient;
+            }
+            for (j, numerator) in digits.into_iter().enumerate().rev() {
+                if !numerator.is_zero() {
+                    terms.push(PartialFraction {
+                        numerator,
+                        factor: q.clone(),
+                        power: NonZeroUsize::new(k.get() - j).unwrap(),
+                    });
+                }
+            }
+        }
+        PartialFractions { polynomial, terms }
+    }
+}
+
+impl<F: Field> Zero for RationalFunction<F> {
+    fn zero() -> Self {
+        Self::from_polynomial(Polynomial::zero())
+    }
+    fn is_zero(&self) -> bool {
+        self.numerator.is_zero()
+    }
+}
+
+impl<F: Field> One for RationalFunction<F> {
+    fn one() -> Self {
+        Self::from_polynomial(Polynomial::one())
+    }
+}
+
+impl<F: Field> Add for RationalFunction<F> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        let numerator = self.numerator * rhs.denominator.clone() + rhs.numerator * self.denominator.clone();
+        Self::new(numerator, self.denominator * rhs.denominator)
+    }
+}
+
+impl<F: Field> Neg for RationalFunction<F> {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            numerator: -self.numerator,
+            denominator: self.denominator,
+        }
+    }
+}
+
+impl<F: Field> Sub for RationalFunction<F> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+impl<F: Field> Mul for RationalFunction<F> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self::new(self.numerator * rhs.numerator, self.denominator * rhs.denominator)
+    }
+}
+
+/// # Panics
+///
+/// Panics on division by zero.
+impl<F: Field> Div for RationalFunction<F> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        self * rhs.checked_inv().expect("division by zero")
+    }
+}
+
+impl<F: Field> CheckedInv for RationalFunction<F> {
+    fn checked_inv(&self) -> Option<Self> {
+        (!self.is_zero()).then(|| Self::new(self.denominator.clone(), self.numerator.clone()))
+    }
+}
+
+impl<F: Field> CommutativeRing for RationalFunction<F> {
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        x.0.checked_inv().unwrap().assert_is_unit()
+    }
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(!self.is_zero());
+        AssertUnit(self)
+    }
+    fn is_nilpotent(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+/// `F(x)` has the characteristic of `F`, but unlike a finite field it is not
+/// perfect: `x` has no `p`th root.
+impl<F: Field> Field for RationalFunction<F> {
+    fn characteristic() -> usize {
+        F::characteristic()
+    }
+    /// In characteristic zero this is `self`, like the default.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this is not a `p`th power, which happens exactly when some
+    /// exponent in the numerator or denominator is not a multiple of `p`.
+    fn pth_root(self) -> Self {
+        let p = F::characteristic();
+        if p == 0 {
+            return self;
+        }
+        let root = |q: Polynomial<F>| {
+            assert!(
+                q.coeffs.iter().enumerate().all(|(i, c)| i % p == 0 || c.is_zero()),
+                "not a pth power"
+            );
+            Polynomial::new(q.coeffs.into_iter().step_by(p).map(F::pth_root).collect())
+        };
+        Self {
+            numerator: root(self.numerator),
+            denominator: root(self.denominator),
+        }
+    }
+}
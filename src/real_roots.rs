@@ -0,0 +1,711 @@
+//! Counting and isolating the real roots of rational polynomials exactly.
+
+use std::cmp::Ordering;
+use std::ops::Neg;
+
+use num::{BigInt, BigRational, One, Signed, Zero};
+
+use crate::interval::Interval;
+use crate::resultant::image_polynomial;
+use crate::Polynomial;
+
+/// The number of sign changes in a sequence, ignoring zeros.
+fn variations(signs: impl Iterator<Item = Ordering>) -> usize {
+    let mut last = None;
+    let mut count = 0;
+    for sign in signs.filter(|&s| s != Ordering::Equal) {
+        if last.is_some_and(|l| l != sign) {
+            count += 1;
+        }
+        last = Some(sign);
+    }
+    count
+}
+
+/// The Sturm sequence `p_0 = f, p_1 = f', p_(i + 1) = -(p_(i - 1) mod p_i)`
+/// of a polynomial, which ends with a gcd `g` of `f` and `f'`.
+///
+/// By Sturm's theorem, if `V(x)` is the number of sign changes of the
+/// sequence at `x`, the number of distinct roots of `f` in `(a, b]` is
+/// `V(a) - V(b)`. When `f` has repeated roots, every `p_i` vanishes at the
+/// roots of `g`, so the sequence is divided by `g`. That gives the Sturm
+/// sequence of the square free part `f / g`, which has the same roots.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SturmSequence {
+    polynomials: Vec<Polynomial<BigRational>>,
+}
+
+impl SturmSequence {
+    pub fn polynomials(&self) -> &[Polynomial<BigRational>] {
+        &self.polynomials
+    }
+
+    /// The number of sign changes in the sequence evaluated at `x`.
+    pub fn variations_at(&self, x: &BigRational) -> usize {
+        variations(self.polynomials.iter().map(|p| p.eval(x).cmp(&BigRational::zero())))
+    }
+
+    /// The number of sign changes at `+∞`, or at `-∞` if `positive` is
+    /// false, which are given by the leading coefficients and degrees.
+    pub fn variations_at_infinity(&self, positive: bool) -> usize {
+        variations(self.polynomials.iter().map(|p| {
+            let sign = p.leading_coefficient_cloned().cmp(&BigRational::zero());
+            if !positive && p.degree().unwrap() % 2 == 1 {
+                sign.reverse()
+            } else {
+                sign
+            }
+        }))
+    }
+
+    /// The number of distinct real roots in the closed interval `[a, b]`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a > b`.
+    pub fn count_in(&self, a: &BigRational, b: &BigRational) -> usize {
+        assert!(a <= b, "empty interval");
+        let at_a = usize::from(self.polynomials[0].eval(a).is_zero());
+        self.variations_at(a) - self.variations_at(b) + at_a
+    }
+
+    /// The number of distinct real roots.
+    pub fn count(&self) -> usize {
+        self.variations_at_infinity(false) - self.variations_at_infinity(true)
+    }
+}
+
+impl Polynomial<BigRational> {
+    /// The [`SturmSequence`] of this polynomial.
+    ///
+    /// Each remainder is divided by the absolute value of its leading
+    /// coefficient, which keeps the signs and the numbers small.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is zero.
+    pub fn sturm_sequence(&self) -> SturmSequence {
+        assert!(!self.is_zero(), "the zero polynomial has no Sturm sequence");
+        let normalize = |p: Self| {
+            let lc = p.leading_coefficient_cloned().abs();
+            p.scalar_mul(lc.recip())
+        };
+        let mut polynomials = vec![normalize(self.clone())];
+        let mut next = self.clone().derivative();
+        while !next.is_zero() {
+            let next_normalized = normalize(next);
+            let r = polynomials.last().unwrap().clone().div_rem(next_normalized.clone()).1;
+            polynomials.push(next_normalized);
+            next = -r;
+        }
+        let gcd = polynomials.last().unwrap().clone();
+        if gcd.degree() != Some(0) {
+            for p in &mut polynomials {
+                *p = p.take().div_rem(gcd.clone()).0;
+            }
+        }
+        SturmSequence { polynomials }
+    }
+
+    /// The number of distinct real roots in the closed interval `[a, b]`,
+    /// by Sturm's theorem.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is zero or `a > b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // (x^2 - 2)(x - 3)^2 has roots ±√2 and 3
+    /// let p: Polynomial<BigRational> = "x^4 - 6x^3 + 7x^2 + 12x - 18".parse().unwrap();
+    /// assert_eq!(3, p.count_real_roots());
+    /// assert_eq!(1, p.count_real_roots_in(&n(0), &n(2)));
+    /// assert_eq!(2, p.count_real_roots_in(&n(1), &n(3)));
+    /// assert_eq!(0, p.count_real_roots_in(&n(-1), &n(1)));
+    /// ```
+    pub fn count_real_roots_in(&self, a: &BigRational, b: &BigRational) -> usize {
+        self.sturm_sequence().count_in(a, b)
+    }
+
+    /// The number of distinct real roots, by Sturm's theorem.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is zero.
+    pub fn count_real_roots(&self) -> usize {
+        self.sturm_sequence().count()
+    }
+
+    /// The Budan–Fourier bound on the number of roots in `(a, b]`, counted
+    /// with multiplicity.
+    ///
+    /// This is `V(a) - V(b)`, where `V(x)` is the number of sign changes in
+    /// `f(x), f'(x), ..., f^(n)(x)` ignoring zeros. The bound exceeds the
+    /// number of roots by an even number, so a bound of zero or one is exact.
+    /// It only needs the derivatives at the two endpoints, which is much
+    /// cheaper than a Sturm sequence, and is good for ruling out intervals
+    /// quickly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is zero or `a > b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // (x - 1)^2 (x^2 + 1) has a double root at 1 and no others
+    /// let p: Polynomial<BigRational> = "x^4 - 2x^3 + 2x^2 - 2x + 1".parse().unwrap();
+    /// let half = BigRational::new(1.into(), 2.into());
+    /// assert_eq!(2, p.budan_fourier_bound(&half, &n(2)));
+    /// assert_eq!(0, p.budan_fourier_bound(&n(2), &n(5)));
+    /// // the complex roots make the bound overshoot on a wider interval
+    /// assert_eq!(4, p.budan_fourier_bound(&n(0), &n(2)));
+    /// ```
+    pub fn budan_fourier_bound(&self, a: &BigRational, b: &BigRational) -> usize {
+        assert!(!self.is_zero(), "the zero polynomial has infinitely many roots");
+        assert!(a <= b, "empty interval");
+        let n = self.degree().unwrap();
+        let v = |x: &BigRational| {
+            variations(self.eval_with_derivatives(x, n).iter().map(|d| d.cmp(&BigRational::zero())))
+        };
+        v(a) - v(b)
+    }
+
+    /// Disjoint intervals in increasing order, each containing exactly one
+    /// distinct real root.
+    ///
+    /// Starting from a power of two above Cauchy's bound
+    /// `1 + max |a_i / a_n|`, intervals are bisected until the Sturm
+    /// sequence counts at most one root in each. Endpoints are never roots,
+    /// except that an interval may shrink to a single point on a rational
+    /// root. Use [`Polynomial::refine_root`] to narrow them down.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let p: Polynomial<BigRational> = "x^3 - 2x".parse().unwrap();
+    /// let roots = p.isolate_real_roots();
+    /// assert_eq!(3, roots.len());
+    /// let width = BigRational::new(1.into(), 1000.into());
+    /// let sqrt2 = p.refine_root(&roots[2], &width);
+    /// assert!(sqrt2.width() <= width);
+    /// assert!(sqrt2.contains(&BigRational::new(14142.into(), 10000.into())));
+    /// ```
+    pub fn isolate_real_roots(&self) -> Vec<Interval> {
+        let sturm = self.sturm_sequence();
+        let sqf = &sturm.polynomials[0];
+        if sqf.degree() == Some(0) {
+            return Vec::new();
+        }
+        let lc = sqf.leading_coefficient_cloned();
+        let cauchy = sqf.coeffs.iter().map(|c| (c / &lc).abs()).max().unwrap() + BigRational::one();
+        let mut bound = BigRational::one();
+        while bound < cauchy {
+            bound *= BigRational::from_integer(BigInt::from(2));
+        }
+        let mut roots = Vec::new();
+        let mut stack = vec![(-bound.clone(), bound)];
+        while let Some((lo, hi)) = stack.pop() {
+            // the endpoints are not roots, so this counts the roots in (lo, hi)
+            match sturm.variations_at(&lo) - sturm.variations_at(&hi) {
+                0 => {}
+                1 => roots.push(Interval::new(lo, hi)),
+                _ => {
+                    let mid = (2..)
+                        .flat_map(|t| (1..t).map(move |j| BigRational::new(j.into(), BigInt::from(t))))
+                        .map(|t| &lo + (&hi - &lo) * t)
+                        .find(|m| !sqf.eval(m).is_zero())
+                        .unwrap();
+                    stack.push((mid.clone(), hi));
+                    stack.push((lo, mid));
+                }
+            }
+        }
+        roots.sort_by(|a, b| a.lo().cmp(b.lo()));
+        // neighbours may share an endpoint, so shrink the left one away from it
+        for i in 1..roots.len() {
+            while roots[i - 1].hi() == roots[i].lo() {
+                roots[i - 1] = bisect_root(sqf, &roots[i - 1]);
+            }
+        }
+        roots
+    }
+
+    /// Bisects an interval from [`Polynomial::isolate_real_roots`] until it
+    /// is at most `width` wide, or is a single point on a rational root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not positive, as an irrational root is never
+    /// reached exactly.
+    pub fn refine_root(&self, interval: &Interval, width: &BigRational) -> Interval {
+        assert!(width.is_positive(), "the width must be positive");
+        let g = self.clone().gcd(self.clone().derivative());
+        let sqf = self.clone().div_rem(g).0;
+        let mut interval = interval.clone();
+        while &interval.width() > width {
+            interval = bisect_root(&sqf, &interval);
+        }
+        interval
+    }
+    /// The distinct real roots in increasing order, as exact algebraic
+    /// numbers defined by the square free part of this polynomial.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this polynomial is zero.
+    pub fn real_roots(&self) -> Vec<RealAlgebraic> {
+        let sqf = self.sturm_sequence().polynomials.swap_remove(0);
+        sqf.isolate_real_roots()
+            .into_iter()
+            .map(|interval| RealAlgebraic { polynomial: sqf.clone(), interval })
+            .collect()
+    }
+
+    /// Whether this polynomial is positive, negative or both on the closed
+    /// interval `[a, b]`.
+    ///
+    /// The roots in `[a, b]` split it into pieces on which the sign is
+    /// constant, so it is enough to look at the roots, the endpoints and a
+    /// rational point between each pair of neighbours.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a > b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use math2::real_roots::IntervalSign;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// let p: Polynomial<BigRational> = "x^2 - 2".parse().unwrap();
+    /// assert_eq!(IntervalSign::Negative, p.sign_on_interval(&n(-1), &n(1)));
+    /// assert_eq!(IntervalSign::Mixed, p.sign_on_interval(&n(1), &n(2)));
+    /// let square: Polynomial<BigRational> = "x^2 - 2x + 1".parse().unwrap();
+    /// assert_eq!(IntervalSign::NonNegative, square.sign_on_interval(&n(0), &n(3)));
+    /// ```
+    pub fn sign_on_interval(&self, a: &BigRational, b: &BigRational) -> IntervalSign {
+        assert!(a <= b, "empty interval");
+        if self.is_zero() {
+            return IntervalSign::Zero;
+        }
+        let (a, b) = (RealAlgebraic::from(a.clone()), RealAlgebraic::from(b.clone()));
+        let mut points = vec![a.clone()];
+        points.extend(self.real_roots().into_iter().filter(|r| &a < r && r < &b));
+        points.push(b);
+        points.dedup();
+        let (mut positive, mut negative, mut zero) = (false, false, false);
+        let gaps = points.windows(2).map(|w| self.eval(&w[0].between(&w[1])).cmp(&BigRational::zero()));
+        for sign in points.iter().map(|x| x.sign_of(self)).chain(gaps) {
+            match sign {
+                Ordering::Greater => positive = true,
+                Ordering::Less => negative = true,
+                Ordering::Equal => zero = true,
+            }
+        }
+        match (positive, negative, zero) {
+            (true, true, _) => IntervalSign::Mixed,
+            (true, false, false) => IntervalSign::Positive,
+            (true, false, true) => IntervalSign::NonNegative,
+            (false, true, false) => IntervalSign::Negative,
+            (false, true, true) => IntervalSign::NonPositive,
+            (false, false, _) => IntervalSign::Zero,
+        }
+    }
+
+    /// The least point of `[a, b]` at which this polynomial attains its
+    /// minimum on `[a, b]`, as in [`Polynomial::global_extrema_on`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a > b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use math2::real_roots::RealAlgebraic;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // the local minimum of x^3 - 6x is at √2
+    /// let p: Polynomial<BigRational> = "x^3 - 6x".parse().unwrap();
+    /// let min = p.minimize_on_interval(&n(0), &n(3));
+    /// assert_eq!(&"x^2 - 2".parse::<Polynomial<BigRational>>().unwrap(), min.polynomial());
+    /// assert!(min > RealAlgebraic::from(n(1)) && min < RealAlgebraic::from(n(2)));
+    /// // but the left end is lower on a wider interval
+    /// assert_eq!(RealAlgebraic::from(n(-4)), p.minimize_on_interval(&n(-4), &n(3)));
+    /// ```
+    pub fn minimize_on_interval(&self, a: &BigRational, b: &BigRational) -> RealAlgebraic {
+        self.global_extrema_on(a, b).argmin
+    }
+
+    /// The roots of the derivative in increasing order, which are empty for
+    /// a constant polynomial.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use math2::real_roots::RealAlgebraic;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // the derivative is 3(x - 1)^2
+    /// let p: Polynomial<BigRational> = "x^3 - 3x^2 + 3x".parse().unwrap();
+    /// assert_eq!(vec![RealAlgebraic::from(n(1))], p.critical_points());
+    /// ```
+    pub fn critical_points(&self) -> Vec<RealAlgebraic> {
+        let derivative = self.clone().derivative();
+        if derivative.is_zero() {
+            return Vec::new();
+        }
+        derivative.real_roots()
+    }
+
+    /// The least and greatest values on `[a, b]`, and the least points at
+    /// which they are attained.
+    ///
+    /// The extrema are at the ends or at [critical
+    /// points](Polynomial::critical_points) in between, whose exact values
+    /// from [`RealAlgebraic::eval`] are compared.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `a > b`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use math2::real_roots::RealAlgebraic;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // on [0, 2], x^3 - 3x is smallest at 1 and largest at 2
+    /// let p: Polynomial<BigRational> = "x^3 - 3x".parse().unwrap();
+    /// let extrema = p.global_extrema_on(&n(0), &n(2));
+    /// assert_eq!(RealAlgebraic::from(n(-2)), extrema.min);
+    /// assert_eq!(RealAlgebraic::from(n(1)), extrema.argmin);
+    /// assert_eq!(RealAlgebraic::from(n(2)), extrema.max);
+    /// assert_eq!(RealAlgebraic::from(n(2)), extrema.argmax);
+    /// ```
+    pub fn global_extrema_on(&self, a: &BigRational, b: &BigRational) -> Extrema {
+        assert!(a <= b, "empty interval");
+        let (a, b) = (RealAlgebraic::from(a.clone()), RealAlgebraic::from(b.clone()));
+        let mut candidates = vec![a.clone()];
+        candidates.extend(self.critical_points().into_iter().filter(|r| &a < r && r < &b));
+        candidates.push(b);
+        let mut values = candidates.into_iter().map(|x| (x.eval(self), x));
+        let (value, x) = values.next().unwrap();
+        let mut extrema = Extrema { min: value.clone(), argmin: x.clone(), max: value, argmax: x };
+        for (value, x) in values {
+            if value < extrema.min {
+                extrema.min = value;
+                extrema.argmin = x;
+            } else if value > extrema.max {
+                extrema.max = value;
+                extrema.argmax = x;
+            }
+        }
+        extrema
+    }
+}
+
+/// The extreme values of a polynomial on an interval, from
+/// [`Polynomial::global_extrema_on`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Extrema {
+    pub min: RealAlgebraic,
+    /// The least point where the minimum is attained.
+    pub argmin: RealAlgebraic,
+    pub max: RealAlgebraic,
+    /// The least point where the maximum is attained.
+    pub argmax: RealAlgebraic,
+}
+
+/// The sign of a polynomial on an interval, from
+/// [`Polynomial::sign_on_interval`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IntervalSign {
+    /// Positive everywhere.
+    Positive,
+    /// Negative everywhere.
+    Negative,
+    /// Positive except at some roots.
+    NonNegative,
+    /// Negative except at some roots.
+    NonPositive,
+    /// Zero everywhere.
+    Zero,
+    /// Both positive and negative somewhere.
+    Mixed,
+}
+
+/// A boolean combination of sign conditions on univariate polynomials, such
+/// as `f > 0 ∧ g = 0`, for [`decide`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Formula {
+    /// The polynomial has the given sign, so `f ≥ 0` is
+    /// `Not(Sign(f, Less))`.
+    Sign(Polynomial<BigRational>, Ordering),
+    Not(Box<Formula>),
+    /// Every formula holds, which is true when there are none.
+    And(Vec<Formula>),
+    /// Some formula holds, which is false when there are none.
+    Or(Vec<Formula>),
+}
+
+impl Formula {
+    /// Whether the formula holds at `x`.
+    pub fn holds_at(&self, x: &RealAlgebraic) -> bool {
+        match self {
+            Formula::Sign(p, sign) => x.sign_of(p) == *sign,
+            Formula::Not(f) => !f.holds_at(x),
+            Formula::And(fs) => fs.iter().all(|f| f.holds_at(x)),
+            Formula::Or(fs) => fs.iter().any(|f| f.holds_at(x)),
+        }
+    }
+
+    fn polynomials<'a>(&'a self, out: &mut Vec<&'a Polynomial<BigRational>>) {
+        match self {
+            Formula::Sign(p, _) => out.push(p),
+            Formula::Not(f) => f.polynomials(out),
+            Formula::And(fs) | Formula::Or(fs) => fs.iter().for_each(|f| f.polynomials(out)),
+        }
+    }
+}
+
+/// A real number satisfying the formula, or `None` if there is none.
+///
+/// The roots of all the polynomials in the formula split the line into
+/// points and open intervals on which every sign is constant. The formula
+/// is checked at each root and at a rational point in each interval, so the
+/// answer is exact. A formula holds everywhere exactly when its negation is
+/// unsatisfiable.
+///
+/// # Example
+///
+/// ```
+/// # use std::cmp::Ordering;
+/// # use math2::Polynomial;
+/// # use math2::real_roots::{decide, Formula, RealAlgebraic};
+/// # use num::BigRational;
+/// let p = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+/// // x^2 = 2 and x < 0 only at -√2
+/// let f = Formula::And(vec![
+///     Formula::Sign(p("x^2 - 2"), Ordering::Equal),
+///     Formula::Sign(p("x"), Ordering::Less),
+/// ]);
+/// let x = decide(&f).unwrap();
+/// assert!(x < RealAlgebraic::from(BigRational::from_integer((-1).into())));
+/// // x^2 + 1 > 0 everywhere
+/// let g = Formula::Sign(p("x^2 + 1"), Ordering::Greater);
+/// assert_eq!(None, decide(&Formula::Not(Box::new(g))));
+/// ```
+pub fn decide(formula: &Formula) -> Option<RealAlgebraic> {
+    let mut polynomials = Vec::new();
+    formula.polynomials(&mut polynomials);
+    let mut roots: Vec<_> = polynomials.into_iter().filter(|p| !p.is_zero()).flat_map(|p| p.real_roots()).collect();
+    roots.sort();
+    roots.dedup();
+    let one = BigRational::one();
+    let (Some(first), Some(last)) = (roots.first(), roots.last()) else {
+        return Some(BigRational::zero().into()).filter(|x| formula.holds_at(x));
+    };
+    let below = RealAlgebraic::from(first.interval.lo() - &one);
+    let above = RealAlgebraic::from(last.interval.hi() + &one);
+    let gaps: Vec<RealAlgebraic> = roots.windows(2).map(|w| w[0].between(&w[1]).into()).collect();
+    let mut samples = [below, above].into_iter().chain(gaps).chain(roots);
+    samples.find(|x| formula.holds_at(x))
+}
+
+/// A real algebraic number, the only root of a square free polynomial in a
+/// closed interval with rational endpoints.
+///
+/// The endpoints are not roots unless the interval is a single point, so it
+/// is narrowed down like the intervals of [`Polynomial::isolate_real_roots`].
+/// Comparisons are exact.
+#[derive(Clone, Debug)]
+pub struct RealAlgebraic {
+    polynomial: Polynomial<BigRational>,
+    interval: Interval,
+}
+
+impl RealAlgebraic {
+    pub fn polynomial(&self) -> &Polynomial<BigRational> {
+        &self.polynomial
+    }
+
+    pub fn interval(&self) -> &Interval {
+        &self.interval
+    }
+
+    /// Bisects the interval until it is at most `width` wide, or is a single
+    /// point on a rational root.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is not positive.
+    pub fn refine(&mut self, width: &BigRational) {
+        assert!(width.is_positive(), "the width must be positive");
+        while &self.interval.width() > width {
+            self.bisect();
+        }
+    }
+
+    fn bisect(&mut self) {
+        self.interval = bisect_root(&self.polynomial, &self.interval);
+    }
+
+    /// The sign of `p` at this number.
+    ///
+    /// It is zero exactly when `p` and the defining polynomial have a common
+    /// root in the interval. Otherwise the interval is bisected until `p`
+    /// evaluated on it in interval arithmetic excludes zero.
+    pub fn sign_of(&self, p: &Polynomial<BigRational>) -> Ordering {
+        if common_root_in(&self.polynomial, p, &self.interval) {
+            return Ordering::Equal;
+        }
+        let mut this = self.clone();
+        loop {
+            let value = p.eval_interval(&this.interval);
+            if value.lo().is_positive() {
+                return Ordering::Greater;
+            } else if value.hi().is_negative() {
+                return Ordering::Less;
+            }
+            this.bisect();
+        }
+    }
+
+    /// The value of `p` at this number.
+    ///
+    /// The values of `p` at the roots of the defining polynomial `f` are the
+    /// roots of `Res_y(f(y), x - p(y))`. The right one is found by bisecting
+    /// until the enclosure of `p` on the interval meets only one of their
+    /// isolating intervals.
+    pub fn eval(&self, p: &Polynomial<BigRational>) -> Self {
+        let p = p.clone().div_rem(self.polynomial.clone()).1;
+        if p.degree().unwrap_or(0) == 0 || self.interval.width().is_zero() {
+            return p.eval(self.interval.lo()).into();
+        }
+        let mut candidates = image_polynomial(&self.polynomial, &p).real_roots();
+        let mut this = self.clone();
+        loop {
+            let value = p.eval_interval(&this.interval);
+            candidates.retain(|c| c.interval.lo() <= value.hi() && value.lo() <= c.interval.hi());
+            if candidates.len() == 1 {
+                return candidates.pop().unwrap();
+            }
+            this.bisect();
+            candidates.iter_mut().for_each(Self::bisect);
+        }
+    }
+
+    /// A rational number strictly between this and a larger `other`.
+    fn between(&self, other: &Self) -> BigRational {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while a.interval.hi() >= b.interval.lo() {
+            a.bisect();
+            b.bisect();
+        }
+        (a.interval.hi() + b.interval.lo()) / BigRational::from_integer(BigInt::from(2))
+    }
+}
+
+/// Whether the square free `f` and `p` have a common root in `interval`.
+fn common_root_in(f: &Polynomial<BigRational>, p: &Polynomial<BigRational>, interval: &Interval) -> bool {
+    let g = f.clone().gcd(p.clone());
+    g.degree() != Some(0) && g.count_real_roots_in(interval.lo(), interval.hi()) > 0
+}
+
+impl From<BigRational> for RealAlgebraic {
+    fn from(x: BigRational) -> Self {
+        Self {
+            polynomial: Polynomial::new(vec![-x.clone(), BigRational::one()]),
+            interval: Interval::point(x),
+        }
+    }
+}
+
+impl Neg for RealAlgebraic {
+    type Output = Self;
+
+    /// The root of `f(-x)` in the negated interval.
+    fn neg(self) -> Self {
+        let coeffs = self.polynomial.coeffs.into_iter().enumerate();
+        let coeffs = coeffs.map(|(i, c)| if i % 2 == 1 { -c } else { c }).collect();
+        Self {
+            polynomial: Polynomial::new(coeffs),
+            interval: -self.interval,
+        }
+    }
+}
+
+impl PartialEq for RealAlgebraic {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for RealAlgebraic {}
+
+impl PartialOrd for RealAlgebraic {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RealAlgebraic {
+    /// Two numbers are equal exactly when their polynomials have a common
+    /// root in the intersection of the intervals. Otherwise both are bisected
+    /// until the intervals are disjoint.
+    fn cmp(&self, other: &Self) -> Ordering {
+        let lo = self.interval.lo().max(other.interval.lo());
+        let hi = self.interval.hi().min(other.interval.hi());
+        if lo <= hi && common_root_in(&self.polynomial, &other.polynomial, &Interval::new(lo.clone(), hi.clone())) {
+            return Ordering::Equal;
+        }
+        let (mut a, mut b) = (self.clone(), other.clone());
+        loop {
+            if a.interval.hi() < b.interval.lo() {
+                return Ordering::Less;
+            } else if b.interval.hi() < a.interval.lo() {
+                return Ordering::Greater;
+            }
+            a.bisect();
+            b.bisect();
+        }
+    }
+}
+
+/// Halves an interval with a single root of the square free `sqf`, whose
+/// lower end is not a root, keeping the half with the sign change.
+fn bisect_root(sqf: &Polynomial<BigRational>, interval: &Interval) -> Interval {
+    if interval.width().is_zero() {
+        return interval.clone();
+    }
+    let (lo, hi) = (interval.lo(), interval.hi());
+    let mid = (lo + hi) / BigRational::from_integer(BigInt::from(2));
+    let at_mid = sqf.eval(&mid);
+    if at_mid.is_zero() {
+        Interval::point(mid)
+    } else if at_mid.is_positive() == sqf.eval(lo).is_positive() {
+        Interval::new(mid, hi.clone())
+    } else {
+        Interval::new(lo.clone(), mid)
+    }
+}
@@ -0,0 +1,132 @@
+//! Deliberately simple implementations to test the fast algorithms against.
+//!
+//! These are brute force and only use the arithmetic of the coefficients, so
+//! they share no code with the algorithms they check. They are far too slow
+//! for anything but small inputs: [`factor_mod_p`] tries every monic divisor
+//! up to half the degree, which is exponential in the degree.
+
+use std::num::NonZeroUsize;
+
+use num::{One, Zero};
+
+use crate::factorization::Factorization;
+use crate::fields::Zp;
+use crate::traits::{CheckedInv, CommutativeRing};
+use crate::Polynomial;
+
+/// The product, summing `a_i b_j` into the coefficient of `x^(i + j)`.
+///
+/// # Example
+///
+/// ```
+/// # use math2::reference;
+/// # use math2::Polynomial;
+/// let p = Polynomial::<i64>::new((0..40).collect());
+/// assert_eq!(reference::mul(&p, &p), p.clone() * p);
+/// ```
+pub fn mul<R: CommutativeRing>(a: &Polynomial<R>, b: &Polynomial<R>) -> Polynomial<R> {
+    if a.coeffs.is_empty() || b.coeffs.is_empty() {
+        return Polynomial::zero();
+    }
+    let mut coeffs = vec![R::zero(); a.coeffs.len() + b.coeffs.len() - 1];
+    for (i, x) in a.coeffs.iter().enumerate() {
+        for (j, y) in b.coeffs.iter().enumerate() {
+            coeffs[i + j] = coeffs[i + j].clone() + x.clone() * y.clone();
+        }
+    }
+    Polynomial::new_trim_zeroes(coeffs)
+}
+
+/// The value at `x`, summing `a_i x^i` with the powers computed one by one.
+fn eval<const P: u64>(f: &Polynomial<Zp<P>>, x: Zp<P>) -> Zp<P> {
+    let mut power = Zp::one();
+    let mut sum = Zp::zero();
+    for c in &f.coeffs {
+        sum = sum + *c * power;
+        power = power * x;
+    }
+    sum
+}
+
+/// The distinct roots in `GF(P)`, in increasing order, by evaluating at
+/// every element. Every element is a root of the zero polynomial.
+///
+/// # Example
+///
+/// ```
+/// # use math2::fields::Zp;
+/// # use math2::reference;
+/// # use math2::Polynomial;
+/// // x^2 - 1 = (x - 1)(x + 1)
+/// let p = Polynomial::new([4, 0, 1].map(Zp::<5>::new).to_vec());
+/// assert_eq!(vec![Zp::new(1), Zp::new(4)], reference::roots(&p));
+/// ```
+pub fn roots<const P: u64>(f: &Polynomial<Zp<P>>) -> Vec<Zp<P>> {
+    (0..P).map(Zp::new).filter(|&x| eval(f, x).is_zero()).collect()
+}
+
+/// Divides `f` by the monic `g` with long division, if `g` divides it.
+fn divide_monic<const P: u64>(f: &[Zp<P>], g: &[Zp<P>]) -> Option<Vec<Zp<P>>> {
+    let n = g.len() - 1;
+    if f.len() <= n {
+        return None;
+    }
+    let mut rem = f.to_vec();
+    let mut quotient = vec![Zp::zero(); f.len() - n];
+    for i in (0..quotient.len()).rev() {
+        let q = rem[i + n];
+        for (r, c) in rem[i..].iter_mut().zip(g) {
+            *r = *r - q * *c;
+        }
+        quotient[i] = q;
+    }
+    rem.iter().all(Zp::is_zero).then_some(quotient)
+}
+
+/// Factors into monic irreducibles by trial division: monic divisors of
+/// each degree are tried in turn, each as often as it divides, until the
+/// degree of what is left is less than twice the degree tried, and what is
+/// left is then irreducible.
+///
+/// The factors are in the order of
+/// [`Polynomial::factor_mod_p`](crate::Polynomial::factor_mod_p), by degree
+/// and then by coefficients from the highest degree down, which is the order
+/// divisors are tried in.
+///
+/// # Panics
+///
+/// Panics if `f` is zero.
+pub fn factor_mod_p<const P: u64>(f: &Polynomial<Zp<P>>) -> Factorization<Zp<P>> {
+    assert!(!f.is_zero(), "the zero polynomial has no factorization");
+    let leading_coeff = *f.coeffs.last().unwrap();
+    let inv = leading_coeff.checked_inv().unwrap();
+    let mut rest: Vec<Zp<P>> = f.coeffs.iter().map(|&c| c * inv).collect();
+    let mut factors: Vec<(Polynomial<Zp<P>>, NonZeroUsize)> = Vec::new();
+    let mut degree = 1;
+    while 2 * degree < rest.len() {
+        // the lower coefficients of the divisor, counted in base P
+        let mut digits = vec![0; degree];
+        loop {
+            let mut divisor: Vec<Zp<P>> = digits.iter().map(|&c| Zp::new(c)).collect();
+            divisor.push(Zp::one());
+            let mut multiplicity = 0;
+            while let Some(quotient) = divide_monic(&rest, &divisor) {
+                rest = quotient;
+                multiplicity += 1;
+            }
+            if let Some(multiplicity) = NonZeroUsize::new(multiplicity) {
+                factors.push((Polynomial::new(divisor), multiplicity));
+            }
+            let Some(i) = digits.iter().position(|&c| c + 1 < P) else {
+                break;
+            };
+            digits[i] += 1;
+            digits[..i].fill(0);
+        }
+        degree += 1;
+    }
+    if rest.len() > 1 {
+        factors.push((Polynomial::new(rest), NonZeroUsize::new(1).unwrap()));
+    }
+    Factorization { leading_coeff, factors }
+}
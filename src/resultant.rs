@@ -0,0 +1,398 @@
+//! Resultants, discriminants and related invariants of polynomials over a field.
+
+use num::{BigRational, One, Zero};
+
+use crate::factorization::lagrange_interpolation;
+use crate::multivariate::MultiPolynomial;
+use crate::traits::{CoefficientDomain, Field, FromUsize};
+use crate::Polynomial;
+
+/// Computes the determinant of a square matrix by Gaussian elimination.
+pub(crate) fn determinant<F: Field>(mut rows: Vec<Vec<F>>) -> F {
+    let n = rows.len();
+    let mut det = F::one();
+    for col in 0..n {
+        let Some(pivot) = (col..n).find(|&r| !rows[r][col].is_zero()) else {
+            return F::zero();
+        };
+        if pivot != col {
+            rows.swap(pivot, col);
+            det = -det;
+        }
+        let (top, bottom) = rows.split_at_mut(col + 1);
+        let pivot_row = &top[col];
+        let p = pivot_row[col].clone();
+        for row in bottom {
+            if row[col].is_zero() {
+                continue;
+            }
+            let factor = row[col].clone() / p.clone();
+            for (x, y) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                let c = std::mem::replace(x, F::zero());
+                *x = c + -(y.clone() * factor.clone());
+            }
+        }
+        det = det * p;
+    }
+    det
+}
+
+/// The Sylvester matrix of `f` and `g`, treated as having degrees `m` and `n`.
+fn sylvester<F: Field>(f: &[F], m: usize, g: &[F], n: usize) -> Vec<Vec<F>> {
+    let coeff = |p: &[F], i: usize| p.get(i).cloned().unwrap_or_else(F::zero);
+    let size = m + n;
+    let mut rows = Vec::with_capacity(size);
+    for i in 0..n {
+        let mut row = vec![F::zero(); size];
+        for k in 0..=m {
+            row[i + k] = coeff(f, m - k);
+        }
+        rows.push(row);
+    }
+    for i in 0..m {
+        let mut row = vec![F::zero(); size];
+        for k in 0..=n {
+            row[i + k] = coeff(g, n - k);
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+/// The Sylvester matrix of `f` and `g`, whose determinant is their resultant.
+///
+/// Rows hold the coefficients in order of decreasing degree.
+pub fn sylvester_matrix<F: Field>(f: &Polynomial<F>, g: &Polynomial<F>) -> Vec<Vec<F>> {
+    let m = f.degree().unwrap_or(0);
+    let n = g.degree().unwrap_or(0);
+    sylvester(&f.coeffs, m, &g.coeffs, n)
+}
+
+/// The Bezout matrix `B` of `f` and `g`, defined by
+/// `(f(x)g(y) - f(y)g(x)) / (x - y) = sum(B[i][j] x^i y^j)`.
+///
+/// It is square of size `max(deg f, deg g)`.
+pub fn bezout_matrix<F: Field>(f: &Polynomial<F>, g: &Polynomial<F>) -> Vec<Vec<F>> {
+    let n = f.coeffs.len().max(g.coeffs.len()).saturating_sub(1);
+    let coeff = |p: &Polynomial<F>, i: usize| p.coeffs.get(i).cloned().unwrap_or_else(F::zero);
+    let numerator = |a: usize, b: usize| coeff(f, a) * coeff(g, b) + -(coeff(f, b) * coeff(g, a));
+    // Comparing coefficients of `(x - y) B(x, y) = N(x, y)` gives
+    // `B[a - 1][b] = N[a][b] + B[a][b - 1]`.
+    let mut rows = vec![vec![F::zero(); n]; n];
+    for a in (1..=n).rev() {
+        for b in 0..n {
+            let mut entry = numerator(a, b);
+            if a < n && b > 0 {
+                entry = entry + rows[a][b - 1].clone();
+            }
+            rows[a - 1][b] = entry;
+        }
+    }
+    rows
+}
+
+/// A handful of invariants of a polynomial, see [`Polynomial::invariants`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Invariants<F> {
+    pub degree: usize,
+    pub discriminant: F,
+    /// The resultant of `f` with its reversal `x^n f(1/x)`.
+    pub reversal_resultant: F,
+    /// The determinant of the Bezout matrix of `f` and `f'`, which is
+    /// `lc(f)^2` times the discriminant.
+    pub bezoutian_determinant: F,
+}
+
+impl<F: Field> Polynomial<F> {
+    /// Returns the resultant of `self` and `other`, the determinant of their
+    /// Sylvester matrix. It is zero exactly when they have a common factor,
+    /// or when both are zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // (x - 2)(x - 3) and x - 5
+    /// let f = Polynomial::new(vec![n(6), n(-5), n(1)]);
+    /// let g = Polynomial::new(vec![n(-5), n(1)]);
+    /// assert_eq!(n(6), f.resultant(&g));
+    /// ```
+    pub fn resultant(&self, other: &Self) -> F {
+        if self.is_zero() || other.is_zero() {
+            return F::zero();
+        }
+        determinant(sylvester_matrix(self, other))
+    }
+
+    /// Returns the discriminant of this polynomial. `None` if it is constant.
+    pub fn discriminant(&self) -> Option<F>
+    where
+        F: FromUsize,
+    {
+        let n = self.degree().filter(|&n| n > 0)?;
+        let derivative = self.clone().derivative();
+        // the derivative has formal degree `n - 1` even if its leading
+        // coefficient vanishes.
+        let res = determinant(sylvester(&self.coeffs, n, &derivative.coeffs, n - 1));
+        let res = res / self.leading_coefficient_cloned();
+        Some(if (n * (n - 1) / 2) % 2 == 0 { res } else { -res })
+    }
+
+    /// Returns the reversal `x^n f(1/x)` of a polynomial of degree `n`, which
+    /// has the coefficients in the opposite order.
+    pub fn reversal(&self) -> Self {
+        Polynomial::new_trim_zeroes(self.coeffs.iter().rev().cloned().collect())
+    }
+
+    /// Computes a few invariants at once. `None` if this polynomial is constant.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // x^2 + 3x + 1
+    /// let inv = Polynomial::new(vec![n(1), n(3), n(1)]).invariants().unwrap();
+    /// assert_eq!(n(5), inv.discriminant);
+    /// assert_eq!(n(5), inv.bezoutian_determinant);
+    /// ```
+    pub fn invariants(&self) -> Option<Invariants<F>>
+    where
+        F: FromUsize,
+    {
+        let discriminant = self.discriminant()?;
+        let bezout = bezout_matrix(self, &self.clone().derivative());
+        Some(Invariants {
+            degree: self.degree().unwrap(),
+            discriminant,
+            reversal_resultant: self.resultant(&self.reversal()),
+            bezoutian_determinant: determinant(bezout),
+        })
+    }
+}
+
+/// Returns the implicit equation `F(x, y) = 0` of the polynomial curve
+/// `(x(t), y(t))`. See [`implicitize_rational`].
+pub fn implicitize(
+    x: &Polynomial<BigRational>,
+    y: &Polynomial<BigRational>,
+) -> Polynomial<Polynomial<BigRational>> {
+    let one = Polynomial::one();
+    implicitize_rational(x, &one, y, &one)
+}
+
+/// Returns the implicit equation `F(x, y) = 0` of the rational curve
+/// `(x_num(t) / x_den(t), y_num(t) / y_den(t))`, as a polynomial in `y` whose
+/// coefficients are polynomials in `x`.
+///
+/// `F` is the resultant `Res_t(x_num - x x_den, y_num - y y_den)`. Rather
+/// than computing a determinant over `Q[x, y]`, this evaluates the resultant
+/// on a grid of rational points, keeping the formal degrees of the two
+/// polynomials in `t` fixed, and interpolates.
+///
+/// # Example
+///
+/// ```
+/// # use math2::resultant::implicitize;
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// // (t, t^2) lies on x^2 - y = 0
+/// let f = implicitize(&Polynomial::new(vec![n(0), n(1)]), &Polynomial::new(vec![n(0), n(0), n(1)]));
+/// let expected = Polynomial::new(vec![
+///     Polynomial::new(vec![n(0), n(0), n(1)]),
+///     Polynomial::new(vec![n(-1)]),
+/// ]);
+/// assert_eq!(expected, f);
+/// ```
+pub fn implicitize_rational(
+    x_num: &Polynomial<BigRational>,
+    x_den: &Polynomial<BigRational>,
+    y_num: &Polynomial<BigRational>,
+    y_den: &Polynomial<BigRational>,
+) -> Polynomial<Polynomial<BigRational>> {
+    let degree = |a: &Polynomial<BigRational>, b: &Polynomial<BigRational>| {
+        a.degree().max(b.degree()).unwrap_or(0)
+    };
+    let m = degree(x_num, x_den);
+    let n = degree(y_num, y_den);
+    let point = |i: usize| BigRational::from_integer(i.into());
+    let at = |num: &Polynomial<BigRational>, den: &Polynomial<BigRational>, v: &BigRational| {
+        num.clone() - den.clone() * v.clone()
+    };
+    // The first `n` rows of the Sylvester matrix are linear in `x` and the
+    // other `m` are linear in `y`, so `F` has degree at most `n` in `x` and
+    // `m` in `y`.
+    let in_x: Vec<_> = (0..=m)
+        .map(|j| {
+            let yv = point(j);
+            let g = at(y_num, y_den, &yv);
+            let values = (0..=n)
+                .map(|i| {
+                    let xv = point(i);
+                    let f = at(x_num, x_den, &xv);
+                    (xv, determinant(sylvester(&f.coeffs, m, &g.coeffs, n)))
+                })
+                .collect();
+            lagrange_interpolation(values)
+        })
+        .collect();
+    let coeff_in_y = |k: usize| {
+        let values = in_x
+            .iter()
+            .enumerate()
+            .map(|(j, p)| {
+                let c = p.coeffs.get(k).cloned().unwrap_or_else(BigRational::zero);
+                (point(j), c)
+            })
+            .collect();
+        lagrange_interpolation(values)
+    };
+    // gather the coefficient of `x^k y^j` for all `k` and `j`, then regroup by
+    // powers of `y`.
+    let by_x: Vec<_> = (0..=n).map(coeff_in_y).collect();
+    let coeffs = (0..=m)
+        .map(|j| {
+            Polynomial::new_trim_zeroes(
+                by_x.iter()
+                    .map(|p| p.coeffs.get(j).cloned().unwrap_or_else(BigRational::zero))
+                    .collect(),
+            )
+        })
+        .collect();
+    Polynomial::new_trim_zeroes(coeffs)
+}
+
+/// The intersection multiplicity of the plane curves `f = 0` and `g = 0` in
+/// `x_0` and `x_1` at `point`, or `None` if they share a component through
+/// it. The multiplicity is zero if the point is not on both curves.
+///
+/// A common factor that does not vanish at the point is a unit near it, so
+/// it is divided out first. After moving the point to the origin, a shear
+/// `x_0 -> x_0 + t x_1` is picked so that both leading coefficients in `x_1`
+/// are constants and no other common point lies on the line `x_0 = 0`. The multiplicity is then
+/// the order of `Res_(x_1)(f, g)` at `x_0 = 0`, which is interpolated from
+/// its values at Bezout's bound of `deg f deg g` points like
+/// [`implicitize_rational`]. For curves in the projective plane,
+/// [dehomogenize](MultiPolynomial::dehomogenize) at a coordinate that is not
+/// zero at the point.
+///
+/// # Panics
+///
+/// Panics if another variable appears.
+///
+/// # Example
+///
+/// ```
+/// # use math2::multivariate::MultiPolynomial;
+/// # use math2::resultant::intersection_multiplicity;
+/// # use num::{BigRational, Zero};
+/// let x = || MultiPolynomial::<BigRational>::var(0);
+/// let y = || MultiPolynomial::<BigRational>::var(1);
+/// let origin = [BigRational::zero(), BigRational::zero()];
+/// // the cusp y^2 = x^3 meets its tangent y = 0 three times
+/// let cusp = y() * y() - x() * x() * x();
+/// assert_eq!(Some(3), intersection_multiplicity(&cusp, &y(), &origin));
+/// assert_eq!(Some(2), intersection_multiplicity(&cusp, &x(), &origin));
+/// assert_eq!(None, intersection_multiplicity(&(x() * y()), &x(), &origin));
+/// ```
+pub fn intersection_multiplicity(
+    f: &MultiPolynomial<BigRational>,
+    g: &MultiPolynomial<BigRational>,
+    point: &[BigRational; 2],
+) -> Option<usize> {
+    if f.is_zero() || g.is_zero() {
+        return None;
+    }
+    if !f.eval(point).is_zero() || !g.eval(point).is_zero() {
+        return Some(0);
+    }
+    // a common component elsewhere is a unit near the point
+    let (f, g) = (nested(f), nested(g));
+    let common = CoefficientDomain::gcd(&f, &g);
+    if flattened(&common).eval(point).is_zero() {
+        return None;
+    }
+    let (f, g) = (flattened(&f.divide_exact(&common)), flattened(&g.divide_exact(&common)));
+    let (x, y) = (MultiPolynomial::var(0), MultiPolynomial::var(1));
+    let moved = |p: &MultiPolynomial<BigRational>| {
+        p.substitute(0, &(x.clone() + MultiPolynomial::constant(point[0].clone())))
+            .substitute(1, &(y.clone() + MultiPolynomial::constant(point[1].clone())))
+    };
+    let (f, g) = (moved(&f), moved(&g));
+    let bound = f.total_degree().unwrap() * g.total_degree().unwrap();
+    // the coefficients in `x_1` after setting `x_0` to `v`
+    let in_y = |p: &MultiPolynomial<BigRational>, v: &BigRational| -> Vec<BigRational> {
+        let coeffs = p.specialize(0, v).to_univariate(1).into_iter();
+        coeffs.map(|c| c.coeff(&[]).cloned().unwrap_or_else(BigRational::zero)).collect()
+    };
+    let monic_in_y = |p: &MultiPolynomial<BigRational>| p.to_univariate(1).leading_coefficient_cloned().total_degree() == Some(0);
+    let mut t = BigRational::zero();
+    loop {
+        let shear = |p: &MultiPolynomial<BigRational>| p.substitute(0, &(x.clone() + y.clone().scalar_mul(t.clone())));
+        let (f, g) = (shear(&f), shear(&g));
+        t += BigRational::one();
+        if !monic_in_y(&f) || !monic_in_y(&g) {
+            continue;
+        }
+        let (m, n) = (f.degree_in(1).unwrap(), g.degree_in(1).unwrap());
+        let values = (0..=bound)
+            .map(|i| {
+                let v = BigRational::from_integer(i.into());
+                let res = determinant(sylvester(&in_y(&f, &v), m, &in_y(&g, &v), n));
+                (v, res)
+            })
+            .collect();
+        let res = lagrange_interpolation(values);
+        debug_assert!(!res.is_zero(), "the common factors were divided out");
+        // any other common point on `x_0 = 0` would add to the order
+        let zero = BigRational::zero();
+        let on_line = Polynomial::new_trim_zeroes(in_y(&f, &zero)).gcd(Polynomial::new_trim_zeroes(in_y(&g, &zero)));
+        if on_line.degree().is_some_and(|d| on_line.coeffs[..d].iter().all(Zero::is_zero)) {
+            return Some(res.coeffs.iter().take_while(|c| c.is_zero()).count());
+        }
+    }
+}
+
+/// A polynomial in `x_0` and `x_1` as a polynomial in `x_1` over `Q[x_0]`.
+fn nested(p: &MultiPolynomial<BigRational>) -> Polynomial<Polynomial<BigRational>> {
+    let degree = |i| p.degree_in(i).map_or(0, |d| d + 1);
+    let mut coeffs = vec![vec![BigRational::zero(); degree(0)]; degree(1)];
+    for (exponents, c) in p.terms() {
+        let exponent = |i| exponents.get(i).copied().unwrap_or(0);
+        coeffs[exponent(1)][exponent(0)] = c.clone();
+    }
+    Polynomial::new_trim_zeroes(coeffs.into_iter().map(Polynomial::new_trim_zeroes).collect())
+}
+
+/// The inverse of [`nested`].
+fn flattened(p: &Polynomial<Polynomial<BigRational>>) -> MultiPolynomial<BigRational> {
+    MultiPolynomial::new(p.coeffs.iter().enumerate().flat_map(|(j, c)| {
+        c.coeffs.iter().enumerate().map(move |(i, c)| (vec![i, j], c.clone()))
+    }))
+}
+
+/// Returns `Res_y(f(y), x - g(y))`, whose roots are the values of `g` at the
+/// roots of `f`, by interpolating over `x` like [`implicitize_rational`].
+///
+/// `f` must not be constant and `g` must have positive degree.
+pub(crate) fn image_polynomial(
+    f: &Polynomial<BigRational>,
+    g: &Polynomial<BigRational>,
+) -> Polynomial<BigRational> {
+    let m = f.degree().unwrap();
+    let n = g.degree().unwrap();
+    // `x - g(y)` keeps degree `n` in `y` for every `x`, so the resultant has
+    // degree `m` in `x`.
+    let values = (0..=m)
+        .map(|i| {
+            let x = BigRational::from_integer(i.into());
+            let h = Polynomial::new_trim_zeroes(vec![x.clone()]) - g.clone();
+            (x, determinant(sylvester(&f.coeffs, m, &h.coeffs, n)))
+        })
+        .collect();
+    lagrange_interpolation(values)
+}
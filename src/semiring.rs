@@ -0,0 +1,61 @@
+//! Semirings without negation.
+//!
+//! A [`Semiring`](crate::traits::Semiring) is all that the addition and
+//! multiplication of [`Matrix`](crate::linalg::Matrix)es need. Polynomials
+//! over a semiring can be added and evaluated, and multiplied with
+//! [`Polynomial::schoolbook_mul`](crate::Polynomial::schoolbook_mul), but not
+//! with `*`. Besides the tropical semiring of
+//! [`crate::tropical`], where matrix powers give shortest paths, this has the
+//! boolean semiring, where they give reachability.
+
+use std::ops::{Add, Mul};
+
+use num::{One, Zero};
+
+/// The boolean semiring, with `or` as addition and `and` as multiplication.
+///
+/// # Example
+///
+/// ```
+/// # use math2::linalg::Matrix;
+/// # use math2::semiring::Boolean;
+/// // the edges 0 -> 1 -> 2 -> 0 -> 3
+/// let edge = |i, j| Boolean([(0, 1), (1, 2), (2, 0), (0, 3)].contains(&(i, j)));
+/// let a = Matrix::from_fn(4, 4, edge);
+/// // walks of exactly three edges lead from 1 back to 1 and to 3
+/// let walks = a.pow(3);
+/// assert_eq!(vec![Boolean(false), Boolean(true), Boolean(false), Boolean(true)], walks.row(1));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Boolean(pub bool);
+
+/// `or`.
+impl Add for Boolean {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self(self.0 || other.0)
+    }
+}
+
+/// `and`.
+impl Mul for Boolean {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self(self.0 && other.0)
+    }
+}
+
+impl Zero for Boolean {
+    fn zero() -> Self {
+        Self(false)
+    }
+    fn is_zero(&self) -> bool {
+        !self.0
+    }
+}
+
+impl One for Boolean {
+    fn one() -> Self {
+        Self(true)
+    }
+}
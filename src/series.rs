@@ -0,0 +1,128 @@
+//! Truncated power series, as polynomials modulo `x^n`.
+//!
+//! The inverse, logarithm and exponential of a series are found by Newton
+//! iteration, which doubles the number of correct coefficients with every
+//! step. Each step costs a few products of the current precision, so the
+//! whole computation costs a constant number of products of `n`
+//! coefficients.
+
+use num::Zero;
+
+use crate::traits::{Field, FromUsize};
+use crate::Polynomial;
+
+impl<F: Field> Polynomial<F> {
+    /// The polynomial modulo `x^n`, keeping the first `n` coefficients.
+    pub fn truncated(&self, n: usize) -> Self {
+        Polynomial::new_trim_zeroes(self.coeffs.iter().take(n).cloned().collect())
+    }
+
+    /// The product modulo `x^n`.
+    pub fn mul_mod_xn(&self, other: &Self, n: usize) -> Self {
+        (self.truncated(n) * other.truncated(n)).truncated(n)
+    }
+
+    /// The inverse `g` of the series modulo `x^n`, with `f g = 1 mod x^n`.
+    ///
+    /// Newton's iteration for `1 / g - f = 0` is `g <- g (2 - f g)`, which
+    /// takes an inverse modulo `x^k` to one modulo `x^2k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// // 1 / (1 - x - x^2) is the generating function of the Fibonacci numbers
+    /// let f: Polynomial<BigRational> = "1 - x - x^2".parse().unwrap();
+    /// let fibonacci: Polynomial<BigRational> = "1 + x + 2x^2 + 3x^3 + 5x^4 + 8x^5".parse().unwrap();
+    /// assert_eq!(fibonacci, f.inv_mod_xn(6));
+    /// ```
+    pub fn inv_mod_xn(&self, n: usize) -> Self {
+        let c = self
+            .coeffs
+            .first()
+            .and_then(|c| c.clone().checked_inv())
+            .expect("series with a zero constant term have no inverse");
+        let mut g = Polynomial::new(vec![c]);
+        let mut k = 1;
+        while k < n {
+            k = (2 * k).min(n);
+            let error = Polynomial::new_trim_zeroes(vec![F::one() + F::one()]) - self.mul_mod_xn(&g, k);
+            g = g.mul_mod_xn(&error, k);
+        }
+        g.truncated(n)
+    }
+
+    /// The logarithm of a series with constant term `1` modulo `x^n`, the
+    /// series `log f = integral(f' / f)` with constant term zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term is not `1`, or if the field has
+    /// characteristic `p` with `0 < p < n`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// // log(1 + x) = x - x^2 / 2 + x^3 / 3 - ...
+    /// let f: Polynomial<BigRational> = "1 + x".parse().unwrap();
+    /// let expected: Polynomial<BigRational> = "x - 1/2 x^2 + 1/3 x^3 - 1/4 x^4".parse().unwrap();
+    /// assert_eq!(expected, f.log_mod_xn(5));
+    /// ```
+    pub fn log_mod_xn(&self, n: usize) -> Self
+    where
+        F: FromUsize,
+    {
+        let constant = self.coeffs.first().cloned().unwrap_or_else(F::zero);
+        assert!((constant + -F::one()).is_zero(), "the logarithm needs a constant term of one");
+        if n == 0 {
+            return Polynomial::zero();
+        }
+        let quotient = self.truncated(n).derivative().mul_mod_xn(&self.inv_mod_xn(n - 1), n - 1);
+        quotient.antiderivative()
+    }
+
+    /// The exponential of a series with constant term zero modulo `x^n`.
+    ///
+    /// Newton's iteration for `log g - f = 0` is `g <- g (1 - log g + f)`,
+    /// which takes `exp f` modulo `x^k` to `exp f` modulo `x^2k`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the constant term is not zero, or if the field has
+    /// characteristic `p` with `0 < p < n`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// // exp(x) = 1 + x + x^2 / 2 + x^3 / 6 + ...
+    /// let x: Polynomial<BigRational> = "x".parse().unwrap();
+    /// let expected: Polynomial<BigRational> = "1 + x + 1/2 x^2 + 1/6 x^3 + 1/24 x^4".parse().unwrap();
+    /// assert_eq!(expected, x.exp_mod_xn(5));
+    /// ```
+    pub fn exp_mod_xn(&self, n: usize) -> Self
+    where
+        F: FromUsize,
+    {
+        assert!(self.coeffs.first().is_none_or(|c| c.is_zero()), "the exponential needs a constant term of zero");
+        if n == 0 {
+            return Polynomial::zero();
+        }
+        let mut g = Polynomial::new(vec![F::one()]);
+        let mut k = 1;
+        while k < n {
+            k = (2 * k).min(n);
+            let error = Polynomial::new(vec![F::one()]) - g.log_mod_xn(k) + self.truncated(k);
+            g = g.mul_mod_xn(&error, k);
+        }
+        g
+    }
+}
@@ -0,0 +1,567 @@
+//! Univariate polynomials stored by their non-zero terms.
+
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+use std::iter::{Product, Sum};
+use std::num::NonZeroUsize;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use num::{BigInt, One, Zero};
+
+use crate::cyclotomic::factor_xn_minus_a;
+use crate::factorization::square_free_monic;
+use crate::traits::{AssertUnit, CommutativeRing, Field, FromUsize};
+use crate::Polynomial;
+
+/// Non-zero coefficients by exponent, the storage shared by
+/// [`SparsePolynomial`] and
+/// [`LaurentPolynomial`](crate::laurent::LaurentPolynomial), which only
+/// differ in their exponents.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub(crate) struct Terms<E, Ring>(BTreeMap<E, Ring>);
+
+impl<E: Ord + Copy, Ring: CommutativeRing> Terms<E, Ring> {
+    /// The terms of `(exponent, coefficient)` pairs, with repeated exponents
+    /// added together.
+    pub(crate) fn new(terms: impl IntoIterator<Item = (E, Ring)>) -> Self {
+        let mut res = Self(BTreeMap::new());
+        for (exponent, c) in terms {
+            res.add_term(exponent, c);
+        }
+        res
+    }
+
+    /// Adds `c` to the coefficient of `x^exponent`.
+    pub(crate) fn add_term(&mut self, exponent: E, c: Ring) {
+        match self.0.entry(exponent) {
+            Entry::Vacant(entry) => {
+                if !c.is_zero() {
+                    entry.insert(c);
+                }
+            }
+            Entry::Occupied(mut entry) => {
+                let sum = entry.get().clone() + c;
+                if sum.is_zero() {
+                    entry.remove();
+                } else {
+                    entry.insert(sum);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn get(&self, exponent: E) -> Option<&Ring> {
+        self.0.get(&exponent)
+    }
+
+    /// The terms in increasing order of their exponents.
+    pub(crate) fn iter(&self) -> impl DoubleEndedIterator<Item = (E, &Ring)> {
+        self.0.iter().map(|(&e, c)| (e, c))
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn lowest(&self) -> Option<E> {
+        self.0.keys().next().copied()
+    }
+
+    pub(crate) fn highest(&self) -> Option<E> {
+        self.0.keys().next_back().copied()
+    }
+
+    pub(crate) fn scalar_mul(self, x: Ring) -> Self {
+        Self::new(self.0.into_iter().map(|(e, c)| (e, c * x.clone())))
+    }
+
+    /// The terms with every exponent replaced by `f(exponent)`, for an
+    /// injective `f`.
+    pub(crate) fn map_exponents<F: Ord + Copy>(&self, f: impl Fn(E) -> F) -> Terms<F, Ring> {
+        Terms(self.0.iter().map(|(&e, c)| (f(e), c.clone())).collect())
+    }
+
+    pub(crate) fn values(&self) -> impl Iterator<Item = &Ring> {
+        self.0.values()
+    }
+}
+
+impl<E, Ring> IntoIterator for Terms<E, Ring> {
+    type Item = (E, Ring);
+    type IntoIter = std::collections::btree_map::IntoIter<E, Ring>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<E: Ord + Copy, Ring: CommutativeRing> AddAssign for Terms<E, Ring> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (exponent, c) in rhs.0 {
+            self.add_term(exponent, c);
+        }
+    }
+}
+
+impl<E: Ord + Copy, Ring: CommutativeRing> Neg for Terms<E, Ring> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(self.0.into_iter().map(|(e, c)| (e, -c)).collect())
+    }
+}
+
+impl<E: Ord + Copy + Add<Output = E>, Ring: CommutativeRing> Mul for &Terms<E, Ring> {
+    type Output = Terms<E, Ring>;
+    fn mul(self, rhs: Self) -> Terms<E, Ring> {
+        count!(multiplications, self.0.len() * rhs.0.len());
+        let mut res = Terms(BTreeMap::new());
+        for (&a, x) in &self.0 {
+            for (&b, y) in &rhs.0 {
+                res.add_term(a + b, x.clone() * y.clone());
+            }
+        }
+        res
+    }
+}
+
+/// A polynomial stored sparsely as a map from exponents to non-zero
+/// coefficients, so that `x^100000 + 1` takes two terms instead of a
+/// hundred thousand coefficients.
+///
+/// Arithmetic costs depend on the number of terms rather than the degree:
+/// a product of polynomials with `s` and `t` terms takes `st`
+/// multiplications.
+///
+/// # Example
+///
+/// ```
+/// # use math2::sparse::SparsePolynomial;
+/// # use math2::Polynomial;
+/// let p = SparsePolynomial::<i64>::new([(100_000, 1), (0, 1)]);
+/// let q = SparsePolynomial::new([(100_000, 1), (0, -1)]);
+/// // (x^100000 + 1)(x^100000 - 1) = x^200000 - 1
+/// assert_eq!(SparsePolynomial::new([(200_000, 1), (0, -1)]), p * q);
+/// let small = SparsePolynomial::new([(3, 2), (1, -1)]);
+/// assert_eq!(Polynomial::new(vec![0, -1, 0, 2]), Polynomial::from(small));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SparsePolynomial<Ring> {
+    terms: Terms<usize, Ring>,
+}
+
+impl<Ring: CommutativeRing> SparsePolynomial<Ring> {
+    /// Creates a polynomial from `(exponent, coefficient)` pairs. Repeated
+    /// exponents are added together.
+    pub fn new(terms: impl IntoIterator<Item = (usize, Ring)>) -> Self {
+        Self {
+            terms: Terms::new(terms),
+        }
+    }
+
+    pub fn constant(c: Ring) -> Self {
+        Self::new([(0, c)])
+    }
+
+    /// The polynomial `c x^exponent`.
+    pub fn monomial(c: Ring, exponent: usize) -> Self {
+        Self::new([(exponent, c)])
+    }
+
+    /// The coefficient of `x^exponent`, or `None` if it is zero.
+    pub fn coeff(&self, exponent: usize) -> Option<&Ring> {
+        self.terms.get(exponent)
+    }
+
+    /// The non-zero terms, in increasing order of their exponents.
+    pub fn terms(&self) -> impl Iterator<Item = (usize, &Ring)> {
+        self.terms.iter()
+    }
+
+    /// The number of non-zero terms.
+    pub fn num_terms(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// The degree, or `None` for the zero polynomial.
+    pub fn degree(&self) -> Option<usize> {
+        self.terms.highest()
+    }
+
+    pub fn scalar_mul(self, x: Ring) -> Self {
+        Self {
+            terms: self.terms.scalar_mul(x),
+        }
+    }
+
+    /// Evaluates at `x`, raising `x` to the gaps between consecutive
+    /// exponents by repeated squaring, so large exponents are cheap.
+    pub fn eval(&self, x: &Ring) -> Ring {
+        let mut power = Ring::one();
+        let mut last = 0;
+        let mut res = Ring::zero();
+        for (e, c) in self.terms.iter() {
+            power = power * pow(x, e - last);
+            last = e;
+            res = res + c.clone() * power.clone();
+        }
+        res
+    }
+}
+
+impl<Ring: CommutativeRing + FromUsize> SparsePolynomial<Ring> {
+    pub fn derivative(&self) -> Self {
+        Self::new(
+            self.terms
+                .iter()
+                .filter(|&(e, _)| e > 0)
+                .map(|(e, c)| (e - 1, c.clone() * Ring::from_usize(e))),
+        )
+    }
+}
+
+impl<F: Field + FromUsize + PartialEq> SparsePolynomial<F> {
+    /// The remainder modulo a dense polynomial. Like [`SparsePolynomial::eval`],
+    /// `x` is raised to the gaps between consecutive exponents by repeated
+    /// squaring modulo `modulus`, so the number of multiplications grows with
+    /// the logarithm of the degree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `modulus` is zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::sparse::SparsePolynomial;
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // x^(10^12) + 1 mod (x^2 + 1) is 2, as x^4 = 1 there
+    /// let p = SparsePolynomial::new([(1_000_000_000_000, n(1)), (0, n(1))]);
+    /// let m = Polynomial::new(vec![n(1), n(0), n(1)]);
+    /// assert_eq!(Polynomial::new(vec![n(2)]), p.rem(&m));
+    /// ```
+    pub fn rem(&self, modulus: &Polynomial<F>) -> Polynomial<F> {
+        let x = Polynomial::new(vec![F::zero(), F::one()]);
+        let mut power = Polynomial::one().div_rem(modulus.clone()).1;
+        let mut last = 0;
+        let mut res = Polynomial::zero();
+        for (e, c) in self.terms.iter() {
+            power = (power * x.pow_mod(&BigInt::from(e - last), modulus)).div_rem(modulus.clone()).1;
+            last = e;
+            res += power.clone().scalar_mul(c.clone());
+        }
+        res
+    }
+
+    /// Divides by the leading coefficient. The zero polynomial is left as is.
+    pub fn monic(self) -> Self {
+        match self.terms.highest() {
+            Some(d) => {
+                let lc = self.terms.get(d).unwrap().clone().checked_inv().unwrap();
+                self.scalar_mul(lc)
+            }
+            None => self,
+        }
+    }
+
+    /// Divides by the largest power of `x` dividing this polynomial, returning
+    /// the quotient and the exponent.
+    fn split_x_power(&self) -> (Self, usize) {
+        let low = self.terms.lowest().unwrap_or(0);
+        let terms = self.terms.map_exponents(|e| e - low);
+        (Self { terms }, low)
+    }
+
+    /// `(n, a)` if this is `c(x^n - a)` with `n > 0` and `a` non-zero.
+    fn as_binomial(&self) -> Option<(usize, F)> {
+        let mut terms = self.terms.iter();
+        let ((0, c0), Some((n, cn)), None) = (terms.next()?, terms.next(), terms.next()) else {
+            return None;
+        };
+        Some((n, -(c0.clone() / cn.clone())))
+    }
+
+    /// The monic greatest common divisor.
+    ///
+    /// The powers of `x` are split off first. Two binomials are handled by
+    /// Euclid's algorithm on the exponents, as `x^m` is `b^q x^r` modulo
+    /// `x^n - b` for `m = qn + r`, so `gcd(x^m - 1, x^n - 1) = x^gcd(m, n) - 1`
+    /// takes a handful of steps whatever the degrees. Otherwise the
+    /// polynomial of higher degree is reduced with [`SparsePolynomial::rem`]
+    /// and the rest is dense.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::sparse::SparsePolynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// let p = SparsePolynomial::new([(6_000_000, n(1)), (0, n(-1))]);
+    /// let q = SparsePolynomial::new([(4_000_000, n(2)), (0, n(-2))]);
+    /// assert_eq!(SparsePolynomial::new([(2_000_000, n(1)), (0, n(-1))]), p.gcd(&q));
+    /// ```
+    pub fn gcd(&self, other: &Self) -> Self {
+        if self.is_zero() || other.is_zero() {
+            return (self.clone() + other.clone()).monic();
+        }
+        let ((f, i), (g, j)) = (self.split_x_power(), other.split_x_power());
+        let common = match (f.as_binomial(), g.as_binomial()) {
+            (Some((m, a)), Some((n, b))) => binomial_gcd(m, a, n, b),
+            _ => {
+                let (f, g) = if f.degree() >= g.degree() { (f, g) } else { (g, f) };
+                let g = Polynomial::from(g);
+                let r = f.rem(&g);
+                Self::from(g.gcd(r))
+            }
+        };
+        common * Self::monomial(F::one(), i.min(j))
+    }
+
+    /// Splits this polynomial into a constant and pairwise coprime monic
+    /// square free factors with distinct multiplicities.
+    ///
+    /// A polynomial with a non-zero constant term is `h(x^k)` for the gcd
+    /// `k` of its exponents. Each square free factor `g` of `h` gives a
+    /// square free `g(x^k)` when the characteristic does not divide `k`,
+    /// so only `h` has to be expanded, which has two terms for `x^n ± c`
+    /// and three for `x^2n + a x^n + b`. In characteristic `p`,
+    /// `h(x^(p^s m)) = h'(x^m)^(p^s)` for `h'` with the `p^s`th roots of the
+    /// coefficients, so the multiplicities are scaled by `p^s`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::sparse::SparsePolynomial;
+    /// # use num::BigRational;
+    /// let n = |x: i32| BigRational::from_integer(x.into());
+    /// // 3x^7 (x^1000000 - 1)^2
+    /// let p = SparsePolynomial::new([(2_000_007, n(3)), (1_000_007, n(-6)), (7, n(3))]);
+    /// let sqf = p.square_free_factorization();
+    /// assert_eq!(n(3), sqf.leading_coeff);
+    /// assert_eq!(2, sqf.factors.len());
+    /// assert_eq!((SparsePolynomial::new([(1_000_000, n(1)), (0, n(-1))]), 2), (sqf.factors[0].0.clone(), sqf.factors[0].1.get()));
+    /// assert_eq!((SparsePolynomial::monomial(n(1), 1), 7), (sqf.factors[1].0.clone(), sqf.factors[1].1.get()));
+    /// ```
+    pub fn square_free_factorization(&self) -> SparseSquareFreeFactorization<F> {
+        let Some(degree) = self.degree() else {
+            return SparseSquareFreeFactorization {
+                leading_coeff: F::zero(),
+                factors: Vec::new(),
+            };
+        };
+        let leading_coeff = self.terms.get(degree).unwrap().clone();
+        let (f, zeros) = self.clone().monic().split_x_power();
+        let mut factors = Vec::new();
+        let k = f.terms.iter().fold(0, |k, (e, _)| gcd(k, e));
+        if k > 0 {
+            let p = F::characteristic();
+            let (mut m, mut scale) = (k, 1);
+            let mut h = Polynomial::from(Self { terms: f.terms.map_exponents(|e| e / k) });
+            while p > 0 && m.is_multiple_of(p) {
+                m /= p;
+                scale *= p;
+                h = Polynomial::new(h.coeffs.into_iter().map(F::pth_root).collect());
+            }
+            let scale = NonZeroUsize::new(scale).unwrap();
+            for (g, j) in square_free_monic(h) {
+                let terms = Self::from(g).terms.map_exponents(|e| e * m);
+                factors.push((Self { terms }, j.checked_mul(scale).unwrap()));
+            }
+        }
+        if let Some(zeros) = NonZeroUsize::new(zeros) {
+            match factors.iter_mut().find(|(_, j)| *j == zeros) {
+                Some((g, _)) => *g = g.clone() * Self::monomial(F::one(), 1),
+                None => factors.push((Self::monomial(F::one(), 1), zeros)),
+            }
+        }
+        factors.sort_by_key(|(_, j)| *j);
+        SparseSquareFreeFactorization {
+            leading_coeff,
+            factors,
+        }
+    }
+}
+
+impl SparsePolynomial<BigInt> {
+    /// Factors `x^n - a` into irreducible polynomials with
+    /// [`factor_xn_minus_a`]. Returns `None` for other polynomials, or when
+    /// that needs a general factorization algorithm.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::sparse::SparsePolynomial;
+    /// # use num::BigInt;
+    /// // x^6 - 1 = (x - 1)(x + 1)(x^2 + x + 1)(x^2 - x + 1)
+    /// let p = SparsePolynomial::new([(6, BigInt::from(1)), (0, BigInt::from(-1))]);
+    /// assert_eq!(4, p.factor_binomial().unwrap().len());
+    /// assert_eq!(None, SparsePolynomial::new([(3, BigInt::from(1)), (1, BigInt::from(1))]).factor_binomial());
+    /// ```
+    pub fn factor_binomial(&self) -> Option<Vec<Self>> {
+        let n = self.degree().filter(|&n| n > 0)?;
+        if !self.terms.get(n).unwrap().is_one() || self.terms.iter().any(|(e, _)| e != 0 && e != n) {
+            return None;
+        }
+        let a = -self.coeff(0).cloned().unwrap_or_else(BigInt::zero);
+        Some(factor_xn_minus_a(n, &a)?.into_iter().map(Self::from).collect())
+    }
+}
+
+/// A square free factorization of a [`SparsePolynomial`], from
+/// [`SparsePolynomial::square_free_factorization`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SparseSquareFreeFactorization<F> {
+    pub leading_coeff: F,
+    pub factors: Vec<(SparsePolynomial<F>, NonZeroUsize)>,
+}
+
+/// `gcd(x^m - a, x^n - b)` for non-zero `a` and `b`.
+fn binomial_gcd<F: Field + PartialEq>(mut m: usize, mut a: F, mut n: usize, mut b: F) -> SparsePolynomial<F> {
+    loop {
+        if m < n {
+            (m, a, n, b) = (n, b, m, a);
+        }
+        // x^m - a = b^q x^r - a = b^q (x^r - a / b^q) modulo x^n - b
+        let bq = pow(&b, m / n);
+        if m.is_multiple_of(n) {
+            return if bq == a {
+                SparsePolynomial::new([(n, F::one()), (0, -b)])
+            } else {
+                SparsePolynomial::one()
+            };
+        }
+        (m, a) = (m % n, a / bq);
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn pow<Ring: CommutativeRing>(x: &Ring, mut e: usize) -> Ring {
+    let (mut base, mut res) = (x.clone(), Ring::one());
+    while e > 0 {
+        if e & 1 == 1 {
+            res = res * base.clone();
+        }
+        e >>= 1;
+        if e > 0 {
+            base = base.clone() * base;
+        }
+    }
+    res
+}
+
+impl<Ring: CommutativeRing> From<Polynomial<Ring>> for SparsePolynomial<Ring> {
+    fn from(p: Polynomial<Ring>) -> Self {
+        Self::new(p.coeffs.into_iter().enumerate())
+    }
+}
+
+/// Expands into a dense coefficient vector, which takes memory linear in
+/// the degree.
+impl<Ring: CommutativeRing> From<SparsePolynomial<Ring>> for Polynomial<Ring> {
+    fn from(p: SparsePolynomial<Ring>) -> Self {
+        let mut coeffs = vec![Ring::zero(); p.degree().map_or(0, |d| d + 1)];
+        for (e, c) in p.terms {
+            coeffs[e] = c;
+        }
+        Polynomial::new(coeffs)
+    }
+}
+
+impl<Ring: CommutativeRing> Zero for SparsePolynomial<Ring> {
+    fn zero() -> Self {
+        Self::new([])
+    }
+    fn is_zero(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+impl<Ring: CommutativeRing> One for SparsePolynomial<Ring> {
+    fn one() -> Self {
+        Self::constant(Ring::one())
+    }
+}
+
+impl<Ring: CommutativeRing> Add for SparsePolynomial<Ring> {
+    type Output = Self;
+    fn add(mut self, rhs: Self) -> Self {
+        self += rhs;
+        self
+    }
+}
+
+impl<Ring: CommutativeRing> AddAssign for SparsePolynomial<Ring> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.terms += rhs.terms;
+    }
+}
+
+impl<Ring: CommutativeRing> Neg for SparsePolynomial<Ring> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self { terms: -self.terms }
+    }
+}
+
+impl<Ring: CommutativeRing> Sub for SparsePolynomial<Ring> {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self {
+        self + rhs.neg()
+    }
+}
+
+impl<Ring: CommutativeRing> Mul for SparsePolynomial<Ring> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Self {
+            terms: &self.terms * &rhs.terms,
+        }
+    }
+}
+
+impl<Ring: CommutativeRing> Sum for SparsePolynomial<Ring> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |a, b| a + b)
+    }
+}
+
+impl<Ring: CommutativeRing> Product for SparsePolynomial<Ring> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::one(), |a, b| a * b)
+    }
+}
+
+impl<Ring: CommutativeRing> CommutativeRing for SparsePolynomial<Ring> {
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(!self.is_zero());
+        for (exponent, c) in self.terms.iter() {
+            if exponent == 0 {
+                c.clone().assert_is_unit();
+            } else {
+                assert!(c.is_nilpotent());
+            }
+        }
+        AssertUnit(self)
+    }
+    /// Only constant units are supported, as for [`Polynomial`].
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        assert_eq!(Some(0), x.0.degree(), "inverting non-constant units is unsupported");
+        let c = Ring::invert(&x.0.terms.get(0).unwrap().clone().assert_is_unit());
+        Self::constant(c.into_inner()).assert_is_unit()
+    }
+    fn is_nilpotent(&self) -> bool {
+        self.terms.values().all(Ring::is_nilpotent)
+    }
+}
@@ -0,0 +1,93 @@
+//! Counting the ring operations done by polynomial arithmetic.
+//!
+//! With the `stats` feature, polynomial multiplication, scalar
+//! multiplication, division, contents and gcds record how many coefficient
+//! operations they perform, so algorithms can be compared by the work they
+//! do rather than by wall clock time. Everything built on top of them, such
+//! as factorization or resultants, is counted along the way.
+//!
+//! The counters are per thread, and without the feature the hooks compile to
+//! nothing.
+
+use std::cell::Cell;
+use std::fmt;
+use std::ops::Add;
+
+/// Numbers of ring operations.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct OpCounts {
+    /// Multiplications of coefficients.
+    pub multiplications: u64,
+    /// Divisions of coefficients.
+    pub divisions: u64,
+    /// Gcds, of coefficients when computing a content, or of polynomials.
+    pub gcds: u64,
+}
+
+impl Add for OpCounts {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        Self {
+            multiplications: self.multiplications + rhs.multiplications,
+            divisions: self.divisions + rhs.divisions,
+            gcds: self.gcds + rhs.gcds,
+        }
+    }
+}
+
+impl fmt::Display for OpCounts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} multiplications, {} divisions, {} gcds",
+            self.multiplications, self.divisions, self.gcds
+        )
+    }
+}
+
+thread_local! {
+    static COUNTS: Cell<OpCounts> = const { Cell::new(OpCounts { multiplications: 0, divisions: 0, gcds: 0 }) };
+}
+
+pub(crate) fn record(f: impl FnOnce(&mut OpCounts)) {
+    COUNTS.with(|counts| {
+        let mut c = counts.get();
+        f(&mut c);
+        counts.set(c);
+    });
+}
+
+/// The operations counted on this thread since it started or since the last
+/// [`reset`].
+pub fn counts() -> OpCounts {
+    COUNTS.with(Cell::get)
+}
+
+/// Sets the counters of this thread to zero.
+pub fn reset() {
+    COUNTS.with(|counts| counts.set(OpCounts::default()));
+}
+
+/// Runs `f` and returns its result with the operations it performed on this
+/// thread. The running totals of [`counts`] still include them.
+///
+/// # Example
+///
+/// ```
+/// # use math2::stats::measure;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// let p = Polynomial::new([1, 2, 3].map(BigInt::from).to_vec());
+/// let q = Polynomial::new([4, 5].map(BigInt::from).to_vec());
+/// let (_, counts) = measure(|| p * q);
+/// assert_eq!(6, counts.multiplications);
+/// assert_eq!(0, counts.divisions);
+/// ```
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, OpCounts) {
+    let before = counts();
+    reset();
+    let res = f();
+    let during = counts();
+    COUNTS.with(|counts| counts.set(before + during));
+    (res, during)
+}
@@ -0,0 +1,202 @@
+//! Sums of two squares of rational polynomials.
+//!
+//! `a^2 + b^2 = (a + bi)(a - bi)`, so a rational polynomial is a sum of two
+//! squares exactly when it is the norm `h h̄` of a polynomial `h` over the
+//! Gaussian rationals `Q(i)`. Every irreducible factor over `Q` of odd
+//! multiplicity must then split over `Q(i)` into a pair of conjugate
+//! factors, and the leading coefficient must be a sum of two rational
+//! squares.
+
+use std::num::NonZeroUsize;
+
+use num::complex::Complex;
+use num::{BigInt, BigRational, Integer, One, Signed, Zero};
+
+use crate::factorization::to_integer;
+use crate::number_theory::factor_integer;
+use crate::Polynomial;
+
+type Gaussian = Complex<BigRational>;
+
+/// Why a polynomial `f` is `a^2 + b^2`, from
+/// [`Polynomial::sos_decomposition`]: `a + bi = unit * q_1^k_1 ... q_m^k_m`
+/// over `Q(i)`, and `f = |unit|^2 (q_1 q̄_1)^k_1 ... (q_m q̄_m)^k_m`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SosCertificate {
+    /// A Gaussian rational whose norm is the leading coefficient of `f`.
+    pub unit: Gaussian,
+    /// Monic polynomials over `Q(i)` with their multiplicities, each with
+    /// `q q̄` a rational factor of `f`.
+    pub factors: Vec<(Polynomial<Gaussian>, NonZeroUsize)>,
+}
+
+impl SosCertificate {
+    /// Checks that `a + bi` is the product of the certificate, and that its
+    /// norm is `f`.
+    pub fn verify(&self, f: &Polynomial<BigRational>, a: &Polynomial<BigRational>, b: &Polynomial<BigRational>) -> bool {
+        let h = self
+            .factors
+            .iter()
+            .fold(Polynomial::new_trim_zeroes(vec![self.unit.clone()]), |acc, (q, k)| {
+                acc * q.pow(k.get() as u32)
+            });
+        let norm = h.clone() * conjugate(&h);
+        (a.clone(), b.clone()) == parts(&h) && parts(&norm) == (f.clone(), Polynomial::zero())
+    }
+}
+
+impl Polynomial<BigRational> {
+    /// Writes this polynomial as `a^2 + b^2` for rational polynomials `a`
+    /// and `b`, with a [`SosCertificate`] pairing its complex conjugate
+    /// factors.
+    ///
+    /// Returns `None` if the polynomial is negative somewhere, or if it is
+    /// nonnegative but not a sum of two rational squares, such as `x^2 + 3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// // (x^2 + 1)(x^2 + 4) = (x^2 - 2)^2 + (3x)^2
+    /// let f: Polynomial<BigRational> = "x^4 + 5x^2 + 4".parse().unwrap();
+    /// let (a, b, certificate) = f.sos_decomposition().unwrap();
+    /// assert_eq!(f, a.clone() * a.clone() + b.clone() * b.clone());
+    /// assert!(certificate.verify(&f, &a, &b));
+    /// assert!("x^2 + 3".parse::<Polynomial<BigRational>>().unwrap().sos_decomposition().is_none());
+    /// ```
+    pub fn sos_decomposition(&self) -> Option<(Self, Self, SosCertificate)> {
+        let Some(n) = self.degree() else {
+            let certificate = SosCertificate { unit: Gaussian::zero(), factors: Vec::new() };
+            return Some((Polynomial::zero(), Polynomial::zero(), certificate));
+        };
+        let lc = self.leading_coefficient_cloned();
+        if lc.is_negative() || n % 2 == 1 {
+            return None;
+        }
+        let lcm = self.coeffs.iter().fold(BigInt::one(), |acc, x| acc.lcm(x.denom()));
+        let integer = to_integer(&self.clone().scalar_mul(BigRational::from_integer(lcm))).unwrap();
+        let mut factors = Vec::new();
+        for (p, multiplicity) in integer.factor().factors {
+            let k = multiplicity.get();
+            let monic = |p: &Polynomial<BigInt>| {
+                let lc = BigRational::from_integer(p.leading_coefficient_cloned());
+                complexify(&Polynomial::new(p.coeffs.iter().map(|c| BigRational::from_integer(c.clone()) / &lc).collect()))
+            };
+            if k % 2 == 0 {
+                // p^k is the norm of p^(k / 2)
+                factors.push((monic(&p), NonZeroUsize::new(k / 2).unwrap()));
+            } else {
+                let rational = Polynomial::new(p.coeffs.iter().cloned().map(BigRational::from_integer).collect());
+                if rational.count_real_roots() > 0 {
+                    // a real root of odd multiplicity is a sign change
+                    return None;
+                }
+                factors.push((split(&monic(&p))?, multiplicity));
+            }
+        }
+        let certificate = SosCertificate { unit: two_squares(&lc)?, factors };
+        let h = certificate
+            .factors
+            .iter()
+            .fold(Polynomial::new(vec![certificate.unit.clone()]), |acc, (q, k)| acc * q.pow(k.get() as u32));
+        let (a, b) = parts(&h);
+        Some((a, b, certificate))
+    }
+}
+
+fn complexify(p: &Polynomial<BigRational>) -> Polynomial<Gaussian> {
+    Polynomial::new(p.coeffs.iter().map(|c| Gaussian::new(c.clone(), BigRational::zero())).collect())
+}
+
+fn conjugate(p: &Polynomial<Gaussian>) -> Polynomial<Gaussian> {
+    Polynomial::new(p.coeffs.iter().map(Complex::conj).collect())
+}
+
+/// The real and imaginary parts of `p`.
+fn parts(p: &Polynomial<Gaussian>) -> (Polynomial<BigRational>, Polynomial<BigRational>) {
+    (
+        Polynomial::new_trim_zeroes(p.coeffs.iter().map(|c| c.re.clone()).collect()),
+        Polynomial::new_trim_zeroes(p.coeffs.iter().map(|c| c.im.clone()).collect()),
+    )
+}
+
+/// A monic factor `q` with `p = q q̄`, for a monic `p` with rational
+/// coefficients that is irreducible over `Q`, or `None` if `p` stays
+/// irreducible over `Q(i)`.
+///
+/// This is Trager's algorithm: for a shift `s` with a square free norm
+/// `g(x) = p(x + si) p(x - si)`, the factors of `g` over `Q` are the norms
+/// of the factors of `p(x + si)` over `Q(i)`, so `p` splits exactly when `g`
+/// does, and `q` is the gcd of `p` with a factor of `g` shifted back.
+fn split(p: &Polynomial<Gaussian>) -> Option<Polynomial<Gaussian>> {
+    for s in 1.. {
+        let shift = Gaussian::new(BigRational::zero(), BigRational::from_integer(s.into()));
+        let shifted = p.taylor_shift(&shift);
+        let norm = parts(&(shifted.clone() * conjugate(&shifted))).0;
+        if norm.clone().gcd(norm.clone().derivative()).degree() != Some(0) {
+            continue;
+        }
+        let factors = to_integer(&norm).unwrap().factor().factors;
+        if factors.len() == 1 {
+            return None;
+        }
+        let g = &factors[0].0;
+        let g = complexify(&Polynomial::new(g.coeffs.iter().cloned().map(BigRational::from_integer).collect()));
+        return Some(p.clone().gcd(g.taylor_shift(&-shift)));
+    }
+    unreachable!()
+}
+
+/// A Gaussian rational `u` with `u ū = c`, for a positive rational `c`, if
+/// there is one.
+///
+/// With `c = a / b` in lowest terms, `c = ab / b^2`, and the integer `ab` is
+/// a sum of two squares exactly when its primes `3 mod 4` have even
+/// exponents. The primes `1 mod 4` are norms of Gaussian primes, which are
+/// multiplied together.
+fn two_squares(c: &BigRational) -> Option<Gaussian> {
+    let gaussian = |x: BigInt, y: BigInt| Gaussian::new(BigRational::from_integer(x), BigRational::from_integer(y));
+    let n = c.numer() * c.denom();
+    let mut res = gaussian(BigInt::one(), BigInt::zero());
+    for (p, e) in factor_integer(&n) {
+        let four = BigInt::from(4);
+        let factor = if p == BigInt::from(2) {
+            gaussian(BigInt::one(), BigInt::one())
+        } else if p.mod_floor(&four) == BigInt::from(3) {
+            if e % 2 == 1 {
+                return None;
+            }
+            res *= gaussian(p.pow(e / 2), BigInt::zero());
+            continue;
+        } else {
+            let (x, y) = gaussian_prime(&p);
+            gaussian(x, y)
+        };
+        for _ in 0..e {
+            res *= factor.clone();
+        }
+    }
+    Some(res / BigRational::from_integer(c.denom().clone()))
+}
+
+/// `(x, y)` with `x^2 + y^2 = p`, for a prime `p` congruent to 1 mod 4.
+///
+/// A square root `z` of `-1` modulo `p` is a power of a non-residue, and
+/// the Euclidean algorithm on `p` and `z` reaches `x` as the first
+/// remainder below `sqrt(p)` (Hermite and Serret).
+fn gaussian_prime(p: &BigInt) -> (BigInt, BigInt) {
+    let minus_one = p - 1u32;
+    let quarter = &minus_one / 4u32;
+    let z = (2u32..)
+        .map(|a| BigInt::from(a).modpow(&quarter, p))
+        .find(|z| (z * z) % p == minus_one)
+        .unwrap();
+    let (mut a, mut b) = (p.clone(), z);
+    while &b * &b > *p {
+        let r = &a % &b;
+        a = std::mem::replace(&mut b, r);
+    }
+    let y = (p - &b * &b).sqrt();
+    (b, y)
+}
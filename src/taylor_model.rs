@@ -0,0 +1,201 @@
+//! Taylor models: a polynomial together with an interval bounding the error.
+//!
+//! A Taylor model `(p, r)` of order `n` on a domain `D` stands for every
+//! function `f` with `f(x) - p(x) ∈ r` for all `x ∈ D`, where `p` has degree at
+//! most `n`. Arithmetic on models keeps this property, so starting from models
+//! of known functions one gets verified enclosures of their combinations.
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+use num::{BigRational, Zero};
+
+use crate::interval::Interval;
+use crate::Polynomial;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TaylorModel {
+    polynomial: Polynomial<BigRational>,
+    remainder: Interval,
+    domain: Interval,
+    order: usize,
+}
+
+impl TaylorModel {
+    /// A model of order `order` on `domain`. Terms of `polynomial` above
+    /// `order` are moved into the remainder.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::interval::Interval;
+    /// # use math2::taylor_model::TaylorModel;
+    /// # use math2::Polynomial;
+    /// # use num::BigRational;
+    /// let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    /// let domain = Interval::new(q(0, 1), q(1, 2));
+    /// // exp(x) = 1 + x + x^2/2 + e^c x^3/6 with c in the domain, and e^c < 2
+    /// let exp = TaylorModel::new(
+    ///     Polynomial::new(vec![q(1, 1), q(1, 1), q(1, 2)]),
+    ///     Interval::new(q(0, 1), q(1, 24)),
+    ///     domain,
+    ///     2,
+    /// );
+    /// // so exp(x)^2 is somewhere in here for x in [0, 1/2]
+    /// let square = exp.clone() * exp;
+    /// assert!(square.bound().contains(&q(1, 1)));
+    /// assert!(square.bound().contains(&q(27, 10)));
+    /// ```
+    pub fn new(
+        polynomial: Polynomial<BigRational>,
+        remainder: Interval,
+        domain: Interval,
+        order: usize,
+    ) -> Self {
+        let (polynomial, high) = split_at_order(polynomial, order);
+        let remainder = remainder + high.eval_interval(&domain);
+        Self {
+            polynomial,
+            remainder,
+            domain,
+            order,
+        }
+    }
+
+    /// The exact model of the constant `c`.
+    pub fn constant(c: BigRational, domain: Interval, order: usize) -> Self {
+        Self::new(Polynomial::new_trim_zeroes(vec![c]), zero(), domain, order)
+    }
+
+    /// The exact model of the identity function `x`.
+    pub fn identity(domain: Interval, order: usize) -> Self {
+        let x = Polynomial::from_elem_with_degree(BigRational::from_integer(1.into()), 1);
+        Self::new(x, zero(), domain, order)
+    }
+
+    pub fn polynomial(&self) -> &Polynomial<BigRational> {
+        &self.polynomial
+    }
+
+    pub fn remainder(&self) -> &Interval {
+        &self.remainder
+    }
+
+    pub fn domain(&self) -> &Interval {
+        &self.domain
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// An interval containing the values of every function this model
+    /// stands for, over the whole domain.
+    pub fn bound(&self) -> Interval {
+        self.polynomial.eval_interval(&self.domain) + self.remainder.clone()
+    }
+
+    /// Encloses the value at a point `x` of the domain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `x` is outside of the domain.
+    pub fn eval(&self, x: &BigRational) -> Interval {
+        assert!(self.domain.contains(x), "point outside of the domain");
+        self.polynomial.eval_interval(&Interval::point(x.clone())) + self.remainder.clone()
+    }
+
+    /// A model of `g(f)`, where `f` is any function modeled by `self`.
+    ///
+    /// This is Horner's scheme in Taylor model arithmetic, so the remainder
+    /// picks up the truncation error of every multiplication.
+    pub fn compose(&self, g: &Polynomial<BigRational>) -> Self {
+        let mut acc = Self::constant(BigRational::zero(), self.domain.clone(), self.order);
+        for c in g.coeffs.iter().rev() {
+            acc = acc * self.clone()
+                + Self::constant(c.clone(), self.domain.clone(), self.order);
+        }
+        acc
+    }
+
+    fn assert_compatible(&self, other: &Self) {
+        assert!(
+            self.domain == other.domain && self.order == other.order,
+            "Taylor models must have the same domain and order"
+        );
+    }
+}
+
+fn zero() -> Interval {
+    Interval::point(BigRational::zero())
+}
+
+/// Splits `p` into its terms of degree at most `order` and the rest.
+fn split_at_order(
+    mut p: Polynomial<BigRational>,
+    order: usize,
+) -> (Polynomial<BigRational>, Polynomial<BigRational>) {
+    if p.coeffs.len() <= order + 1 {
+        return (p, Polynomial::zero());
+    }
+    let mut high = p.coeffs.split_off(order + 1);
+    p.trim_zeros();
+    high.splice(0..0, std::iter::repeat_n(BigRational::zero(), order + 1));
+    (p, Polynomial::new(high))
+}
+
+/// # Panics
+///
+/// Panics if the models have different domains or orders, which is also true
+/// for the other arithmetic operations.
+impl Add for TaylorModel {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self::Output {
+        self.assert_compatible(&rhs);
+        Self {
+            polynomial: self.polynomial + rhs.polynomial,
+            remainder: self.remainder + rhs.remainder,
+            domain: self.domain,
+            order: self.order,
+        }
+    }
+}
+
+impl Neg for TaylorModel {
+    type Output = Self;
+    fn neg(self) -> Self::Output {
+        Self {
+            polynomial: -self.polynomial,
+            remainder: -self.remainder,
+            domain: self.domain,
+            order: self.order,
+        }
+    }
+}
+
+impl Sub for TaylorModel {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + -rhs
+    }
+}
+
+/// `(p1 + r1)(p2 + r2) = p1 p2 + (p1 r2 + p2 r1 + r1 r2)`, where the terms of
+/// `p1 p2` above the order are bounded and moved into the remainder too.
+impl Mul for TaylorModel {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self::Output {
+        self.assert_compatible(&rhs);
+        let left = self.polynomial.eval_interval(&self.domain);
+        let right = rhs.polynomial.eval_interval(&self.domain);
+        let remainder = left * rhs.remainder.clone()
+            + right * self.remainder.clone()
+            + self.remainder * rhs.remainder;
+        Self::new(
+            self.polynomial * rhs.polynomial,
+            remainder,
+            self.domain,
+            self.order,
+        )
+    }
+}
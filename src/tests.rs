@@ -1,3 +1,5 @@
+use num::Zero;
+
 use crate::Polynomial;
 
 macro_rules! v {
@@ -42,6 +44,23 @@ pub fn test_div() {
     assert_eq!(r, Polynomial::new(vec![424]));
 }
 
+#[test]
+pub fn test_into_iter() {
+    // the coefficients in order of increasing degree
+    let p = Polynomial::<i64>::new(vec![3, 0, -5, 1]);
+    let iter = p.clone().into_iter();
+    assert_eq!(4, iter.len());
+    assert_eq!(vec![3, 0, -5, 1], iter.collect::<Vec<_>>());
+    assert_eq!(vec![1, -5, 0, 3], p.clone().into_iter().rev().collect::<Vec<_>>());
+    let mut sum = 0;
+    for c in p.clone() {
+        sum += c;
+    }
+    assert_eq!(-1, sum);
+    assert_eq!(p, p.clone().into_iter().collect());
+    assert_eq!(None, Polynomial::<i64>::new(vec![]).into_iter().next());
+}
+
 #[test]
 pub fn test_gcd() {
     let u = Polynomial::new(v![3, -6, -2, 17, 4, -3, 7, 5, 1]);
@@ -75,3 +94,3879 @@ pub fn test_square_free_factorization() {
         sqf.print_with_var("x").to_string().as_str()
     );
 }
+
+#[test]
+pub fn test_print_with_var() {
+    // the variable name is borrowed, so it can come from a local string
+    let var = format!("t{}", 1);
+    let p = Polynomial::new(v![-2, 0, 1]);
+    assert_eq!("t1^2 - 2", p.print_with_var(&var).to_string());
+    let sqf = (p.clone() * p).square_free_factorization();
+    assert_eq!("(t1^2 - 2)^2", sqf.print_with_var(&var).to_string());
+}
+
+#[test]
+pub fn test_square_free_contents() {
+    use num::{BigRational, One};
+
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    // (2/3)(x/2 - 1/4)(3x + 6)^2 = (3/2)(2x - 1)(x + 2)^2
+    let linear = Polynomial::new(vec![q(-1, 4), q(1, 2)]);
+    let square = Polynomial::new(vec![q(6, 1), q(3, 1)]);
+    let u = (linear * square.clone() * square).scalar_mul(q(2, 3));
+    let sqf = u.clone().square_free_factorization();
+    assert_eq!(q(3, 2), sqf.leading_coeff);
+    let factors: Vec<_> = sqf.factors.iter().map(|(f, j)| (f.clone(), j.get())).collect();
+    assert_eq!(vec![(Polynomial::new(v![-1, 2]), 1), (Polynomial::new(v![2, 1]), 2)], factors);
+    for (f, _) in &sqf.factors {
+        assert!(f.coeffs.iter().all(|c| c.is_integer()));
+        assert!(f.clone().content().is_one());
+    }
+    let product = sqf.factors.iter().fold(Polynomial::new(vec![sqf.leading_coeff.clone()]), |acc, (f, j)| {
+        (0..j.get()).fold(acc, |acc, _| acc * f.clone())
+    });
+    assert_eq!(u, product);
+}
+
+#[test]
+pub fn test_sheffer_sequence() {
+    use crate::umbral::{sheffer_sequence, Operator};
+    use num::{BigRational, One};
+
+    // with S = E, the Sheffer sequence for Δ is the falling factorials of x - 1
+    let seq = sheffer_sequence(
+        &Operator::forward_difference(),
+        &Operator::shift(BigRational::one()),
+        2,
+    );
+    assert_eq!(seq[0], Polynomial::new(v![1]));
+    assert_eq!(seq[1], Polynomial::new(v![-1, 1]));
+    assert_eq!(seq[2], Polynomial::new(v![2, -3, 1]));
+}
+
+#[test]
+pub fn test_q_analogs() {
+    use crate::combinatorics::{q_binomial, q_factorial, q_pochhammer};
+    use num::BigInt;
+
+    let int_poly = |v: &[i32]| Polynomial::new(v.iter().copied().map(BigInt::from).collect());
+
+    assert_eq!(q_binomial(5, 2), q_binomial(5, 3));
+    assert_eq!(int_poly(&[1]), q_binomial(3, 0));
+    assert!(q_binomial(2, 3).is_zero());
+    assert_eq!(
+        q_factorial(5),
+        q_binomial(5, 2) * q_factorial(2) * q_factorial(3)
+    );
+
+    let q = int_poly(&[0, 1]);
+    let one_minus_q = int_poly(&[1, -1]);
+    assert_eq!(
+        q_pochhammer(&q, 3),
+        q_factorial(3) * one_minus_q.clone() * one_minus_q.clone() * one_minus_q
+    );
+}
+
+#[test]
+pub fn test_partitions() {
+    use crate::combinatorics::{partition_count, restricted_partition_count};
+    use num::BigInt;
+
+    let all_parts: Vec<_> = (1..=30).collect();
+    for n in 0..=30 {
+        assert_eq!(
+            partition_count(n),
+            restricted_partition_count(&all_parts, false, n)
+        );
+    }
+
+    // partitions into distinct parts and into odd parts are equinumerous
+    let odd_parts: Vec<_> = (1..=30).step_by(2).collect();
+    for n in 0..=30 {
+        assert_eq!(
+            restricted_partition_count(&all_parts, true, n),
+            restricted_partition_count(&odd_parts, false, n)
+        );
+    }
+
+    assert_eq!(BigInt::from(0), restricted_partition_count(&[2], false, 3));
+}
+
+#[test]
+pub fn test_eval_with_derivatives() {
+    use num::{BigRational, One};
+
+    let u = Polynomial::new(v![-16, -24, -4, 10, 6, 1]);
+    let x = BigRational::new(1.into(), 3.into());
+    let mut derivative = u.clone();
+    let values = u.eval_with_derivatives(&x, 6);
+    for value in values {
+        let horner = derivative
+            .coeffs
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * &x + c);
+        assert_eq!(horner, value);
+        derivative = derivative.derivative();
+    }
+
+    assert_eq!(
+        vec![BigRational::one(), BigRational::zero()],
+        Polynomial::new(v![1]).eval_with_derivatives(&x, 1)
+    );
+    assert!(Polynomial::<BigRational>::zero()
+        .eval_with_derivatives(&x, 2)
+        .iter()
+        .all(Zero::is_zero));
+}
+
+#[test]
+pub fn test_invariants() {
+    use num::BigRational;
+
+    let n = |x: i32| BigRational::from_integer(x.into());
+
+    // x^3 + px + q has discriminant -4p^3 - 27q^2
+    let cubic = Polynomial::new(v![5, -2, 0, 1]);
+    assert_eq!(Some(n(32 - 675)), cubic.discriminant());
+
+    // a repeated root makes the discriminant vanish
+    let u = Polynomial::new(v![-16, -24, -4, 10, 6, 1]);
+    assert_eq!(Some(n(0)), u.discriminant());
+
+    let f = Polynomial::new(v![3, -6, -2, 17, 4]);
+    let inv = f.invariants().unwrap();
+    assert_eq!(4, inv.degree);
+    assert_eq!(
+        inv.bezoutian_determinant,
+        inv.discriminant.clone() * n(16)
+    );
+    assert_eq!(inv.reversal_resultant, f.resultant(&f.reversal()));
+
+    // x - 2 and its reversal 1 - 2x
+    let linear = Polynomial::new(v![-2, 1]);
+    assert_eq!(n(-3), linear.resultant(&linear.reversal()));
+
+    assert_eq!(None, Polynomial::new(v![3]).invariants());
+}
+
+#[test]
+pub fn test_cyclotomic_factorizations() {
+    use crate::cyclotomic::{factor_xn_minus_1, factor_xn_minus_a, factor_xn_plus_1};
+    use num::{BigInt, One};
+
+    let binomial = |n: usize, a: i32| {
+        Polynomial::from_elem_with_degree(BigInt::one(), n)
+            - Polynomial::new(vec![BigInt::from(a)])
+    };
+
+    let factors = factor_xn_minus_1(12);
+    assert_eq!(6, factors.len());
+    assert_eq!(binomial(12, 1), factors.into_iter().product());
+
+    let factors = factor_xn_plus_1(6);
+    assert_eq!(2, factors.len());
+    assert_eq!(binomial(6, -1), factors.into_iter().product());
+
+    for (n, a, count) in [(6, 64, 4), (4, -4, 2), (8, 4, 2), (3, 5, 1), (12, 1, 6), (2, 0, 2)] {
+        let factors = factor_xn_minus_a(n, &BigInt::from(a)).unwrap();
+        assert_eq!(count, factors.len(), "x^{n} - {a}");
+        assert_eq!(binomial(n, a), factors.into_iter().product());
+    }
+
+    assert_eq!(None, factor_xn_minus_a(6, &BigInt::from(8)));
+}
+
+#[test]
+pub fn test_decimal_period() {
+    use crate::number_theory::{decimal_period, multiplicative_order, Expansion};
+    use num::{BigInt, BigRational};
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    let expansion = |pre_period, period: i32| Expansion {
+        pre_period,
+        period: BigInt::from(period),
+    };
+
+    assert_eq!(expansion(0, 6), decimal_period(&q(1, 7)));
+    assert_eq!(expansion(3, 0), decimal_period(&q(-3, 8)));
+    assert_eq!(expansion(0, 0), decimal_period(&q(5, 1)));
+    // 1/28 = 0.03(571428)
+    assert_eq!(expansion(2, 6), decimal_period(&q(1, 28)));
+    // 1/97 has the longest possible period
+    assert_eq!(expansion(0, 96), decimal_period(&q(1, 97)));
+
+    assert_eq!(
+        Some(BigInt::from(1)),
+        multiplicative_order(&BigInt::from(1), &BigInt::from(9))
+    );
+}
+
+#[test]
+pub fn test_digit_polynomials() {
+    use crate::radix::{digit_polynomial, digit_sum, from_digit_polynomial};
+    use num::BigInt;
+
+    let base = BigInt::from(7);
+    for n in [-1000, -1, 0, 1, 6, 7, 49, 123456] {
+        let n = BigInt::from(n);
+        assert_eq!(n, from_digit_polynomial(&digit_polynomial(&n, &base), &base));
+    }
+    assert!(digit_polynomial(&BigInt::from(0), &base).is_zero());
+    assert_eq!(BigInt::from(9), digit_sum(&BigInt::from(-1233), &BigInt::from(10)));
+
+    // Kronecker substitution: multiply polynomials by multiplying integers
+    let a = Polynomial::new([3, 1, 4].map(BigInt::from).to_vec());
+    let b = Polynomial::new([1, 5, 9, 2].map(BigInt::from).to_vec());
+    let base = BigInt::from(1000);
+    let packed = from_digit_polynomial(&a, &base) * from_digit_polynomial(&b, &base);
+    assert_eq!(a * b, digit_polynomial(&packed, &base));
+}
+
+#[test]
+pub fn test_eval_decimal() {
+    use crate::decimal::to_decimal_string;
+    use crate::interval::Interval;
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+
+    let u = Polynomial::new(v![24, 132, 90, -525, -750]);
+    for x in [q(1, 3), q(-7, 11), q(22, 7), q(0, 1)] {
+        let exact = u
+            .coeffs
+            .iter()
+            .rev()
+            .fold(BigRational::zero(), |acc, c| acc * &x + c);
+        assert_eq!(to_decimal_string(&exact, 20), u.eval_decimal(&x, 20));
+        assert!(u.eval_interval(&Interval::point(x.clone())).contains(&exact));
+    }
+
+    // 9/8 * 1/9 is exactly 0.125, which only exact evaluation can round
+    let p = Polynomial::new(vec![q(0, 1), q(9, 8)]);
+    assert_eq!("0.13", p.eval_decimal(&q(1, 9), 2));
+    assert_eq!("-0.13", p.eval_decimal(&q(-1, 9), 2));
+    assert_eq!("0", p.eval_decimal(&q(1, 9), 0));
+
+    let range = p.eval_interval(&Interval::new(q(-1, 1), q(2, 1)));
+    assert_eq!(&q(-9, 8), range.lo());
+    assert_eq!(&q(9, 4), range.hi());
+}
+
+#[test]
+pub fn test_implicitize() {
+    use crate::resultant::implicitize_rational;
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+
+    // the unit circle as ((1 - t^2) / (1 + t^2), 2t / (1 + t^2))
+    let den = Polynomial::new(v![1, 0, 1]);
+    let f = implicitize_rational(
+        &Polynomial::new(v![1, 0, -1]),
+        &den,
+        &Polynomial::new(v![0, 2]),
+        &den,
+    );
+    let circle = Polynomial::new(vec![
+        Polynomial::new(v![-1, 0, 1]),
+        Polynomial::zero(),
+        Polynomial::new(v![1]),
+    ]);
+    let c = f.coeff_at(2).coeff_at(0).clone();
+    assert!(!c.is_zero());
+    assert_eq!(circle.scalar_mul(Polynomial::new(vec![c])), f);
+
+    // and the equation vanishes on points of the curve
+    let t = q(3, 5);
+    let d = &t * &t + q(1, 1);
+    let (x, y) = ((q(1, 1) - &t * &t) / &d, (q(2, 1) * &t) / &d);
+    let value = f.coeffs.iter().rev().fold(BigRational::zero(), |acc, p| {
+        acc * &y + p.coeffs.iter().rev().fold(BigRational::zero(), |acc, c| acc * &x + c)
+    });
+    assert!(value.is_zero());
+}
+
+#[test]
+pub fn test_least_squares_fit() {
+    use crate::fitting::least_squares_fit;
+    use num::BigRational;
+
+    let n = |x: i32| BigRational::from_integer(x.into());
+
+    // points on a cubic are fitted exactly once the degree allows it
+    let u = Polynomial::new(v![3, -1, 0, 2]);
+    let points: Vec<_> = (-3..=3)
+        .map(|x| {
+            let x = n(x);
+            let y = u.coeffs.iter().rev().fold(n(0), |acc, c| acc * &x + c);
+            (x, y)
+        })
+        .collect();
+    let fit = least_squares_fit(&points, 3).unwrap();
+    assert_eq!(u, fit.polynomial);
+    assert!(fit.residuals.iter().all(Zero::is_zero));
+
+    // a lower degree fit has residuals orthogonal to the powers of x
+    let fit = least_squares_fit(&points, 1).unwrap();
+    for k in 0..=1 {
+        let dot: BigRational = points
+            .iter()
+            .zip(&fit.residuals)
+            .map(|((x, _), r)| x.pow(k) * r)
+            .sum();
+        assert!(dot.is_zero());
+    }
+
+    let repeated = vec![(n(1), n(2)), (n(1), n(3))];
+    assert_eq!(None, least_squares_fit(&repeated, 1));
+}
+
+#[test]
+pub fn test_natural_cubic_spline() {
+    use crate::fitting::natural_cubic_spline;
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+
+    let points = vec![
+        (q(-1, 1), q(2, 1)),
+        (q(0, 1), q(0, 1)),
+        (q(1, 2), q(3, 1)),
+        (q(3, 1), q(-1, 3)),
+    ];
+    let spline = natural_cubic_spline(&points);
+    assert_eq!(3, spline.pieces().len());
+    for (x, y) in &points {
+        assert_eq!(Some(y.clone()), spline.eval(x));
+    }
+    assert_eq!(None, spline.eval(&q(4, 1)));
+
+    // first and second derivatives are continuous at the interior points
+    let d1 = spline.clone().derivative();
+    let d2 = d1.clone().derivative();
+    for (i, (x, _)) in points.iter().enumerate().take(3).skip(1) {
+        for d in [&d1, &d2] {
+            let eval = |p: &Polynomial<BigRational>| {
+                p.coeffs.iter().rev().fold(q(0, 1), |acc, c| acc * x + c)
+            };
+            assert_eq!(eval(&d.pieces()[i - 1]), eval(&d.pieces()[i]));
+        }
+    }
+    // natural boundary conditions
+    assert_eq!(Some(q(0, 1)), d2.eval(&q(-1, 1)));
+    assert_eq!(Some(q(0, 1)), d2.eval(&q(3, 1)));
+}
+
+#[test]
+pub fn test_piecewise_polynomial() {
+    use crate::piecewise::PiecewisePolynomial;
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+
+    // |x| on [-1, 1] and a step at 1/2
+    let abs = PiecewisePolynomial::new(
+        vec![q(-1, 1), q(0, 1), q(1, 1)],
+        vec![Polynomial::new(v![0, -1]), Polynomial::new(v![0, 1])],
+    );
+    let step = PiecewisePolynomial::new(
+        vec![q(-1, 1), q(1, 2), q(1, 1)],
+        vec![Polynomial::zero(), Polynomial::new(v![1])],
+    );
+    assert_eq!(
+        "x^2 for -1 <= x < 0\nx^2 for 0 <= x < 1/2\nx^2 + 1 for 1/2 <= x <= 1",
+        (abs.clone() * abs.clone() + step.clone())
+            .print_with_var("x")
+            .to_string()
+    );
+
+    let product = abs.clone() * step.clone();
+    assert_eq!(4, product.breakpoints().len());
+    assert_eq!(Some(q(0, 1)), product.eval(&q(1, 4)));
+    assert_eq!(Some(q(3, 4)), product.eval(&q(3, 4)));
+    assert_eq!(Some(q(-1, 1)), (step - abs.clone()).eval(&q(-1, 1)));
+
+    // the integral of |x| is continuous with total area 1
+    let integral = abs.integral();
+    assert_eq!(Some(q(1, 2)), integral.eval(&q(0, 1)));
+    assert_eq!(Some(q(1, 2) - q(1, 8)), integral.eval(&q(-1, 2)));
+    assert_eq!(q(1, 1), abs.definite_integral());
+    assert_eq!(abs, integral.derivative());
+}
+
+#[test]
+pub fn test_taylor_model() {
+    use crate::interval::Interval;
+    use crate::taylor_model::TaylorModel;
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    let domain = Interval::new(q(-1, 1), q(1, 1));
+
+    // x^3 does not fit in order 2, so the whole term ends up in the remainder
+    let x = TaylorModel::identity(domain.clone(), 2);
+    let cube = x.compose(&Polynomial::new(v![1, 0, 1, 1]));
+    assert_eq!(&Polynomial::new(v![1, 0, 1]), cube.polynomial());
+    assert_eq!(&domain, cube.remainder());
+    assert_eq!(Interval::new(q(0, 1), q(2, 1)), cube.eval(&q(0, 1)));
+
+    // sin(x) = x - x^3/6 + r with |r| <= 1/120 on [-1, 1]
+    let sin = TaylorModel::new(
+        Polynomial::new(vec![q(0, 1), q(1, 1), q(0, 1), q(-1, 6)]),
+        Interval::new(q(-1, 120), q(1, 120)),
+        domain.clone(),
+        3,
+    );
+    let cos = TaylorModel::new(
+        Polynomial::new(vec![q(1, 1), q(0, 1), q(-1, 2)]),
+        Interval::new(q(0, 1), q(1, 24)),
+        domain,
+        3,
+    );
+    // sin^2 + cos^2 = 1, and the truncated parts cancel exactly
+    let one = sin.clone() * sin + cos.clone() * cos;
+    assert_eq!(&Polynomial::new(v![1]), one.polynomial());
+    assert!(one.remainder().contains(&q(0, 1)));
+    assert!(one.eval(&q(1, 3)).contains(&q(1, 1)));
+}
+
+#[test]
+pub fn test_chebyshev_approximate() {
+    use crate::approximation::{chebyshev_approximate, chebyshev_to_monomial};
+
+    // T_5 = 16x^5 - 20x^3 + 5x
+    assert_eq!(
+        Polynomial::new(vec![0, 5, 0, -20, 0, 16]),
+        chebyshev_to_monomial(&[0i64, 0, 0, 0, 0, 1])
+    );
+
+    // polynomials of low enough degree are reproduced
+    let approx = chebyshev_approximate(|x| x * x * x - 2.0 * x, 4, -1.0..=3.0);
+    let expected = [0.0, -2.0, 0.0, 1.0];
+    assert_eq!(5, approx.polynomial.coeffs.len());
+    for (c, e) in approx.polynomial.coeffs.iter().zip(expected.iter().chain(&[0.0])) {
+        assert!((c - e).abs() < 1e-12, "{c} != {e}");
+    }
+    assert!(approx.error_estimate < 1e-12);
+    assert!(approx.chebyshev_coeffs[4].abs() < 1e-12);
+
+    // the error shrinks quickly with the degree for smooth functions
+    let low = chebyshev_approximate(f64::sin, 3, 0.0..=2.0).error_estimate;
+    let high = chebyshev_approximate(f64::sin, 9, 0.0..=2.0).error_estimate;
+    assert!(low > 1e-4 && high < 1e-9);
+}
+
+#[test]
+pub fn test_remez() {
+    use crate::approximation::{chebyshev_approximate, remez};
+
+    let exp = remez(f64::exp, 3, -1.0..=1.0);
+    let chebyshev = chebyshev_approximate(f64::exp, 3, -1.0..=1.0);
+    assert!(exp.error < chebyshev.error_estimate);
+    // the error equioscillates on 5 points
+    let errors: Vec<f64> = exp
+        .reference
+        .iter()
+        .map(|&x| x.exp() - exp.polynomial.coeffs.iter().rev().fold(0.0, |acc, c| acc * x + c))
+        .collect();
+    assert_eq!(5, errors.len());
+    for e in &errors {
+        assert!((e.abs() - exp.error).abs() < 1e-6 * exp.error);
+    }
+    assert!(errors.windows(2).all(|w| w[0].signum() == -w[1].signum()));
+    assert!((exp.error - 5.5e-3).abs() < 1e-4);
+
+    let line = remez(|x| x * x, 1, 0.0..=1.0);
+    let expected = [-0.125, 1.0];
+    for (c, e) in line.polynomial.coeffs.iter().zip(&expected) {
+        assert!((c - e).abs() < 1e-6, "{c} != {e}");
+    }
+    assert!(exp.converged && line.converged);
+
+    // a polynomial of low degree is its own minimax approximation
+    let f = |x: f64| 3.0 * x * x - x + 0.5;
+    for degree in [2, 4] {
+        let exact = remez(f, degree, -2.0..=1.0);
+        assert!(exact.converged);
+        assert!(exact.error < 1e-12);
+        for (c, e) in exact.polynomial.coeffs.iter().zip(&[0.5, -1.0, 3.0]) {
+            assert!((c - e).abs() < 1e-12, "{c} != {e}");
+        }
+    }
+    assert!(!remez(|_| f64::NAN, 2, 0.0..=1.0).converged);
+}
+
+#[test]
+pub fn test_sup_norm_difference() {
+    use crate::approximation::{remez, sup_norm_difference};
+    use crate::interval::Interval;
+    use crate::real_roots::RealAlgebraic;
+    use num::BigRational;
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let parse = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+    let unit = Interval::new(q(-1, 1), q(1, 1));
+
+    // the best line through x^3 on [-1, 1] is 3x/4, with error exactly 1/4
+    let cube = parse("x^3");
+    let line = remez(|x| x * x * x, 1, -1.0..=1.0).polynomial.to_rational_with_tolerance(1e-9).unwrap();
+    assert_eq!(Polynomial::new(vec![q(0, 1), q(3, 4)]), line);
+    assert_eq!(RealAlgebraic::from(q(1, 4)), sup_norm_difference(&cube, &line, &unit));
+
+    // |x^3 - x| is largest at 1/√3 on [0, 1], where it is 2 / (3√3)
+    let error = sup_norm_difference(&cube, &parse("x"), &Interval::new(q(0, 1), q(1, 1)));
+    assert_eq!(parse("27x^2 - 4").real_roots().pop().unwrap(), error);
+    // but at the ends of [1, 2]
+    assert_eq!(RealAlgebraic::from(q(6, 1)), sup_norm_difference(&cube, &parse("x"), &Interval::new(q(1, 1), q(2, 1))));
+    // the sign of the difference does not matter
+    assert_eq!(RealAlgebraic::from(q(2, 1)), sup_norm_difference(&parse("x^2 - 2"), &parse("0"), &unit));
+    assert_eq!(RealAlgebraic::from(q(0, 1)), sup_norm_difference(&cube, &cube, &unit));
+    assert_eq!(RealAlgebraic::from(q(1, 8)), sup_norm_difference(&cube, &parse("0"), &Interval::point(q(1, 2))));
+}
+
+#[test]
+pub fn test_codegen() {
+    use crate::codegen::Scheme;
+
+    let p = Polynomial::new(vec![1.0, 2.0, -3.0, 0.5, 1e-3]);
+    assert_eq!(
+        "pub fn p(x: f64) -> f64 {
+    let a0 = f64::mul_add(2.0_f64, x, 1.0_f64);
+    let a1 = f64::mul_add(0.5_f64, x, -3.0_f64);
+    let a2 = 0.001_f64;
+    let x2 = x * x;
+    let b0 = f64::mul_add(a1, x2, a0);
+    let x4 = x2 * x2;
+    let c0 = f64::mul_add(a2, x4, b0);
+    c0
+}
+",
+        p.emit_rust("p", Scheme::Estrin)
+    );
+
+    let horner = p.to_closure(Scheme::Horner);
+    let estrin = p.to_closure(Scheme::Estrin);
+    for x in [-2.0, -0.3, 0.0, 0.7, 5.0] {
+        let expected = p.coeffs.iter().rev().fold(0.0, |acc, c| acc * x + c);
+        assert!((horner(x) - expected).abs() <= 1e-12 * expected.abs().max(1.0));
+        assert!((estrin(x) - expected).abs() <= 1e-12 * expected.abs().max(1.0));
+    }
+    assert_eq!(0.0, Polynomial::new(vec![]).to_closure(Scheme::Estrin)(3.0));
+}
+
+#[cfg(feature = "batch")]
+#[test]
+pub fn test_eval_many_f64() {
+    let p = Polynomial::new(vec![0.5, -1.0, 0.25, 2.0]);
+    let xs: Vec<f64> = (0..21).map(|i| i as f64 / 4.0 - 2.0).collect();
+    let mut out = vec![0.0; xs.len()];
+    p.eval_many_f64(&xs, &mut out);
+    for (x, y) in xs.iter().zip(&out) {
+        let expected = p.coeffs.iter().rev().fold(0.0, |acc, c| acc * x + c);
+        assert_eq!(expected, *y);
+        assert!((p.eval_estrin(*x) - expected).abs() < 1e-12);
+    }
+}
+
+#[test]
+pub fn test_to_rational_with_tolerance() {
+    use crate::approximation::remez;
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+
+    // the minimax line for x^2 on [0, 1] is x - 1/8, up to the grid accuracy
+    let line = remez(|x| x * x, 1, 0.0..=1.0);
+    let exact = line.polynomial.to_rational_with_tolerance(1e-4).unwrap();
+    assert_eq!(Polynomial::new(vec![q(-1, 8), q(1, 1)]), exact);
+
+    let pi = Polynomial::new(vec![std::f64::consts::PI]);
+    let rounded = |eps| pi.to_rational_with_tolerance(eps).unwrap();
+    assert_eq!(Polynomial::new(vec![q(3, 1)]), rounded(0.5));
+    assert_eq!(Polynomial::new(vec![q(22, 7)]), rounded(1e-2));
+    assert_eq!(Polynomial::new(vec![q(355, 113)]), rounded(1e-6));
+    // with no tolerance the exact value of the float comes back
+    let exact = BigRational::from_float(std::f64::consts::PI).unwrap();
+    assert_eq!(Polynomial::new(vec![exact]), rounded(0.0));
+
+    assert!(Polynomial::new(vec![f64::NAN]).to_rational_with_tolerance(1.0).is_none());
+}
+
+#[test]
+pub fn test_rational_gcd() {
+    use crate::traits::CoefficientDomain;
+    use num::BigRational;
+
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+
+    // gcd of the numerators over lcm of the denominators
+    assert_eq!(q(2, 9), CoefficientDomain::gcd(&q(2, 3), &q(4, 9)));
+    assert_eq!(q(2, 1), CoefficientDomain::gcd(&q(-6, 1), &q(4, 1)));
+    assert_eq!(q(3, 4), CoefficientDomain::gcd(&q(0, 1), &q(-3, 4)));
+    assert_eq!(q(1, 35), CoefficientDomain::gcd(&q(3, 5), &q(2, 7)));
+
+    // so the content turns a rational polynomial into a primitive integer one
+    let p = Polynomial::new(vec![q(1, 2), q(1, 3), q(-5, 6)]);
+    let content = p.clone().content();
+    assert_eq!(q(1, 6), content);
+    assert_eq!(Polynomial::new(vec![q(3, 1), q(2, 1), q(-5, 1)]), p.scalar_mul(content.recip()));
+}
+
+#[test]
+pub fn test_integer_polynomial_gcd() {
+    use crate::traits::CoefficientDomain;
+    use num::BigInt;
+
+    let z = |coeffs: &[i32]| {
+        Polynomial::new_trim_zeroes(coeffs.iter().map(|&c| BigInt::from(c)).collect())
+    };
+
+    // 6(x^2 - 1)(2x + 3) and -4(x - 1)(2x + 3)
+    let a = z(&[-18, -12, 18, 12]);
+    let b = z(&[12, -4, -8]);
+    assert_eq!(z(&[-6, 2, 4]), CoefficientDomain::gcd(&a, &b));
+    assert_eq!(z(&[3, 2]), a.divide_exact(&z(&[-6, 0, 6])));
+    assert_eq!(z(&[1]), CoefficientDomain::gcd(&z(&[1, 1]), &z(&[-1, 1])));
+    assert_eq!(z(&[2, 1]), CoefficientDomain::gcd(&z(&[0]), &z(&[-2, -1])));
+
+    // the unit of a polynomial is a constant
+    let (unit, normal) = z(&[1, -1]).unit_and_normal();
+    assert_eq!(z(&[-1]), unit.into_inner());
+    assert_eq!(z(&[-1, 1]), normal);
+
+    // bivariate: gcd((x + y)(x - y), (x + y)^2 y) = x + y, with y outermost
+    let x = Polynomial::new(vec![z(&[0, 1])]);
+    let y = Polynomial::new(vec![Polynomial::zero(), z(&[1])]);
+    let sum = x.clone() + y.clone();
+    let a = sum.clone() * (x - y.clone());
+    let b = sum.clone() * sum.clone() * y;
+    assert_eq!(sum, CoefficientDomain::gcd(&a, &b));
+}
+
+#[test]
+pub fn test_kronecker() {
+    use num::BigInt;
+
+    let z = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+
+    // -6x^3 + 6 = -6 (x - 1)(x^2 + x + 1)
+    let p = z(&[6, 0, 0, -6]);
+    let factors = p.clone().kronecker().factors().to_vec();
+    assert_eq!(z(&[-6]), factors[0]);
+    assert_eq!(3, factors.len());
+    assert!(factors.contains(&z(&[-1, 1])));
+    assert!(factors.contains(&z(&[1, 1, 1])));
+
+    // (2x + 1)^2 (x^2 + 3) (x^3 - x - 1)
+    let q = z(&[1, 2]) * z(&[1, 2]) * z(&[3, 0, 1]) * z(&[-1, -1, 0, 1]);
+    let mut factors = q.clone().kronecker().factors().to_vec();
+    assert_eq!(q, factors.iter().cloned().product());
+    factors.sort_by_key(|f| f.coeffs.clone());
+    assert_eq!(
+        vec![z(&[-1, -1, 0, 1]), z(&[1, 2]), z(&[1, 2]), z(&[3, 0, 1])],
+        factors
+    );
+
+    // irreducible polynomials come back unchanged
+    for p in [z(&[1, 0, 0, 0, 1]), z(&[-2, 0, 0, 1]), z(&[5, 3])] {
+        assert_eq!(vec![p.clone()], p.kronecker().factors());
+    }
+}
+
+#[test]
+pub fn test_kronecker_factors() {
+    use num::BigInt;
+
+    let z = |coeffs: &[i32]| Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect());
+
+    // the content and sign come first, and the factors multiply back
+    for (p, expected) in [
+        (z(&[]), vec![z(&[])]),
+        (z(&[12]), vec![z(&[12])]),
+        (z(&[4, 2]), vec![z(&[2]), z(&[2, 1])]),
+        (z(&[-1, -1]), vec![z(&[-1]), z(&[1, 1])]),
+        (z(&[-3, 1]), vec![z(&[-3, 1])]),
+    ] {
+        let kronecker = p.clone().kronecker();
+        assert_eq!(expected.as_slice(), kronecker.factors());
+        assert_eq!(p, kronecker.factors().iter().cloned().product());
+    }
+}
+
+#[test]
+pub fn test_modular_linear_algebra() {
+    use crate::linalg::{determinant_modular, solve_modular};
+    use crate::resultant::determinant;
+    use num::{BigInt, BigRational};
+
+    // a pseudo-random 7x7 matrix with large entries
+    let mut seed = 12345u64;
+    let mut next = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        BigInt::from((seed >> 20) as i64 - (1 << 43))
+    };
+    let a: Vec<Vec<BigInt>> = (0..7).map(|_| (0..7).map(|_| next()).collect()).collect();
+    let b: Vec<BigInt> = (0..7).map(|_| next()).collect();
+
+    let rational = |rows: &[Vec<BigInt>]| {
+        rows.iter()
+            .map(|row| row.iter().cloned().map(BigRational::from_integer).collect())
+            .collect()
+    };
+    let det = determinant_modular(&a);
+    assert_eq!(BigRational::from_integer(det.clone()), determinant(rational(&a)));
+
+    let (y, d) = solve_modular(&a, &b).unwrap();
+    assert_eq!(d, num::Signed::abs(&det));
+    for (row, rhs) in a.iter().zip(&b) {
+        let lhs: BigInt = row.iter().zip(&y).map(|(a, y)| a * y).sum();
+        assert_eq!(lhs, rhs * &d);
+    }
+
+    // singular
+    let mut singular = a.clone();
+    singular[3] = singular[1].iter().map(|x| x * 3).collect();
+    assert!(determinant_modular(&singular).is_zero());
+    assert!(solve_modular(&singular, &b).is_none());
+}
+
+#[test]
+pub fn test_square_free_factorization_mod_p() {
+    use crate::fields::Zp;
+    use std::num::NonZeroUsize;
+
+    type F3 = Zp<3>;
+    let p = |coeffs: &[u64]| Polynomial::new(coeffs.iter().map(|&c| F3::new(c)).collect());
+
+    assert_eq!(F3::new(2), F3::new(5));
+    assert_eq!(F3::new(2), F3::new(2).pow(3));
+    assert_eq!(F3::new(1), F3::new(2) * F3::new(2));
+    assert_eq!(F3::new(2), F3::new(1) / F3::new(2));
+    assert_eq!(F3::new(1), -F3::new(2));
+
+    // 2 (x + 1)^2 (x + 2)^4 x^3, where x^3 only has a vanishing derivative
+    let x = p(&[0, 1]);
+    let a = p(&[1, 1]);
+    let b = p(&[2, 1]);
+    let f: Polynomial<F3> = [&a, &a, &b, &b, &b, &b, &x, &x, &x]
+        .into_iter()
+        .cloned()
+        .product::<Polynomial<F3>>()
+        .scalar_mul(F3::new(2));
+    let sqf = f.square_free_factorization();
+    assert_eq!(F3::new(2), sqf.leading_coeff);
+    let n = |n| NonZeroUsize::new(n).unwrap();
+    assert_eq!(
+        vec![(a.clone(), n(2)), (x.clone(), n(3)), (b.clone(), n(4))],
+        sqf.factors
+    );
+
+    // x^6 + 2x^3 + 1 = (x + 1)^6
+    let sqf = p(&[1, 0, 0, 2, 0, 0, 1]).square_free_factorization();
+    assert_eq!(vec![(a, n(6))], sqf.factors);
+}
+
+#[test]
+pub fn test_dixon() {
+    use crate::linalg::{solve_dixon, solve_rational};
+    use num::{BigInt, BigRational};
+
+    let mut seed = 987654321u64;
+    let mut next = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 40) as i64 - (1 << 23)
+    };
+    let a: Vec<Vec<BigInt>> = (0..6).map(|_| (0..6).map(|_| next().into()).collect()).collect();
+    let b: Vec<BigInt> = (0..6).map(|_| next().into()).collect();
+    let x = solve_dixon(&a, &b).unwrap();
+    for (row, rhs) in a.iter().zip(&b) {
+        let lhs: BigRational = row.iter().zip(&x).map(|(a, x)| x * a).sum();
+        assert_eq!(BigRational::from_integer(rhs.clone()), lhs);
+    }
+
+    // rational coefficients
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    let a = vec![vec![q(1, 2), q(1, 3)], vec![q(-1, 4), q(5, 1)]];
+    let b = vec![q(1, 1), q(-7, 6)];
+    let x = solve_rational(&a, &b).unwrap();
+    for (row, rhs) in a.iter().zip(&b) {
+        let lhs: BigRational = row.iter().zip(&x).map(|(a, x)| a * x).sum();
+        assert_eq!(rhs, &lhs);
+    }
+
+    let singular = vec![vec![q(1, 2), q(1, 3)], vec![q(3, 2), q(1, 1)]];
+    assert!(solve_rational(&singular, &b).is_none());
+}
+
+#[test]
+pub fn test_berlekamp() {
+    use crate::fields::Zp;
+
+    fn poly<const P: u64>(coeffs: &[u64]) -> Polynomial<Zp<P>> {
+        Polynomial::new_trim_zeroes(coeffs.iter().map(|&c| Zp::new(c)).collect())
+    }
+
+    // x^7 - x is the product of all monic linear polynomials over GF(7)
+    let mut coeffs = vec![0; 8];
+    coeffs[1] = 6;
+    coeffs[7] = 1;
+    let factorization = poly::<7>(&coeffs).berlekamp();
+    let linear: Vec<_> = (0..7).map(|a| poly::<7>(&[a, 1])).collect();
+    assert_eq!(
+        linear,
+        factorization.factors.iter().map(|(f, _)| f.clone()).collect::<Vec<_>>()
+    );
+
+    // 3 (x^2 + 1)^2 (x^3 + x + 1) (x + 4) over GF(5), where x^2 + 1 = (x + 2)(x + 3)
+    let f = poly::<5>(&[1, 0, 1]) * poly(&[1, 0, 1]) * poly(&[1, 1, 0, 1]) * poly(&[4, 1]);
+    let factorization = f.clone().scalar_mul(Zp::new(3)).berlekamp();
+    assert_eq!(Zp::new(3), factorization.leading_coeff);
+    let product: Polynomial<Zp<5>> = factorization
+        .factors
+        .iter()
+        .flat_map(|(f, k)| std::iter::repeat_n(f.clone(), k.get()))
+        .product();
+    assert_eq!(f, product);
+    let degrees: Vec<_> = factorization
+        .factors
+        .iter()
+        .map(|(f, k)| (f.degree().unwrap(), k.get()))
+        .collect();
+    assert_eq!(vec![(1, 2), (1, 2), (1, 1), (3, 1)], degrees);
+    assert!(factorization.factors.iter().any(|(f, _)| f == &poly(&[2, 1])));
+
+    // the intermediate objects
+    let f = poly::<5>(&[1, 1, 0, 1]) * poly(&[2, 1]) * poly(&[3, 1]);
+    let steps = f.berlekamp_steps();
+    assert_eq!(3, steps.nullspace.len());
+    assert_eq!(vec![Zp::new(1), Zp::new(0), Zp::new(0), Zp::new(0), Zp::new(0)], steps.nullspace[0]);
+    for v in &steps.nullspace {
+        let g = Polynomial::new_trim_zeroes(v.clone());
+        assert_eq!(g.clone().div_rem(f.clone()).1, crate::modular_factorization::pow_mod(&g, 5, &f));
+    }
+    for step in &steps.splittings {
+        let shifted = step.splitting_polynomial.clone() - Polynomial::new_trim_zeroes(vec![step.shift]);
+        assert_eq!(step.gcd, step.factor.clone().gcd(shifted));
+    }
+    assert_eq!(f, steps.factors.iter().cloned().product());
+}
+
+#[test]
+pub fn test_cantor_zassenhaus() {
+    use crate::fields::Zp;
+
+    fn poly<const P: u64>(coeffs: &[u64]) -> Polynomial<Zp<P>> {
+        Polynomial::new_trim_zeroes(coeffs.iter().map(|&c| Zp::new(c)).collect())
+    }
+
+    // agrees with Berlekamp on small fields, including characteristic 2
+    let f = poly::<2>(&[1, 1, 0, 1]) * poly(&[1, 1]) * poly(&[1, 1]) * poly(&[1, 1, 1]) * poly(&[0, 1]);
+    assert_eq!(f.clone().berlekamp(), f.factor_mod_p());
+    let f = poly::<7>(&[3, 1, 4, 1, 5, 2, 6, 1]) * poly(&[2, 0, 1]) * poly(&[2, 0, 1]);
+    assert_eq!(f.clone().berlekamp(), f.factor_mod_p());
+
+    // 10^9 + 7 is 3 mod 4, so x^2 + 1 is irreducible
+    const P: u64 = 1_000_000_007;
+    let f = poly::<P>(&[1, 0, 1]) * poly(&[5, 1]) * poly(&[P - 5, 1]) * poly(&[P - 5, 1]);
+    let factorization = f.factor_mod_p();
+    assert_eq!(Zp::new(1), factorization.leading_coeff);
+    let factors: Vec<_> = factorization.factors.iter().map(|(f, k)| (f.clone(), k.get())).collect();
+    assert_eq!(
+        vec![(poly(&[5, 1]), 1), (poly(&[P - 5, 1]), 2), (poly(&[1, 0, 1]), 1)],
+        factors
+    );
+}
+
+#[test]
+pub fn test_lattice() {
+    use crate::lattice::{babai_nearest_plane, lll, shortest_vector};
+    use num::{BigInt, BigRational};
+
+    let basis: Vec<Vec<BigInt>> = [[7, -3, 11], [2, 9, -4], [-5, 8, 6]]
+        .map(|v| v.map(BigInt::from).to_vec())
+        .to_vec();
+    let norm = |v: &[BigInt]| v.iter().map(|x| x * x).sum::<BigInt>();
+
+    // brute force over small coefficients
+    let mut brute = None;
+    for a in -6..=6 {
+        for b in -6..=6 {
+            for c in -6..=6 {
+                if (a, b, c) == (0, 0, 0) {
+                    continue;
+                }
+                let v: Vec<BigInt> = (0..3)
+                    .map(|i| &basis[0][i] * a + &basis[1][i] * b + &basis[2][i] * c)
+                    .collect();
+                let n = norm(&v);
+                if brute.as_ref().is_none_or(|best| &n < best) {
+                    brute = Some(n);
+                }
+            }
+        }
+    }
+    assert_eq!(brute.unwrap(), norm(&shortest_vector(&basis)));
+
+    // reduction keeps the lattice: the determinant is unchanged up to sign
+    let det = |b: &[Vec<BigInt>]| {
+        &b[0][0] * (&b[1][1] * &b[2][2] - &b[1][2] * &b[2][1])
+            - &b[0][1] * (&b[1][0] * &b[2][2] - &b[1][2] * &b[2][0])
+            + &b[0][2] * (&b[1][0] * &b[2][1] - &b[1][1] * &b[2][0])
+    };
+    let reduced = lll(&basis);
+    assert_eq!(num::Signed::abs(&det(&basis)), num::Signed::abs(&det(&reduced)));
+
+    // a lattice point is its own closest vector
+    let point: Vec<BigInt> = (0..3).map(|i| &basis[0][i] * 2 - &basis[2][i]).collect();
+    let target: Vec<BigRational> = point.iter().cloned().map(BigRational::from_integer).collect();
+    assert_eq!(point, babai_nearest_plane(&basis, &target));
+
+    // a larger basis comes out size reduced and satisfying the Lovász condition
+    let n = 8;
+    let skew = crate::random::unimodular(n, 3);
+    let basis: Vec<Vec<BigInt>> = (0..n)
+        .map(|i| (0..n).map(|j| &skew[(i, j)] * BigInt::from(if j == i { 1000 + 17 * i } else { 1 })).collect())
+        .collect();
+    let reduced = lll(&basis);
+    let matrix = |b: &[Vec<BigInt>]| crate::linalg::Matrix::from_rows(b.to_vec());
+    assert_eq!(num::Signed::abs(&matrix(&basis).determinant()), num::Signed::abs(&matrix(&reduced).determinant()));
+    let rational: Vec<Vec<BigRational>> =
+        reduced.iter().map(|v| v.iter().cloned().map(BigRational::from_integer).collect()).collect();
+    let dot = |a: &[BigRational], b: &[BigRational]| a.iter().zip(b).map(|(x, y)| x * y).sum::<BigRational>();
+    let (half, delta) = (BigRational::new(1.into(), 2.into()), BigRational::new(3.into(), 4.into()));
+    let mut orthogonal: Vec<Vec<BigRational>> = Vec::new();
+    for b in &rational {
+        let mut v = b.clone();
+        let mut last_mu = BigRational::zero();
+        for o in &orthogonal {
+            last_mu = dot(b, o) / dot(o, o);
+            assert!(num::Signed::abs(&last_mu) <= half);
+            for (x, y) in v.iter_mut().zip(o) {
+                *x -= &last_mu * y;
+            }
+        }
+        if let Some(o) = orthogonal.last() {
+            assert!(dot(&v, &v) >= (&delta - &last_mu * &last_mu) * dot(o, o));
+        }
+        orthogonal.push(v);
+    }
+}
+
+#[test]
+pub fn test_integer_relation() {
+    use crate::lattice::{find_integer_relation, simultaneous_approximation};
+    use num::{BigInt, BigRational, One};
+
+    // sqrt(2) + sqrt(3) is a root of x^4 - 10x^2 + 1
+    let x: BigRational = "3146264369941972342329135065715570445513/1000000000000000000000000000000000000000"
+        .parse()
+        .unwrap();
+    let powers: Vec<_> = (0..5).map(|k| num::pow(x.clone(), k)).collect();
+    let relation = find_integer_relation(&powers, &BigInt::from(20)).unwrap();
+    assert_eq!([1, 0, -10, 0, 1].map(BigInt::from).to_vec(), relation);
+
+    // exact relations among exact inputs
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    let relation = find_integer_relation(&[q(1, 3), q(1, 2), q(5, 6)], &BigInt::from(5)).unwrap();
+    let sum: BigRational = relation
+        .iter()
+        .zip([q(1, 3), q(1, 2), q(5, 6)])
+        .map(|(a, x)| BigRational::from_integer(a.clone()) * x)
+        .sum();
+    assert!(sum.is_zero());
+
+    // no small relation between 1 and an irrational-looking number
+    let pi: BigRational = "314159265358979323846/100000000000000000000".parse().unwrap();
+    assert_eq!(None, find_integer_relation(&[BigRational::one(), pi], &BigInt::from(100)));
+
+    // exact fractions with a common denominator are recovered exactly
+    let (d, p) = simultaneous_approximation(&[q(2, 11), q(5, 11), q(-7, 11)], &BigInt::from(50));
+    assert_eq!(BigInt::from(11), d);
+    assert_eq!([2, 5, -7].map(BigInt::from).to_vec(), p);
+}
+
+#[test]
+pub fn test_zassenhaus() {
+    use num::BigInt;
+
+    fn poly(coeffs: &[i64]) -> Polynomial<BigInt> {
+        Polynomial::new_trim_zeroes(coeffs.iter().map(|&c| BigInt::from(c)).collect())
+    }
+    fn expand(factorization: &crate::factorization::Factorization<BigInt>) -> Polynomial<BigInt> {
+        factorization
+            .factors
+            .iter()
+            .flat_map(|(f, k)| std::iter::repeat_n(f.clone(), k.get()))
+            .product::<Polynomial<BigInt>>()
+            .scalar_mul(factorization.leading_coeff.clone())
+    }
+
+    // splits into quadratics modulo every prime but is irreducible
+    let f = poly(&[1, 0, -10, 0, 1]);
+    let factorization = f.clone().factor();
+    assert_eq!(vec![(f, 1)], factorization.factors.iter().map(|(f, k)| (f.clone(), k.get())).collect::<Vec<_>>());
+
+    // x^12 - 1 is the product of the cyclotomic polynomials of the divisors of 12
+    let factorization = poly(&[-1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]).factor();
+    assert_eq!(6, factorization.factors.len());
+    assert_eq!(poly(&[-1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]), expand(&factorization));
+
+    // non-monic factors, repeated factors and content
+    let f = (poly(&[-1, 2, 3]) * poly(&[7, -1, 0, 5]) * poly(&[3, 2]) * poly(&[3, 2])).scalar_mul(BigInt::from(6));
+    let factorization = f.clone().factor();
+    assert_eq!(BigInt::from(6), factorization.leading_coeff);
+    assert_eq!(f, expand(&factorization));
+    let degrees: Vec<_> = factorization
+        .factors
+        .iter()
+        .map(|(f, k)| (f.degree().unwrap(), k.get()))
+        .collect();
+    // 3x^2 + 2x - 1 = (3x - 1)(x + 1)
+    assert_eq!(vec![(1, 1), (1, 2), (1, 1), (3, 1)], degrees);
+
+    // agrees with Kronecker's method
+    let f = poly(&[4, 0, 0, 0, 1]) * poly(&[-2, 0, 0, 1]);
+    let mut kronecker = f.clone().kronecker().factors().to_vec();
+    kronecker.sort_by_key(|f| f.degree());
+    let zassenhaus: Vec<_> = f.factor().factors.into_iter().map(|(f, _)| f).collect();
+    assert_eq!(kronecker.len(), zassenhaus.len());
+    for f in kronecker {
+        assert!(zassenhaus.contains(&f));
+    }
+
+    // every one of the fixed primes divides the leading coefficient
+    let l: BigInt = [3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97]
+        .into_iter()
+        .chain([101, 103, 107, 109, 113, 65537])
+        .map(BigInt::from)
+        .product();
+    let g = Polynomial::new(vec![BigInt::from(1), BigInt::from(0), l]);
+    let f = g.clone() * poly(&[-2, 0, 1]) * poly(&[3, 1]);
+    let factorization = f.clone().factor();
+    assert_eq!(f, expand(&factorization));
+    let factors: Vec<_> = factorization.factors.into_iter().map(|(f, _)| f).collect();
+    assert_eq!(vec![poly(&[3, 1]), poly(&[-2, 0, 1]), g], factors);
+}
+
+#[test]
+pub fn test_factorization_state() {
+    use crate::cache::Cache;
+    use crate::zassenhaus::FactorizationState;
+    use num::BigInt;
+
+    fn poly(coeffs: &[i64]) -> Polynomial<BigInt> {
+        Polynomial::new_trim_zeroes(coeffs.iter().map(|&c| BigInt::from(c)).collect())
+    }
+    let cache = Cache::new();
+
+    // x^12 - 1, resumed from a copy of every intermediate state
+    let f = poly(&[-1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+    let mut state = FactorizationState::new(f.clone(), &cache);
+    assert!(!state.is_done());
+    assert!(state.lifted.iter().all(|g| g.leading_coefficient_cloned() == BigInt::from(1)));
+    let mut saved = vec![state.clone()];
+    while state.step() {
+        // the found factors and the rest always multiply back to f
+        let product: Polynomial<BigInt> = state.found.iter().cloned().product();
+        assert_eq!(f, product * state.remaining.clone());
+        saved.push(state.clone());
+    }
+    assert!(state.is_done() && !state.step());
+    let factors = state.found;
+    assert_eq!(6, factors.len());
+    for state in saved {
+        // encoding round trips, and resuming the decoded state agrees
+        let decoded = FactorizationState::decode(&state.encode()).unwrap();
+        assert_eq!(state, decoded);
+        assert_eq!(factors, decoded.resume());
+    }
+    let encoded = FactorizationState::new(f, &cache).encode();
+    assert!(encoded.starts_with("zassenhaus 1\nprime "));
+    assert_eq!(None, FactorizationState::decode(&encoded.replace("zassenhaus 1", "zassenhaus 2")));
+    assert_eq!(None, FactorizationState::decode(&encoded.replace("subset 0", "subset 99")));
+    assert_eq!(None, FactorizationState::decode(&encoded.replacen("lifted", "remaining", 1)));
+    assert_eq!(None, FactorizationState::decode(""));
+
+    // x^4 - 10 x^2 + 1 splits modulo every prime but is irreducible
+    let f = poly(&[1, 0, -10, 0, 1]);
+    let state = FactorizationState::new(f.clone(), &cache);
+    assert!(state.lifted.len() > 1 && state.precision > 1);
+    assert_eq!(vec![f], state.resume());
+
+    // linear polynomials need no prime
+    let state = FactorizationState::new(poly(&[3, 2]), &cache);
+    assert!(state.is_done());
+    assert_eq!(vec![poly(&[3, 2])], state.resume());
+}
+
+#[test]
+pub fn test_guess_minimal_polynomial() {
+    use crate::lattice::guess_minimal_polynomial;
+    use num::{BigInt, BigRational};
+
+    fn poly(coeffs: &[i64]) -> Polynomial<BigInt> {
+        Polynomial::new(coeffs.iter().map(|&c| BigInt::from(c)).collect())
+    }
+
+    let guess = guess_minimal_polynomial(&BigRational::from_float(1.5f64.sqrt()).unwrap(), 4).unwrap();
+    assert_eq!(poly(&[-3, 0, 2]), guess.polynomial);
+
+    let guess = guess_minimal_polynomial(&BigRational::from_float(2f64.cbrt()).unwrap(), 5).unwrap();
+    assert_eq!(poly(&[-2, 0, 0, 1]), guess.polynomial);
+
+    // the golden ratio to 30 digits
+    let phi: BigRational = "1618033988749894848204586834366/1000000000000000000000000000000".parse().unwrap();
+    let guess = guess_minimal_polynomial(&phi, 8).unwrap();
+    assert_eq!(poly(&[-1, -1, 1]), guess.polynomial);
+    assert!(guess.excess_digits > 5.0);
+
+    // pi is transcendental, so anything found is a chance relation
+    let pi = BigRational::from_float(std::f64::consts::PI).unwrap();
+    if let Some(guess) = guess_minimal_polynomial(&pi, 3) {
+        assert!(guess.excess_digits < 2.0, "{guess:?}");
+    }
+}
+
+#[test]
+pub fn test_multivariate() {
+    use crate::multivariate::MultiPolynomial;
+
+    let x = || MultiPolynomial::<i64>::var(0);
+    let y = || MultiPolynomial::<i64>::var(1);
+    let z = || MultiPolynomial::<i64>::var(2);
+    let c = MultiPolynomial::constant;
+
+    // (x + y)(x - y) = x^2 - y^2
+    let p = (x() + y()) * (x() - y());
+    assert_eq!(x() * x() - y() * y(), p);
+    assert_eq!(2, p.terms().count());
+    assert_eq!(Some(&-1), p.coeff(&[0, 2, 0]));
+    assert_eq!(None, p.coeff(&[1, 1]));
+
+    let q = x() * y() * y() * z() + c(3) * x() + c(-5);
+    assert_eq!(Some(4), q.total_degree());
+    assert_eq!(Some(1), q.degree_in(0));
+    assert_eq!(Some(2), q.degree_in(1));
+    assert_eq!(Some(0), q.degree_in(3));
+    assert_eq!(3, q.num_vars());
+    assert_eq!(None, MultiPolynomial::<i64>::zero().total_degree());
+    assert_eq!(2 * 9 * 4 + 3 * 2 - 5, q.eval(&[2, 3, 4]));
+
+    // substitution agrees with evaluation
+    let s = x() * y() + c(1);
+    let r = q.substitute(2, &s);
+    assert_eq!(q.eval(&[2, 3, s.eval(&[2, 3])]), r.eval(&[2, 3]));
+    assert_eq!(None, r.coeff(&[0, 0, 1]));
+
+    // round trip through nested univariate polynomials
+    let nested = q.to_univariate(1);
+    assert_eq!(Some(2), nested.degree());
+    let back: MultiPolynomial<i64> = nested
+        .coeffs
+        .into_iter()
+        .enumerate()
+        .map(|(d, coeff)| coeff * MultiPolynomial::from_univariate(Polynomial::from_elem_with_degree(1, d), 1))
+        .sum();
+    assert_eq!(q, back);
+
+    // the ring axioms hold for nested use
+    assert_eq!(p.clone() * q.clone(), q.clone() * p.clone());
+    assert!((p.clone() - p).is_zero());
+}
+
+#[test]
+pub fn test_homogenize() {
+    use crate::multivariate::MultiPolynomial;
+    use crate::resultant::intersection_multiplicity;
+    use num::BigRational;
+
+    let x = |i| MultiPolynomial::<i64>::var(i);
+    let c = MultiPolynomial::constant;
+    // x^3 + x y - 2 becomes x^3 + x y z - 2 z^3, and back
+    let p = x(0) * x(0) * x(0) + x(0) * x(1) + c(-2);
+    let h = p.homogenize(2);
+    assert_eq!(x(0) * x(0) * x(0) + x(0) * x(1) * x(2) + c(-2) * x(2) * x(2) * x(2), h);
+    assert!(h.is_homogeneous() && !p.is_homogeneous());
+    assert_eq!(p, h.dehomogenize(2));
+    // a homogeneous polynomial is unchanged, and the variable can skip indices
+    assert_eq!(h, h.dehomogenize(5).homogenize(5));
+    assert_eq!(c(7), c(7).homogenize(4));
+    assert!(MultiPolynomial::<i64>::zero().homogenize(0).is_zero());
+    // the affine chart x = 1 of x^2 z - y^3
+    let cubic = x(0) * x(0) * x(2) - x(1) * x(1) * x(1);
+    assert_eq!(x(2) - x(1) * x(1) * x(1), cubic.dehomogenize(0));
+
+    let q = |a: i64| BigRational::from_integer(a.into());
+    let x = || MultiPolynomial::<BigRational>::var(0);
+    let y = || MultiPolynomial::<BigRational>::var(1);
+    let c = |a: i64| MultiPolynomial::constant(q(a));
+    let origin = [q(0), q(0)];
+    let at = |f: &MultiPolynomial<BigRational>, g: &MultiPolynomial<BigRational>, point: &[BigRational; 2]| {
+        let m = intersection_multiplicity(f, g, point);
+        assert_eq!(m, intersection_multiplicity(g, f, point));
+        m
+    };
+    // transversal lines, a tangent line and a point on only one curve
+    assert_eq!(Some(1), at(&x(), &y(), &origin));
+    assert_eq!(Some(2), at(&(y() - x() * x()), &y(), &origin));
+    assert_eq!(Some(0), at(&(y() - x() * x()), &y(), &[q(1), q(1)]));
+    // the node y^2 = x^2 (x + 1) meets the x axis twice at the origin and
+    // once at (-1, 0), which the resultant also sees
+    let node = y() * y() - x() * x() * (x() + c(1));
+    assert_eq!(Some(2), at(&node, &y(), &origin));
+    assert_eq!(Some(1), at(&node, &y(), &[q(-1), q(0)]));
+    // tangent circles, away from the origin
+    let circle = x() * x() + y() * y() - c(1);
+    let shifted = (x() - c(2)) * (x() - c(2)) + y() * y() - c(1);
+    assert_eq!(Some(2), at(&circle, &shifted, &[q(1), q(0)]));
+    // Fulton's example, with multiplicity 14 at the origin
+    let r2 = x() * x() + y() * y();
+    let f = r2.clone() * r2.clone() + c(3) * x() * x() * y() - y() * y() * y();
+    let g = r2.clone() * r2.clone() * r2 - c(4) * x() * x() * y() * y();
+    assert_eq!(Some(14), at(&f, &g, &origin));
+    // a common component through the point, and the zero polynomial
+    assert_eq!(None, at(&(x() * (y() - c(1))), &(x() * (x() + y())), &origin));
+    assert_eq!(Some(1), at(&(x() * (y() - c(1))), &(x() - c(1)), &[q(1), q(1)]));
+    assert_eq!(None, at(&MultiPolynomial::zero(), &y(), &origin));
+    // the common component x = 5 misses the origin, where y and y - x are
+    // transversal
+    let (f, g) = ((x() - c(5)) * y(), (x() - c(5)) * (y() - x()));
+    assert_eq!(Some(1), at(&f, &g, &origin));
+    assert_eq!(None, at(&f, &g, &[q(5), q(3)]));
+
+    // parallel lines meet once at the point at infinity [1 : 0 : 0] of the
+    // chart x = 1, in the variables y and z renamed to x_0 and x_1
+    let (line, other) = (y(), y() - c(1));
+    let chart = |p: &MultiPolynomial<BigRational>| {
+        let h = p.homogenize(2).dehomogenize(0);
+        h.substitute(1, &x()).substitute(2, &y())
+    };
+    assert_eq!(Some(1), at(&chart(&line), &chart(&other), &origin));
+}
+
+#[test]
+pub fn test_specialize() {
+    use std::collections::BTreeMap;
+
+    use crate::multivariate::MultiPolynomial;
+
+    let x = |i| MultiPolynomial::<i64>::var(i);
+    let c = MultiPolynomial::constant;
+    let q = x(0) * x(1) * x(1) * x(2) + c(3) * x(0) + c(-5) + x(1).substitute(1, &(x(1) * x(1) * x(1)));
+    // specializing every variable in turn is evaluation
+    let point = [2, -3, 4];
+    for i in 0..3 {
+        let s = q.specialize(i, &point[i]);
+        assert_eq!(Some(0), s.degree_in(i));
+        assert_eq!(q.eval(&point), s.eval(&point));
+        assert_eq!(q.substitute(i, &c(point[i])), s);
+    }
+    let all: BTreeMap<_, _> = point.iter().copied().enumerate().collect();
+    assert_eq!(c(q.eval(&point)), q.partial_eval(&all));
+    assert_eq!(q, q.partial_eval(&BTreeMap::new()));
+    // a variable that does not appear changes nothing
+    assert_eq!(q, q.specialize(7, &100));
+
+    // gaps between the powers, and a value of zero
+    let p = x(1).substitute(1, &(x(1) * x(1) * x(1) * x(1) * x(1))) * x(0) + x(1) * x(1) + c(1);
+    assert_eq!(c(32) * x(0) + c(5), p.specialize(1, &2));
+    assert_eq!(c(1), p.specialize(1, &0));
+    assert_eq!(x(1) * x(1) + c(1), p.partial_eval(&BTreeMap::from([(0, 0)])));
+    assert!(MultiPolynomial::<i64>::zero().specialize(0, &3).is_zero());
+}
+
+#[test]
+pub fn test_jacobian() {
+    use crate::multivariate::MultiPolynomial;
+
+    let x = |i| MultiPolynomial::<i64>::var(i);
+    let c = MultiPolynomial::constant;
+
+    // x_0^3 x_1 + 2 x_1^2 x_2 - 7 x_2
+    let f = x(0) * x(0) * x(0) * x(1) + c(2) * x(1) * x(1) * x(2) - c(7) * x(2);
+    assert_eq!(c(3) * x(0) * x(0) * x(1), f.derivative(0));
+    assert_eq!(x(0) * x(0) * x(0) + c(4) * x(1) * x(2), f.derivative(1));
+    assert_eq!(c(2) * x(1) * x(1) - c(7), f.derivative(2));
+    assert!(f.derivative(3).is_zero());
+    let gradient = f.gradient();
+    assert_eq!(3, gradient.len());
+
+    // the Hessian is symmetric and is the Jacobian of the gradient
+    let h = f.hessian();
+    assert_eq!(h, h.transpose());
+    assert_eq!(h, MultiPolynomial::jacobian(&gradient));
+    assert_eq!(c(6) * x(0) * x(1), h[(0, 0)]);
+    assert_eq!(c(4) * x(1), h[(1, 2)]);
+
+    // (x_0 x_1, x_0 (1 - x_1)) has Jacobian determinant -x_0
+    let map = [x(0) * x(1), x(0) * (c(1) - x(1))];
+    let j = MultiPolynomial::jacobian(&map);
+    assert_eq!((2, 2), (j.num_rows(), j.num_cols()));
+    assert_eq!(-x(0), j.determinant_division_free());
+    // Euler's identity for the homogeneous x_0^2 x_1 + x_1^3 of degree 3
+    let g = x(0) * x(0) * x(1) + x(1) * x(1) * x(1);
+    let euler: MultiPolynomial<i64> = g.gradient().into_iter().enumerate().map(|(i, d)| x(i) * d).sum();
+    assert_eq!(c(3) * g, euler);
+    assert_eq!(0, MultiPolynomial::<i64>::jacobian(&[]).num_rows());
+}
+
+#[test]
+#[cfg(feature = "float")]
+pub fn test_approximate_gcd() {
+    // (x^2 + 1)(x - 2) and (x^2 + 1)(x + 3)(x - 1), with the second perturbed
+    let f = Polynomial::new(vec![-2.0, 1.0, -2.0, 1.0]);
+    let g = Polynomial::new(vec![-3.0, 2.0, -2.0, 2.0, 1.0]) + Polynomial::new(vec![1e-9, 0.0, -1e-9]);
+
+    // the exact gcd is 1, but a common quadratic factor is within tolerance
+    assert_eq!(Some(0), f.approximate_gcd(&g, 1e-14).gcd.degree());
+    let result = f.approximate_gcd(&g, 1e-6);
+    assert_eq!(Some(2), result.gcd.degree());
+    for (c, expected) in result.gcd.coeffs.iter().zip([1.0, 0.0, 1.0]) {
+        assert!((c - expected).abs() < 1e-6, "{:?}", result.gcd);
+    }
+    assert!(result.residual < 1e-8);
+    let (u, v) = result.cofactors;
+    for (c, expected) in u.coeffs.iter().zip([-2.0, 1.0]) {
+        assert!((c - expected).abs() < 1e-6, "{u:?}");
+    }
+    for (c, expected) in v.coeffs.iter().zip([-3.0, 2.0, 1.0]) {
+        assert!((c - expected).abs() < 1e-6, "{v:?}");
+    }
+
+    let result = f.approximate_gcd(&Polynomial::new(vec![]), 1e-6);
+    assert_eq!(Some(3), result.gcd.degree());
+}
+
+#[test]
+pub fn test_parse() {
+    use num::BigRational;
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    let parse = |s: &str| s.parse::<Polynomial<BigRational>>();
+
+    assert_eq!(Ok(Polynomial::zero()), parse("0"));
+    assert_eq!(Ok(Polynomial::zero()), parse("x - x"));
+    assert_eq!(Ok(Polynomial::new(vec![q(0, 1), q(-1, 1)])), parse("-x"));
+    assert_eq!(
+        Ok(Polynomial::new(vec![q(5, 4), q(3, 1), q(1, 2)])),
+        parse("1/2*x^2 + x + 2x + 1.25")
+    );
+    assert_eq!(Ok(Polynomial::new(vec![q(0, 1), q(-1, 1)])), parse("-1x"));
+
+    // printing and parsing round trips
+    let p = Polynomial::new(vec![q(-7, 3), q(0, 1), q(1, 1), q(-1, 1), q(12, 5)]);
+    assert_eq!(Ok(p.clone()), parse(&p.print_with_var("x").to_string()));
+
+    // errors point at the offending position
+    assert_eq!(4, parse("3x +").unwrap_err().position());
+    assert_eq!(2, parse("3 y").unwrap_err().position());
+    assert_eq!(3, parse("1/0").unwrap_err().position());
+    assert_eq!(4, parse("x^2 x").unwrap_err().position());
+    assert!(parse("").is_err());
+
+    // exponents that overflow or would need huge allocations are errors
+    assert_eq!(6, parse("1 + x^18446744073709551615").unwrap_err().position());
+    assert_eq!(3, parse("x^ 1000000000000").unwrap_err().position());
+    let max = crate::parse::MAX_DEGREE;
+    assert_eq!(Some(max), parse(&format!("x^{max}")).unwrap().degree());
+    assert!(parse(&format!("x^{}", max + 1)).is_err());
+}
+
+#[test]
+pub fn test_polyeig() {
+    use crate::linalg::polyeig;
+    use num::{BigInt, BigRational};
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    let zero = || q(0, 1);
+    // P diag((λ - 1)(λ - 2), (λ + 1/2)^2, λ^2 - 3) with P = [[1, 1, 0], [0, 1, 2], [1, 0, 1]],
+    // split by powers of λ
+    let diagonal = [[q(2, 1), q(1, 4), q(-3, 1)], [q(-3, 1), q(1, 1), zero()], [q(1, 1), q(1, 1), q(1, 1)]];
+    let p = [[1, 1, 0], [0, 1, 2], [1, 0, 1]];
+    let matrices: Vec<Vec<Vec<BigRational>>> = diagonal
+        .iter()
+        .map(|d| {
+            (0..3)
+                .map(|i| (0..3).map(|j| d[j].clone() * q(p[i][j], 1)).collect())
+                .collect()
+        })
+        .collect();
+    let eig = polyeig(&matrices).unwrap();
+    let one = std::num::NonZeroUsize::new(1).unwrap();
+    let two = std::num::NonZeroUsize::new(2).unwrap();
+    assert_eq!(vec![(q(-1, 2), two), (q(1, 1), one), (q(2, 1), one)], eig.rational);
+    assert_eq!(
+        vec![(Polynomial::new([-3, 0, 1].map(BigInt::from).to_vec()), one)],
+        eig.irrational
+    );
+    // det P = 3, and the determinant has degree 6
+    assert_eq!(Some(6), eig.determinant.degree());
+    assert_eq!(q(3, 1), eig.determinant.leading_coefficient_cloned());
+
+    // [[λ, λ], [1, 1]] is singular for every λ
+    let singular = [
+        vec![vec![zero(), zero()], vec![q(1, 1), q(1, 1)]],
+        vec![vec![q(1, 1), q(1, 1)], vec![zero(), zero()]],
+    ];
+    assert_eq!(None, polyeig(&singular));
+}
+
+#[test]
+pub fn test_print_latex() {
+    use num::BigRational;
+
+    let p: Polynomial<BigRational> = "3x^12 - 2/5 x + 1".parse().unwrap();
+    assert_eq!("3x^{12} - \\frac{2}{5}x + 1", p.print_latex("x").to_string());
+    let p: Polynomial<BigRational> = "-x^2 - 7/3".parse().unwrap();
+    assert_eq!("-x^{2} - \\frac{7}{3}", p.print_latex("x").to_string());
+    assert_eq!("0", Polynomial::<i64>::zero().print_latex("x").to_string());
+    assert_eq!("-1", Polynomial::new(vec![-1i64]).print_latex("x").to_string());
+
+    let p: Polynomial<BigRational> = "3x^4 + 6x^3 + 6x^2 + 6x + 3".parse().unwrap();
+    let sqf = p.square_free_factorization();
+    assert_eq!(
+        "3\\left(x^{2} + 1\\right)\\left(x + 1\\right)^{2}",
+        sqf.print_latex("x").to_string()
+    );
+}
+
+#[test]
+pub fn test_euclidean_domain() {
+    use crate::traits::EuclideanDomain;
+    use num::{BigInt, BigRational, One, Signed};
+
+    assert_eq!(6, EuclideanDomain::euclidean_gcd(&48i64, &-18).abs());
+    assert_eq!((-2, -1), EuclideanDomain::div_rem(&-7i64, &3));
+    assert_eq!(
+        BigInt::from(12),
+        EuclideanDomain::euclidean_gcd(&BigInt::from(1284), &BigInt::from(156)).abs()
+    );
+
+    // remainders have a smaller norm than the divisor
+    let p: Polynomial<BigRational> = "x^5 + 3x^2 - 1".parse().unwrap();
+    let q: Polynomial<BigRational> = "2x^2 + x".parse().unwrap();
+    let (quotient, r) = EuclideanDomain::div_rem(&p, &q);
+    assert!(r.norm() < q.norm());
+    assert_eq!(p, quotient * q.clone() + r);
+    assert!(Polynomial::<BigRational>::zero().norm() < Polynomial::<BigRational>::one().norm());
+
+    // (x - 1)(x + 2) and (x - 1)(x - 3) have gcd x - 1 up to a unit
+    let a: Polynomial<BigRational> = "x^2 + x - 2".parse().unwrap();
+    let b: Polynomial<BigRational> = "x^2 - 4x + 3".parse().unwrap();
+    let expected: Polynomial<BigRational> = "x - 1".parse().unwrap();
+    assert_eq!(expected, a.clone().gcd(b.clone()));
+    assert_eq!(Some(1), a.euclidean_gcd(&b).degree());
+}
+
+#[test]
+pub fn test_divisor_lattice() {
+    use crate::number_theory::{coprime_divisor_pairs, divisor_lattice, unitary_divisors};
+    use num::{BigInt, Integer, One};
+
+    let n = BigInt::from(360);
+    let lattice = divisor_lattice(&n);
+    let count = lattice.divisors().len();
+    assert_eq!(24, count);
+    assert!(lattice.divisors().windows(2).all(|w| w[0] < w[1]));
+
+    // the Möbius function inverts the zeta function of the poset
+    for a in 0..count {
+        for b in 0..count {
+            let sum: i32 = (0..count)
+                .filter(|&d| lattice.divides(a, d) && lattice.divides(d, b))
+                .map(|d| lattice.mobius(a, d))
+                .sum();
+            assert_eq!(i32::from(a == b), sum);
+        }
+    }
+
+    // covering pairs differ by a prime
+    for (a, b) in lattice.covers() {
+        let q = &lattice.divisors()[b] / &lattice.divisors()[a];
+        assert!([2, 3, 5].map(BigInt::from).contains(&q));
+    }
+    // 360 = 2^3 3^2 5: each divisor is covered once per prime it can grow in
+    assert_eq!(3 * 3 * 2 + 4 * 2 * 2 + 4 * 3, lattice.covers().len());
+
+    let unitary: Vec<_> = unitary_divisors(&n).collect();
+    assert_eq!(8, unitary.len());
+    assert!(unitary.iter().all(|d| d.gcd(&(&n / d)).is_one()));
+
+    let pairs: Vec<_> = coprime_divisor_pairs(&n).collect();
+    assert_eq!(7 * 5 * 3, pairs.len());
+    assert!(pairs.iter().all(|(a, b)| a.gcd(b).is_one() && n.is_multiple_of(a) && n.is_multiple_of(b)));
+    let mut sorted = pairs.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(pairs.len(), sorted.len());
+    assert!(coprime_divisor_pairs(&BigInt::one()).eq([(BigInt::one(), BigInt::one())]));
+}
+
+#[test]
+pub fn test_extended_gcd() {
+    use crate::fields::Zp;
+    use crate::traits::EuclideanDomain;
+    use num::{BigRational, One};
+
+    let a: Polynomial<BigRational> = "x^4 - 2x^3 + 2x - 1".parse().unwrap();
+    let b: Polynomial<BigRational> = "3x^3 + x^2 - 5x + 1".parse().unwrap();
+    let (g, s, t) = a.clone().extended_gcd(b.clone());
+    assert_eq!(g, "x - 1".parse().unwrap());
+    assert_eq!(g, s.clone() * a.clone() + t.clone() * b.clone());
+    assert!(s.degree() < Some(b.degree().unwrap() - 1));
+    assert!(t.degree() < Some(a.degree().unwrap() - 1));
+
+    // inverting x + 1 modulo the irreducible x^2 + x + 2 over GF(3)
+    let m = Polynomial::new([2, 1, 1].map(Zp::<3>::new).to_vec());
+    let x = Polynomial::new([1, 1].map(Zp::<3>::new).to_vec());
+    let (g, inv, _) = x.clone().extended_gcd(m.clone());
+    assert!(g.is_one());
+    assert!((x * inv).div_rem(m).1.is_one());
+
+    // one side zero
+    let (g, s, t) = Polynomial::zero().extended_gcd(b.clone());
+    assert_eq!(g, b.clone().gcd(Polynomial::zero()));
+    assert!(s.is_zero());
+    assert_eq!(g, t * b);
+
+    let (g, s, t) = EuclideanDomain::extended_euclidean(&240i64, &46);
+    assert_eq!((2, 240 * s + 46 * t), (g, g));
+}
+
+#[test]
+pub fn test_summatory_functions() {
+    use crate::number_theory::{divisor_summatory, mertens, totient_summatory};
+
+    // brute force up to a few thousand, which crosses the sieve limits
+    let limit = 3000;
+    let mut phi: Vec<u64> = (0..=limit).collect();
+    let mut mu = vec![1i64; limit as usize + 1];
+    for p in 2..=limit as usize {
+        if phi[p] == p as u64 {
+            for k in (p..=limit as usize).step_by(p) {
+                phi[k] -= phi[k] / p as u64;
+                mu[k] = -mu[k];
+            }
+            for k in (p * p..=limit as usize).step_by(p * p) {
+                mu[k] = 0;
+            }
+        }
+    }
+    let (mut big_phi, mut m, mut d) = (0u128, 0i64, 0u128);
+    assert_eq!((0, 0, 0), (totient_summatory(0), mertens(0), divisor_summatory(0)));
+    for n in 1..=limit {
+        big_phi += u128::from(phi[n as usize]);
+        m += mu[n as usize];
+        d += (1..=n).filter(|k| n % k == 0).count() as u128;
+        assert_eq!(big_phi, totient_summatory(n));
+        assert_eq!(m, mertens(n));
+        assert_eq!(d, divisor_summatory(n));
+    }
+    assert_eq!(1037, mertens(10_000_000));
+    assert_eq!(30_396_356_427_242, totient_summatory(10_000_000));
+}
+
+#[test]
+pub fn test_poly_mod() {
+    use crate::fields::{PolyMod, Zp};
+    use crate::traits::CheckedInv;
+    use num::BigRational;
+
+    // Q(√2)
+    let m: Polynomial<BigRational> = "x^2 - 2".parse().unwrap();
+    let elem = |s: &str| PolyMod::new(s.parse().unwrap(), m.clone());
+    let sqrt2 = PolyMod::x(m.clone());
+    assert_eq!(elem("2"), sqrt2.clone() * sqrt2.clone());
+    assert_eq!(elem("x - 1"), elem("x + 1").checked_inv().unwrap());
+    assert_eq!(elem("17 + 12x"), elem("x + 1").pow(4));
+    assert_eq!(elem("1/2 x"), PolyMod::one(m.clone()) / sqrt2.clone());
+    assert_eq!(elem("x^3"), elem("2x"));
+    assert!((sqrt2.clone() - sqrt2).is_zero());
+
+    // GF(9) = GF(3)[x]/(x^2 + 1)
+    let m = Polynomial::new([1, 0, 1].map(Zp::<3>::new).to_vec());
+    let one = PolyMod::one(m.clone());
+    for a in 0..3 {
+        for b in 0..3 {
+            let e = PolyMod::new(Polynomial::new_trim_zeroes(vec![Zp::new(a), Zp::new(b)]), m.clone());
+            if e.is_zero() {
+                assert_eq!(None, e.checked_inv());
+                continue;
+            }
+            assert_eq!(one, e.pow(8));
+            assert_eq!(one, e.clone() * e.checked_inv().unwrap());
+        }
+    }
+
+    // x - 1 is a zero divisor modulo x^2 - 1
+    let m: Polynomial<BigRational> = "x^2 - 1".parse().unwrap();
+    assert_eq!(None, PolyMod::new("x - 1".parse().unwrap(), m).checked_inv());
+}
+
+#[test]
+pub fn test_prime_pi() {
+    use crate::number_theory::{nth_prime, prime_pi};
+
+    let limit = 20_000u64;
+    let primes: Vec<u64> = (2..=limit).filter(|&n| (2..).take_while(|d| d * d <= n).all(|d| n % d != 0)).collect();
+    let mut count = 0;
+    for n in 0..=limit {
+        if primes.binary_search(&n).is_ok() {
+            count += 1;
+            assert_eq!(n, nth_prime(count));
+        }
+        assert_eq!(count, prime_pi(n));
+    }
+    assert_eq!(664_579, prime_pi(10_000_000));
+    assert_eq!(104_729, nth_prime(10_000));
+    // the boundaries between the prime_pi count and the sieved segment
+    for n in [6, 7, 1 << 16, 1_000_003] {
+        assert_eq!(n, prime_pi(nth_prime(n)));
+        assert_eq!(n - 1, prime_pi(nth_prime(n) - 1));
+    }
+}
+
+#[test]
+pub fn test_rational_function() {
+    use crate::rational_function::RationalFunction;
+    use crate::traits::{CheckedInv, Field};
+    use num::{BigRational, One};
+
+    let p = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+    let f = |n: &str, d: &str| RationalFunction::new(p(n), p(d));
+
+    // 1 / (x - 1) - 1 / (x + 1) = 2 / (x^2 - 1)
+    assert_eq!(f("2", "x^2 - 1"), f("1", "x - 1") - f("1", "x + 1"));
+    assert_eq!(f("x + 1", "x"), f("x^2 - 1", "x^2 - x"));
+    assert_eq!(f("-2x", "-4x^2"), f("1/2", "x"));
+    assert_eq!(f("1", "1"), f("x", "x + 1") * f("x + 1", "x"));
+    assert_eq!(f("x", "x + 1"), f("x + 1", "x").checked_inv().unwrap());
+    assert_eq!(None, RationalFunction::<BigRational>::zero().checked_inv());
+    assert_eq!(f("3", "x - 2") / f("1", "x"), f("3x", "x - 2"));
+
+    let q = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+    assert_eq!(Some(q(3, 2)), f("x + 1", "x^2 - 2").eval(&q(2, 1)));
+    assert_eq!(None, f("1", "x - 2").eval(&q(2, 1)));
+
+    assert_eq!("(x + 1) / (x^2 - 2)", f("x + 1", "x^2 - 2").print_with_var("x").to_string());
+    assert_eq!("3 / s", f("6", "2x").print_with_var("s").to_string());
+    assert_eq!("x^2 + 1", f("x^2 + 1", "1").print_with_var("x").to_string());
+    assert_eq!("0", f("0", "x").print_with_var("x").to_string());
+
+    // as coefficients, the gcd of t x - 1 and x^2 - 1/t^2 over Q(t) is x - 1/t
+    let a = Polynomial::new(vec![f("-1", "1"), f("x", "1")]);
+    let b = Polynomial::new(vec![f("-1", "x^2"), RationalFunction::zero(), RationalFunction::one()]);
+    assert_eq!(Polynomial::new(vec![f("-1", "x"), RationalFunction::one()]), a.gcd(b));
+
+    // in characteristic zero the pth root is the identity, as for BigRational
+    assert_eq!(f("x + 1", "x^2 - 2"), Field::pth_root(f("x + 1", "x^2 - 2")));
+}
+
+#[test]
+pub fn test_partial_fractions() {
+    use crate::rational_function::RationalFunction;
+    use num::BigRational;
+
+    let p = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+    let recombine = |f: &RationalFunction<BigRational>| {
+        let decomposition = f.partial_fractions();
+        for term in &decomposition.terms {
+            assert!(term.numerator.degree() < term.factor.degree());
+        }
+        decomposition.terms.into_iter().fold(
+            RationalFunction::from_polynomial(decomposition.polynomial),
+            |acc, term| {
+                let power = (0..term.power.get()).map(|_| term.factor.clone()).product();
+                acc + RationalFunction::new(term.numerator, power)
+            },
+        )
+    };
+    for (n, d) in [
+        ("x^5 + 2", "x^3 - x"),
+        ("1", "x^2 - 1"),
+        ("3x^4 - 1/2 x + 7", "4x^6 + 12x^5 + 9x^4 + 8x^3 + 24x^2 + 18x"),
+        ("x^7", "x^2 + 1"),
+        ("2x + 5", "x^4 + 2x^2 + 1"),
+    ] {
+        let f = RationalFunction::new(p(n), p(d));
+        assert_eq!(f, recombine(&f));
+    }
+
+    // (x^3 + 2x + 5) / (x^2 + 1)^2 = x / (x^2 + 1) + (x + 5) / (x^2 + 1)^2
+    let decomposition = RationalFunction::new(p("x^3 + 2x + 5"), p("x^4 + 2x^2 + 1")).partial_fractions();
+    assert!(decomposition.polynomial.is_zero());
+    let terms: Vec<_> = decomposition.terms.iter().map(|t| (t.numerator.clone(), t.power.get())).collect();
+    assert_eq!(vec![(p("x"), 1), (p("x + 5"), 2)], terms);
+
+    let polynomial = RationalFunction::from_polynomial(p("x^2 + 1")).partial_fractions();
+    assert_eq!(p("x^2 + 1"), polynomial.polynomial);
+    assert!(polynomial.terms.is_empty());
+}
+
+#[test]
+pub fn test_irreducibles_mod_p() {
+    use crate::fields::Zp;
+    use crate::modular_factorization::count_irreducible;
+    use num::BigInt;
+
+    fn check<const P: u64>(max_degree: usize) {
+        for n in 1..=max_degree {
+            let irreducibles: Vec<_> = Polynomial::<Zp<P>>::irreducibles_of_degree(n).collect();
+            assert_eq!(count_irreducible(P, n), BigInt::from(irreducibles.len()));
+            for f in irreducibles {
+                let factorization = f.clone().factor_mod_p();
+                assert_eq!(1, factorization.factors.len());
+                assert_eq!(1, factorization.factors[0].1.get());
+            }
+        }
+    }
+    check::<2>(8);
+    check::<3>(5);
+    check::<5>(3);
+
+    let poly = |c: &[u64]| Polynomial::new(c.iter().map(|&c| Zp::<2>::new(c)).collect());
+    let primes: Vec<_> = Polynomial::<Zp<2>>::irreducibles().take(5).collect();
+    assert_eq!(vec![poly(&[0, 1]), poly(&[1, 1]), poly(&[1, 1, 1]), poly(&[1, 1, 0, 1]), poly(&[1, 0, 1, 1])], primes);
+    // x^2 + 1 = (x + 1)^2 over GF(2)
+    assert!(!poly(&[1, 0, 1]).is_irreducible());
+    assert!(!poly(&[1]).is_irreducible());
+    // not monic: 2x + 2 over GF(3) is irreducible
+    assert!(Polynomial::new([2, 2].map(Zp::<3>::new).to_vec()).is_irreducible());
+    // over GF(4), (16 - 4) / 2 quadratics have no root
+    assert_eq!(BigInt::from(6), count_irreducible(4, 2));
+}
+
+#[test]
+pub fn test_necklaces() {
+    use crate::combinatorics::{lyndon_count, lyndon_words, necklace_count, necklaces};
+    use num::BigInt;
+
+    for k in 1..=4usize {
+        for n in 1..=7 {
+            // brute force: words that are the smallest of their rotations
+            let words: Vec<Vec<usize>> = (0..k.pow(n as u32))
+                .map(|mut i| {
+                    let mut w = vec![0; n];
+                    for c in w.iter_mut().rev() {
+                        *c = i % k;
+                        i /= k;
+                    }
+                    w
+                })
+                .collect();
+            let rotations = |w: &Vec<usize>| (1..n).map(|r| [&w[r..], &w[..r]].concat()).collect::<Vec<_>>();
+            let expected_necklaces: Vec<_> = words.iter().filter(|w| rotations(w).iter().all(|r| *w <= r)).cloned().collect();
+            let expected_lyndon: Vec<_> = words.iter().filter(|w| rotations(w).iter().all(|r| *w < r)).cloned().collect();
+
+            assert_eq!(expected_necklaces, necklaces(n, k).collect::<Vec<_>>());
+            assert_eq!(BigInt::from(expected_necklaces.len()), necklace_count(n, k as u64));
+            let lyndon: Vec<_> = lyndon_words(n, k).filter(|w| w.len() == n).collect();
+            assert_eq!(expected_lyndon, lyndon);
+            assert_eq!(BigInt::from(expected_lyndon.len()), lyndon_count(n, k as u64));
+        }
+    }
+    let all: Vec<_> = lyndon_words(5, 3).collect();
+    assert!(all.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(0, lyndon_words(0, 2).count());
+    assert_eq!(0, lyndon_words(3, 0).count());
+}
+
+#[test]
+pub fn test_composed_power() {
+    use num::BigInt;
+    let poly = |c: &[i64]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+
+    // -(x + 1)^6 is a cube but not a square
+    let p: Polynomial<BigInt> = (0..6).map(|_| poly(&[1, 1])).product::<Polynomial<BigInt>>() * BigInt::from(-1);
+    assert_eq!(Some((poly(&[-1, -2, -1]), 3)), p.perfect_power());
+    let (h, k) = p.perfect_power().unwrap();
+    assert_eq!(p, (0..k).map(|_| h.clone()).product());
+    // 8 (x - 2)^6 (x^2 + 1)^3 = (2 (x - 2)^2 (x^2 + 1))^3
+    let q = (0..6).map(|_| poly(&[-2, 1])).chain((0..3).map(|_| poly(&[1, 0, 1]))).product::<Polynomial<BigInt>>() * BigInt::from(8);
+    let (h, k) = q.perfect_power().unwrap();
+    assert_eq!(3, k);
+    assert_eq!(&BigInt::from(2), h.leading_coefficient().unwrap());
+    assert_eq!(q, (0..k).map(|_| h.clone()).product());
+    assert_eq!(None, poly(&[5]).perfect_power());
+    assert_eq!(None, poly(&[1, 2, 1]).inner_power());
+
+    let inner = poly(&[3, 0, 0, 0, 1, 0, 0, 0, 2]).inner_power().unwrap();
+    assert_eq!((poly(&[3, 1, 2]), 4), inner);
+    assert_eq!(poly(&[3, 0, 0, 0, 1, 0, 0, 0, 2]), inner.0.compose_power(4));
+
+    // factoring a polynomial in x^3 agrees with factoring it directly
+    let f = (poly(&[-1, 0, 0, 1]) * poly(&[2, 0, 0, 1]) * poly(&[1, 0, 0, 0, 0, 0, 1])).factor();
+    let product: Polynomial<BigInt> = f.factors.iter().map(|(g, k)| (0..k.get()).map(|_| g.clone()).product::<Polynomial<BigInt>>()).product();
+    assert_eq!(poly(&[-1, 0, 0, 1]) * poly(&[2, 0, 0, 1]) * poly(&[1, 0, 0, 0, 0, 0, 1]), product);
+    // x^3 - 1 = (x - 1)(x^2 + x + 1), x^3 + 2 and x^6 + 1 = (x^2 + 1)(x^4 - x^2 + 1)
+    assert_eq!(5, f.factors.len());
+}
+
+#[test]
+pub fn test_sturm() {
+    use num::BigRational;
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+
+    // roots -3, -1/2 (twice), 1/3, 2 (three times), and x^2 + 1 has none
+    let roots = [q(-3, 1), q(-1, 2), q(-1, 2), q(1, 3), q(2, 1), q(2, 1), q(2, 1)];
+    let p: Polynomial<BigRational> = roots
+        .iter()
+        .map(|r| Polynomial::new(vec![-r.clone(), q(1, 1)]))
+        .product::<Polynomial<BigRational>>()
+        * "x^2 + 1".parse::<Polynomial<BigRational>>().unwrap()
+        * q(-5, 2);
+    let distinct = [q(-3, 1), q(-1, 2), q(1, 3), q(2, 1)];
+    let sturm = p.sturm_sequence();
+    assert_eq!(4, sturm.count());
+    let points: Vec<_> = (-16..=16).map(|i| q(i, 4)).chain(distinct.iter().cloned()).collect();
+    for a in &points {
+        for b in points.iter().filter(|b| a <= *b) {
+            let expected = distinct.iter().filter(|r| a <= *r && *r <= b).count();
+            assert_eq!(expected, sturm.count_in(a, b), "[{a}, {b}]");
+        }
+    }
+    assert_eq!(0, "x^2 + 1".parse::<Polynomial<BigRational>>().unwrap().count_real_roots());
+    assert_eq!(0, "7".parse::<Polynomial<BigRational>>().unwrap().count_real_roots());
+    // x^3 - 2 has one real root, near 1.26
+    let cubic: Polynomial<BigRational> = "x^3 - 2".parse().unwrap();
+    assert_eq!(1, cubic.count_real_roots());
+    assert_eq!(1, cubic.count_real_roots_in(&q(125, 100), &q(127, 100)));
+    assert_eq!(0, cubic.count_real_roots_in(&q(127, 100), &q(2, 1)));
+}
+
+#[test]
+pub fn test_multimodular_reconstruction() {
+    use crate::multimodular::MultiModularReconstructor;
+    use num::{BigInt, Integer, ToPrimitive};
+
+    let is_prime = |n: u64| (2..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d));
+    let primes: Vec<u64> = (1_000_000_000u64..).filter(|&n| is_prime(n)).take(12).collect();
+    // coefficients below 10^30, so every run of 4 primes suffices
+    let f = Polynomial::new(vec![
+        "-123456789012345678901234567890".parse::<BigInt>().unwrap(),
+        BigInt::from(0),
+        BigInt::from(primes[5]) * 17,
+        "987654321098765432109876543210".parse().unwrap(),
+    ]);
+    let bound: BigInt = "1000000000000000000000000000000".parse().unwrap();
+    let image = |p: u64| -> Vec<u64> { f.coeffs.iter().map(|c| c.mod_floor(&BigInt::from(p)).to_u64().unwrap()).collect() };
+
+    let reconstruct = |faulty: &[usize]| {
+        let mut reconstructor = MultiModularReconstructor::new(bound.clone());
+        for (i, &p) in primes.iter().enumerate() {
+            let mut residues = image(p);
+            if faulty.contains(&i) {
+                let j = i % residues.len();
+                residues[j] = (residues[j] + 1) % p;
+            }
+            reconstructor.add_residues(p, residues);
+        }
+        reconstructor.reconstruct()
+    };
+    let clean = reconstruct(&[]).unwrap();
+    assert_eq!(f, clean.polynomial);
+    assert!(clean.faulty.is_empty());
+    // three runs of four primes, so two faults anywhere are corrected
+    for faulty in [vec![0, 1], vec![3, 8], vec![2, 11], vec![5, 6]] {
+        let reconstruction = reconstruct(&faulty).unwrap();
+        assert_eq!(f, reconstruction.polynomial);
+        assert_eq!(faulty.iter().map(|&i| primes[i]).collect::<Vec<_>>(), reconstruction.faulty);
+    }
+    // a majority of faults cannot be outvoted
+    assert_eq!(None, reconstruct(&[0, 1, 2, 4, 5, 7, 9]));
+
+    // too few primes for the bound
+    let mut reconstructor = MultiModularReconstructor::new(bound);
+    for &p in &primes[..3] {
+        reconstructor.add_residues(p, image(p));
+    }
+    assert_eq!(None, reconstructor.reconstruct());
+    assert_eq!(primes[..3], reconstructor.primes().collect::<Vec<_>>());
+}
+
+#[test]
+pub fn test_isolate_real_roots() {
+    use num::BigRational;
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let linear = |r: &BigRational| Polynomial::new(vec![-r.clone(), q(1, 1)]);
+
+    // close roots, a repeated root, a root at zero and an irreducible quadratic
+    let roots = [q(-7, 3), q(0, 1), q(1, 1000), q(2, 1000), q(2, 1000), q(5, 1)];
+    let p: Polynomial<BigRational> = roots.iter().map(linear).product::<Polynomial<BigRational>>()
+        * "3x^2 + 1".parse::<Polynomial<BigRational>>().unwrap();
+    let intervals = p.isolate_real_roots();
+    let distinct = [q(-7, 3), q(0, 1), q(1, 1000), q(2, 1000), q(5, 1)];
+    assert_eq!(distinct.len(), intervals.len());
+    for (interval, root) in intervals.iter().zip(&distinct) {
+        assert!(interval.contains(root));
+        assert_eq!(1, distinct.iter().filter(|r| interval.contains(r)).count());
+        let refined = p.refine_root(interval, &q(1, 1_000_000));
+        assert!(refined.contains(root));
+        assert!(refined.width() <= q(1, 1_000_000));
+    }
+    assert!(intervals.windows(2).all(|w| w[0].hi() < w[1].lo()));
+
+    // √2 and -√2 from x^2 - 2, with 30 correct digits
+    let sqrt2: Polynomial<BigRational> = "x^2 - 2".parse().unwrap();
+    let intervals = sqrt2.isolate_real_roots();
+    assert_eq!(2, intervals.len());
+    let width = BigRational::new(1.into(), num::pow(num::BigInt::from(10), 30));
+    let refined = sqrt2.refine_root(&intervals[1], &width);
+    let lo_squared = refined.lo() * refined.lo();
+    let hi_squared = refined.hi() * refined.hi();
+    assert!(lo_squared <= q(2, 1) && q(2, 1) <= hi_squared);
+
+    assert!("x^2 + 1".parse::<Polynomial<BigRational>>().unwrap().isolate_real_roots().is_empty());
+    assert!("4".parse::<Polynomial<BigRational>>().unwrap().isolate_real_roots().is_empty());
+}
+
+#[test]
+pub fn test_real_algebraic() {
+    use crate::real_roots::{IntervalSign, RealAlgebraic};
+    use num::BigRational;
+    use std::cmp::Ordering;
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let r = |a: i64, b: i64| RealAlgebraic::from(q(a, b));
+    let parse = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+
+    // -√2, 1 and √2, and √2 again as a root of x^4 - 4
+    let roots = parse("x^3 - x^2 - 2x + 2").real_roots();
+    assert_eq!(3, roots.len());
+    assert!(roots[0] < r(-1, 1) && r(1, 1) == roots[1] && roots[2] > r(141, 100));
+    let sqrt2 = parse("x^4 - 4").real_roots().pop().unwrap();
+    assert_eq!(roots[2], sqrt2);
+    assert_eq!(Ordering::Greater, sqrt2.cmp(&roots[0]));
+    assert!(sqrt2 < r(142, 100));
+    // 1 + √2 is not √2
+    let silver = parse("x^2 - 2x - 1").real_roots().pop().unwrap();
+    assert!(sqrt2 < silver);
+    assert_eq!(silver, sqrt2.eval(&parse("x + 1")));
+
+    // exact values and signs at √2
+    assert_eq!(r(2, 1), sqrt2.eval(&parse("x^2")));
+    assert_eq!(parse("x^2 - 8").real_roots().pop().unwrap(), sqrt2.eval(&parse("x^3")));
+    assert_eq!(roots[0], sqrt2.eval(&parse("-x")));
+    assert_eq!(Ordering::Equal, sqrt2.sign_of(&parse("x^3 - 2x")));
+    assert_eq!(Ordering::Less, sqrt2.sign_of(&parse("x^2 - 2x")));
+    assert_eq!(Ordering::Greater, sqrt2.sign_of(&parse("100x^2 - 199")));
+    let mut refined = sqrt2.clone();
+    refined.refine(&q(1, 1000));
+    assert!(refined.interval().width() <= q(1, 1000) && refined == sqrt2);
+
+    let p = parse("x^3 - x^2 - 2x + 2");
+    assert_eq!(IntervalSign::Positive, p.sign_on_interval(&q(-1, 1), &q(1, 2)));
+    assert_eq!(IntervalSign::Negative, p.sign_on_interval(&q(11, 10), &q(14, 10)));
+    assert_eq!(IntervalSign::Mixed, p.sign_on_interval(&q(0, 1), &q(2, 1)));
+    assert_eq!(IntervalSign::NonNegative, p.sign_on_interval(&q(0, 1), &q(1, 1)));
+    assert_eq!(IntervalSign::NonPositive, p.sign_on_interval(&q(1, 1), &q(14, 10)));
+    assert_eq!(IntervalSign::Zero, p.sign_on_interval(&q(1, 1), &q(1, 1)));
+    assert_eq!(IntervalSign::Zero, Polynomial::zero().sign_on_interval(&q(0, 1), &q(1, 1)));
+    assert_eq!(IntervalSign::NonPositive, parse("-x^4 + 4x^2 - 4").sign_on_interval(&q(-2, 1), &q(2, 1)));
+
+    // (x^2 - 2)^2 is smallest at ±√2, and the least of those is taken
+    let square = parse("x^4 - 4x^2 + 4");
+    assert_eq!(sqrt2, square.minimize_on_interval(&q(0, 1), &q(3, 1)));
+    assert_eq!(roots[0], square.minimize_on_interval(&q(-3, 1), &q(3, 1)));
+    assert_eq!(r(2, 1), square.minimize_on_interval(&q(2, 1), &q(3, 1)));
+    assert_eq!(r(-1, 4), parse("-x^3").minimize_on_interval(&q(-1, 2), &q(-1, 4)));
+    assert_eq!(r(5, 1), parse("7").minimize_on_interval(&q(5, 1), &q(6, 1)));
+}
+
+#[test]
+pub fn test_extrema() {
+    use crate::real_roots::RealAlgebraic;
+    use num::BigRational;
+    let q = |a: i64, b: i64| RealAlgebraic::from(BigRational::new(a.into(), b.into()));
+    let n = |a: i64| BigRational::from_integer(a.into());
+    let parse = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+
+    // x^4 - 4x^2 + x has critical points at the roots of 4x^3 - 8x + 1
+    let p = parse("x^4 - 4x^2 + x");
+    let critical = p.critical_points();
+    assert_eq!(3, critical.len());
+    let derivative = parse("4x^3 - 8x + 1");
+    for x in &critical {
+        assert_eq!(std::cmp::Ordering::Equal, x.sign_of(&derivative));
+    }
+    assert!(q(-3, 2) < critical[0] && critical[0] < q(-1, 1) && critical[1] < q(1, 4) && q(1, 1) < critical[2]);
+
+    // the global minimum on [-3, 3] is at the left critical point, and the
+    // value there is an algebraic number of degree 3 near -5.444
+    let extrema = p.global_extrema_on(&n(-3), &n(3));
+    assert_eq!(critical[0], extrema.argmin);
+    assert!(q(-545, 100) < extrema.min && extrema.min < q(-544, 100));
+    assert_eq!(3, extrema.min.polynomial().degree().unwrap());
+    assert_eq!(q(48, 1), extrema.max);
+    assert_eq!(q(3, 1), extrema.argmax);
+    // on [0, 1] the interior critical point is a local maximum
+    let extrema = p.global_extrema_on(&n(0), &n(1));
+    assert_eq!(q(1, 1), extrema.argmin);
+    assert_eq!(q(-2, 1), extrema.min);
+    assert_eq!(critical[1], extrema.argmax);
+    assert!(extrema.max > q(0, 1));
+
+    // a constant has no critical points and every point is extreme
+    assert!(parse("5").critical_points().is_empty());
+    let extrema = parse("5").global_extrema_on(&n(1), &n(2));
+    assert_eq!((q(5, 1), q(1, 1)), (extrema.min, extrema.argmin));
+    assert_eq!((q(5, 1), q(1, 1)), (extrema.max, extrema.argmax));
+    let extrema = parse("x^2").global_extrema_on(&n(-1), &n(1));
+    assert_eq!((q(0, 1), q(0, 1)), (extrema.min, extrema.argmin));
+    assert_eq!((q(1, 1), q(-1, 1)), (extrema.max, extrema.argmax));
+}
+
+#[test]
+pub fn test_sos_decomposition() {
+    use num::BigRational;
+    let parse = |s: &str| s.parse::<Polynomial<BigRational>>().unwrap();
+    let sos = |s: &str| {
+        let f = parse(s);
+        let (a, b, certificate) = f.sos_decomposition()?;
+        assert_eq!(f, a.clone() * a.clone() + b.clone() * b.clone());
+        assert!(certificate.verify(&f, &a, &b));
+        Some((a, b))
+    };
+
+    // x^4 + 1 = (x^2 + i)(x^2 - i)
+    let (a, b) = sos("x^4 + 1").unwrap();
+    assert_eq!((2, 0), (a.degree().unwrap(), b.degree().unwrap()));
+    // (x^2 + 1)(x^2 + 4) pairs x + i with x + 2i or x - 2i
+    let (a, b) = sos("x^4 + 5x^2 + 4").unwrap();
+    assert_eq!((2, 1), (a.degree().unwrap(), b.degree().unwrap()));
+    // x^4 + 4 = (x^2 + 2x + 2)(x^2 - 2x + 2), and both factors split
+    assert!(sos("x^4 + 4").is_some());
+    // squares, even multiplicities and leading coefficients that are sums
+    // of two squares, like 5 = 1 + 4 and 1/2 = 1/4 + 1/4
+    assert!(sos("x^2 - 2x + 1").is_some());
+    assert!(sos("5x^6 + 10x^4 + 5x^2").is_some());
+    let f = parse("x^2 + 1").scalar_mul(BigRational::new(1.into(), 2.into()));
+    assert!(f.sos_decomposition().is_some());
+    assert!(sos("0").is_some() && sos("25").is_some());
+
+    // nonnegative, but x^2 + 3 and x^2 + 2 stay irreducible over Q(i), and
+    // 3 is not a sum of two rational squares
+    assert!(sos("x^2 + 3").is_none());
+    assert!(sos("x^2 + 2").is_none());
+    assert!(sos("3x^2 + 3").is_none());
+    // (x^2 + 3)^2 is still a square
+    assert!(sos("x^4 + 6x^2 + 9").is_some());
+
+    // negative somewhere
+    assert!(sos("x^2 - 1").is_none());
+    assert!(sos("x^3 + 1").is_none());
+    assert!(sos("-x^2 - 1").is_none());
+    // (x^2 + 1)(x - 1)(x - 2) has even degree and a positive leading
+    // coefficient, but is negative on (1, 2)
+    assert!(sos("x^4 - 3x^3 + 3x^2 - 3x + 2").is_none());
+}
+
+#[test]
+pub fn test_decide() {
+    use crate::real_roots::{decide, Formula, RealAlgebraic};
+    use num::BigRational;
+    use std::cmp::Ordering;
+    let q = |a: i64| RealAlgebraic::from(BigRational::from_integer(a.into()));
+    let sign = |s: &str, o| Formula::Sign(s.parse::<Polynomial<BigRational>>().unwrap(), o);
+    let not = |f| Formula::Not(Box::new(f));
+
+    // 1 < x < √2 and x^3 = 2 has the solution ∛2
+    let f = Formula::And(vec![
+        sign("x - 1", Ordering::Greater),
+        sign("x^2 - 2", Ordering::Less),
+        sign("x^3 - 2", Ordering::Equal),
+    ]);
+    let x = decide(&f).unwrap();
+    assert!(f.holds_at(&x) && q(1) < x && x < q(2));
+    assert_eq!(Ordering::Equal, x.sign_of(&"x^3 - 2".parse().unwrap()));
+    // but x > √2 and x^3 = 2 has none
+    let g = Formula::And(vec![sign("x^2 - 2", Ordering::Greater), sign("x", Ordering::Greater), sign("x^3 - 2", Ordering::Equal)]);
+    assert_eq!(None, decide(&g));
+
+    // only the open gap between the roots of x^2 - 1 and x^2 - 4
+    let h = Formula::And(vec![sign("x^2 - 1", Ordering::Greater), sign("x^2 - 4", Ordering::Less), sign("x", Ordering::Less)]);
+    let x = decide(&h).unwrap();
+    assert!(q(-2) < x && x < q(-1));
+    // x^2 - 2x + 1 ≥ 0 holds everywhere, and so do empty conjunctions
+    assert_eq!(None, decide(&not(not(sign("x^2 - 2x + 1", Ordering::Less)))));
+    assert_eq!(None, decide(&not(Formula::Or(vec![sign("x^2 - 2x + 1", Ordering::Greater), sign("x - 1", Ordering::Equal)]))));
+    assert!(decide(&Formula::And(Vec::new())).is_some());
+    assert_eq!(None, decide(&Formula::Or(Vec::new())));
+    assert_eq!(None, decide(&sign("3", Ordering::Less)));
+    assert!(decide(&sign("0", Ordering::Equal)).is_some());
+}
+
+#[test]
+pub fn test_cache() {
+    use crate::cache::Cache;
+    use crate::cyclotomic::cyclotomic;
+    use crate::fields::Zp;
+
+    assert!(std::ptr::eq(Cache::global(), Cache::global()));
+    let cache = Cache::new();
+    assert_eq!(vec![2, 3, 5, 7], cache.primes_up_to(10));
+    assert_eq!(25, cache.primes_up_to(100).len());
+    assert_eq!(vec![2, 3, 5], cache.primes_up_to(6));
+    assert!(cache.primes_up_to(1).is_empty());
+
+    std::thread::scope(|s| {
+        for t in 0..4 {
+            let cache = &cache;
+            s.spawn(move || {
+                for n in 1..30 {
+                    assert_eq!(cyclotomic(n + t), cache.cyclotomic(n + t));
+                }
+                assert_eq!(1229, cache.primes_up_to(10_000).len());
+                let m = cache.irreducible::<3>(4);
+                assert!(m.is_irreducible());
+                assert_eq!(Some(4), m.degree());
+            });
+        }
+    });
+    assert_eq!(cache.irreducible::<5>(2), Polynomial::<Zp<5>>::irreducibles_of_degree(2).next().unwrap());
+
+    // the variants of algorithms that take a cache agree with the plain ones
+    use crate::cyclotomic::{factor_xn_minus_1, factor_xn_minus_1_with_cache, factor_xn_plus_1, factor_xn_plus_1_with_cache};
+    use crate::fields::PolyMod;
+    for n in [1, 6, 12, 30] {
+        assert_eq!(factor_xn_minus_1(n), factor_xn_minus_1_with_cache(n, &cache));
+        assert_eq!(factor_xn_plus_1(n), factor_xn_plus_1_with_cache(n, Cache::global()));
+    }
+    assert_eq!(PolyMod::<Zp<3>>::x_of_degree(4), PolyMod::x_of_degree_with_cache(4, &cache));
+    let p = Polynomial::new([-8, 0, -16, 0, -10, 0, -4, 0, -2].map(num::BigInt::from).to_vec());
+    assert_eq!(p.clone().factor(), p.factor_with_cache(&cache));
+}
+
+#[test]
+pub fn test_eval() {
+    use num::{BigInt, BigRational};
+
+    let p = Polynomial::new([-6, 11, -6, 1].map(BigInt::from).to_vec());
+    for r in 1..=3 {
+        assert!(p.eval(&BigInt::from(r)).is_zero());
+    }
+    assert_eq!(BigInt::from(-6), p.eval(&BigInt::zero()));
+    assert!(Polynomial::<BigInt>::zero().eval(&BigInt::from(5)).is_zero());
+
+    let r = |n: i32, d: i32| BigRational::new(n.into(), d.into());
+    // (1/2 - 1)(1/2 - 2)(1/2 - 3) = -15/8
+    assert_eq!(r(-15, 8), p.eval(&r(1, 2)));
+
+    // p(x + 1) = x (x - 1) (x - 2)
+    let shift = Polynomial::new([1, 1].map(BigInt::from).to_vec());
+    assert_eq!(Polynomial::new([0, 2, -3, 1].map(BigInt::from).to_vec()), p.eval(&shift));
+    let x = Polynomial::new([0, 1].map(BigInt::from).to_vec());
+    assert_eq!(p, p.eval(&x));
+    assert_eq!(Polynomial::new(vec![BigInt::from(7)]), Polynomial::new(vec![BigInt::from(7)]).eval(&shift));
+}
+
+#[cfg(feature = "stats")]
+#[test]
+pub fn test_stats() {
+    use crate::stats::{counts, measure, reset, OpCounts};
+    use num::{BigInt, BigRational};
+
+    reset();
+    let p: Polynomial<BigRational> = "x^3 - 1".parse().unwrap();
+    let q: Polynomial<BigRational> = "x^2 - 1".parse().unwrap();
+    let (product, outer) = measure(|| {
+        let (g, inner) = measure(|| p.clone().gcd(q.clone()));
+        assert_eq!(g, "x - 1".parse().unwrap());
+        assert_eq!(1, inner.gcds);
+        assert!(inner.divisions > 0);
+        p.clone() * q.clone()
+    });
+    assert_eq!(Some(5), product.degree());
+    assert_eq!(1, outer.gcds);
+    assert!(outer.multiplications >= 12);
+    assert_eq!(outer, counts());
+
+    let (_, division) = measure(|| p.clone().div_rem(q.clone()));
+    assert_eq!(1, division.divisions);
+    assert_eq!(outer + division, counts());
+
+    let content = OpCounts { multiplications: 0, divisions: 0, gcds: 2 };
+    let integer = Polynomial::new([6, 4, 10].map(BigInt::from).to_vec());
+    assert_eq!((BigInt::from(2), content), measure(|| integer.content()));
+    assert_eq!("0 multiplications, 0 divisions, 2 gcds", content.to_string());
+    reset();
+    assert_eq!(OpCounts::default(), counts());
+}
+
+#[test]
+pub fn test_compose() {
+    use crate::COMPOSE_THRESHOLD;
+    use num::{BigInt, BigRational};
+
+    let poly = |c: &[i64]| Polynomial::new_trim_zeroes(c.iter().map(|&c| BigInt::from(c)).collect());
+    let shift = poly(&[1, 1]);
+    let zero = Polynomial::<BigInt>::zero();
+    assert_eq!(zero, zero.compose(&shift));
+    assert_eq!(poly(&[3]), poly(&[3]).compose(&shift));
+    assert_eq!(poly(&[5]), poly(&[1, 2]).compose(&poly(&[2])));
+    assert_eq!(poly(&[0, 0, 0, 0, 1]), poly(&[0, 0, 1]).compose(&poly(&[0, 0, 1])));
+
+    // both paths agree with Horner, for lengths around the threshold and powers of two
+    for n in [COMPOSE_THRESHOLD - 1, COMPOSE_THRESHOLD, 17, 31, 32, 33, 64, 100] {
+        let p = poly(&(0..n as i64).map(|i| (i * 7) % 11 - 5).collect::<Vec<_>>());
+        for q in [poly(&[-2, 3, 1]), poly(&[1, 1]), poly(&[0, 0, 0, 1]), poly(&[4])] {
+            assert_eq!(p.eval(&q), p.compose(&q));
+        }
+        let x = BigInt::from(3);
+        assert_eq!(p.eval(&poly(&[-2, 3, 1]).eval(&x)), p.compose(&poly(&[-2, 3, 1])).eval(&x));
+    }
+
+    // (x + 1)^20 composed with x - 1 is x^20
+    let p: Polynomial<BigRational> = (0..20).map(|_| "x + 1".parse::<Polynomial<BigRational>>().unwrap()).product();
+    let x20 = Polynomial::from_elem_with_degree(BigRational::from_integer(1.into()), 20);
+    assert_eq!(x20, p.compose(&"x - 1".parse().unwrap()));
+}
+
+#[test]
+pub fn test_diff_display() {
+    use crate::fields::Zp;
+
+    let p = Polynomial::new(vec![1, 2, 3]);
+    assert_eq!("no differences", p.diff_display(&p.clone()).to_string());
+    assert_eq!("no differences", Polynomial::<i64>::zero().diff_display(&Polynomial::zero()).to_string());
+    assert_eq!("- x^0: 0\n+ x^0: 7\n", Polynomial::zero().diff_display(&Polynomial::new(vec![7])).to_string());
+
+    let q = Polynomial::new(vec![1, 2]);
+    assert_eq!("- x^2: 3\n+ x^2: 0\n  (2 equal terms)\n", p.diff_display(&q).to_string());
+    let r = Polynomial::new(vec![-1, 2, 3]);
+    assert_eq!("  (2 equal terms)\n- x^0: 1\n+ x^0: -1\n", p.diff_display(&r).to_string());
+
+    // large polynomials with a single difference stay short
+    let big: Polynomial<Zp<7>> = Polynomial::new((1..=100).map(|i| Zp::new(i % 6 + 1)).collect());
+    let mut changed = big.clone();
+    changed.coeffs[40] = Zp::new(0);
+    let expected = format!("  (59 equal terms)\n- x^40: {}\n+ x^40: 0\n  (40 equal terms)\n", big.coeffs[40]);
+    assert_eq!(expected, big.diff_display(&changed).to_string());
+}
+
+#[test]
+pub fn test_laws() {
+    use crate::fields::Zp;
+    use crate::laws::{
+        check_coefficient_domain_laws, check_commutative_ring_laws, check_euclidean_domain_laws, check_field_laws,
+    };
+    use crate::rational_function::RationalFunction;
+    use num::{BigInt, BigRational};
+
+    let zp: Vec<_> = (0..5).map(Zp::<5>::new).collect();
+    check_field_laws(&zp).unwrap();
+    check_coefficient_domain_laws(&zp).unwrap();
+
+    let integers: Vec<_> = [-6, -1, 0, 1, 2, 9].map(BigInt::from).to_vec();
+    check_euclidean_domain_laws(&integers).unwrap();
+    check_coefficient_domain_laws(&integers).unwrap();
+    check_euclidean_domain_laws(&[-4i64, 0, 3, 7]).unwrap();
+
+    let r = |n: i32, d: i32| BigRational::new(n.into(), d.into());
+    let rationals = [r(0, 1), r(1, 1), r(-2, 3), r(5, 2)];
+    check_field_laws(&rationals).unwrap();
+    check_coefficient_domain_laws(&rationals).unwrap();
+
+    let polynomials: Vec<Polynomial<BigRational>> =
+        ["0", "1", "x - 1", "2x^2 + 3", "x^3 - x"].iter().map(|s| s.parse().unwrap()).collect();
+    check_euclidean_domain_laws(&polynomials).unwrap();
+
+    let p = |c: &[u64]| Polynomial::new(c.iter().map(|&c| Zp::<3>::new(c)).collect());
+    let functions = [
+        RationalFunction::zero(),
+        RationalFunction::from_polynomial(p(&[0, 1])),
+        RationalFunction::new(p(&[1]), p(&[1, 1])),
+        RationalFunction::new(p(&[2, 0, 1]), p(&[0, 2])),
+    ];
+    check_field_laws(&functions).unwrap();
+
+    // floating point addition is not associative: 1 + (1 + 10^16) rounds to 10^16
+    let violation = check_commutative_ring_laws(&[1.0, 1e16, -1e16]).unwrap_err();
+    assert_eq!("a + (b + c) = (a + b) + c", violation.law);
+    assert_eq!(3, violation.elements.len());
+    assert!(violation.to_string().starts_with("`a + (b + c) = (a + b) + c` does not hold for "));
+}
+
+#[test]
+pub fn test_taylor_shift() {
+    use crate::COMPOSE_THRESHOLD;
+    use num::{BigInt, BigRational};
+
+    let poly = |c: &[i64]| Polynomial::new_trim_zeroes(c.iter().map(|&c| BigInt::from(c)).collect());
+    assert_eq!(Polynomial::zero(), Polynomial::<BigInt>::zero().taylor_shift(&BigInt::from(3)));
+    assert_eq!(poly(&[5]), poly(&[5]).taylor_shift(&BigInt::from(3)));
+    // (x - 1)^3 shifted by 1 is x^3
+    assert_eq!(poly(&[0, 0, 0, 1]), poly(&[-1, 3, -3, 1]).taylor_shift(&BigInt::from(1)));
+
+    for n in [2, COMPOSE_THRESHOLD - 1, COMPOSE_THRESHOLD, 40] {
+        let p = poly(&(0..n as i64).map(|i| (i * 5) % 9 - 4).collect::<Vec<_>>());
+        for a in [-3, 0, 1, 7] {
+            let a = BigInt::from(a);
+            let shifted = p.taylor_shift(&a);
+            assert_eq!(p.compose(&poly(&[0, 1]).taylor_shift(&a)), shifted);
+            assert_eq!(p, shifted.taylor_shift(&-a.clone()));
+            for x in -2..=2 {
+                let x = BigInt::from(x);
+                assert_eq!(p.eval(&(&x + &a)), shifted.eval(&x));
+            }
+        }
+    }
+
+    let p: Polynomial<BigRational> = "x^2 - 2".parse().unwrap();
+    let half = BigRational::new(1.into(), 2.into());
+    assert_eq!("x^2 + x - 7/4".parse::<Polynomial<BigRational>>().unwrap(), p.taylor_shift(&half));
+}
+
+#[test]
+pub fn test_karatsuba() {
+    use crate::fields::Zp;
+    use crate::KARATSUBA_THRESHOLD;
+    use num::BigInt;
+
+    let poly = |len: usize, seed: i64| {
+        Polynomial::new_trim_zeroes((0..len as i64).map(|i| BigInt::from((i * seed + 3) % 17 - 8)).collect())
+    };
+    let zero = Polynomial::<BigInt>::zero();
+    assert_eq!(zero, zero.karatsuba_mul(&poly(50, 3), 2));
+    assert_eq!(zero, poly(50, 3).karatsuba_mul(&zero, 2));
+
+    // balanced, unbalanced and very unbalanced factors, with and without recursion
+    for (m, n) in [(1, 1), (2, 2), (5, 3), (33, 33), (64, 63), (100, 7), (100, 40), (257, 129), (300, 31)] {
+        let (p, q) = (poly(m, 5), poly(n, 7));
+        let expected = p.schoolbook_mul(&q);
+        for threshold in [0, 2, 3, 8, KARATSUBA_THRESHOLD] {
+            assert_eq!(expected, p.karatsuba_mul(&q, threshold), "{m} x {n} with threshold {threshold}");
+            assert_eq!(expected, q.karatsuba_mul(&p, threshold));
+        }
+        assert_eq!(expected, p.clone() * q.clone());
+    }
+
+    // leading coefficients can cancel modulo a prime in the middle term
+    let p: Polynomial<Zp<2>> = Polynomial::new((0..70).map(|i| Zp::new(i % 3 % 2)).chain([Zp::new(1)]).collect());
+    assert_eq!(p.schoolbook_mul(&p), p.karatsuba_mul(&p, 2));
+}
+
+#[test]
+pub fn test_reference_oracles() {
+    use crate::fields::Zp;
+    use crate::modular_factorization::count_irreducible;
+    use crate::reference;
+    use num::{BigInt, One};
+
+    // every polynomial over GF(3) with up to 6 coefficients, by its digits in base 3
+    let polynomials = (1..3u64.pow(6)).map(|mut n| {
+        let mut coeffs = Vec::new();
+        while n > 0 {
+            coeffs.push(Zp::<3>::new(n % 3));
+            n /= 3;
+        }
+        Polynomial::new(coeffs)
+    });
+    let mut irreducibles = [0; 6];
+    for p in polynomials {
+        let factorization = reference::factor_mod_p(&p);
+        assert_eq!(factorization, p.clone().factor_mod_p(), "{p:?}");
+        let roots: Vec<_> = factorization
+            .factors
+            .iter()
+            .filter(|(f, _)| f.degree() == Some(1))
+            .map(|(f, _)| -f.coeffs[0])
+            .collect();
+        let mut sorted = roots.clone();
+        sorted.sort_by_key(|r| r.value());
+        assert_eq!(sorted, reference::roots(&p));
+        let irreducible = factorization.factors.len() == 1 && factorization.factors[0].1.get() == 1;
+        assert_eq!(irreducible, p.is_irreducible());
+        if irreducible && factorization.leading_coeff.is_one() {
+            irreducibles[p.degree().unwrap()] += 1;
+        }
+    }
+    for (n, &count) in irreducibles.iter().enumerate().skip(1) {
+        assert_eq!(count_irreducible(3, n), BigInt::from(count));
+    }
+
+    let p = Polynomial::new((0..45).map(|i| Zp::<7>::new(i * i % 7)).chain([Zp::new(3)]).collect());
+    let q = Polynomial::new((0..30).map(|i| Zp::<7>::new(i % 5)).chain([Zp::new(1)]).collect());
+    assert_eq!(reference::mul(&p, &q), p.clone() * q.clone());
+    assert_eq!(reference::mul(&p, &Polynomial::zero()), Polynomial::zero());
+}
+
+#[test]
+pub fn test_mul_fft() {
+    use crate::ntt::FFT_THRESHOLD;
+    use num::BigInt;
+
+    let poly = |len: usize, seed: i64, shift: usize| {
+        Polynomial::new_trim_zeroes(
+            (0..len as i64).map(|i| (BigInt::from((i * seed + 5) % 23 - 11) << shift) + i).collect(),
+        )
+    };
+    let zero = Polynomial::<BigInt>::zero();
+    assert_eq!(zero, zero.mul_fft(&poly(10, 3, 0)));
+    let one = Polynomial::new(vec![BigInt::from(1)]);
+    assert_eq!(poly(10, 3, 0), one.mul_fft(&poly(10, 3, 0)));
+
+    // small and huge coefficients need one and many primes
+    for (m, n, shift) in [(1, 1, 0), (7, 3, 0), (100, 37, 0), (64, 64, 500), (300, 2, 62), (513, 255, 2000)] {
+        let (p, q) = (poly(m, 5, shift), -poly(n, 7, shift / 2));
+        assert_eq!(p.schoolbook_mul(&q), p.mul_fft(&q), "{m} x {n} with {shift} bits");
+    }
+    // the operator switches to transforms at the threshold
+    let (p, q) = (poly(FFT_THRESHOLD, 3, 70), poly(FFT_THRESHOLD + 10, 11, 10));
+    assert_eq!(p.karatsuba_mul(&q, 16), p * q);
+}
+
+#[test]
+pub fn test_mobius() {
+    use crate::mobius::Mobius;
+    use num::BigRational;
+
+    let r = |n: i32, d: i32| BigRational::new(n.into(), d.into());
+    let p: Polynomial<BigRational> = "x^3 - 2x^2 - x + 2".parse().unwrap();
+    let zero = Polynomial::<BigRational>::zero();
+    assert_eq!(zero, Mobius::reciprocal().apply(&zero));
+    assert_eq!(p, Mobius::identity().apply(&p));
+
+    // affine transformations are compositions
+    let affine = Mobius::new(r(2, 1), r(-1, 3), r(0, 1), r(1, 1));
+    assert_eq!(p.compose(&"2x - 1/3".parse().unwrap()), affine.apply(&p));
+    assert_eq!(p.taylor_shift(&r(5, 1)), Mobius::translation(r(5, 1)).apply(&p));
+    // the reciprocal reverses the coefficients
+    assert_eq!("2x^3 - x^2 - 2x + 1".parse::<Polynomial<BigRational>>().unwrap(), Mobius::reciprocal().apply(&p));
+
+    // the numerator vanishes where p does at the image, and loses a degree
+    // for the root that goes to infinity
+    let m = Mobius::new(r(1, 1), r(2, 1), r(2, 1), r(-3, 1));
+    let q = m.apply(&p);
+    assert_eq!(Some(3), q.degree());
+    for x in [r(0, 1), r(1, 2), r(7, 1), r(-4, 5)] {
+        let image = m.eval(&x).unwrap();
+        let den = r(2, 1) * x.clone() - r(3, 1);
+        assert_eq!(p.eval(&image) * den.clone() * den.clone() * den, q.eval(&x));
+    }
+    assert_eq!(None, m.eval(&r(3, 2)));
+    let root_at_infinity = Mobius::new(r(2, 1), r(0, 1), r(1, 1), r(1, 1));
+    assert_eq!(Some(r(2, 1)), root_at_infinity.eval_at_infinity());
+    assert_eq!(Some(2), root_at_infinity.apply(&p).degree());
+    assert_eq!(None, Mobius::translation(r(1, 1)).eval_at_infinity());
+
+    // composition agrees with applying one after the other, and inverses undo
+    let n = Mobius::new(r(3, 1), r(1, 1), r(-1, 1), r(2, 1));
+    let composed = m.compose(&n);
+    assert_eq!(n.apply(&m.apply(&p)), composed.apply(&p));
+    assert_eq!(m.eval(&n.eval(&r(1, 3)).unwrap()), composed.eval(&r(1, 3)));
+    assert_eq!(m.determinant() * n.determinant(), composed.determinant());
+    let identity = m.compose(&m.inverse());
+    assert_eq!(Some(r(5, 7)), identity.eval(&r(5, 7)));
+    assert_eq!(Mobius::identity(), m.inverse().compose(&m));
+}
+
+#[test]
+pub fn test_budan_fourier() {
+    use num::BigRational;
+
+    let n = |x: i32| BigRational::from_integer(x.into());
+    // (x + 2)(x - 1)^3 (x - 3)
+    let p: Polynomial<BigRational> = "x^5 - 4x^4 + 14x^2 - 17x + 6".parse().unwrap();
+    let points: Vec<BigRational> = (-8..=8).map(|k| BigRational::new(k.into(), 2.into())).collect();
+    let roots = [(n(-2), 1), (n(1), 3), (n(3), 1)];
+    for (i, a) in points.iter().enumerate() {
+        for b in &points[i..] {
+            let count: usize = roots.iter().filter(|(r, _)| a < r && r <= b).map(|(_, k)| k).sum();
+            let bound = p.budan_fourier_bound(a, b);
+            assert!(bound >= count && (bound - count).is_multiple_of(2), "({a}, {b}]");
+        }
+    }
+    assert_eq!(5, p.budan_fourier_bound(&n(-100), &n(100)));
+    assert_eq!(0, p.budan_fourier_bound(&n(1), &n(1)));
+    assert_eq!(1, p.budan_fourier_bound(&n(2), &n(3)));
+    // constants have no roots
+    assert_eq!(0, Polynomial::new(vec![n(3)]).budan_fourier_bound(&n(-1), &n(1)));
+}
+
+#[test]
+pub fn test_sparse_polynomial() {
+    use crate::fields::Zp;
+    use crate::laws::check_commutative_ring_laws;
+    use crate::sparse::SparsePolynomial;
+    use num::{BigInt, One};
+
+    let dense = |c: &[i64]| Polynomial::new_trim_zeroes(c.iter().map(|&c| BigInt::from(c)).collect());
+    let samples = [dense(&[]), dense(&[1]), dense(&[0, 3, 0, -1]), dense(&[2, 0, 0, 0, 0, 5]), dense(&[-1, 1])];
+    for p in &samples {
+        let sp = SparsePolynomial::from(p.clone());
+        assert_eq!(p.degree(), sp.degree());
+        assert_eq!(p.coeffs.iter().filter(|c| !c.is_zero()).count(), sp.num_terms());
+        assert_eq!(*p, Polynomial::from(sp.clone()));
+        assert_eq!(p.eval(&BigInt::from(-3)), sp.eval(&BigInt::from(-3)));
+        for q in &samples {
+            let sq = SparsePolynomial::from(q.clone());
+            assert_eq!(p.clone() + q.clone(), (sp.clone() + sq.clone()).into());
+            assert_eq!(p.clone() - q.clone(), (sp.clone() - sq.clone()).into());
+            assert_eq!(p.clone() * q.clone(), (sp.clone() * sq).into());
+        }
+    }
+    let sparse: Vec<_> = samples.iter().cloned().map(SparsePolynomial::from).collect();
+    check_commutative_ring_laws(&sparse).unwrap();
+
+    // cancellation removes terms, and repeated exponents are combined
+    let p = SparsePolynomial::<i64>::new([(5, 2), (1, 1), (5, -2), (0, 4), (0, 0)]);
+    assert_eq!(SparsePolynomial::new([(1, 1), (0, 4)]), p);
+    assert_eq!(None, p.coeff(5));
+    assert_eq!(Some(&4), p.coeff(0));
+    assert_eq!(vec![(0, &4), (1, &1)], p.terms().collect::<Vec<_>>());
+
+    // x^(12 * 10^11) + 1 at 2 modulo 13, where 2 has order 12
+    let huge = SparsePolynomial::new([(1_200_000_000_000, Zp::<13>::one()), (0, Zp::one())]);
+    assert_eq!(Zp::new(2), huge.eval(&Zp::new(2)));
+    let squared = huge.clone() * huge;
+    assert_eq!(3, squared.num_terms());
+    assert_eq!(Some(2_400_000_000_000), squared.degree());
+    assert_eq!(SparsePolynomial::constant(Zp::<13>::new(4)), SparsePolynomial::constant(Zp::new(2)) * SparsePolynomial::constant(Zp::new(2)));
+}
+
+#[test]
+pub fn test_sparse_gcd_square_free() {
+    use crate::fields::Zp;
+    use crate::sparse::SparsePolynomial;
+    use num::{BigInt, BigRational, One};
+
+    let n = |x: i64| BigRational::from_integer(x.into());
+    let sparse = |terms: &[(usize, i64)]| SparsePolynomial::new(terms.iter().map(|&(e, c)| (e, n(c))));
+    let dense = |c: &[i64]| Polynomial::new_trim_zeroes(c.iter().map(|&c| n(c)).collect());
+
+    // the sparse gcd agrees with the dense one on small polynomials
+    let samples = [dense(&[-2, 0, 1]), dense(&[4, -4, 1]), dense(&[0, -2, 0, 1]), dense(&[1, 1]), dense(&[-8, 0, 0, 0, 0, 0, 1]), dense(&[3])];
+    for p in &samples {
+        for q in &samples {
+            let expected = p.clone().gcd(q.clone());
+            assert_eq!(expected, Polynomial::from(SparsePolynomial::from(p.clone()).gcd(&SparsePolynomial::from(q.clone()))));
+        }
+    }
+    // gcd(x^m - 1, x^k - 1) = x^gcd(m, k) - 1 and gcd(x^6 - 8, x^4 - 4) = x^2 - 2
+    let xn_minus = |e: usize, c: i64| sparse(&[(e, 1), (0, -c)]);
+    assert_eq!(xn_minus(6, 1), xn_minus(1_000_002, 1).gcd(&xn_minus(600, 1)));
+    assert_eq!(xn_minus(2, 2), xn_minus(6, 8).gcd(&xn_minus(4, 4)));
+    assert_eq!(SparsePolynomial::one(), xn_minus(6, 8).gcd(&xn_minus(4, 5)));
+    // x (x^2 - 1) divides x^7 (x^1000000 - 1)
+    let p = sparse(&[(1_000_007, 1), (7, -1)]);
+    assert_eq!(sparse(&[(3, 1), (1, -1)]), p.gcd(&sparse(&[(3, 5), (1, -5)])));
+    // a sparse multiple of x^2 + 1, reduced modulo it
+    let p = sparse(&[(1_000, 1), (1, 3), (0, 1)]) * sparse(&[(2, 1), (0, 1)]);
+    assert_eq!(sparse(&[(2, 1), (0, 1)]), p.gcd(&sparse(&[(2, 2), (0, 2)])));
+    assert_eq!(SparsePolynomial::one(), p.gcd(&sparse(&[(3, 1), (1, 2), (0, 5)])));
+    assert_eq!(sparse(&[(1, 1), (0, 1)]), SparsePolynomial::zero().gcd(&sparse(&[(1, 3), (0, 3)])));
+    let m = dense(&[1, 0, 1]);
+    assert_eq!(Polynomial::from(p.clone()).div_rem(m.clone()).1, p.rem(&m));
+    let zp = |terms: &[(usize, u64)]| SparsePolynomial::new(terms.iter().map(|&(e, c)| (e, Zp::<101>::new(c))));
+    let huge = zp(&[(1_000_000_000_000, 1), (1, 3), (0, 1)]) * zp(&[(2, 1), (0, 1)]);
+    assert!(huge.rem(&Polynomial::new(vec![Zp::new(1), Zp::new(0), Zp::new(1)])).is_zero());
+    assert_eq!(zp(&[(2, 1), (0, 1)]), huge.gcd(&zp(&[(4, 1), (0, 100)])));
+
+    // every factorization multiplies back to the polynomial
+    let check = |p: SparsePolynomial<BigRational>, expected: Vec<(SparsePolynomial<BigRational>, usize)>| {
+        let sqf = p.square_free_factorization();
+        let factors: Vec<_> = sqf.factors.iter().map(|(g, j)| (g.clone(), j.get())).collect();
+        assert_eq!(expected, factors);
+        let product = sqf.factors.iter().fold(SparsePolynomial::constant(sqf.leading_coeff), |acc, (g, j)| {
+            (0..j.get()).fold(acc, |acc, _| acc * g.clone())
+        });
+        assert_eq!(p, product);
+    };
+    check(xn_minus(1_000_000, 3), vec![(xn_minus(1_000_000, 3), 1)]);
+    // x^2n - 2x^n + 1 = (x^n - 1)^2
+    check(sparse(&[(2_000, 1), (1_000, -2), (0, 1)]), vec![(xn_minus(1_000, 1), 2)]);
+    // x^2 (x^10 - 1)^2, where x joins the factor of the same multiplicity
+    check(sparse(&[(22, 2), (12, -4), (2, 2)]), vec![(sparse(&[(11, 1), (1, -1)]), 2)]);
+    check(sparse(&[(5, 7)]), vec![(sparse(&[(1, 1)]), 5)]);
+    check(sparse(&[(0, 7)]), vec![]);
+    assert!(SparsePolynomial::<BigRational>::zero().square_free_factorization().factors.is_empty());
+
+    // in characteristic 3, x^9 - 1 = (x - 1)^9 and x^18 + 2x^9 + 1 = (x + 1)^18
+    let zp = |terms: &[(usize, u64)]| SparsePolynomial::new(terms.iter().map(|&(e, c)| (e, Zp::<3>::new(c))));
+    let sqf = zp(&[(9, 1), (0, 2)]).square_free_factorization();
+    assert_eq!(vec![(zp(&[(1, 1), (0, 2)]), 9)], sqf.factors.into_iter().map(|(g, j)| (g, j.get())).collect::<Vec<_>>());
+    let sqf = zp(&[(18, 1), (9, 2), (0, 1)]).square_free_factorization();
+    assert_eq!(vec![(zp(&[(1, 1), (0, 1)]), 18)], sqf.factors.into_iter().map(|(g, j)| (g, j.get())).collect::<Vec<_>>());
+    // x^6 + x^3 = x^3 (x + 1)^3
+    let sqf = zp(&[(6, 1), (3, 1)]).square_free_factorization();
+    assert_eq!(vec![(zp(&[(2, 1), (1, 1)]), 3)], sqf.factors.into_iter().map(|(g, j)| (g, j.get())).collect::<Vec<_>>());
+
+    // x^8 - 16 = (x^2 - 2)(x^2 + 2)(x^2 + 2x + 2)(x^2 - 2x + 2), and x^4 - 2x + 1
+    // is not a binomial
+    let int = |terms: &[(usize, i64)]| SparsePolynomial::new(terms.iter().map(|&(e, c)| (e, BigInt::from(c))));
+    let factors = int(&[(8, 1), (0, -16)]).factor_binomial().unwrap();
+    let expected = [int(&[(2, 1), (0, -2)]), int(&[(2, 1), (0, 2)]), int(&[(2, 1), (1, 2), (0, 2)]), int(&[(2, 1), (1, -2), (0, 2)])];
+    assert_eq!(expected.to_vec(), factors);
+    assert_eq!(Some(vec![int(&[(7, 1), (0, -3)])]), int(&[(7, 1), (0, -3)]).factor_binomial());
+    assert_eq!(None, int(&[(4, 1), (1, -2), (0, 1)]).factor_binomial());
+    assert_eq!(None, int(&[(4, 2), (0, 1)]).factor_binomial());
+}
+
+#[test]
+pub fn test_factorization_arithmetic() {
+    use num::BigInt;
+
+    use crate::fields::Zp;
+
+    let zp = |c: &[u64]| Polynomial::new(c.iter().map(|&c| Zp::<7>::new(c)).collect());
+    let samples = [
+        zp(&[1, 1]),
+        zp(&[6, 0, 1]),
+        zp(&[3, 0, 0, 2]),
+        zp(&[1, 2, 1, 0, 5]),
+        zp(&[0, 0, 4]),
+        zp(&[2]),
+    ];
+    for p in &samples {
+        for q in &samples {
+            let (a, b) = (p.clone().factor_mod_p(), q.clone().factor_mod_p());
+            let product = a.clone() * b.clone();
+            assert_eq!(p.clone() * q.clone(), product.expand());
+            assert_eq!(Some(a.clone()), product.divide(&b));
+            let g = a.gcd(&b);
+            assert_eq!(p.clone().gcd(q.clone()), g.expand());
+
+            let (a, b) = (p.clone().square_free_factorization(), q.clone().square_free_factorization());
+            assert_eq!((p.clone() * q.clone()).square_free_factorization(), a.clone() * b.clone());
+            assert_eq!(Some(a.clone()), (a.clone() * b.clone()).divide(&b));
+            assert_eq!(p.clone().gcd(q.clone()).square_free_factorization(), a.gcd(&b));
+            let divides = p.clone().div_rem(q.clone()).1.is_zero();
+            assert_eq!(divides, a.divide(&b).is_some());
+        }
+    }
+
+    // zero is a multiple of everything
+    let zero = Polynomial::<Zp<7>>::zero().square_free_factorization();
+    let a = samples[3].clone().square_free_factorization();
+    assert_eq!(zero, zero.clone() * a.clone());
+    assert_eq!(Some(zero.clone()), zero.divide(&a));
+    assert_eq!(None, a.divide(&zero));
+    assert_eq!(samples[3].clone().gcd(Polynomial::zero()).square_free_factorization(), a.gcd(&zero));
+
+    // the leading coefficients divide as integers
+    let poly = |c: &[i32]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+    let a = poly(&[-6, 0, 6]).factor();
+    let b = poly(&[4, 4]).factor();
+    assert_eq!(None, a.divide(&b));
+    assert_eq!(poly(&[-3, 3]), a.divide(&poly(&[2, 2]).factor()).unwrap().expand());
+    assert_eq!(poly(&[2, 2]), a.gcd(&b).expand());
+}
+
+#[test]
+pub fn test_matrix() {
+    use num::{BigInt, BigRational, Integer};
+
+    use crate::fields::Zp;
+    use crate::linalg::{determinant_modular, Matrix};
+
+    let int = |rows: &[&[i64]]| Matrix::from_rows(rows.iter().map(|row| row.iter().map(|&x| BigInt::from(x)).collect()).collect());
+    let a = int(&[&[2, -3, 1], &[2, 0, -1], &[1, 4, 5]]);
+    assert_eq!(BigInt::from(49), a.determinant());
+    assert_eq!(3, a.rank());
+    // a zero pivot needs a row swap, which flips the sign
+    let b = int(&[&[0, 1, 2], &[3, 4, 5], &[6, 7, 9]]);
+    assert_eq!(determinant_modular(&b.clone().into_rows()), b.determinant());
+    assert_eq!(a.determinant() * b.determinant(), (a.clone() * b.clone()).determinant());
+    assert_eq!(a.determinant(), a.transpose().determinant());
+    assert_eq!(a.clone() + b.clone() - b.clone(), a);
+    assert_eq!(a.clone() * Matrix::identity(3), a);
+    assert_eq!(Matrix::zero(3, 3), a.clone() - a.clone());
+    assert_eq!(a.clone().scalar_mul(BigInt::from(2)), a.clone() + a.clone());
+    assert_eq!(vec![BigInt::from(0), BigInt::from(1), BigInt::from(10)], a.mul_vec(&[1, 1, 1].map(BigInt::from)));
+
+    // singular and rectangular matrices
+    let singular = int(&[&[1, 2, 3], &[2, 4, 6], &[1, 0, 1]]);
+    assert_eq!(BigInt::zero(), singular.determinant());
+    assert_eq!(2, singular.rank());
+    let wide = int(&[&[0, 0, 1, 2], &[0, 0, 2, 4], &[0, 1, 0, 0]]);
+    assert_eq!(2, wide.rank());
+    assert_eq!(2, wide.transpose().rank());
+    assert_eq!(0, Matrix::<BigInt>::zero(2, 3).rank());
+    assert_eq!(BigInt::from(1), Matrix::<BigInt>::identity(0).determinant());
+    assert_eq!((3, 4), (wide.num_rows(), wide.num_cols()));
+    assert_eq!((4, 3), (wide.transpose().num_rows(), wide.transpose().num_cols()));
+    assert_eq!(&[BigInt::from(0), BigInt::from(1), BigInt::from(0), BigInt::from(0)], wide.row(2));
+
+    // over Z[x], det [[x, 1], [1, x]] = x^2 - 1
+    let x = Polynomial::new(vec![BigInt::zero(), BigInt::from(1)]);
+    let one = Polynomial::new(vec![BigInt::from(1)]);
+    let m = Matrix::from_rows(vec![vec![x.clone(), one.clone()], vec![one.clone(), x.clone()]]);
+    assert_eq!(x.clone() * x - one, m.determinant());
+
+    // Gaussian elimination agrees with Bareiss over fields
+    let q = |rows: &Matrix<BigInt>| Matrix::from_fn(rows.num_rows(), rows.num_cols(), |i, j| BigRational::from_integer(rows[(i, j)].clone()));
+    for m in [&a, &b, &singular] {
+        assert_eq!(BigRational::from_integer(m.determinant()), q(m).determinant_gaussian());
+        assert_eq!(q(m).determinant(), q(m).determinant_gaussian());
+        assert_eq!(m.rank(), q(m).rank_gaussian());
+    }
+    let zp = |m: &Matrix<BigInt>| {
+        Matrix::from_fn(m.num_rows(), m.num_cols(), |i, j| Zp::<7>::new(Integer::mod_floor(&m[(i, j)], &BigInt::from(7)).try_into().unwrap()))
+    };
+    // 49 = 0 modulo 7
+    assert_eq!(Zp::zero(), zp(&a).determinant_gaussian());
+    assert_eq!(2, zp(&a).rank_gaussian());
+    assert_eq!(zp(&b).determinant(), zp(&b).determinant_gaussian());
+    let float = Matrix::from_rows(vec![vec![2.0, 1.0], vec![4.0, 3.0]]);
+    assert_eq!(2.0, float.determinant_gaussian());
+    let (rref, pivots) = q(&wide).row_echelon_form();
+    assert_eq!(vec![1, 2], pivots);
+    assert_eq!(q(&int(&[&[0, 1, 0, 0], &[0, 0, 1, 2], &[0, 0, 0, 0]])), rref);
+}
+
+#[test]
+pub fn test_solve() {
+    use num::BigRational;
+
+    use crate::fields::Zp;
+    use crate::linalg::{solve_rational, Matrix};
+
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let m = |rows: &[&[i64]]| Matrix::from_rows(rows.iter().map(|row| row.iter().map(|&x| q(x, 1)).collect()).collect());
+
+    // a square system agrees with Dixon's method
+    let a = m(&[&[2, 1, -1], &[-3, -1, 2], &[-2, 1, 2]]);
+    let b = [q(8, 1), q(-11, 1), q(-3, 1)];
+    let x = a.solve(&b).unwrap();
+    assert_eq!(vec![q(2, 1), q(3, 1), q(-1, 1)], x);
+    assert_eq!(Some(x), solve_rational(&a.clone().into_rows(), &b));
+    assert!(a.nullspace().is_empty());
+
+    // fractions
+    let a = Matrix::from_rows(vec![vec![q(1, 2), q(1, 3)], vec![q(1, 4), q(-1, 5)]]);
+    let x = a.solve(&[q(1, 1), q(0, 1)]).unwrap();
+    assert_eq!(vec![q(1, 1), q(0, 1)], a.mul_vec(&x));
+
+    // underdetermined: every solution is the particular one plus the null space
+    let a = m(&[&[1, 1, 0, 2], &[0, 0, 1, -1], &[1, 1, 1, 1]]);
+    let b = [q(3, 1), q(1, 1), q(4, 1)];
+    let x = a.solve(&b).unwrap();
+    assert_eq!(b.to_vec(), a.mul_vec(&x));
+    let basis = a.nullspace();
+    assert_eq!(4 - a.rank_gaussian(), basis.len());
+    for v in &basis {
+        assert!(a.mul_vec(v).iter().all(Zero::is_zero));
+        let y: Vec<_> = x.iter().zip(v).map(|(x, v)| x + v * q(5, 2)).collect();
+        assert_eq!(b.to_vec(), a.mul_vec(&y));
+    }
+    assert_eq!(vec![vec![q(-1, 1), q(1, 1), q(0, 1), q(0, 1)], vec![q(-2, 1), q(0, 1), q(1, 1), q(1, 1)]], basis);
+
+    // overdetermined and inconsistent
+    let a = m(&[&[1, 0], &[0, 1], &[1, 1]]);
+    assert_eq!(Some(vec![q(1, 1), q(2, 1)]), a.solve(&[q(1, 1), q(2, 1), q(3, 1)]));
+    assert_eq!(None, a.solve(&[q(1, 1), q(2, 1), q(4, 1)]));
+
+    // over GF(5), 2x + 3y = 1, x + 4y = 3 is singular but consistent
+    let zp = |rows: &[&[u64]]| Matrix::from_rows(rows.iter().map(|row| row.iter().map(|&x| Zp::<5>::new(x)).collect()).collect());
+    let a = zp(&[&[2, 3], &[1, 4]]);
+    let b = [Zp::new(1), Zp::new(3)];
+    assert_eq!(Zp::zero(), a.determinant());
+    let x = a.solve(&b).unwrap();
+    assert_eq!(b.to_vec(), a.mul_vec(&x));
+    assert_eq!(vec![vec![Zp::new(1), Zp::new(1)]], a.nullspace());
+    assert_eq!(None, a.solve(&[Zp::new(1), Zp::new(1)]));
+
+    // the zero matrix has everything in its null space
+    let zero = Matrix::<Zp<5>>::zero(2, 3);
+    assert_eq!(3, zero.nullspace().len());
+    assert_eq!(Some(vec![Zp::zero(); 3]), zero.solve(&[Zp::zero(), Zp::zero()]));
+}
+
+#[test]
+pub fn test_char_poly() {
+    use num::{BigInt, BigRational};
+
+    use crate::fields::Zp;
+    use crate::linalg::{determinant_polynomial, Matrix};
+
+    let q = |a: i64| BigRational::from_integer(a.into());
+    let samples: Vec<Matrix<BigRational>> = [
+        vec![vec![2, -3, 1], vec![2, 0, -1], vec![1, 4, 5]],
+        vec![vec![0, 1, 2, 0], vec![3, 4, 5, 1], vec![6, 7, 9, 2], vec![0, 0, 1, 0]],
+        vec![vec![0, 0, 1], vec![0, 0, 0], vec![1, 0, 0]],
+        vec![vec![1, 2], vec![2, 4]],
+        vec![vec![5]],
+    ]
+    .into_iter()
+    .map(|rows| Matrix::from_rows(rows.into_iter().map(|row| row.into_iter().map(q).collect()).collect()))
+    .collect();
+    for a in &samples {
+        let n = a.num_rows();
+        let p = a.char_poly();
+        assert_eq!(Some(n), p.degree());
+        assert_eq!(p, a.char_poly_hessenberg());
+        // det(x I - A)
+        let rows = |m: Matrix<BigRational>| m.into_rows();
+        assert_eq!(p, determinant_polynomial(&[rows(-a.clone()), rows(Matrix::identity(n))]));
+        let trace = (0..n).fold(q(0), |acc, i| acc + a[(i, i)].clone());
+        assert_eq!(-trace, p.coeffs[n - 1]);
+        let sign = if n % 2 == 0 { q(1) } else { q(-1) };
+        assert_eq!(sign * a.determinant(), p.coeffs[0]);
+        // Cayley–Hamilton
+        let at_a = p
+            .coeffs
+            .iter()
+            .rev()
+            .fold(Matrix::zero(n, n), |acc, c| acc * a.clone() + Matrix::identity(n).scalar_mul(c.clone()));
+        assert_eq!(Matrix::zero(n, n), at_a);
+    }
+    assert_eq!(Polynomial::new(vec![q(1)]), Matrix::<BigRational>::zero(0, 0).char_poly());
+    assert_eq!(Polynomial::new(vec![q(1)]), Matrix::<BigRational>::zero(0, 0).char_poly_hessenberg());
+
+    // over the integers and modulo a prime
+    let a = Matrix::from_rows(vec![vec![BigInt::from(0), BigInt::from(1)], vec![BigInt::from(-1), BigInt::from(0)]]);
+    assert_eq!(Polynomial::new(vec![BigInt::from(1), BigInt::zero(), BigInt::from(1)]), a.char_poly());
+    let zp = Matrix::from_rows(vec![vec![Zp::<7>::new(3), Zp::new(5), Zp::new(0)], vec![Zp::new(0), Zp::new(0), Zp::new(2)], vec![Zp::new(0), Zp::new(4), Zp::new(1)]]);
+    assert_eq!(zp.char_poly(), zp.char_poly_hessenberg());
+}
+
+#[test]
+pub fn test_newton_solve() {
+    use num::{BigInt, BigRational, Signed};
+
+    use crate::multivariate::MultiPolynomial;
+    use crate::newton::newton_solve;
+
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let x = |i| MultiPolynomial::<BigRational>::var(i);
+    let c = |a| MultiPolynomial::constant(q(a, 1));
+
+    // a linear system is solved in one step, and then the residual is zero
+    let linear = [c(2) * x(0) + x(1) - c(1), x(0) + c(3) * x(1) - c(2)];
+    let iterates = newton_solve(&linear, &[q(7, 1), q(-4, 1)], 10, None);
+    assert_eq!(vec![vec![q(7, 1), q(-4, 1)], vec![q(1, 5), q(3, 5)]], iterates);
+
+    // x^2 = 2 from 1: 3/2, 17/12, 577/408, with quadratic convergence
+    let sqrt2 = [x(0) * x(0) - c(2)];
+    let iterates = newton_solve(&sqrt2, &[q(1, 1)], 3, None);
+    let firsts: Vec<_> = iterates.iter().map(|x| x[0].clone()).collect();
+    assert_eq!(vec![q(1, 1), q(3, 2), q(17, 12), q(577, 408)], firsts);
+
+    // rounding keeps the denominators at 10^digits without losing accuracy
+    let exact = newton_solve(&sqrt2, &[q(1, 1)], 7, None).pop().unwrap();
+    let rounded = newton_solve(&sqrt2, &[q(1, 1)], 7, Some(40)).pop().unwrap();
+    assert!(exact[0].denom().bits() > 100);
+    assert!(rounded[0].denom() <= &BigInt::from(10).pow(40));
+    let error = (&rounded[0] * &rounded[0] - q(2, 1)).abs();
+    assert!(error < q(1, 1) / BigRational::from_integer(BigInt::from(10).pow(39)));
+
+    // intersecting the circle x^2 + y^2 = 25 with the hyperbola xy = 12 near (4, 3)
+    let system = [x(0) * x(0) + x(1) * x(1) - c(25), x(0) * x(1) - c(12)];
+    let last = newton_solve(&system, &[q(5, 1), q(2, 1)], 8, Some(60)).pop().unwrap();
+    assert!((&last[0] - q(4, 1)).abs() < q(1, 1_000_000_000_000) && (&last[1] - q(3, 1)).abs() < q(1, 1_000_000_000_000));
+
+    // the Jacobian of x^2 = 2 is singular at 0
+    assert_eq!(vec![vec![q(0, 1)]], newton_solve(&sqrt2, &[q(0, 1)], 5, None));
+}
+
+#[test]
+pub fn test_crt() {
+    use num::{BigInt, BigRational, Integer};
+
+    use crate::factorization::lagrange_interpolation;
+    use crate::fields::Zp;
+    use crate::multimodular::{crt, crt_poly};
+
+    let n = BigInt::from;
+    // every x modulo 2 * 3 * 5 * 7 is recovered from its residues
+    let moduli = [n(2), n(3), n(5), n(7)];
+    for x in 0..210 {
+        let residues: Vec<_> = moduli.iter().map(|m| (n(x).mod_floor(m), m.clone())).collect();
+        assert_eq!(Some(n(x)), crt(&residues));
+    }
+    // residues outside the range and large moduli
+    let big: BigInt = n(1) << 100;
+    assert_eq!(Some(&big - 1), crt(&[(n(-1), big.clone()), (&big * 3 - 1, big.clone())]));
+    assert_eq!(Some(n(0)), crt(&[]));
+    // non-coprime moduli: x = 3 (mod 10), x = 13 (mod 15), x = 8 (mod 25)
+    assert_eq!(Some(n(133)), crt(&[(n(3), n(10)), (n(13), n(15)), (n(8), n(25))]));
+    assert_eq!(None, crt(&[(n(3), n(10)), (n(4), n(15))]));
+
+    // congruences modulo x - x_i interpolate
+    let q = |a: i64| BigRational::from_integer(a.into());
+    let points = [(q(0), q(3)), (q(1), q(-1)), (q(2), q(4)), (q(-3), q(7))];
+    let residues: Vec<_> = points
+        .iter()
+        .map(|(x, y)| (Polynomial::new(vec![y.clone()]), Polynomial::new(vec![-x.clone(), q(1)])))
+        .collect();
+    assert_eq!(Some(lagrange_interpolation(points.to_vec())), crt_poly(&residues));
+
+    // over GF(5): f = x + 1 modulo x^2 + 2, and f = 3 modulo x^3 + x + 1
+    let zp = |c: &[u64]| Polynomial::new(c.iter().map(|&c| Zp::<5>::new(c)).collect());
+    let (m1, m2) = (zp(&[2, 0, 1]), zp(&[1, 1, 0, 1]));
+    let f = crt_poly(&[(zp(&[1, 1]), m1.clone()), (zp(&[3]), m2.clone())]).unwrap();
+    assert!(f.degree().unwrap() < 5);
+    assert_eq!(zp(&[1, 1]), f.clone().div_rem(m1.clone()).1);
+    assert_eq!(zp(&[3]), f.clone().div_rem(m2.clone()).1);
+    // the same congruence twice is redundant
+    assert_eq!(Some(f.clone()), crt_poly(&[(zp(&[1, 1]), m1.clone()), (zp(&[3]), m2), (f.clone() + m1.clone(), m1)]));
+}
+
+#[test]
+#[cfg(feature = "float")]
+pub fn test_homotopy() {
+    use num::complex::Complex64;
+    use num::BigRational;
+
+    use crate::homotopy::{certify, solve_certified, track_total_degree, PathStatus};
+    use crate::multivariate::MultiPolynomial;
+
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let x = |i| MultiPolynomial::<BigRational>::var(i);
+    let c = |a| MultiPolynomial::constant(q(a, 1));
+    let close = |a: &[Complex64], b: &[Complex64]| a.iter().zip(b).all(|(a, b)| (a - b).norm() < 1e-8);
+
+    // x^2 + 1 = 0 and y = x have the complex roots (i, i) and (-i, -i)
+    let system = [x(0) * x(0) + c(1), x(1) - x(0)];
+    let roots = solve_certified(&system);
+    assert_eq!(2, roots.len());
+    let i = Complex64::i();
+    for expected in [[i, i], [-i, -i]] {
+        let root = roots.iter().find(|root| close(&root.point, &expected)).unwrap();
+        assert!(root.radius < q(1, 1_000_000_000));
+    }
+
+    // xy = 1 and x = 2 have the single root (2, 1/2), and the other path of
+    // the two goes to infinity
+    let paths = track_total_degree(&[x(0) * x(1) - c(1), x(0) - c(2)]);
+    let mut statuses: Vec<_> = paths.iter().map(|path| path.status).collect();
+    statuses.sort_by_key(|&status| status as u8);
+    assert_eq!(vec![PathStatus::Converged, PathStatus::Diverged], statuses);
+    let end = &paths.iter().find(|path| path.status == PathStatus::Converged).unwrap().end;
+    assert!(close(end, &[Complex64::new(2.0, 0.0), Complex64::new(0.5, 0.0)]));
+
+    // the double root of x^2 = 0 ends two paths but cannot be certified
+    let system = [x(0) * x(0), x(1) - c(1)];
+    let paths = track_total_degree(&system);
+    assert_eq!(2, paths.len());
+    for path in &paths {
+        assert_ne!(PathStatus::Converged, path.status);
+        assert!(path.end[0].norm() < 1e-3 && (path.end[1] - 1.0).norm() < 1e-8);
+    }
+    assert!(solve_certified(&system).is_empty());
+    assert_eq!(None, certify(&system, &[Complex64::new(0.0, 0.0), Complex64::new(1.0, 0.0)]));
+
+    // an exact root has a certificate of radius zero
+    let system = [x(0) * x(1) - c(2), x(0) + x(1) - c(3)];
+    assert_eq!(Some(q(0, 1)), certify(&system, &[Complex64::new(1.0, 0.0), Complex64::new(2.0, 0.0)]));
+    // a system without roots has diverging paths only
+    let parallel = [x(0) + x(1) - c(1), x(0) + x(1) - c(2)];
+    assert!(track_total_degree(&parallel).iter().all(|path| path.status != PathStatus::Converged));
+}
+
+#[test]
+pub fn test_modular_gcd() {
+    use num::BigInt;
+
+    use crate::multimodular::modular_gcd;
+    use crate::traits::CoefficientDomain;
+    use crate::Polynomial;
+
+    let p = |c: &[i64]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+    let big: BigInt = BigInt::from(1) << 100;
+
+    // a gcd with coefficients beyond a single prime, and cofactors with a
+    // common leading coefficient
+    let g = Polynomial::new(vec![big.clone() + 1, BigInt::from(-3), BigInt::from(0), big.clone() * 5 - 7]);
+    let a = g.clone() * p(&[1, 0, 6]) * p(&[-2, 3]);
+    let b = g.clone() * p(&[5, -1, 0, 6]);
+    let expected = g.clone().unit_and_normal().1;
+    assert_eq!(expected, modular_gcd(&a, &b));
+    assert_eq!(CoefficientDomain::gcd(&a, &b), modular_gcd(&a, &b));
+
+    // contents and signs
+    let a = p(&[-18, 6, 12]).scalar_mul(BigInt::from(-1));
+    let b = p(&[12, 8, 12, 8]);
+    assert_eq!(p(&[6, 4]), modular_gcd(&a, &b));
+    assert_eq!(p(&[3]), modular_gcd(&p(&[3, 6]), &p(&[9])));
+    assert_eq!(p(&[1, 2]), modular_gcd(&p(&[-1, -2]), &Polynomial::zero()));
+    assert!(modular_gcd(&Polynomial::zero(), &Polynomial::zero()).is_zero());
+
+    // coprime polynomials and equal ones
+    let a = p(&[1, 0, 0, 0, 1]);
+    let b = p(&[1, 0, 0, 1]);
+    assert_eq!(p(&[1]), modular_gcd(&a, &b));
+    assert_eq!(a, modular_gcd(&a, &a));
+}
+
+#[test]
+pub fn test_mixed_volume() {
+    use num::{BigInt, BigRational};
+
+    use crate::multivariate::MultiPolynomial;
+    use crate::polytope::{affine_bernstein_bound, bernstein_bound, minkowski_sum, mixed_volume, vertices, volume};
+
+    let points = |ps: &[&[i64]]| ps.iter().map(|p| p.to_vec()).collect::<Vec<_>>();
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+
+    // a cube with points on its faces and inside
+    let mut cube = points(&[&[1, 1, 1], &[1, 0, 1], &[2, 1, 1]]);
+    for i in 0..8 {
+        cube.push(vec![(i & 1) * 2, (i >> 1 & 1) * 2, (i >> 2 & 1) * 2]);
+    }
+    assert_eq!(8, vertices(&cube).len());
+    assert_eq!(q(8, 1), volume(&cube));
+    // flat polytopes have no volume, but still have vertices
+    let flat = points(&[&[0, 0, 1], &[2, 0, 1], &[0, 2, 1], &[1, 1, 1]]);
+    assert_eq!(q(0, 1), volume(&flat));
+    assert_eq!(points(&[&[0, 0, 1], &[0, 2, 1], &[2, 0, 1]]), vertices(&flat));
+    assert_eq!(points(&[&[5]]), vertices(&points(&[&[5], &[5]])));
+    // a triangle and a segment sum to a quadrilateral
+    let triangle = points(&[&[0, 0], &[1, 0], &[0, 1]]);
+    let segment = points(&[&[0, 0], &[1, 1]]);
+    assert_eq!(points(&[&[0, 0], &[0, 1], &[1, 0], &[1, 2], &[2, 1]]), minkowski_sum(&triangle, &segment));
+    assert_eq!(q(5, 2), volume(&minkowski_sum(&triangle, &segment)));
+
+    // n copies of a polytope have mixed volume n! vol
+    assert_eq!(BigInt::from(6 * 8), mixed_volume(&[cube.clone(), cube.clone(), cube]));
+    assert_eq!(BigInt::from(7), mixed_volume(&[points(&[&[-3], &[4]])]));
+    assert_eq!(BigInt::from(1), mixed_volume(&[]));
+
+    let x = |i| MultiPolynomial::<i64>::var(i);
+    let c = MultiPolynomial::constant;
+    // dense polynomials attain Bézout's bound
+    let f = x(0) * x(0) + x(0) * x(1) + x(1) * x(1) + x(0) + x(1) + c(1);
+    let g = x(0) * x(0) * x(0) + x(1) * x(1) * x(1) + x(0) * x(1) + c(1);
+    assert_eq!(BigInt::from(6), bernstein_bound(&[f, g]));
+    // the cyclic 3-roots problem has six solutions
+    let cyclic = [
+        x(0) + x(1) + x(2),
+        x(0) * x(1) + x(1) * x(2) + x(2) * x(0),
+        x(0) * x(1) * x(2) - c(1),
+    ];
+    assert_eq!(BigInt::from(6), bernstein_bound(&cyclic));
+    // x y = 0 and x + y = 1 have two solutions, both on the axes
+    let axes = [x(0) * x(1), x(0) + x(1) - c(1)];
+    assert_eq!(BigInt::from(0), bernstein_bound(&axes));
+    assert_eq!(BigInt::from(2), affine_bernstein_bound(&axes));
+}
+
+#[test]
+pub fn test_is_prime() {
+    use num::BigInt;
+
+    use crate::number_theory::{is_prime, next_prime, primes_up_to};
+
+    let primes = primes_up_to(10_000);
+    for n in -5..=10_000i64 {
+        assert_eq!(primes.binary_search(&(n as u64)).is_ok() && n >= 0, is_prime(&BigInt::from(n)), "{n}");
+    }
+    // strong pseudoprimes to the first few prime bases
+    for n in [2047u128, 1_373_653, 3_215_031_751, 3_825_123_056_546_413_051, 318_665_857_834_031_151_167_461, 3_317_044_064_679_887_385_961_981] {
+        assert!(!is_prime(&BigInt::from(n)), "{n}");
+    }
+    // the Carmichael numbers 1729 and (6k + 1)(12k + 1)(18k + 1) for k = 10^15 + 2207
+    assert!(!is_prime(&BigInt::from(1729)));
+    let k = BigInt::from(1_000_000_000_002_207u64);
+    let carmichael = (&k * 6 + 1) * (&k * 12 + 1) * (&k * 18 + 1);
+    assert!(!is_prime(&carmichael));
+
+    // Mersenne numbers beyond the deterministic range
+    let mersenne = |p: u32| (BigInt::from(1) << p) - 1;
+    for p in [89, 107, 127, 521, 607] {
+        assert!(is_prime(&mersenne(p)), "{p}");
+    }
+    for p in [101, 103, 109, 512] {
+        assert!(!is_prime(&mersenne(p)), "{p}");
+    }
+    // squares of primes and products of large primes
+    let p = next_prime(&(BigInt::from(1) << 100));
+    let q = next_prime(&p);
+    assert!(is_prime(&p) && is_prime(&q) && p < q);
+    assert!(!is_prime(&(&p * &p)));
+    assert!(!is_prime(&(&p * &q)));
+    assert_eq!(BigInt::from(10_007), next_prime(&BigInt::from(10_000)));
+}
+
+#[test]
+pub fn test_tropical() {
+    use num::{BigInt, BigRational, One, Zero};
+
+    use crate::multivariate::MultiPolynomial;
+    use crate::tropical::{tropicalize, tropicalize_multi, Tropical, TropicalMultiPolynomial, TropicalPolynomial};
+    use crate::Polynomial;
+
+    let q = |a: i64| BigRational::from_integer(a.into());
+    let t = |a: i64| Tropical::new(q(a));
+    let inf = Tropical::infinity;
+
+    // the semiring operations
+    assert_eq!(t(2), t(2) + t(5));
+    assert_eq!(t(7), t(2) * t(5));
+    assert_eq!(t(3), t(3) + inf());
+    assert_eq!(inf(), t(3) * inf());
+    assert_eq!(t(3), t(3) * Tropical::one());
+    assert!(Tropical::zero().is_zero() && inf() > t(1_000_000));
+    assert_eq!(Tropical::one(), inf().pow(0));
+
+    // (x + 1)(x + 2) = x^2 + 1x + 3 tropically, with roots 1 and 2
+    let f = TropicalPolynomial::new(vec![t(1), t(0)]);
+    let g = TropicalPolynomial::new(vec![t(2), t(0), inf()]);
+    let product = f.clone() * g.clone();
+    assert_eq!(TropicalPolynomial::new(vec![t(3), t(1), t(0)]), product);
+    assert_eq!(vec![(q(1), 1), (q(2), 1)], product.roots());
+    assert_eq!(TropicalPolynomial::new(vec![t(1), t(0)]), f.clone() + g);
+    assert_eq!(t(3), product.eval(&t(5)));
+    assert_eq!(t(-4), product.eval(&t(-2)));
+    // points above or on the edges of the lower hull are not vertices
+    let h = TropicalPolynomial::new(vec![t(0), t(5), t(2), inf(), t(4), t(9)]);
+    assert_eq!(vec![(0, q(0)), (4, q(4)), (5, q(9))], h.newton_polygon());
+    assert_eq!(vec![(q(-5), 1), (q(-1), 4)], h.roots());
+    assert_eq!(Vec::<(BigRational, usize)>::new(), TropicalPolynomial::new(vec![inf(), t(4)]).roots());
+
+    // 2-adic valuations of the roots of (x - 4)^2 (x - 1/2) (x - 3)
+    let p = |roots: &[BigRational]| roots.iter().map(|r| Polynomial::new(vec![-r.clone(), q(1)])).product::<Polynomial<BigRational>>();
+    let f = p(&[q(4), q(4), q(1) / q(2), q(3)]);
+    assert_eq!(vec![(q(-1), 1), (q(0), 1), (q(2), 2)], tropicalize(&f, &BigInt::from(2)).roots());
+
+    // the tropicalization of 2 + 4x + y, with a vertex at (-1, 1) where all
+    // three terms meet
+    let x = || MultiPolynomial::<BigRational>::var(0);
+    let y = || MultiPolynomial::<BigRational>::var(1);
+    let f = MultiPolynomial::constant(q(2)) + x().scalar_mul(q(4)) + y() * y() * y();
+    let trop = tropicalize_multi(&f, &BigInt::from(2));
+    assert_eq!(TropicalMultiPolynomial::new([(vec![], q(1)), (vec![1], q(2)), (vec![0, 3], q(0))]), trop);
+    let point = [q(-1), BigRational::new(1.into(), 3.into())];
+    assert_eq!(3, trop.dominant_terms(&point).len());
+    assert_eq!(Tropical::new(q(1)), trop.eval(&point));
+    assert_eq!(vec![vec![0, 0], vec![0, 3], vec![1, 0]], trop.newton_polytope(2));
+}
+
+#[test]
+pub fn test_factor_integer() {
+    use num::{BigInt, One};
+
+    use crate::number_theory::{factor_integer, is_prime};
+
+    let check = |n: &BigInt| {
+        let factors = factor_integer(n);
+        assert!(factors.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(factors.iter().all(|(p, _)| is_prime(p)));
+        let product: BigInt = factors.iter().map(|(p, e)| p.pow(*e)).product();
+        assert_eq!(n.magnitude(), product.magnitude());
+        factors
+    };
+    for n in 1..2000 {
+        check(&BigInt::from(n));
+    }
+    let n = |x: u64| BigInt::from(x);
+    // primes beyond trial division, with repeated factors and powers
+    assert_eq!(vec![(n(1009), 3), (n(1013), 1)], check(&(n(1009).pow(3) * 1013)));
+    assert_eq!(vec![(n(2), 1), (n(999_983), 4)], check(&(n(999_983).pow(4) * -2)));
+    // rho on a product of two primes near 10^10
+    let (p, q) = (n(10_000_000_019), n(10_000_000_033));
+    assert_eq!(vec![(p.clone(), 1), (q.clone(), 2)], check(&(&p * &q * &q)));
+    // p - 1 on a factor whose predecessor is smooth, next to a Mersenne prime
+    let smooth: BigInt = (1..=40u32).map(BigInt::from).fold(BigInt::one(), num::integer::lcm);
+    let p = (1..).map(|k: u32| &smooth * k + 1).find(is_prime).unwrap();
+    let q: BigInt = (BigInt::one() << 89u32) - 1u32;
+    assert_eq!(vec![(p.clone(), 1), (q.clone(), 1)], check(&(&p * &q)));
+    // a large prime next to small ones
+    let big = &q * 6;
+    assert_eq!(vec![(n(2), 1), (n(3), 1), (q, 1)], check(&big));
+}
+
+#[test]
+pub fn test_factor_integer_traced() {
+    use num::{BigInt, One};
+
+    use crate::number_theory::{factor_integer, factor_integer_traced, is_prime, Stage};
+
+    let n = |x: u64| BigInt::from(x);
+    // every step splits its number, and the leaves are the factorization
+    let check = |m: &BigInt| {
+        let trace = factor_integer_traced(m);
+        assert_eq!(factor_integer(m), trace.factors);
+        for step in &trace.steps {
+            assert!(step.factor > BigInt::one() && step.factor < step.n);
+            assert!((&step.n % &step.factor).is_zero());
+        }
+        trace
+    };
+    let smooth: BigInt = (1..=40u32).map(BigInt::from).fold(BigInt::one(), num::integer::lcm);
+    let p = (1..).map(|k: u32| &smooth * k + 1).find(is_prime).unwrap();
+    let q: BigInt = (BigInt::one() << 89u32) - 1u32;
+    let trace = check(&(&p * &q * 12));
+    let stages: Vec<_> = trace.steps.iter().map(|s| s.stage).collect();
+    assert_eq!(vec![Stage::TrialDivision, Stage::TrialDivision, Stage::TrialDivision, Stage::PMinusOne], stages);
+    assert_eq!(&p * &q, trace.steps[3].n);
+    assert!(trace.steps[3].factor == p || trace.steps[3].factor == q);
+    for stage in [Stage::TrialDivision, Stage::Primality, Stage::PMinusOne] {
+        assert!(trace.time.contains_key(&stage));
+    }
+    assert!(!trace.time.contains_key(&Stage::Rho));
+
+    // rho only after p - 1 fails, once for each copy of the repeated factor
+    let (a, b) = (n(10_000_000_019), n(10_000_000_033));
+    let trace = check(&(&a * &b * &b * &b));
+    assert_eq!(vec![Stage::Rho; 3], trace.steps.iter().map(|s| s.stage).collect::<Vec<_>>());
+    assert!(trace.steps.iter().all(|s| s.factor == b));
+    assert!(trace.time.contains_key(&Stage::PMinusOne));
+    // a perfect power is split once and its root factored for every copy
+    let root = n(1009 * 1013);
+    let trace = check(&(&root * &root));
+    assert_eq!(vec![Stage::PerfectPower, Stage::PMinusOne, Stage::PMinusOne], trace.steps.iter().map(|s| s.stage).collect::<Vec<_>>());
+    assert_eq!(root, trace.steps[0].factor);
+
+    // primes and units have no steps
+    assert!(check(&n(1_000_000_007)).steps.is_empty());
+    assert!(check(&n(1)).steps.is_empty());
+}
+
+#[test]
+pub fn test_semirings() {
+    use num::{BigRational, One, Zero};
+
+    use crate::linalg::Matrix;
+    use crate::semiring::Boolean;
+    use crate::tropical::{Tropical, TropicalPolynomial};
+    use crate::Polynomial;
+
+    let t = |x: i64| Tropical::new(BigRational::from_integer(x.into()));
+    let inf = Tropical::infinity;
+
+    // shortest paths from powers of the tropical adjacency matrix, against
+    // Floyd–Warshall
+    let n = 6;
+    let length = |i: usize, j: usize| {
+        if i == j {
+            Tropical::one()
+        } else if (i * 7 + j * 3).is_multiple_of(4) {
+            inf()
+        } else {
+            t(((i * 5 + j * 11) % 9 + 1) as i64)
+        }
+    };
+    let a = Matrix::from_fn(n, n, length);
+    let mut dist = a.clone();
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                let through = dist[(i, k)].clone() * dist[(k, j)].clone();
+                dist[(i, j)] = dist[(i, j)].clone() + through;
+            }
+        }
+    }
+    assert_eq!(dist, a.pow(n as u64 - 1));
+    assert_eq!(dist, a.pow(2 * n as u64));
+    assert_eq!(Matrix::identity(n), a.pow(0));
+
+    // reachability is the boolean image of the distances
+    let reachable = Matrix::from_fn(n, n, |i, j| Boolean(!a[(i, j)].is_zero()));
+    let closure = reachable.pow(n as u64);
+    assert_eq!(Matrix::from_fn(n, n, |i, j| Boolean(!dist[(i, j)].is_zero())), closure);
+    assert_eq!(closure.clone(), closure.clone() + reachable.clone() * closure);
+
+    // polynomials over the semirings
+    let f = Polynomial::new(vec![t(3), t(1), t(0)]);
+    let g = Polynomial::new(vec![t(2), inf(), t(-1)]);
+    let product = f.schoolbook_mul(&g);
+    let tropical = TropicalPolynomial::new(vec![t(3), t(1), t(0)]) * TropicalPolynomial::new(vec![t(2), inf(), t(-1)]);
+    assert_eq!(tropical.coeffs(), product.coeffs);
+    assert_eq!(tropical.eval(&t(4)), product.eval(&t(4)));
+    assert_eq!(Polynomial::new(vec![t(2), t(1), t(-1)]), f + g);
+    let p = Polynomial::new(vec![Boolean(false), Boolean(true)]);
+    assert_eq!(Boolean(false), p.eval(&Boolean(false)));
+    // (x + 1)^2 = x^2 + x + 1 when 1 + 1 = 1
+    let q = p.clone() + Boolean(true);
+    assert_eq!(Polynomial::new(vec![Boolean(true); 3]), q.schoolbook_mul(&q));
+    assert!([p.clone(), Polynomial::zero()].into_iter().sum::<Polynomial<Boolean>>() == p);
+}
+
+#[test]
+pub fn test_boolean_polynomial() {
+    use num::{One, Zero};
+
+    use crate::boolean::BooleanPolynomial;
+    use crate::fields::Zp;
+    use crate::laws::check_commutative_ring_laws;
+    use crate::multivariate::MultiPolynomial;
+
+    let x = BooleanPolynomial::var;
+    // pseudo-random truth tables of five variables
+    let table = |seed: u64| -> Vec<bool> {
+        (0..32u64).map(|k| (k.wrapping_mul(0x9e37_79b9).wrapping_add(seed * 0x85eb_ca6b) >> 7).count_ones() % 2 == 1).collect()
+    };
+    let samples: Vec<_> = (0..6).map(|seed| BooleanPolynomial::from_truth_table(&table(seed))).collect();
+    for (seed, f) in samples.iter().enumerate() {
+        assert_eq!(table(seed as u64), f.truth_table(5));
+        for g in &samples {
+            let (tf, tg) = (f.truth_table(5), g.truth_table(5));
+            let and: Vec<bool> = tf.iter().zip(&tg).map(|(a, b)| a & b).collect();
+            let xor: Vec<bool> = tf.iter().zip(&tg).map(|(a, b)| a ^ b).collect();
+            assert_eq!(and, (f.clone() * g.clone()).truth_table(5));
+            assert_eq!(xor, (f.clone() + g.clone()).truth_table(5));
+            // substitution agrees with evaluation
+            let h = f.substitute(2, g);
+            for point in 0..32u64 {
+                let bit = if g.eval(point) { 1 << 2 } else { 0 };
+                assert_eq!(f.eval(point & !(1 << 2) | bit), h.eval(point));
+            }
+        }
+        assert_eq!(f.clone(), f.clone() * f.clone());
+        assert!((f.clone() + f.clone()).is_zero());
+        let on = f.specialize(4, true);
+        assert!((0..16u64).all(|point| on.eval(point) == f.eval(point | 1 << 4)));
+        assert!(on.num_vars() <= 4);
+    }
+    check_commutative_ring_laws(&samples[..4]).unwrap();
+
+    // degrees and the algebraic normal form
+    assert_eq!(None, BooleanPolynomial::zero().degree());
+    assert_eq!(Some(0), BooleanPolynomial::one().degree());
+    let f = x(0) * x(3) * x(5) + x(1) + BooleanPolynomial::one();
+    assert_eq!(Some(3), f.degree());
+    assert_eq!(6, f.num_vars());
+    assert_eq!(vec![0, 0b10, 0b101001], f.monomials().collect::<Vec<_>>());
+    assert_eq!(BooleanPolynomial::new([0b11, 0b11, 0b1]), x(0));
+    assert_eq!(BooleanPolynomial::from_truth_table(&[false, true, true, false]), x(0) + x(1));
+    assert_eq!(vec![true], BooleanPolynomial::one().truth_table(0));
+
+    // the conversion to GF(2)[x] drops the powers
+    let y = |i| MultiPolynomial::<Zp<2>>::var(i);
+    let p = y(0) * y(0) * y(0) * y(2) + y(1) * y(1) + y(0) * y(2);
+    assert_eq!(x(1), BooleanPolynomial::from_multi(&p));
+    assert_eq!(f, BooleanPolynomial::from_multi(&f.to_multi()));
+    assert_eq!(y(0) * y(1) + MultiPolynomial::one(), (x(0) * x(1) + BooleanPolynomial::one()).to_multi());
+}
+
+#[test]
+pub fn test_divisors() {
+    use crate::factorization::{divisors, integer_divisors};
+    use crate::number_theory::divisor_lattice;
+    use num::BigInt;
+
+    for n in 1..200u32 {
+        let mut streamed: Vec<_> = divisors(n).collect();
+        let mut expected = integer_divisors(n);
+        streamed.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(expected, streamed);
+    }
+
+    // 10! = 2^8 3^4 5^2 7
+    let n = BigInt::from(3_628_800);
+    let mut streamed: Vec<_> = divisors(n.clone()).collect();
+    streamed.sort();
+    assert_eq!(divisor_lattice(&n).divisors(), &streamed[..]);
+
+    // a product of two primes has exactly four divisors
+    let p = BigInt::from(998_244_353);
+    let q = BigInt::from(1_000_000_007);
+    let mut streamed: Vec<_> = divisors(&p * &q).collect();
+    streamed.sort();
+    assert_eq!(vec![BigInt::from(1), p.clone(), q.clone(), p * q], streamed);
+}
+
+#[test]
+pub fn test_lagrange_interpolation() {
+    use crate::factorization::lagrange_interpolation;
+    use crate::fields::Zp;
+
+    // multiplication by evaluation and interpolation over GF(101)
+    type F = Zp<101>;
+    let f = Polynomial::new([3, 0, 7, 1].map(F::new).to_vec());
+    let g = Polynomial::new([100, 5, 2].map(F::new).to_vec());
+    let points: Vec<_> = (0..6)
+        .map(|x| {
+            let x = F::new(x);
+            (x, f.eval(&x) * g.eval(&x))
+        })
+        .collect();
+    assert_eq!(f.clone() * g.clone(), lagrange_interpolation(points));
+
+    // a single point gives a constant, no points the zero polynomial
+    assert_eq!(Polynomial::new(vec![F::new(4)]), lagrange_interpolation(vec![(F::new(9), F::new(4))]));
+    assert!(lagrange_interpolation(Vec::<(F, F)>::new()).is_zero());
+}
+
+#[test]
+pub fn test_boolean_groebner_basis() {
+    use num::One;
+
+    use crate::boolean::{groebner_basis, BooleanPolynomial};
+
+    let mut seed = 2718281828u64;
+    let mut next = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        seed >> 33
+    };
+    for _ in 0..40 {
+        // a few sparse equations in five variables
+        let system: Vec<_> = (0..3 + next() % 3)
+            .map(|_| BooleanPolynomial::new((0..1 + next() % 4).map(|_| next() % 32)))
+            .collect();
+        let basis = groebner_basis(&system);
+        let solutions: Vec<bool> = (0..32).map(|point| system.iter().all(|f| !f.eval(point))).collect();
+
+        for f in &system {
+            assert!(f.reduce(&basis).is_zero());
+        }
+        if solutions.iter().all(|&s| !s) {
+            assert_eq!(vec![BooleanPolynomial::one()], basis);
+            continue;
+        }
+        // the ideal is every polynomial vanishing on the solutions, which is
+        // also generated by the one polynomial vanishing exactly there
+        for g in &basis {
+            assert!((0..32).all(|point| !solutions[point as usize] || !g.eval(point)));
+        }
+        let vanishing = BooleanPolynomial::from_truth_table(&solutions) + BooleanPolynomial::one();
+        assert_eq!(groebner_basis(&[vanishing]), basis);
+        // a reduced basis has no monomial divisible by another leading monomial
+        for (i, g) in basis.iter().enumerate() {
+            let others: Vec<_> = basis.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, h)| h.clone()).collect();
+            assert_eq!(*g, g.reduce(&others));
+        }
+    }
+}
+
+#[test]
+pub fn test_groebner_state() {
+    use num::One;
+
+    use crate::boolean::{groebner_basis, BooleanPolynomial, GroebnerState};
+
+    let x = BooleanPolynomial::var;
+    let one = BooleanPolynomial::one;
+    // x_0 x_1 = 1, x_1 = x_2 and x_0 = x_1 x_2, resumed from every step
+    let system = [x(0) * x(1) + one(), x(1) + x(2), x(0) + x(1) * x(2) + x(3) * x(4)];
+    let expected = groebner_basis(&system);
+    let mut state = GroebnerState::new(&system);
+    assert_eq!(3, state.queue.len());
+    let mut steps = 0;
+    loop {
+        let decoded = GroebnerState::decode(&state.encode()).unwrap();
+        assert_eq!(state, decoded);
+        assert_eq!(expected, decoded.resume());
+        steps += 1;
+        if !state.step() {
+            break;
+        }
+    }
+    assert!(steps > 3 && state.is_done());
+    assert_eq!(expected, state.resume());
+
+    // an inconsistent system stops at 1
+    let mut state = GroebnerState::new(&[x(0) + x(1), x(0) + x(1) + one()]);
+    while state.step() {}
+    assert_eq!(vec![one()], state.basis);
+    assert_eq!("groebner 1\nbasis 0\n", state.encode());
+    assert_eq!(vec![one()], state.resume());
+
+    // zero polynomials may wait in the queue but not in the basis
+    let state = GroebnerState::decode("groebner 1\nbasis 1 2\nqueue\nqueue 3\n").unwrap();
+    assert_eq!(vec![x(0) + x(1)], state.basis);
+    assert_eq!(vec![BooleanPolynomial::zero(), x(0) * x(1)], Vec::from(state.queue));
+    assert_eq!(None, GroebnerState::decode("groebner 1\nbasis\n"));
+    assert_eq!(None, GroebnerState::decode("groebner 1\nqueue 1\nbasis 2\n"));
+    assert_eq!(None, GroebnerState::decode("groebner 2\n"));
+    assert_eq!(None, GroebnerState::decode("groebner 1\nqueue x\n"));
+}
+
+#[test]
+pub fn test_newton_interpolator() {
+    use num::BigRational;
+
+    use crate::factorization::{lagrange_interpolation, NewtonInterpolator};
+    use crate::fields::Zp;
+
+    // every prefix of the points agrees with Lagrange interpolation
+    let q = |a: i64| BigRational::from_integer(a.into());
+    let points: Vec<_> = [(0, 3), (1, -1), (-2, 4), (5, 7), (3, 0), (-4, -9)]
+        .map(|(x, y)| (q(x), q(y)))
+        .to_vec();
+    let mut interpolator = NewtonInterpolator::new();
+    for k in 0..points.len() {
+        interpolator.push(points[k].0.clone(), points[k].1.clone());
+        let expected = lagrange_interpolation(points[..=k].to_vec());
+        assert_eq!(&expected, interpolator.polynomial());
+        assert_eq!(k + 1, interpolator.len());
+        for x in -3..4 {
+            assert_eq!(expected.eval(&q(x)), interpolator.eval(&q(x)));
+        }
+    }
+
+    // a polynomial is recovered once enough points are added, and further
+    // points leave it unchanged over GF(13)
+    type F = Zp<13>;
+    let f = Polynomial::new([5, 0, 12, 3].map(F::new).to_vec());
+    let mut interpolator = NewtonInterpolator::default();
+    interpolator.extend((0..4).map(|x| (F::new(x), f.eval(&F::new(x)))));
+    assert_eq!(&f, interpolator.polynomial());
+    interpolator.extend((4..13).map(|x| (F::new(x), f.eval(&F::new(x)))));
+    assert_eq!(&f, interpolator.polynomial());
+    assert!(interpolator.divided_differences()[4..].iter().all(|c| c.is_zero()));
+}
+
+#[test]
+pub fn test_permanent_and_pfaffian() {
+    use num::BigInt;
+
+    use crate::linalg::Matrix;
+
+    let mut seed = 1618033988u64;
+    let mut next = || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as i64 % 7 - 3
+    };
+
+    // the permanent of the all ones matrix is n!, and that of a 0/1 matrix
+    // counts perfect matchings
+    for n in 0..7 {
+        let ones = Matrix::from_fn(n, n, |_, _| BigInt::from(1));
+        assert_eq!(BigInt::from((1..=n as u64).product::<u64>()), ones.permanent());
+    }
+    // by expansion along the first row
+    fn permanent_by_expansion(a: &[Vec<i64>]) -> i64 {
+        if a.is_empty() {
+            return 1;
+        }
+        (0..a.len())
+            .map(|j| {
+                let minor: Vec<Vec<i64>> =
+                    a[1..].iter().map(|row| row.iter().enumerate().filter(|&(k, _)| k != j).map(|(_, &x)| x).collect()).collect();
+                a[0][j] * permanent_by_expansion(&minor)
+            })
+            .sum()
+    }
+    for n in 1..6 {
+        let rows: Vec<Vec<i64>> = (0..n).map(|_| (0..n).map(|_| next()).collect()).collect();
+        let a = Matrix::from_rows(rows.clone());
+        assert_eq!(permanent_by_expansion(&rows), a.permanent());
+    }
+
+    // the Pfaffian squares to the determinant and Pf(B A B^T) = det(B) Pf(A)
+    for n in 0..9 {
+        let mut a = Matrix::<i64>::zero(n, n);
+        for i in 0..n {
+            for j in i + 1..n {
+                // zeros near the pivots force swaps
+                let x = if i == 0 && j < 3 { 0 } else { next() };
+                a[(i, j)] = x;
+                a[(j, i)] = -x;
+            }
+        }
+        let b = Matrix::from_fn(n, n, |_, _| next());
+        let pf = a.pfaffian();
+        assert_eq!(a.determinant(), pf * pf);
+        assert_eq!(b.determinant() * pf, (b.clone() * a.clone() * b.transpose()).pfaffian());
+        if n % 2 == 1 {
+            assert_eq!(0, pf);
+        }
+    }
+    // the identity blocks [[0, 1], [-1, 0]] along the diagonal
+    let j = Matrix::<i64>::from_fn(6, 6, |r, c| match (r % 2, c) {
+        (0, c) if c == r + 1 => 1,
+        (1, c) if c + 1 == r => -1,
+        _ => 0,
+    });
+    assert_eq!(1, j.pfaffian());
+}
+
+#[test]
+pub fn test_graph_polynomials() {
+    use num::{BigInt, One};
+
+    use crate::graph::Graph;
+
+    let n = |a: i64| BigInt::from(a);
+    let linear = |a: i64| Polynomial::new(vec![n(-a), n(1)]);
+    let power = |p: Polynomial<BigInt>, k: usize| (0..k).map(|_| p.clone()).product::<Polynomial<BigInt>>();
+
+    // the outer 5-cycle, the inner pentagram and the spokes
+    let petersen = Graph::new(10, (0..5).flat_map(|i| [(i, (i + 1) % 5), (5 + i, 5 + (i + 2) % 5), (i, 5 + i)]));
+    assert_eq!(15, petersen.num_edges());
+    assert_eq!(petersen, Graph::from_adjacency_matrix(&petersen.adjacency_matrix()));
+    assert_eq!(
+        linear(3) * power(linear(1), 5) * power(linear(-2), 4),
+        petersen.characteristic_polynomial()
+    );
+    // 6 perfect matchings of 5 edges, 120 colourings with three colours and
+    // none with two
+    assert_eq!(&n(-6), petersen.matching_polynomial().coeff_at(0));
+    let chromatic = petersen.chromatic_polynomial();
+    assert_eq!(n(120), chromatic.eval(&n(3)));
+    assert_eq!(n(0), chromatic.eval(&n(2)));
+    assert_eq!(&n(-15), chromatic.coeff_at(9));
+
+    // cycles have (x - 1)^n + (-1)^n (x - 1), trees x (x - 1)^(n - 1), and for
+    // forests the matching polynomial is the characteristic polynomial
+    for k in 3..9 {
+        let sign = if k % 2 == 0 { n(1) } else { n(-1) };
+        assert_eq!(power(linear(1), k) + linear(1).scalar_mul(sign), Graph::cycle(k).chromatic_polynomial());
+    }
+    for k in 1..9 {
+        let path = Graph::path(k);
+        assert_eq!(linear(0) * power(linear(1), k - 1), path.chromatic_polynomial());
+        assert_eq!(path.characteristic_polynomial(), path.matching_polynomial());
+    }
+    assert_eq!((0..6).map(linear).product::<Polynomial<BigInt>>(), Graph::complete(6).chromatic_polynomial());
+    assert!(Graph::new(0, []).chromatic_polynomial().is_one());
+
+    // colourings of a small graph counted directly
+    let g = Graph::new(6, [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (5, 3), (1, 4)]);
+    let chromatic = g.chromatic_polynomial();
+    for k in 1..5u32 {
+        let colourings = (0..k.pow(6))
+            .filter(|&c| {
+                let colour = |v: usize| c / k.pow(v as u32) % k;
+                g.edges().all(|(u, v)| colour(u) != colour(v))
+            })
+            .count();
+        assert_eq!(n(colourings as i64), chromatic.eval(&n(k.into())));
+    }
+    // matchings of the same graph counted directly
+    let edges: Vec<_> = g.edges().collect();
+    let matching = g.matching_polynomial();
+    for k in 0..4 {
+        let count = (0..1u32 << edges.len())
+            .filter(|&s| s.count_ones() == k)
+            .filter(|&s| {
+                let covered: Vec<_> =
+                    edges.iter().enumerate().filter(|&(i, _)| s >> i & 1 == 1).flat_map(|(_, &(u, v))| [u, v]).collect();
+                (0..6).all(|v| covered.iter().filter(|&&w| w == v).count() <= 1)
+            })
+            .count();
+        let sign = if k % 2 == 0 { 1 } else { -1 };
+        assert_eq!(&n(sign * count as i64), matching.coeff_at(6 - 2 * k as usize));
+    }
+}
+
+#[test]
+pub fn test_derivatives_and_integrals() {
+    use num::{BigInt, BigRational};
+
+    use crate::fields::Zp;
+
+    // the k-th derivative agrees with k derivatives
+    let p = Polynomial::new((1..=9).map(|c| BigInt::from(c * c - 20)).collect());
+    let mut repeated = p.clone();
+    for k in 0..11 {
+        assert_eq!(repeated, p.nth_derivative(k));
+        repeated = repeated.derivative();
+    }
+
+    // differentiation undoes integration, and the definite integral is additive
+    let q = |a: i64| BigRational::from_integer(a.into());
+    let f: Polynomial<BigRational> = "4x^5 - 1/3 x^3 + 7x - 2".parse().unwrap();
+    assert_eq!(f, f.antiderivative().derivative());
+    assert!(f.antiderivative().eval(&q(0)).is_zero());
+    assert_eq!(f.integral(&q(-2), &q(5)), f.integral(&q(-2), &q(1)) + f.integral(&q(1), &q(5)));
+    assert!(Polynomial::<BigRational>::zero().antiderivative().is_zero());
+
+    // over GF(5), x^5 has derivative zero and the third derivative of x^4 is 24 x = 4x
+    type F = Zp<5>;
+    let x = |k: usize| Polynomial::from_elem_with_degree(F::new(1), k);
+    assert!(x(5).derivative().is_zero());
+    assert_eq!(x(1).scalar_mul(F::new(4)), x(4).nth_derivative(3));
+    let g = Polynomial::new([1, 2, 3, 4].map(F::new).to_vec());
+    assert_eq!(g, g.antiderivative().derivative());
+}
+
+#[test]
+pub fn test_laurent_polynomial() {
+    use num::BigRational;
+
+    use crate::laurent::LaurentPolynomial;
+    use crate::laws::check_commutative_ring_laws;
+    use crate::traits::CommutativeRing;
+
+    let l = |terms: &[(i64, i64)]| LaurentPolynomial::new(terms.iter().map(|&(e, c)| (e, BigRational::from_integer(c.into()))));
+    let samples = [l(&[]), l(&[(0, 1)]), l(&[(-3, 2), (1, -1)]), l(&[(-1, 1), (0, 4), (2, 5)]), l(&[(5, -2)])];
+    check_commutative_ring_laws(&samples).unwrap();
+
+    let p = l(&[(-2, 3), (0, -1), (1, 2)]);
+    assert_eq!(Some(1), p.degree());
+    assert_eq!(Some(-2), p.low_degree());
+    // 3/4 - 1 + 4 at x = 2
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    assert_eq!(q(15, 4), p.eval(&q(2, 1)));
+    let (poly, shift) = p.to_polynomial();
+    assert_eq!(-2, shift);
+    assert_eq!(p, LaurentPolynomial::from(poly) * l(&[(-2, 1)]));
+    assert_eq!(l(&[(2, 3), (0, -1), (-1, 2)]), p.compose_power(-1));
+    // monomials are units
+    let x = l(&[(3, 2)]);
+    let inverse = LaurentPolynomial::invert(&x.clone().assert_is_unit()).into_inner();
+    assert_eq!(l(&[(-3, 1)]).scalar_mul(q(1, 2)), inverse);
+}
+
+#[test]
+pub fn test_jones_polynomial() {
+    use num::{BigInt, One};
+
+    use crate::knot::LinkDiagram;
+    use crate::laurent::LaurentPolynomial;
+
+    // in t^(1/2), so the exponents are twice those of t
+    let v = |terms: &[(i64, i64)]| LaurentPolynomial::new(terms.iter().map(|&(e, c)| (e, BigInt::from(c))));
+
+    let figure_eight = v(&[(-4, 1), (-2, -1), (0, 1), (2, -1), (4, 1)]);
+    let pd = LinkDiagram::from_pd_code(&[[4, 2, 5, 1], [8, 6, 1, 5], [6, 3, 7, 4], [2, 7, 3, 8]]);
+    assert_eq!(figure_eight, pd.jones_polynomial());
+    assert_eq!(figure_eight, LinkDiagram::from_braid(3, &[1, -2, 1, -2]).jones_polynomial());
+
+    // the left-handed trefoil has V(1/t), conjugation and stabilization of
+    // the braid do not change the link
+    let trefoil = v(&[(2, 1), (6, 1), (8, -1)]);
+    assert_eq!(trefoil.compose_power(-1), LinkDiagram::from_braid(2, &[-1, -1, -1]).jones_polynomial());
+    assert_eq!(trefoil, LinkDiagram::from_braid(3, &[1, 1, 1, 2]).jones_polynomial());
+    assert_eq!(trefoil, LinkDiagram::from_braid(3, &[-1, 1, 1, 1, 2, 1]).jones_polynomial());
+    assert_eq!(trefoil, LinkDiagram::from_braid(3, &[2, 1, 1, 1, 2, -2]).jones_polynomial());
+
+    // the bracket changes under the first Reidemeister move but the Jones
+    // polynomial does not
+    let kink = LinkDiagram::from_braid(2, &[1]);
+    assert_eq!(v(&[(3, -1)]), kink.kauffman_bracket());
+    assert!(kink.jones_polynomial().is_one());
+    assert!(LinkDiagram::from_pd_code(&[]).jones_polynomial().is_one());
+
+    // links: the positive Hopf link and the two-component unlink
+    let hopf = LinkDiagram::from_braid(2, &[1, 1]);
+    assert_eq!(2, hopf.num_components());
+    assert_eq!(v(&[(1, -1), (5, -1)]), hopf.jones_polynomial());
+    assert_eq!(v(&[(-1, -1), (1, -1)]), LinkDiagram::from_braid(2, &[1, -1]).jones_polynomial());
+
+    // V(1) = (-2)^(c - 1)
+    for (strands, word) in [(3, vec![1, 1, 2, 2]), (4, vec![1, 2, 3, -1, 2]), (3, vec![1, 1, 1, 1, 2, -2])] {
+        let link = LinkDiagram::from_braid(strands, &word);
+        let value: BigInt = link.jones_polynomial().terms().map(|(_, c)| c.clone()).sum();
+        assert_eq!(BigInt::from(-2).pow(link.num_components() as u32 - 1), value);
+    }
+}
+
+#[test]
+pub fn test_cycle_index() {
+    use num::{BigInt, BigRational, One};
+
+    use crate::combinatorics::{
+        cycle_index_cyclic, cycle_index_dihedral, cycle_index_symmetric, necklace_count, polya_count, polya_substitution,
+    };
+    use crate::multivariate::MultiPolynomial;
+
+    let ones = |z: &MultiPolynomial<BigRational>| z.eval(&vec![BigRational::one(); z.num_vars()]);
+    for n in 1..9 {
+        let (cyclic, dihedral, symmetric) = (cycle_index_cyclic(n), cycle_index_dihedral(n), cycle_index_symmetric(n));
+        // averages of monomials over the group, of degree n in the cycle lengths
+        for z in [&cyclic, &dihedral, &symmetric] {
+            assert!(ones(z).is_one());
+            assert!(z.terms().all(|(e, _)| e.iter().enumerate().map(|(k, c)| (k + 1) * c).sum::<usize>() == n));
+        }
+        for k in 1..4 {
+            assert_eq!(necklace_count(n, k), polya_count(&cyclic, k));
+            // multisets of n colours out of k
+            let multisets = (1..k as usize).fold(BigInt::one(), |acc, i| acc * (n + i) / i);
+            assert_eq!(multisets, polya_count(&symmetric, k));
+        }
+    }
+    let bracelets: Vec<_> = (1..9).map(|n| polya_count(&cycle_index_dihedral(n), 2)).collect();
+    assert_eq!([2, 3, 4, 6, 8, 13, 18, 30].map(BigInt::from).to_vec(), bracelets);
+    assert!(cycle_index_symmetric(0).is_one());
+
+    // bracelets of 6 beads in three colours, by the number of each
+    let colours = MultiPolynomial::var(0) + MultiPolynomial::var(1) + MultiPolynomial::var(2);
+    let counts = polya_substitution(&cycle_index_dihedral(6), &colours);
+    let total: BigRational = counts.terms().map(|(_, c)| c.clone()).sum();
+    assert_eq!(BigRational::from_integer(polya_count(&cycle_index_dihedral(6), 3)), total);
+    let n = |a: i64| BigRational::from_integer(a.into());
+    assert_eq!(Some(&n(11)), counts.coeff(&[2, 2, 2]));
+    assert_eq!(Some(&n(3)), counts.coeff(&[4, 1, 1]));
+}
+
+#[test]
+pub fn test_power_series() {
+    use num::{BigRational, One};
+
+    use crate::combinatorics::partition_count;
+    use crate::fields::Zp;
+    use crate::traits::CheckedInv;
+
+    let q = |a: i64, b: i64| BigRational::new(a.into(), b.into());
+    let f = Polynomial::new(vec![q(1, 1), q(3, 2), q(-2, 1), q(0, 1), q(5, 7)]);
+    let g = Polynomial::new(vec![q(0, 1), q(1, 3), q(1, 1), q(-4, 1)]);
+    let n = 12;
+    assert!(f.mul_mod_xn(&f.inv_mod_xn(n), n).is_one());
+    assert_eq!(f.truncated(n), f.log_mod_xn(n).exp_mod_xn(n));
+    assert_eq!(g, g.exp_mod_xn(n).log_mod_xn(n));
+    // exp turns sums into products and log products into sums
+    let h = g.clone().scalar_mul(q(-5, 2)) + Polynomial::new(vec![q(0, 1), q(0, 1), q(7, 1)]);
+    assert_eq!((g.clone() + h.clone()).exp_mod_xn(n), g.exp_mod_xn(n).mul_mod_xn(&h.exp_mod_xn(n), n));
+    let f2 = f.inv_mod_xn(n).scalar_mul(f.coeff_at(0).clone());
+    assert_eq!(f.log_mod_xn(n) + f2.log_mod_xn(n), f.mul_mod_xn(&f2, n).log_mod_xn(n));
+    assert!(Polynomial::<BigRational>::one().log_mod_xn(n).is_zero());
+
+    // prod(1 / (1 - x^k)) = exp(sum(σ(m) x^m / m)) counts partitions
+    let n = 30;
+    let sigma = |m: i64| (1..=m).filter(|d| m % d == 0).sum::<i64>();
+    let exponent = Polynomial::new((0..n as i64).map(|m| if m == 0 { q(0, 1) } else { q(sigma(m), m) }).collect());
+    let partitions = exponent.exp_mod_xn(n);
+    for m in 0..n {
+        assert_eq!(&BigRational::from_integer(partition_count(m)), partitions.coeff_at(m));
+    }
+
+    // over a large prime field the same identities hold
+    type F = Zp<998_244_353>;
+    let f = Polynomial::new((1..40).map(|i| F::new(i * i + 7)).collect());
+    let unit = f.clone().scalar_mul(f.coeff_at(0).clone().checked_inv().unwrap());
+    assert!(f.mul_mod_xn(&f.inv_mod_xn(50), 50).is_one());
+    assert_eq!(unit.truncated(50), unit.log_mod_xn(50).exp_mod_xn(50));
+}
+
+#[test]
+pub fn test_random_matrices() {
+    use num::{BigInt, BigRational, Signed};
+
+    use crate::fields::Zp;
+    use crate::linalg::Matrix;
+    use crate::random::{unimodular, well_conditioned, with_rank};
+
+    for seed in 0..5 {
+        for n in [1, 3, 6] {
+            assert_eq!(BigInt::from(1), unimodular(n, seed).determinant().abs());
+        }
+        for (rows, cols, rank) in [(5, 5, 5), (4, 7, 2), (6, 3, 3), (3, 3, 0)] {
+            assert_eq!(rank, with_rank::<BigRational>(rows, cols, rank, seed).rank());
+            assert_eq!(rank, with_rank::<Zp<7>>(rows, cols, rank, seed).rank_gaussian());
+        }
+        // the singular values are 1 or 2, so (A^T A - I)(A^T A - 4 I) = 0
+        let n = 5;
+        let a = well_conditioned(n, seed);
+        let gram = a.transpose() * a;
+        let scaled = |k: i64| Matrix::from_fn(n, n, |i, j| BigRational::from_integer(BigInt::from(k * i64::from(i == j))));
+        let product = (gram.clone() - scaled(1)) * (gram - scaled(4));
+        assert_eq!(Matrix::zero(n, n), product);
+    }
+}
+
+#[test]
+pub fn test_polynomial_pow() {
+    use num::{BigInt, BigRational, One};
+
+    use crate::fields::Zp;
+
+    let p: Polynomial<BigRational> = "2x^3 - 1/2 x + 3".parse().unwrap();
+    let mut expected = Polynomial::one();
+    for n in 0..12 {
+        assert_eq!(expected, p.pow(n));
+        expected = expected * p.clone();
+    }
+    assert!(Polynomial::<BigRational>::zero().pow(3).is_zero());
+
+    // reducing a power agrees with the power of the reduction
+    let m: Polynomial<BigRational> = "x^4 + x - 7".parse().unwrap();
+    for n in [0, 1, 2, 5, 13] {
+        assert_eq!(p.pow(n).div_rem(m.clone()).1, p.pow_mod(&BigInt::from(n), &m));
+    }
+
+    // x^2 + 1 is irreducible over GF(7), so GF(7)[x] / (x^2 + 1) has 48 units
+    type F = Zp<7>;
+    let f = Polynomial::new([1, 0, 1].map(F::new).to_vec());
+    let g = Polynomial::new([3, 5].map(F::new).to_vec());
+    assert!(g.pow_mod(&BigInt::from(48), &f).is_one());
+    assert_eq!(g, g.pow_mod(&(BigInt::from(48) * BigInt::from(10).pow(30) + 1), &f));
+    // and (a + bx)^7 = a - bx, the Frobenius conjugate
+    assert_eq!(Polynomial::new([3, 2].map(F::new).to_vec()), g.pow_mod(&BigInt::from(7), &f));
+    // every power is zero modulo a constant
+    assert!(g.pow_mod(&BigInt::from(3), &Polynomial::new(vec![F::new(2)])).is_zero());
+}
@@ -1,6 +1,7 @@
-use std::{ops::{Div, Neg, Mul}, mem::{take, swap}};
+use std::{ops::{Div, Neg}, mem::swap};
 
-use num::{BigInt, BigRational, One, Zero, Signed, Integer};
+use num::complex::Complex;
+use num::{BigInt, BigRational, Integer, Num, One, Signed, Zero};
 
 use crate::Polynomial;
 
@@ -12,7 +13,7 @@ pub trait CheckedInv {
 }
 
 /// Asserts this value is a unit (invertible element) in a ring.
-pub struct AssertUnit<T>(T);
+pub struct AssertUnit<T>(pub(crate) T);
 
 impl<T> AssertUnit<T> {
     pub fn into_inner(self) -> T {
@@ -20,7 +21,21 @@ impl<T> AssertUnit<T> {
     }
 }
 
-pub trait CommutativeRing: Zero + One + Neg<Output = Self> + Clone {
+/// A commutative semiring: addition and multiplication with identities
+/// `0` and `1`, but no negation, such as the tropical semiring of
+/// [`crate::tropical`] or the [`Boolean`](crate::semiring::Boolean) one.
+///
+/// It is implemented for every type with a zero, a one and `Clone`. Matrix
+/// addition and multiplication only need a semiring, and so do polynomial
+/// addition, evaluation and [`Polynomial::schoolbook_mul`]. The `*` operator
+/// on polynomials needs a [`CommutativeRing`], because it picks Karatsuba's
+/// method or an NTT, which subtract, and so does everything else that
+/// subtracts.
+pub trait Semiring: Zero + One + Clone {}
+
+impl<T: Zero + One + Clone> Semiring for T {}
+
+pub trait CommutativeRing: Semiring + Neg<Output = Self> {
     fn sub(self, other: Self) -> Self {
         self.add(other.neg())
     }
@@ -29,11 +44,65 @@ pub trait CommutativeRing: Zero + One + Neg<Output = Self> + Clone {
     /// Returns whether this element is nilpotent (i.e. there exists some n such that x^n == 0).
     /// Reduced rings has no non-zero nilpotent elements. Integral domains are an example of reduced rings.
     fn is_nilpotent(&self) -> bool;
+    /// Multiplies two polynomials given by their coefficients, for rings
+    /// with a faster method than Karatsuba's for these sizes. `None`, the
+    /// default, leaves the product to Karatsuba's method.
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Option<Vec<Self>> {
+        let _ = (a, b);
+        None
+    }
 }
 
 pub trait CoefficientDomain: CommutativeRing{
     fn unit_and_normal(self) -> (AssertUnit<Self>, Self);
     fn gcd(&self, other: &Self) -> Self;
+    /// Divides `self` by `other`, which must be a non-zero divisor of `self`.
+    /// The result is unspecified if it is not.
+    fn divide_exact(&self, other: &Self) -> Self;
+}
+
+/// A ring with division with remainder, such as the integers or polynomials
+/// over a field.
+pub trait EuclideanDomain: CommutativeRing {
+    /// The values of the Euclidean function, ordered by size.
+    type Norm: Ord;
+    /// The Euclidean function, such as the absolute value of an integer or
+    /// the degree of a polynomial.
+    fn norm(&self) -> Self::Norm;
+    /// Returns `(q, r)` with `self = q * other + r`, where `r` is zero or has
+    /// a smaller norm than `other`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is zero.
+    fn div_rem(&self, other: &Self) -> (Self, Self);
+    /// A gcd by the Euclidean algorithm. It is not normalized, so it is only
+    /// determined up to a unit.
+    fn euclidean_gcd(&self, other: &Self) -> Self {
+        let (mut a, mut b) = (self.clone(), other.clone());
+        while !b.is_zero() {
+            let r = a.div_rem(&b).1;
+            a = b;
+            b = r;
+        }
+        a
+    }
+    /// Returns `(g, s, t)` with `s * self + t * other = g`, where `g` is
+    /// [`EuclideanDomain::euclidean_gcd`].
+    fn extended_euclidean(&self, other: &Self) -> (Self, Self, Self) {
+        let (mut r0, mut r1) = (self.clone(), other.clone());
+        let (mut s0, mut s1) = (Self::one(), Self::zero());
+        let (mut t0, mut t1) = (Self::zero(), Self::one());
+        while !r1.is_zero() {
+            let (q, r) = r0.div_rem(&r1);
+            r0 = std::mem::replace(&mut r1, r);
+            let s = s0.sub(q.clone() * s1.clone());
+            s0 = std::mem::replace(&mut s1, s);
+            let t = t0.sub(q * t1.clone());
+            t0 = std::mem::replace(&mut t1, t);
+        }
+        (r0, s0, t0)
+    }
 }
 
 pub trait FromUsize {
@@ -46,18 +115,40 @@ impl FromUsize for BigInt {
     }
 }
 
+impl FromUsize for i64 {
+    fn from_usize(n: usize) -> Self {
+        n.try_into().unwrap()
+    }
+}
+
 impl FromUsize for BigRational {
     fn from_usize(n: usize) -> Self {
         Self::from(BigInt::from(n))
     }
 }
 
+impl FromUsize for f64 {
+    fn from_usize(n: usize) -> Self {
+        n as f64
+    }
+}
+
 /// any implementors of this trait have their set of field elements represented
 /// by the possible values the implementor type can take.
 pub trait Field: CommutativeRing + CheckedInv + Div<Output = Self> {
     fn div(self, other: Self) -> Option<Self> {
         other.checked_inv().map(|b| self.mul(b))
     }
+    /// The characteristic of this field, which is zero unless some sum
+    /// `1 + 1 + ... + 1` vanishes.
+    fn characteristic() -> usize {
+        0
+    }
+    /// For a field of characteristic `p`, the `x` with `x^p == self`. In a
+    /// prime field this is `self` itself, which is the default.
+    fn pth_root(self) -> Self {
+        self
+    }
 }
 
 impl CheckedInv for BigRational {
@@ -76,12 +167,19 @@ impl CommutativeRing for BigInt {
         assert!(self.abs().is_one());
         AssertUnit(self)
     }
+    /// The units `1` and `-1` are their own inverses.
     fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
-        (-&x.0).assert_is_unit()
+        x.0.clone().assert_is_unit()
     }
     fn is_nilpotent(&self) -> bool {
         self.is_zero()
     }
+    /// Uses [`Polynomial::mul_fft`] from [`FFT_THRESHOLD`] coefficients on.
+    ///
+    /// [`FFT_THRESHOLD`]: crate::ntt::FFT_THRESHOLD
+    fn mul_polynomials(a: &[Self], b: &[Self]) -> Option<Vec<Self>> {
+        (a.len().min(b.len()) >= crate::ntt::FFT_THRESHOLD).then(|| crate::ntt::mul_coeffs(a, b))
+    }
 }
 
 impl CoefficientDomain for BigInt {
@@ -95,6 +193,10 @@ impl CoefficientDomain for BigInt {
     fn gcd(&self, other: &Self) -> Self {
         Integer::gcd(self, other)
     }
+    fn divide_exact(&self, other: &Self) -> Self {
+        debug_assert!((self % other).is_zero());
+        self / other
+    }
 }
 
 impl CommutativeRing for i64 {
@@ -103,7 +205,7 @@ impl CommutativeRing for i64 {
         AssertUnit(self)
     }
     fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
-        (-x.0).assert_is_unit()
+        x.0.assert_is_unit()
     }
     fn is_nilpotent(&self) -> bool {
         *self == 0
@@ -121,6 +223,32 @@ impl CoefficientDomain for i64 {
     fn gcd(&self, other: &Self) -> Self {
         Integer::gcd(self, other)
     }
+    fn divide_exact(&self, other: &Self) -> Self {
+        debug_assert_eq!(0, self % other);
+        self / other
+    }
+}
+
+/// Truncating division, so the remainder has the sign of `self`.
+impl EuclideanDomain for BigInt {
+    type Norm = BigInt;
+    fn norm(&self) -> BigInt {
+        self.abs()
+    }
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Integer::div_rem(self, other)
+    }
+}
+
+/// Truncating division, so the remainder has the sign of `self`.
+impl EuclideanDomain for i64 {
+    type Norm = u64;
+    fn norm(&self) -> u64 {
+        self.unsigned_abs()
+    }
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Integer::div_rem(self, other)
+    }
 }
 
 /// The ring of rationals (`Q`)
@@ -147,18 +275,88 @@ impl CoefficientDomain for BigRational {
             (self.assert_is_unit(), Self::one())
         }
     }
+    /// Every non-zero rational is a unit, so any non-zero rational is a gcd.
+    /// We pick the gcd of the numerators over the lcm of the denominators,
+    /// which makes the content of a rational polynomial the factor that turns
+    /// it into a primitive integer polynomial.
     fn gcd(&self, other: &Self) -> Self {
         if self.is_zero() && other.is_zero() {
             panic!("0 gcd 0");
         }
 
-        Self::one()
+        Self::new(
+            Integer::gcd(self.numer(), other.numer()),
+            Integer::lcm(self.denom(), other.denom()),
+        )
+    }
+    fn divide_exact(&self, other: &Self) -> Self {
+        self / other
     }
 }
 
 /// The field of rationals (`Q`)
 impl Field for BigRational {}
 
+/// Floating point numbers, as an approximation of the reals. None of the
+/// ring axioms hold exactly, so this is only meant for numerical work.
+impl CommutativeRing for f64 {
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        x.0.recip().assert_is_unit()
+    }
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(self != 0.0);
+        AssertUnit(self)
+    }
+    fn is_nilpotent(&self) -> bool {
+        *self == 0.0
+    }
+}
+
+impl CheckedInv for f64 {
+    fn checked_inv(&self) -> Option<Self> {
+        if *self == 0.0 {
+            None
+        } else {
+            Some(self.recip())
+        }
+    }
+}
+
+impl Field for f64 {}
+
+/// Complex numbers over an ordered field such as `BigRational` or `f64`.
+/// They form a field because `a^2 + b^2` vanishes only when `a` and `b` do.
+impl<T: Field + Num + PartialOrd> CommutativeRing for Complex<T> {
+    fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
+        x.0.checked_inv().unwrap().assert_is_unit()
+    }
+    fn assert_is_unit(self) -> AssertUnit<Self> {
+        assert!(!self.is_zero());
+        AssertUnit(self)
+    }
+    fn is_nilpotent(&self) -> bool {
+        self.is_zero()
+    }
+}
+
+impl<T: Field + Num + PartialOrd> CheckedInv for Complex<T> {
+    fn checked_inv(&self) -> Option<Self> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(Self::one() / self.clone())
+        }
+    }
+}
+
+impl<T: Field + Num + PartialOrd> Field for Complex<T> {}
+
+impl<T: FromUsize + Zero> FromUsize for Complex<T> {
+    fn from_usize(n: usize) -> Self {
+        Self::new(T::from_usize(n), T::zero())
+    }
+}
+
 /// The ring of polynomials over a ring (`R[x]`)
 impl<Ring: CommutativeRing> CommutativeRing for Polynomial<Ring> {
     fn assert_is_unit(self) -> AssertUnit<Self> {
@@ -172,8 +370,12 @@ impl<Ring: CommutativeRing> CommutativeRing for Polynomial<Ring> {
         }
         AssertUnit(self)
     }
+    /// Only constant units are supported. Over a reduced ring these are all
+    /// of the units.
     fn invert(x: &AssertUnit<Self>) -> AssertUnit<Self> {
-        todo!()
+        assert_eq!(Some(0), x.0.degree(), "inverting non-constant units is unsupported");
+        let c = Ring::invert(&x.0.coeffs[0].clone().assert_is_unit());
+        Polynomial::new(vec![c.into_inner()]).assert_is_unit()
     }
     /// https://kconrad.math.uconn.edu/blurbs/ringtheory/polynomial-properties.pdf
     fn is_nilpotent(&self) -> bool {
@@ -181,7 +383,20 @@ impl<Ring: CommutativeRing> CommutativeRing for Polynomial<Ring> {
     }
 }
 
+/// The norm is the degree, with the zero polynomial below every constant.
+impl<F: Field> EuclideanDomain for Polynomial<F> {
+    type Norm = Option<usize>;
+    fn norm(&self) -> Option<usize> {
+        self.degree()
+    }
+    fn div_rem(&self, other: &Self) -> (Self, Self) {
+        Polynomial::div_rem(self.clone(), other.clone())
+    }
+}
+
 impl<K: CoefficientDomain> CoefficientDomain for Polynomial<K> {
+    /// The unit is the unit part of the leading coefficient, as a constant
+    /// polynomial.
     fn unit_and_normal(mut self) -> (AssertUnit<Self>, Self) {
         if self.is_zero() {
             return (Self::one().assert_is_unit(), Self::zero());
@@ -192,9 +407,73 @@ impl<K: CoefficientDomain> CoefficientDomain for Polynomial<K> {
             swap(coeff, &mut c);
             *coeff = c.mul(K::invert(&unit).into_inner());
         }
-        (Polynomial::from_elem_with_degree(unit.into_inner(), 1).assert_is_unit(), self)
+        (Polynomial::new(vec![unit.into_inner()]).assert_is_unit(), self)
     }
+    /// Computes the gcd with the primitive polynomial remainder sequence,
+    /// which stays within `K[x]` by taking pseudo-remainders and keeps the
+    /// coefficients small by dividing out their content at every step.
+    ///
+    /// The result is normalized with [`CoefficientDomain::unit_and_normal`].
     fn gcd(&self, other: &Self) -> Self {
-        todo!()
+        if self.is_zero() {
+            return other.clone().unit_and_normal().1;
+        }
+        if other.is_zero() {
+            return self.clone().unit_and_normal().1;
+        }
+        let content = self.clone().content().gcd(&other.clone().content());
+        let (mut a, mut b) = (primitive_part(self.clone()), primitive_part(other.clone()));
+        if a.degree() < b.degree() {
+            swap(&mut a, &mut b);
+        }
+        loop {
+            let r = pseudo_remainder(a, &b);
+            if r.is_zero() {
+                break;
+            }
+            a = b;
+            b = primitive_part(r);
+        }
+        b.scalar_mul(content).unit_and_normal().1
     }
-}
\ No newline at end of file
+    /// Long division, dividing leading coefficients exactly.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` does not divide `self`.
+    fn divide_exact(&self, other: &Self) -> Self {
+        let degree = other.degree().expect("division by zero");
+        let lc = other.leading_coefficient_cloned();
+        let mut quotient = Self::zero();
+        let mut remainder = self.clone();
+        while let Some(m) = remainder.degree() {
+            assert!(m >= degree, "polynomial does not divide exactly");
+            let t = remainder.leading_coefficient_cloned().divide_exact(&lc);
+            quotient += Polynomial::from_elem_with_degree(t.clone(), m - degree);
+            remainder = remainder - other.clone().scalar_mul(t).raised_by(m - degree);
+            assert!(
+                remainder.degree().is_none_or(|d| d < m),
+                "polynomial does not divide exactly"
+            );
+        }
+        quotient
+    }
+}
+
+/// Divides `p` by its content.
+fn primitive_part<K: CoefficientDomain>(p: Polynomial<K>) -> Polynomial<K> {
+    let content = p.clone().content();
+    Polynomial::new(p.coeffs.iter().map(|c| c.divide_exact(&content)).collect())
+}
+
+/// A remainder of `lc(b)^k a` by `b` for some `k`, computed without
+/// division. Requires `b` to be non-zero.
+fn pseudo_remainder<K: CoefficientDomain>(mut a: Polynomial<K>, b: &Polynomial<K>) -> Polynomial<K> {
+    let degree = b.degree().unwrap();
+    let lc = b.leading_coefficient_cloned();
+    while let Some(m) = a.degree().filter(|&m| m >= degree) {
+        let lca = a.leading_coefficient_cloned();
+        a = a.scalar_mul(lc.clone()) - b.clone().scalar_mul(lca).raised_by(m - degree);
+    }
+    a
+}
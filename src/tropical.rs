@@ -0,0 +1,370 @@
+//! The tropical (min-plus) semiring, tropical polynomials, and Newton
+//! polygons.
+//!
+//! Tropical addition is the minimum and tropical multiplication is the
+//! ordinary sum, so a polynomial `sum(c_i x^i)` evaluates to `min(c_i + i x)`,
+//! a concave piecewise linear function. Its corners, the tropical roots, are
+//! where the minimum is attained at least twice, and they are the negated
+//! slopes of the edges of the Newton polygon, the lower convex hull of the
+//! points `(i, c_i)`.
+//!
+//! Tropicalizing a rational polynomial replaces each coefficient by its
+//! `p`-adic valuation, and then the tropical roots are the valuations of its
+//! roots over the `p`-adic numbers, with the widths of the edges as
+//! multiplicities. In several variables the Newton polytope of the support
+//! is [`polytope::newton_polytope`](crate::polytope::newton_polytope).
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::ops::{Add, Mul};
+
+use num::{BigInt, BigRational, Integer, One, Zero};
+
+use crate::multivariate::MultiPolynomial;
+use crate::polytope;
+use crate::Polynomial;
+
+/// An element of the min-plus semiring: a rational number or infinity,
+/// which is the tropical zero. The tropical one is the number `0`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Tropical(Option<BigRational>);
+
+impl Tropical {
+    pub fn new(x: BigRational) -> Self {
+        Self(Some(x))
+    }
+
+    pub fn infinity() -> Self {
+        Self(None)
+    }
+
+    /// The value, or `None` for infinity.
+    pub fn value(&self) -> Option<&BigRational> {
+        self.0.as_ref()
+    }
+
+    /// The tropical power `n x`, which is the tropical one for `n = 0`.
+    pub fn pow(&self, n: usize) -> Self {
+        match &self.0 {
+            _ if n == 0 => Self::one(),
+            Some(x) => Self::new(x * BigRational::from_integer(n.into())),
+            None => Self::infinity(),
+        }
+    }
+}
+
+/// Infinity is larger than every number.
+impl Ord for Tropical {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Some(a), Some(b)) => a.cmp(b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+}
+
+impl PartialOrd for Tropical {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The minimum.
+impl Add for Tropical {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        self.min(other)
+    }
+}
+
+/// The sum, with infinity absorbing.
+impl Mul for Tropical {
+    type Output = Self;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, other: Self) -> Self {
+        match (self.0, other.0) {
+            (Some(a), Some(b)) => Self::new(a + b),
+            _ => Self::infinity(),
+        }
+    }
+}
+
+impl Zero for Tropical {
+    fn zero() -> Self {
+        Self::infinity()
+    }
+    fn is_zero(&self) -> bool {
+        self.0.is_none()
+    }
+}
+
+impl One for Tropical {
+    fn one() -> Self {
+        Self::new(BigRational::zero())
+    }
+}
+
+/// A univariate tropical polynomial `min(c_i + i x)`, with the coefficients
+/// in order of increasing degree and no trailing infinities.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TropicalPolynomial {
+    coeffs: Vec<Tropical>,
+}
+
+impl TropicalPolynomial {
+    /// Trailing infinities are removed.
+    pub fn new(mut coeffs: Vec<Tropical>) -> Self {
+        while coeffs.last().is_some_and(Tropical::is_zero) {
+            coeffs.pop();
+        }
+        Self { coeffs }
+    }
+
+    pub fn coeffs(&self) -> &[Tropical] {
+        &self.coeffs
+    }
+
+    /// The degree, or `None` if every coefficient is infinite.
+    pub fn degree(&self) -> Option<usize> {
+        self.coeffs.len().checked_sub(1)
+    }
+
+    /// The minimum of `c_i + i x` over the terms.
+    pub fn eval(&self, x: &Tropical) -> Tropical {
+        self.coeffs
+            .iter()
+            .enumerate()
+            .fold(Tropical::infinity(), |acc, (i, c)| acc + c.clone() * x.pow(i))
+    }
+
+    /// The vertices of the Newton polygon, the lower convex hull of the points
+    /// `(i, c_i)` for the finite coefficients, from left to right.
+    pub fn newton_polygon(&self) -> Vec<(usize, BigRational)> {
+        let mut hull: Vec<(usize, BigRational)> = Vec::new();
+        for (i, c) in self.coeffs.iter().enumerate() {
+            let Some(c) = c.value() else {
+                continue;
+            };
+            // pop the last vertex while it is on or above the segment from
+            // the one before it to the new point
+            while let [.., (i0, c0), (i1, c1)] = hull.as_slice() {
+                let left = (c1 - c0) * BigRational::from_integer((i - i0).into());
+                let right = (c - c0) * BigRational::from_integer((i1 - i0).into());
+                if left < right {
+                    break;
+                }
+                hull.pop();
+            }
+            hull.push((i, c.clone()));
+        }
+        hull
+    }
+
+    /// The tropical roots, where the minimum is attained at least twice, in
+    /// increasing order with their multiplicities. An edge of the Newton
+    /// polygon from `(i, a)` to `(j, b)` gives the root `(a - b) / (j - i)`
+    /// of multiplicity `j - i`.
+    ///
+    /// A polynomial whose first `k` coefficients are infinite also has the
+    /// root minus infinity of multiplicity `k`, which is not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::tropical::{Tropical, TropicalPolynomial};
+    /// # use num::BigRational;
+    /// let t = |x: i32| Tropical::new(BigRational::from_integer(x.into()));
+    /// // min(3, 1 + x, 2x) has corners at 1 and 2, and min(3, 2x) has a
+    /// // double corner at 3/2
+    /// let f = TropicalPolynomial::new(vec![t(3), t(1), t(0)]);
+    /// assert_eq!(vec![(BigRational::from_integer(1.into()), 1), (BigRational::from_integer(2.into()), 1)], f.roots());
+    /// let g = TropicalPolynomial::new(vec![t(3), Tropical::infinity(), t(0)]);
+    /// assert_eq!(vec![(BigRational::new(3.into(), 2.into()), 2)], g.roots());
+    /// ```
+    pub fn roots(&self) -> Vec<(BigRational, usize)> {
+        let hull = self.newton_polygon();
+        let mut roots: Vec<_> = hull
+            .windows(2)
+            .map(|edge| {
+                let ((i, a), (j, b)) = (&edge[0], &edge[1]);
+                ((a - b) / BigRational::from_integer((j - i).into()), j - i)
+            })
+            .collect();
+        roots.reverse();
+        roots
+    }
+}
+
+/// The coefficientwise minimum.
+impl Add for TropicalPolynomial {
+    type Output = Self;
+    fn add(self, other: Self) -> Self {
+        Self::new((Polynomial::new(self.coeffs) + Polynomial::new(other.coeffs)).coeffs)
+    }
+}
+
+/// The min-plus convolution of the coefficients.
+impl Mul for TropicalPolynomial {
+    type Output = Self;
+    fn mul(self, other: Self) -> Self {
+        Self::new(Polynomial::new(self.coeffs).schoolbook_mul(&Polynomial::new(other.coeffs)).coeffs)
+    }
+}
+
+/// The `p`-adic valuation of a non-zero rational.
+fn valuation(q: &BigRational, p: &BigInt) -> i64 {
+    let multiplicity = |mut n: BigInt| {
+        let mut k = 0;
+        while n.is_multiple_of(p) {
+            n /= p;
+            k += 1;
+        }
+        k
+    };
+    multiplicity(q.numer().clone()) - multiplicity(q.denom().clone())
+}
+
+fn tropicalize_coeff(c: &BigRational, p: &BigInt) -> Tropical {
+    if c.is_zero() {
+        Tropical::infinity()
+    } else {
+        Tropical::new(BigRational::from_integer(valuation(c, p).into()))
+    }
+}
+
+/// The tropical polynomial of the `p`-adic valuations of the coefficients
+/// of `f`, whose roots are the valuations of the roots of `f` in the
+/// algebraic closure of the `p`-adic numbers.
+///
+/// # Panics
+///
+/// Panics if `p` is not at least 2.
+///
+/// # Example
+///
+/// ```
+/// # use math2::tropical::tropicalize;
+/// # use math2::Polynomial;
+/// # use num::{BigInt, BigRational};
+/// let q = |x: i32| BigRational::from_integer(x.into());
+/// // (x - 1/3)(x - 9) = x^2 - 28/3 x + 3 has roots of 3-adic valuations -1 and 2
+/// let f = Polynomial::new(vec![q(3), -q(28) / q(3), q(1)]);
+/// let roots = tropicalize(&f, &BigInt::from(3)).roots();
+/// assert_eq!(vec![(q(-1), 1), (q(2), 1)], roots);
+/// ```
+pub fn tropicalize(f: &Polynomial<BigRational>, p: &BigInt) -> TropicalPolynomial {
+    assert!(*p >= BigInt::from(2), "p must be at least 2");
+    TropicalPolynomial::new(f.coeffs.iter().map(|c| tropicalize_coeff(c, p)).collect())
+}
+
+/// A tropical polynomial `min(c_e + e . x)` in the variables `x_0, x_1, ...`,
+/// stored as a map from exponent vectors without trailing zeros to the
+/// finite coefficients.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TropicalMultiPolynomial {
+    terms: BTreeMap<Vec<usize>, BigRational>,
+}
+
+impl TropicalMultiPolynomial {
+    /// Creates a polynomial from `(exponents, coefficient)` pairs. Repeated
+    /// exponent vectors take the minimum of their coefficients.
+    pub fn new(terms: impl IntoIterator<Item = (Vec<usize>, BigRational)>) -> Self {
+        let mut res = BTreeMap::new();
+        for (mut exponents, c) in terms {
+            while exponents.last() == Some(&0) {
+                exponents.pop();
+            }
+            let entry = res.entry(exponents).or_insert_with(|| c.clone());
+            if c < *entry {
+                *entry = c;
+            }
+        }
+        Self { terms: res }
+    }
+
+    /// The terms, in lexicographic order of their exponents.
+    pub fn terms(&self) -> impl Iterator<Item = (&[usize], &BigRational)> {
+        self.terms.iter().map(|(e, c)| (e.as_slice(), c))
+    }
+
+    /// The value `c + e . x` of a term.
+    fn term_value(exponents: &[usize], c: &BigRational, point: &[BigRational]) -> BigRational {
+        assert!(exponents.len() <= point.len(), "missing values for some variables");
+        exponents
+            .iter()
+            .zip(point)
+            .fold(c.clone(), |acc, (&e, x)| acc + x * BigRational::from_integer(e.into()))
+    }
+
+    /// The minimum of `c_e + e . x` over the terms, infinity for the
+    /// polynomial without terms.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable that appears has no value.
+    pub fn eval(&self, point: &[BigRational]) -> Tropical {
+        self.terms()
+            .fold(Tropical::infinity(), |acc, (e, c)| acc + Tropical::new(Self::term_value(e, c, point)))
+    }
+
+    /// The exponents of the terms attaining the minimum at `point`. The
+    /// point is on the tropical hypersurface when there are at least two.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable that appears has no value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::tropical::TropicalMultiPolynomial;
+    /// # use num::BigRational;
+    /// let q = |x: i32| BigRational::from_integer(x.into());
+    /// // the tropical line min(0, x, y) has its vertex at the origin
+    /// let line = TropicalMultiPolynomial::new([(vec![], q(0)), (vec![1], q(0)), (vec![0, 1], q(0))]);
+    /// assert_eq!(3, line.dominant_terms(&[q(0), q(0)]).len());
+    /// assert_eq!(vec![&[][..], &[1][..]], line.dominant_terms(&[q(0), q(5)]));
+    /// assert_eq!(1, line.dominant_terms(&[q(1), q(2)]).len());
+    /// ```
+    pub fn dominant_terms(&self, point: &[BigRational]) -> Vec<&[usize]> {
+        let Some(min) = self.eval(point).0 else {
+            return Vec::new();
+        };
+        self.terms()
+            .filter(|(e, c)| Self::term_value(e, c, point) == min)
+            .map(|(e, _)| e)
+            .collect()
+    }
+
+    /// The vertices of the Newton polytope in `R^n`, the convex hull of the
+    /// exponent vectors, sorted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a variable `x_i` with `i >= n` appears.
+    pub fn newton_polytope(&self, n: usize) -> Vec<Vec<i64>> {
+        assert!(self.terms.keys().all(|e| e.len() <= n), "polynomial uses more than n variables");
+        let support: Vec<Vec<i64>> = self
+            .terms
+            .keys()
+            .map(|e| (0..n).map(|i| e.get(i).map_or(0, |&e| e as i64)).collect())
+            .collect();
+        polytope::vertices(&support)
+    }
+}
+
+/// The tropical polynomial of the `p`-adic valuations of the coefficients
+/// of `f`.
+///
+/// # Panics
+///
+/// Panics if `p` is not at least 2.
+pub fn tropicalize_multi(f: &MultiPolynomial<BigRational>, p: &BigInt) -> TropicalMultiPolynomial {
+    assert!(*p >= BigInt::from(2), "p must be at least 2");
+    TropicalMultiPolynomial::new(
+        f.terms()
+            .map(|(e, c)| (e.to_vec(), BigRational::from_integer(valuation(c, p).into()))),
+    )
+}
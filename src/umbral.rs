@@ -0,0 +1,215 @@
+//! Finite operator calculus (umbral calculus) over a field.
+//!
+//! A *delta operator* `Q` is a shift-invariant linear operator on `F[x]` for
+//! which `Q x` is a non-zero constant. Every delta operator has a unique
+//! *basic sequence* `p_n` with `p_0 = 1`, `p_n(0) = 0` for `n > 0` and
+//! `Q p_n = n p_{n-1}`. A *Sheffer sequence* for `Q` is `s_n = S^{-1} p_n` for
+//! some invertible shift-invariant operator `S`. Sheffer sequences for the
+//! derivative `D` are called *Appell sequences*.
+
+use num::{One, Zero};
+
+use crate::traits::{Field, FromUsize};
+use crate::Polynomial;
+
+type Action<F> = dyn Fn(&Polynomial<F>) -> Polynomial<F>;
+
+/// A linear operator on polynomials.
+///
+/// The algorithms in this module assume the operator is shift-invariant,
+/// which is not checked.
+pub struct Operator<F> {
+    action: Box<Action<F>>,
+}
+
+impl<F: Field + FromUsize + PartialEq + 'static> Operator<F> {
+    pub fn new(action: impl Fn(&Polynomial<F>) -> Polynomial<F> + 'static) -> Self {
+        Self {
+            action: Box::new(action),
+        }
+    }
+
+    /// The derivative `D`.
+    pub fn derivative() -> Self {
+        Self::new(|p| p.clone().derivative())
+    }
+
+    /// The shift `E^a`, sending `p(x)` to `p(x + a)`.
+    pub fn shift(a: F) -> Self {
+        Self::new(move |p| shift(p, &a))
+    }
+
+    /// The forward difference `Δ = E - 1`.
+    pub fn forward_difference() -> Self {
+        Self::new(|p| shift(p, &F::one()) - p.clone())
+    }
+
+    /// The backward difference `∇ = 1 - E^{-1}`.
+    pub fn backward_difference() -> Self {
+        Self::new(|p| p.clone() - shift(p, &-F::one()))
+    }
+
+    /// The operator `q(D) = q_0 + q_1 D + q_2 D^2 + ...`.
+    ///
+    /// Infinite series such as `e^D - 1` can be given truncated: applied to a
+    /// polynomial of degree `n`, only the terms up to `D^n` matter.
+    pub fn from_series(series: Polynomial<F>) -> Self {
+        Self::new(move |p| {
+            let mut term = p.clone();
+            let mut res = Polynomial::zero();
+            for q in series.coeffs.iter() {
+                if term.is_zero() {
+                    break;
+                }
+                res += term.clone() * q.clone();
+                term.derive_in_place();
+            }
+            res
+        })
+    }
+
+    pub fn apply(&self, p: &Polynomial<F>) -> Polynomial<F> {
+        (self.action)(p)
+    }
+}
+
+/// `p(x + a)`, by Horner's scheme.
+fn shift<F: Field + PartialEq>(p: &Polynomial<F>, a: &F) -> Polynomial<F> {
+    let x_plus_a = Polynomial::new_trim_zeroes(vec![a.clone(), F::one()]);
+    p.coeffs.iter().rev().fold(Polynomial::zero(), |acc, c| {
+        acc * x_plus_a.clone() + Polynomial::new_trim_zeroes(vec![c.clone()])
+    })
+}
+
+/// Writes `target` as `sum(c_i * basis[i])`, where `basis[i]` has degree `i`.
+///
+/// # Panics
+///
+/// Panics if `target` has degree at least `basis.len()`, or if some basis
+/// element that is needed does not have the right degree.
+fn triangular_coordinates<F: Field + PartialEq>(
+    basis: &[Polynomial<F>],
+    mut target: Polynomial<F>,
+) -> Vec<F> {
+    let mut coords = vec![F::zero(); basis.len()];
+    while let Some(d) = target.degree() {
+        assert!(d < basis.len(), "not enough basis polynomials");
+        let b = &basis[d];
+        assert_eq!(Some(d), b.degree(), "basis polynomial {d} must have degree {d}");
+        let c = target.leading_coefficient_cloned() / b.leading_coefficient_cloned();
+        target = target - b.clone().scalar_mul(c.clone());
+        coords[d] = c;
+    }
+    coords
+}
+
+/// Returns `p_0, ..., p_n`, the first terms of the basic sequence of `delta`.
+///
+/// # Example
+///
+/// The basic sequence of the forward difference is the falling factorials.
+///
+/// ```
+/// # use math2::umbral::{basic_sequence, Operator};
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// let seq = basic_sequence(&Operator::forward_difference(), 3);
+/// // x(x - 1)(x - 2)
+/// assert_eq!(seq[3], Polynomial::new(vec![n(0), n(2), n(-3), n(1)]));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `delta` is not a delta operator.
+pub fn basic_sequence<F: Field + FromUsize + PartialEq + 'static>(
+    delta: &Operator<F>,
+    n: usize,
+) -> Vec<Polynomial<F>> {
+    // `delta` lowers degrees by exactly one, so the images of x, x^2, ...
+    // form a triangular basis to solve `Q p_m = m p_{m-1}` in.
+    let images: Vec<_> = (1..=n)
+        .map(|j| delta.apply(&Polynomial::from_elem_with_degree(F::one(), j)))
+        .collect();
+    let mut seq = vec![Polynomial::one()];
+    for m in 1..=n {
+        let target = seq[m - 1].clone().scalar_mul(F::from_usize(m));
+        let coords = triangular_coordinates(&images[..m], target);
+        seq.push(Polynomial::new_trim_zeroes(coords).raised_by(1));
+    }
+    seq
+}
+
+/// Returns `s_0, ..., s_n` where `s_k = S^{-1} p_k`, `p_k` being the basic
+/// sequence of `delta` and `S` the invertible shift-invariant operator `s`.
+///
+/// # Panics
+///
+/// Panics if `delta` is not a delta operator or `s` is not invertible.
+pub fn sheffer_sequence<F: Field + FromUsize + PartialEq + 'static>(
+    delta: &Operator<F>,
+    s: &Operator<F>,
+    n: usize,
+) -> Vec<Polynomial<F>> {
+    let images: Vec<_> = (0..=n)
+        .map(|j| s.apply(&Polynomial::from_elem_with_degree(F::one(), j)))
+        .collect();
+    basic_sequence(delta, n)
+        .into_iter()
+        .map(|p| Polynomial::new_trim_zeroes(triangular_coordinates(&images, p)))
+        .collect()
+}
+
+/// Returns the Appell sequence `S^{-1} x^k` for `k = 0, ..., n`.
+///
+/// # Example
+///
+/// The Bernoulli polynomials are the Appell sequence for `(e^D - 1) / D`.
+///
+/// ```
+/// # use math2::umbral::{appell_sequence, Operator};
+/// # use math2::Polynomial;
+/// # use num::BigRational;
+/// let r = |a: i32, b: i32| BigRational::new(a.into(), b.into());
+/// // 1 + D/2 + D^2/6, enough for degree 2
+/// let s = Operator::from_series(Polynomial::new(vec![r(1, 1), r(1, 2), r(1, 6)]));
+/// let bernoulli = appell_sequence(&s, 2);
+/// assert_eq!(bernoulli[2], Polynomial::new(vec![r(1, 6), r(-1, 1), r(1, 1)]));
+/// ```
+pub fn appell_sequence<F: Field + FromUsize + PartialEq + 'static>(
+    s: &Operator<F>,
+    n: usize,
+) -> Vec<Polynomial<F>> {
+    sheffer_sequence(&Operator::derivative(), s, n)
+}
+
+/// Returns the connection constants `c[n][k]` with `a[n] = sum(c[n][k] * b[k])`.
+///
+/// `b[k]` must have degree `k`, and every `a[n]` must have degree less than
+/// `b.len()`.
+///
+/// # Example
+///
+/// Powers in terms of falling factorials are the Stirling numbers of the
+/// second kind.
+///
+/// ```
+/// # use math2::umbral::{basic_sequence, connection_constants, Operator};
+/// # use math2::Polynomial;
+/// # use num::{BigRational, One};
+/// let n = |x: i32| BigRational::from_integer(x.into());
+/// let falling = basic_sequence(&Operator::forward_difference(), 3);
+/// let powers: Vec<_> = (0..=3)
+///     .map(|k| Polynomial::from_elem_with_degree(BigRational::one(), k))
+///     .collect();
+/// let c = connection_constants(&powers, &falling);
+/// assert_eq!(c[3], vec![n(0), n(1), n(3), n(1)]);
+/// ```
+pub fn connection_constants<F: Field + PartialEq>(
+    a: &[Polynomial<F>],
+    b: &[Polynomial<F>],
+) -> Vec<Vec<F>> {
+    a.iter()
+        .map(|p| triangular_coordinates(b, p.clone()))
+        .collect()
+}
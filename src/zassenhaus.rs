@@ -0,0 +1,616 @@
+//! Factorization of integer polynomials with the Zassenhaus algorithm.
+//!
+//! A square free polynomial is factored modulo a small prime `p`, the
+//! factors are Hensel lifted until `p^k` exceeds twice a bound on the
+//! coefficients of any integer factor, and then products of subsets of the
+//! lifted factors are tried as integer factors.
+
+use std::num::NonZeroUsize;
+
+use num::{BigInt, Integer, One, Signed, ToPrimitive, Zero};
+
+use crate::cache::Cache;
+use crate::factorization::{to_integer, to_rational, Factorization};
+use crate::fields::Zp;
+use crate::modular_factorization::Xorshift;
+use crate::multimodular::{gcd_mod, inv_mod, mul_mod, reduce as reduce_word, rem_mod, trim};
+use crate::traits::CoefficientDomain;
+use crate::Polynomial;
+
+fn reduce<const P: u64>(f: &Polynomial<BigInt>) -> Polynomial<Zp<P>> {
+    let p = BigInt::from(P);
+    Polynomial::new_trim_zeroes(
+        f.coeffs
+            .iter()
+            .map(|c| Zp::new(c.mod_floor(&p).to_u64().unwrap()))
+            .collect(),
+    )
+}
+
+fn lift(f: &[u64]) -> Polynomial<BigInt> {
+    Polynomial::new(f.iter().map(|&c| BigInt::from(c)).collect())
+}
+
+/// The image of `f` modulo `p`, as coefficients without trailing zeros.
+fn image(f: &Polynomial<BigInt>, p: u64) -> Vec<u64> {
+    trim(f.coeffs.iter().map(|c| reduce_word(c, p)).collect())
+}
+
+fn scale_word(f: &[u64], c: u64, p: u64) -> Vec<u64> {
+    trim(f.iter().map(|&x| mul_mod(x, c, p)).collect())
+}
+
+fn sub_word(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    let coeff = |f: &[u64], i| f.get(i).copied().unwrap_or(0);
+    trim((0..a.len().max(b.len())).map(|i| (coeff(a, i) + p - coeff(b, i)) % p).collect())
+}
+
+fn mul_word(a: &[u64], b: &[u64], p: u64) -> Vec<u64> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut res = vec![0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] = (res[i + j] + mul_mod(x, y, p)) % p;
+        }
+    }
+    trim(res)
+}
+
+/// The quotient and remainder of `a` by the non-zero `b` modulo `p`.
+fn div_rem_word(mut a: Vec<u64>, b: &[u64], p: u64) -> (Vec<u64>, Vec<u64>) {
+    let n = b.len() - 1;
+    let inv = inv_mod(b[n], p);
+    let mut quotient = vec![0; a.len().saturating_sub(n)];
+    while a.len() > n {
+        let m = a.len() - 1;
+        let factor = mul_mod(a[m], inv, p);
+        quotient[m - n] = factor;
+        for (x, y) in a[m - n..].iter_mut().zip(b) {
+            *x = (*x + p - mul_mod(factor, *y, p)) % p;
+        }
+        a = trim(a);
+    }
+    (trim(quotient), a)
+}
+
+/// `base^exp mod m` modulo `p`, by repeated squaring.
+fn pow_mod_word(base: &[u64], mut exp: u64, m: &[u64], p: u64) -> Vec<u64> {
+    let mut base = rem_mod(base.to_vec(), m, p);
+    let mut res = rem_mod(vec![1], m, p);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            res = rem_mod(mul_word(&res, &base, p), m, p);
+        }
+        base = rem_mod(mul_word(&base, &base, p), m, p);
+        exp >>= 1;
+    }
+    res
+}
+
+/// `(s, t)` with `s a + t b = 1` modulo `p`, for coprime `a` and `b`.
+fn bezout_word(a: &[u64], b: &[u64], p: u64) -> (Vec<u64>, Vec<u64>) {
+    let (mut r0, mut r1) = (a.to_vec(), b.to_vec());
+    let (mut s0, mut s1) = (vec![1], Vec::new());
+    let (mut t0, mut t1) = (Vec::new(), vec![1]);
+    while !r1.is_empty() {
+        let (q, r) = div_rem_word(r0, &r1, p);
+        r0 = std::mem::replace(&mut r1, r);
+        let s = sub_word(&s0, &mul_word(&q, &s1, p), p);
+        s0 = std::mem::replace(&mut s1, s);
+        let t = sub_word(&t0, &mul_word(&q, &t1, p), p);
+        t0 = std::mem::replace(&mut t1, t);
+    }
+    // r0 is the non-zero constant gcd
+    let inv = inv_mod(r0[0], p);
+    (scale_word(&s0, inv, p), scale_word(&t0, inv, p))
+}
+
+/// Reduces the coefficients into `(-m/2, m/2]`.
+fn symmetric(f: &Polynomial<BigInt>, m: &BigInt) -> Polynomial<BigInt> {
+    Polynomial::new_trim_zeroes(
+        f.coeffs
+            .iter()
+            .map(|c| {
+                let r = c.mod_floor(m);
+                if &r * 2 > *m {
+                    r - m
+                } else {
+                    r
+                }
+            })
+            .collect(),
+    )
+}
+
+/// The primitive part, with a positive leading coefficient.
+pub(crate) fn primitive(f: Polynomial<BigInt>) -> Polynomial<BigInt> {
+    let content = f.clone().content();
+    let f = Polynomial::new(f.coeffs.iter().map(|c| c.divide_exact(&content)).collect());
+    f.unit_and_normal().1
+}
+
+/// The quotient `g / h`, if `h` divides `g` over the integers.
+pub(crate) fn divide(g: &Polynomial<BigInt>, h: &Polynomial<BigInt>) -> Option<Polynomial<BigInt>> {
+    let n = h.degree()?;
+    let lc = h.leading_coefficient_cloned();
+    let mut rem = g.coeffs.clone();
+    let mut quotient = vec![BigInt::zero(); rem.len().checked_sub(n)?];
+    for i in (0..quotient.len()).rev() {
+        let (q, r) = rem[i + n].div_rem(&lc);
+        if !r.is_zero() {
+            return None;
+        }
+        for (x, y) in rem[i..].iter_mut().zip(&h.coeffs) {
+            *x -= &q * y;
+        }
+        quotient[i] = q;
+    }
+    rem.iter().all(Zero::is_zero).then(|| Polynomial::new(quotient))
+}
+
+/// Lifts `g = lc(g) a b (mod p)`, with `a` and `b` monic and coprime modulo
+/// `p`, to a factorization modulo `p^k`.
+///
+/// Each step writes `g - lc(g) a b = m e` for the current modulus `m` and
+/// corrects `a` and `b` by `m α` and `m β`, where `α b + β a = e / lc(g)`
+/// modulo `p` is solved with the Bezout coefficients of `a` and `b`.
+fn lift_pair(
+    g: &Polynomial<BigInt>,
+    a: &[u64],
+    b: &[u64],
+    p: u64,
+    k: u32,
+) -> (Polynomial<BigInt>, Polynomial<BigInt>) {
+    let lc = g.leading_coefficient_cloned();
+    let lc_inv = inv_mod(reduce_word(&lc, p), p);
+    let (s, t) = bezout_word(a, b, p);
+    let (mut a_lifted, mut b_lifted) = (lift(a), lift(b));
+    let mut m = BigInt::from(p);
+    for _ in 1..k {
+        let diff = g.clone() - (a_lifted.clone() * b_lifted.clone()).scalar_mul(lc.clone());
+        let e = Polynomial::new(diff.coeffs.iter().map(|c| c.divide_exact(&m)).collect());
+        let c = scale_word(&image(&e, p), lc_inv, p);
+        let alpha = rem_mod(mul_word(&t, &c, p), a, p);
+        let beta = rem_mod(mul_word(&s, &c, p), b, p);
+        a_lifted += lift(&alpha).scalar_mul(m.clone());
+        b_lifted += lift(&beta).scalar_mul(m.clone());
+        m *= p;
+    }
+    (a_lifted, b_lifted)
+}
+
+/// The monic factors of `g` modulo `P`, if `P` does not divide the leading
+/// coefficient and `g` stays square free modulo `P`.
+fn factor_modulo<const P: u64>(g: &Polynomial<BigInt>) -> Option<Vec<Vec<u64>>> {
+    let reduced = reduce::<P>(g);
+    if reduced.degree() != g.degree() {
+        return None;
+    }
+    let factorization = reduced.factor_mod_p();
+    factorization
+        .factors
+        .into_iter()
+        .map(|(f, k)| (k.get() == 1).then(|| f.coeffs.iter().map(|c| c.value()).collect()))
+        .collect()
+}
+
+/// The same as [`factor_modulo`] for a prime `p` that is only known at run
+/// time, with the distinct and equal degree factorizations of
+/// [`Polynomial::factor_mod_p`] on coefficient vectors.
+fn factor_modulo_word(g: &Polynomial<BigInt>, p: u64) -> Option<Vec<Vec<u64>>> {
+    let f = image(g, p);
+    if f.len() != g.coeffs.len() {
+        return None;
+    }
+    let derivative: Vec<u64> = f.iter().enumerate().skip(1).map(|(i, &c)| mul_mod(c, i as u64 % p, p)).collect();
+    if gcd_mod(f.clone(), trim(derivative), p).len() != 1 {
+        return None;
+    }
+    let mut rest = scale_word(&f, inv_mod(*f.last().unwrap(), p), p);
+    let x = vec![0, 1];
+    let mut h = x.clone();
+    let mut factors = Vec::new();
+    let mut d = 0;
+    while rest.len() > 2 * (d + 1) {
+        d += 1;
+        h = pow_mod_word(&h, p, &rest, p);
+        let common = gcd_mod(rest.clone(), sub_word(&h, &x, p), p);
+        if common.len() > 1 {
+            rest = div_rem_word(rest, &common, p).0;
+            h = rem_mod(h, &rest, p);
+            factors.extend(split_equal_degree_word(common, d, p));
+        }
+    }
+    if rest.len() > 1 {
+        factors.push(rest);
+    }
+    Some(factors)
+}
+
+/// Splits a monic product of distinct irreducibles of degree `degree`
+/// modulo an odd prime `p`, as in [`Polynomial::equal_degree_factorization`].
+fn split_equal_degree_word(f: Vec<u64>, degree: usize, p: u64) -> Vec<Vec<u64>> {
+    let mut rng = Xorshift(0x9e37_79b9_7f4a_7c15);
+    let mut done = Vec::new();
+    let mut stack = vec![f];
+    while let Some(f) = stack.pop() {
+        let n = f.len() - 1;
+        if n <= degree {
+            done.push(f);
+            continue;
+        }
+        let a = trim((0..n).map(|_| rng.next() % p).collect());
+        if a.len() <= 1 {
+            stack.push(f);
+            continue;
+        }
+        // (p^d - 1) / 2 = (1 + p + ... + p^(d - 1)) (p - 1) / 2
+        let mut t = a.clone();
+        let mut norm = a;
+        for _ in 1..degree {
+            t = pow_mod_word(&t, p, &f, p);
+            norm = rem_mod(mul_word(&norm, &t, p), &f, p);
+        }
+        let b = sub_word(&pow_mod_word(&norm, (p - 1) / 2, &f, p), &[1], p);
+        let g = gcd_mod(f.clone(), b, p);
+        if g.len() > 1 && g.len() < f.len() {
+            stack.push(div_rem_word(f, &g, p).0);
+            stack.push(g);
+        } else {
+            stack.push(f);
+        }
+    }
+    done
+}
+
+/// Lifts the monic factors of `g` modulo `p` to factors modulo `p^k`,
+/// splitting off one factor at a time.
+fn lift_factors(g: &Polynomial<BigInt>, factors: &[Vec<u64>], p: u64, k: u32) -> Vec<Polynomial<BigInt>> {
+    let lc = g.leading_coefficient_cloned();
+    let mut lifted = Vec::with_capacity(factors.len());
+    let mut h = g.clone();
+    for (i, a) in factors.iter().enumerate().take(factors.len() - 1) {
+        let b = factors[i + 1..].iter().fold(vec![1], |acc, f| mul_word(&acc, f, p));
+        let (a, b) = lift_pair(&h, a, &b, p, k);
+        lifted.push(a);
+        // h = lc(g) b, which is congruent to lc(g) times the remaining factors
+        h = b.scalar_mul(lc.clone());
+    }
+    lifted.push(Polynomial::new(h.coeffs.iter().map(|c| c.divide_exact(&lc)).collect()));
+    lifted
+}
+
+/// A prime to factor modulo, with the modular factorization specialized to
+/// it.
+struct Prime {
+    p: u64,
+    factor: fn(&Polynomial<BigInt>) -> Option<Vec<Vec<u64>>>,
+}
+
+macro_rules! primes {
+    ($($p:literal),*) => {
+        [$(Prime {
+            p: $p,
+            factor: factor_modulo::<$p>,
+        }),*]
+    };
+}
+
+/// The primes tried first. If they all divide the leading coefficient or
+/// the discriminant, the search goes on with [`more_primes`], through
+/// [`factor_modulo_word`].
+const PRIMES: [Prime; 30] = primes!(
+    3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+    101, 103, 107, 109, 113, 65537
+);
+
+/// How many suitable primes are compared before picking the one with the
+/// fewest modular factors, which keeps the recombination small.
+const CANDIDATE_PRIMES: usize = 5;
+
+/// A bound on `lc(g) / lc(h) * h` for the integer factors `h` of `g`, from
+/// Mignotte's bound `|h|_inf <= 2^deg(g) |g|_2`.
+fn coefficient_bound(g: &Polynomial<BigInt>) -> BigInt {
+    let norm_squared: BigInt = g.coeffs.iter().map(|c| c * c).sum();
+    let norm = norm_squared.sqrt() + 1;
+    g.leading_coefficient_cloned().abs() * (BigInt::one() << g.degree().unwrap()) * norm
+}
+
+/// The primes after the last of [`PRIMES`], in increasing order, from the
+/// sieve in `cache`.
+fn more_primes(cache: &Cache) -> impl Iterator<Item = u64> + '_ {
+    let start = PRIMES[PRIMES.len() - 1].p;
+    (0..).flat_map(move |i| {
+        let (lo, hi) = (start << i, start << (i + 1));
+        cache.primes_up_to(hi).into_iter().filter(move |&p| p > lo)
+    })
+}
+
+/// Factors a square free primitive polynomial with a positive leading
+/// coefficient.
+fn factor_square_free(g: Polynomial<BigInt>, cache: &Cache) -> Vec<Polynomial<BigInt>> {
+    if g.degree().is_none_or(|n| n <= 1) {
+        return vec![g];
+    }
+    // g = h(x^k) splits along the factors of h, unless h is irreducible
+    if let Some((inner, k)) = g.inner_power() {
+        let inner_factors = factor_square_free(inner, cache);
+        if inner_factors.len() > 1 {
+            return inner_factors
+                .into_iter()
+                .flat_map(|h| factor_square_free(h.compose_power(k), cache))
+                .collect();
+        }
+    }
+    FactorizationState::new(g, cache).resume()
+}
+
+/// The state of a Zassenhaus factorization between recombination steps.
+///
+/// A long recombination can be saved with [`FactorizationState::encode`]
+/// and picked up later, after a restart, with [`FactorizationState::decode`]
+/// and [`FactorizationState::resume`].
+///
+/// # Example
+///
+/// ```
+/// # use math2::cache::Cache;
+/// # use math2::zassenhaus::FactorizationState;
+/// # use math2::Polynomial;
+/// # use num::BigInt;
+/// // (x^2 + 1) (x^2 - 2)
+/// let p = Polynomial::new([-2, 0, -1, 0, 1].map(BigInt::from).to_vec());
+/// let mut state = FactorizationState::new(p, &Cache::new());
+/// state.step();
+/// let saved = state.encode();
+/// let factors = state.resume();
+/// assert_eq!(2, factors.len());
+/// assert_eq!(factors, FactorizationState::decode(&saved).unwrap().resume());
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FactorizationState {
+    /// The irreducible integer factors found so far.
+    pub found: Vec<Polynomial<BigInt>>,
+    /// The product of the factors not found yet.
+    pub remaining: Polynomial<BigInt>,
+    /// The prime the modular factors were computed for.
+    pub prime: u64,
+    /// The lifting precision `k`, with the lifted factors known modulo
+    /// `prime^k`.
+    pub precision: u32,
+    /// The monic factors of `remaining` modulo `prime^precision`.
+    pub lifted: Vec<Polynomial<BigInt>>,
+    /// The indices into `lifted` of the next product to try, in
+    /// lexicographic order among the subsets of the same size.
+    pub subset: Vec<usize>,
+}
+
+impl FactorizationState {
+    /// Picks a prime for the square free primitive polynomial `g` with a
+    /// positive leading coefficient and lifts its modular factors, taking
+    /// the primes to try after the fixed ones from the sieve in `cache`.
+    pub fn new(g: Polynomial<BigInt>, cache: &Cache) -> Self {
+        let done = |g| FactorizationState {
+            found: vec![g],
+            remaining: Polynomial::one(),
+            prime: 0,
+            precision: 0,
+            lifted: Vec::new(),
+            subset: Vec::new(),
+        };
+        if g.degree().is_none_or(|n| n <= 1) {
+            return done(g);
+        }
+        let (p, factors) = PRIMES
+            .iter()
+            .map(|prime| (prime.p, (prime.factor)(&g)))
+            .chain(more_primes(cache).map(|p| (p, factor_modulo_word(&g, p))))
+            .filter_map(|(p, factors)| Some((p, factors?)))
+            .take(CANDIDATE_PRIMES)
+            .min_by_key(|(_, factors)| factors.len())
+            .expect("only finitely many primes divide the discriminant");
+        if factors.len() == 1 {
+            return FactorizationState { prime: p, ..done(g) };
+        }
+        let bound = coefficient_bound(&g) * 2;
+        let mut k = 1;
+        let mut m = BigInt::from(p);
+        while m <= bound {
+            m *= p;
+            k += 1;
+        }
+        FactorizationState {
+            found: Vec::new(),
+            lifted: lift_factors(&g, &factors, p, k),
+            remaining: g,
+            prime: p,
+            precision: k,
+            subset: vec![0],
+        }
+    }
+
+    /// Whether all factors have been found.
+    pub fn is_done(&self) -> bool {
+        self.lifted.is_empty()
+    }
+
+    /// Tries the product of the lifted factors in `subset` as an integer
+    /// factor, then moves on to the next subset. Returns whether there is
+    /// more work to do.
+    pub fn step(&mut self) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        let size = self.subset.len();
+        if 2 * size > self.lifted.len() {
+            // the remaining factors cannot split further
+            let g = std::mem::replace(&mut self.remaining, Polynomial::one());
+            self.found.push(primitive(g));
+            self.lifted.clear();
+            self.subset.clear();
+            return false;
+        }
+        let m = BigInt::from(self.prime).pow(self.precision);
+        let lc = self.remaining.leading_coefficient_cloned();
+        let product = self
+            .subset
+            .iter()
+            .fold(Polynomial::new(vec![lc]), |acc, &i| symmetric(&(acc * self.lifted[i].clone()), &m));
+        let candidate = primitive(product);
+        if let Some(q) = divide(&self.remaining, &candidate) {
+            self.found.push(candidate);
+            self.remaining = q;
+            for &i in self.subset.iter().rev() {
+                self.lifted.remove(i);
+            }
+            self.subset = (0..size).collect();
+            return true;
+        }
+        // advance to the next subset in lexicographic order, or to the
+        // first subset of the next size
+        let n = self.lifted.len();
+        match (0..size).rev().find(|&i| self.subset[i] < n - size + i) {
+            Some(i) => {
+                self.subset[i] += 1;
+                for j in i + 1..size {
+                    self.subset[j] = self.subset[j - 1] + 1;
+                }
+            }
+            None => self.subset = (0..size + 1).collect(),
+        }
+        true
+    }
+
+    /// Runs the remaining steps and returns the irreducible factors.
+    pub fn resume(mut self) -> Vec<Polynomial<BigInt>> {
+        while self.step() {}
+        self.found
+    }
+
+    /// A text form of this state for [`FactorizationState::decode`], which
+    /// stays readable by later versions.
+    ///
+    /// The first line is `zassenhaus 1`, the format version. It is followed
+    /// by one line per field, a name and then space separated decimal
+    /// integers: `prime`, `precision`, `subset`, `remaining` with the
+    /// coefficients from the constant term up, and then a `found` line for
+    /// each found factor and a `lifted` line for each lifted factor.
+    pub fn encode(&self) -> String {
+        let line = |name: &str, values: Vec<String>| {
+            std::iter::once(name.to_string()).chain(values).collect::<Vec<_>>().join(" ") + "\n"
+        };
+        let coeffs = |f: &Polynomial<BigInt>| f.coeffs.iter().map(BigInt::to_string).collect();
+        let mut res = line("zassenhaus", vec!["1".to_string()]);
+        res += &line("prime", vec![self.prime.to_string()]);
+        res += &line("precision", vec![self.precision.to_string()]);
+        res += &line("subset", self.subset.iter().map(usize::to_string).collect());
+        res += &line("remaining", coeffs(&self.remaining));
+        for f in &self.found {
+            res += &line("found", coeffs(f));
+        }
+        for f in &self.lifted {
+            res += &line("lifted", coeffs(f));
+        }
+        res
+    }
+
+    /// Reads a state written by [`FactorizationState::encode`], or `None`
+    /// if `s` is not one.
+    pub fn decode(s: &str) -> Option<Self> {
+        fn values<T: std::str::FromStr>(rest: &str) -> Option<Vec<T>> {
+            rest.split_whitespace().map(|v| v.parse().ok()).collect()
+        }
+        let mut lines = s.lines().map(|line| line.split_once(' ').unwrap_or((line, "")));
+        if lines.next()? != ("zassenhaus", "1") {
+            return None;
+        }
+        let mut field = |name| lines.next().filter(|(n, _)| *n == name).map(|(_, rest)| rest);
+        let prime = field("prime")?.parse().ok()?;
+        let precision = field("precision")?.parse().ok()?;
+        let subset: Vec<usize> = values(field("subset")?)?;
+        let remaining = Polynomial::new_trim_zeroes(values(field("remaining")?)?);
+        let (mut found, mut lifted) = (Vec::new(), Vec::new());
+        for (name, rest) in lines {
+            let f = Polynomial::new_trim_zeroes(values(rest)?);
+            match name {
+                "found" if lifted.is_empty() => found.push(f),
+                "lifted" => lifted.push(f),
+                _ => return None,
+            }
+        }
+        // the subset must be increasing indices into `lifted`, and empty
+        // exactly when the factorization is done
+        let increasing = subset.windows(2).all(|w| w[0] < w[1]);
+        if !increasing || subset.last().is_some_and(|&i| i >= lifted.len()) || subset.is_empty() != lifted.is_empty() {
+            return None;
+        }
+        Some(FactorizationState {
+            found,
+            remaining,
+            prime,
+            precision,
+            lifted,
+            subset,
+        })
+    }
+}
+
+impl Polynomial<BigInt> {
+    /// Factors this polynomial into irreducibles over the integers with the
+    /// Zassenhaus algorithm.
+    ///
+    /// The leading coefficient holds the content, with the sign of this
+    /// polynomial's leading coefficient, and the factors are primitive with
+    /// positive leading coefficients. The recombination step is exponential
+    /// in the number of modular factors in the worst case, but this is far
+    /// faster than [`Polynomial::kronecker`] in practice.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use math2::Polynomial;
+    /// # use num::BigInt;
+    /// // -2 (x^2 + 1)^2 (x^4 + 4)
+    /// let p = Polynomial::new([-8, 0, -16, 0, -10, 0, -4, 0, -2].map(BigInt::from).to_vec());
+    /// let factorization = p.factor();
+    /// assert_eq!(BigInt::from(-2), factorization.leading_coeff);
+    /// let poly = |c: &[i32]| Polynomial::new(c.iter().map(|&c| BigInt::from(c)).collect());
+    /// let factors: Vec<_> = factorization.factors.iter().map(|(f, k)| (f.clone(), k.get())).collect();
+    /// assert_eq!(
+    ///     vec![(poly(&[2, -2, 1]), 1), (poly(&[1, 0, 1]), 2), (poly(&[2, 2, 1]), 1)],
+    ///     factors
+    /// );
+    /// ```
+    pub fn factor(self) -> Factorization<BigInt> {
+        self.factor_with_cache(&Cache::new())
+    }
+
+    /// [`Polynomial::factor`], taking the primes to try after the fixed ones
+    /// from the sieve in `cache`.
+    pub fn factor_with_cache(self, cache: &Cache) -> Factorization<BigInt> {
+        if self.is_zero() {
+            return Factorization {
+                leading_coeff: BigInt::zero(),
+                factors: Vec::new(),
+            };
+        }
+        let sign = self.leading_coefficient_cloned().signum();
+        let leading_coeff = self.clone().content() * sign;
+        let sqf = to_rational(&self).square_free_factorization();
+        let mut factors: Vec<(Polynomial<BigInt>, NonZeroUsize)> = Vec::new();
+        for (f, multiplicity) in sqf.factors {
+            let f = primitive(to_integer(&f).unwrap());
+            factors.extend(factor_square_free(f, cache).into_iter().map(|g| (g, multiplicity)));
+        }
+        factors.sort_by(|(f, j), (g, k)| {
+            (f.coeffs.len(), f.coeffs.iter().rev().collect::<Vec<_>>(), j)
+                .cmp(&(g.coeffs.len(), g.coeffs.iter().rev().collect(), k))
+        });
+        Factorization {
+            leading_coeff,
+            factors,
+        }
+    }
+}